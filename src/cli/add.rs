@@ -5,8 +5,9 @@ use clap::Args;
 use std::path::{Path, PathBuf};
 
 use crate::docker::{self, DockerContainer};
+use crate::session::devcontainer::DevContainer;
 use crate::session::repo_config;
-use crate::session::{civilizations, Config, GroupTree, Instance, SandboxInfo, Storage};
+use crate::session::{civilizations, resolve_config, GroupTree, Instance, SandboxInfo, Storage};
 
 #[derive(Args)]
 pub struct AddArgs {
@@ -42,6 +43,19 @@ pub struct AddArgs {
     #[arg(short = 'b', long = "new-branch")]
     create_branch: bool,
 
+    /// Start the new worktree branch from this branch, tag or commit instead of HEAD
+    /// (implies --new-branch)
+    #[arg(long, requires = "worktree_branch")]
+    base: Option<String>,
+
+    /// Check out a pull request into a worktree for review (branch defaults to pr-<NUMBER>)
+    #[arg(long = "pr", value_name = "NUMBER", conflicts_with = "base")]
+    pull_request: Option<u64>,
+
+    /// Remote to fetch the pull request from (defaults to origin)
+    #[arg(long, requires = "pull_request")]
+    remote: Option<String>,
+
     /// Run session in Docker sandbox
     #[arg(short = 's', long)]
     sandbox: bool,
@@ -50,6 +64,19 @@ pub struct AddArgs {
     #[arg(long = "sandbox-image")]
     sandbox_image: Option<String>,
 
+    /// CPU limit for the sandbox container, e.g. 2 or 1.5 (implies --sandbox)
+    #[arg(long = "cpus", value_name = "CPUS", value_parser = parse_cpu_limit)]
+    cpu_limit: Option<String>,
+
+    /// Memory limit for the sandbox container, e.g. 8g (implies --sandbox)
+    #[arg(long = "memory", value_name = "SIZE", value_parser = parse_memory_limit)]
+    memory_limit: Option<String>,
+
+    /// Mount the project read-only; the agent works on a copy whose changes are
+    /// applied with 'aoe sandbox apply' (implies --sandbox)
+    #[arg(long)]
+    isolated: bool,
+
     /// Automatically trust repository hooks without prompting
     #[arg(long = "trust-hooks")]
     trust_hooks: bool,
@@ -68,22 +95,34 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
 
     let mut worktree_info_opt = None;
 
-    if let Some(branch_raw) = &args.worktree_branch {
+    let worktree_branch = match (&args.worktree_branch, args.pull_request) {
+        (Some(branch), _) => Some(branch.trim().to_string()),
+        (None, Some(number)) => Some(format!("pr-{}", number)),
+        (None, None) => None,
+    };
+
+    if let Some(branch) = worktree_branch.as_deref() {
         use crate::git::GitWorktree;
         use crate::session::WorktreeInfo;
         use chrono::Utc;
 
-        let branch = branch_raw.trim();
-
         if !GitWorktree::is_git_repo(&path) {
             bail!("Path is not in a git repository\nTip: Navigate to a git repository first");
         }
 
-        let config = Config::load()?;
+        let config = crate::session::resolve_config_with_repo(profile, &path)?;
 
         let main_repo_path = GitWorktree::find_main_repo(&path)?;
         let git_wt = GitWorktree::new(main_repo_path.clone())?;
 
+        let base = if let Some(number) = args.pull_request {
+            let remote = args.remote.as_deref().unwrap_or("origin");
+            println!("Fetching pull request #{} from {}", number, remote);
+            Some(git_wt.fetch_pull_request(remote, number, &config.worktree.pull_request_ref)?)
+        } else {
+            args.base.clone()
+        };
+
         let session_id = uuid::Uuid::new_v4().to_string();
         let session_id_short = &session_id[..8];
 
@@ -105,7 +144,10 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
         }
 
         println!("Creating worktree at: {}", worktree_path.display());
-        git_wt.create_worktree(branch, &worktree_path, args.create_branch)?;
+        match &base {
+            Some(base) => git_wt.create_worktree_from_base(branch, &worktree_path, base)?,
+            None => git_wt.create_worktree(branch, &worktree_path, args.create_branch)?,
+        }
 
         path = worktree_path;
 
@@ -115,6 +157,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             managed_by_aoe: true,
             created_at: Utc::now(),
             cleanup_on_delete: true,
+            sync_conflicts: Vec::new(),
         });
 
         println!("✓ Worktree created successfully");
@@ -153,6 +196,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     };
 
     let mut instance = Instance::new(&final_title, path.to_str().unwrap_or(""));
+    instance.profile = storage.profile().to_string();
 
     if let Some(group) = &group_path {
         instance.group_path = group.trim().to_string();
@@ -172,25 +216,48 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     }
 
     // Handle sandbox setup
-    let use_sandbox = args.sandbox || args.sandbox_image.is_some();
-    let config = Config::load()?;
+    let use_sandbox = args.sandbox
+        || args.sandbox_image.is_some()
+        || args.cpu_limit.is_some()
+        || args.memory_limit.is_some()
+        || args.isolated;
+    let config = resolve_config(profile)?;
 
     if use_sandbox || config.sandbox.enabled_by_default {
-        if !docker::is_docker_available() {
+        let runtime = config.sandbox.runtime;
+        if !runtime.is_available() {
             if use_sandbox {
                 bail!(
-                    "Docker is not installed or not accessible.\n\
-                     Install Docker: https://docs.docker.com/get-docker/\n\
-                     Tip: Use 'aoe add' without --sandbox to run directly on host"
+                    "{} is not installed or not accessible.\n\
+                     Install it or set [sandbox] runtime in config.toml.\n\
+                     Tip: Use 'aoe add' without --sandbox to run directly on host",
+                    runtime.display_name()
                 );
             }
         } else {
             let container_name = DockerContainer::generate_name(&instance.id);
-            let image = args
-                .sandbox_image
-                .as_ref()
-                .map(|s| s.trim().to_string())
-                .unwrap_or_else(docker::effective_default_image);
+            // An explicit --sandbox-image wins over a configured Dockerfile
+            // and over the repo's devcontainer.json
+            let devcontainer = DevContainer::load_or_warn(&path);
+            let (image, build) = match &args.sandbox_image {
+                Some(image) => (image.trim().to_string(), None),
+                None => {
+                    let build = match crate::session::resolve_config_with_repo(profile, &path)?
+                        .sandbox
+                        .image_build(&path)?
+                    {
+                        Some(build) => Some(build),
+                        None => match &devcontainer {
+                            Some(devcontainer) => devcontainer.image_build()?,
+                            None => None,
+                        },
+                    };
+                    let image = devcontainer
+                        .and_then(|d| d.image)
+                        .unwrap_or_else(docker::effective_default_image);
+                    (image, build)
+                }
+            };
             instance.sandbox_info = Some(SandboxInfo {
                 enabled: true,
                 container_id: None,
@@ -200,6 +267,14 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                 yolo_mode: None,
                 extra_env_keys: None,
                 extra_env_values: None,
+                network: None,
+                runtime,
+                build,
+                forwarded_ports: Vec::new(),
+                snapshot: None,
+                cpu_limit: args.cpu_limit.clone(),
+                memory_limit: args.memory_limit.clone(),
+                isolated: args.isolated,
             });
         }
     }
@@ -223,6 +298,18 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                         println!("    {}", cmd);
                     }
                 }
+                if !hooks.devcontainer_on_create.is_empty() {
+                    println!("  postCreateCommand (devcontainer.json, sandbox only):");
+                    for cmd in &hooks.devcontainer_on_create {
+                        println!("    {}", cmd);
+                    }
+                }
+                if !hooks.devcontainer_settings.is_empty() {
+                    println!("  containerEnv and mounts (devcontainer.json, sandbox only):");
+                    for setting in &hooks.devcontainer_settings {
+                        println!("    {}", setting);
+                    }
+                }
                 print!("\nTrust and run these hooks? [y/N] ");
                 use std::io::Write;
                 std::io::stdout().flush()?;
@@ -232,17 +319,13 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             };
 
             if should_trust {
-                trust_and_run_on_create(&path, &hooks_hash, &hooks)?;
+                trust_and_run_on_create(&mut instance, &path, &hooks_hash, &hooks)?;
             } else {
                 println!("Hooks skipped (session created without running hooks)");
             }
         }
         Ok(repo_config::HookTrustStatus::Trusted(hooks)) => {
-            if !hooks.on_create.is_empty() {
-                println!("Running on_create hooks...");
-                repo_config::execute_hooks(&hooks.on_create, &path)?;
-                println!("✓ on_create hooks completed");
-            }
+            run_on_create(&mut instance, &path, &hooks)?;
         }
         Ok(repo_config::HookTrustStatus::NoHooks) => {}
         Err(e) => {
@@ -329,20 +412,62 @@ pub fn generate_unique_title(instances: &[Instance], base_title: &str, path: &st
 }
 
 fn trust_and_run_on_create(
+    instance: &mut Instance,
     project_path: &Path,
     hooks_hash: &str,
     hooks: &crate::session::HooksConfig,
 ) -> Result<()> {
     repo_config::trust_repo(project_path, hooks_hash)?;
     println!("✓ Repository hooks trusted");
+    run_on_create(instance, project_path, hooks)
+}
+
+fn run_on_create(
+    instance: &mut Instance,
+    project_path: &Path,
+    hooks: &crate::session::HooksConfig,
+) -> Result<()> {
     if !hooks.on_create.is_empty() {
         println!("Running on_create hooks...");
         repo_config::execute_hooks(&hooks.on_create, project_path)?;
         println!("✓ on_create hooks completed");
     }
+    if instance.is_sandboxed() && !hooks.devcontainer_on_create.is_empty() {
+        println!("Running devcontainer postCreateCommand...");
+        instance.ensure_container_running()?;
+        if let Some(sandbox) = &instance.sandbox_info {
+            repo_config::execute_hooks_in_container(
+                &hooks.devcontainer_on_create,
+                sandbox.runtime,
+                &sandbox.container_name,
+                &instance.container_workdir(),
+            )?;
+        }
+        println!("✓ postCreateCommand completed");
+    }
     Ok(())
 }
 
+/// Parse a `--cpus` value.
+pub(super) fn parse_cpu_limit(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("CPU limit cannot be empty".to_string());
+    }
+    crate::session::validate_cpu_limit(value)?;
+    Ok(value.to_string())
+}
+
+/// Parse a `--memory` value.
+pub(super) fn parse_memory_limit(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Memory limit cannot be empty".to_string());
+    }
+    crate::session::validate_memory_limit(value)?;
+    Ok(value.to_string())
+}
+
 fn detect_tool(cmd: &str) -> Result<String> {
     let cmd_lower = cmd.to_lowercase();
     if cmd_lower.is_empty() || cmd_lower.contains("claude") {
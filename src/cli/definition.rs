@@ -6,11 +6,14 @@
 use clap::{Parser, Subcommand};
 
 use super::add::AddArgs;
+use super::diff::DiffArgs;
+use super::doctor::DoctorArgs;
 use super::group::GroupCommands;
 use super::init::InitArgs;
 use super::list::ListArgs;
 use super::profile::ProfileCommands;
 use super::remove::RemoveArgs;
+use super::sandbox::SandboxCommands;
 use super::session::SessionCommands;
 use super::status::StatusArgs;
 use super::tmux::TmuxCommands;
@@ -40,7 +43,7 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Add a new session
-    Add(AddArgs),
+    Add(Box<AddArgs>),
 
     /// Initialize .aoe/config.toml in a repository
     Init(InitArgs),
@@ -56,12 +59,21 @@ pub enum Commands {
     /// Show session status summary
     Status(StatusArgs),
 
+    /// Show or export a session's changes against a base branch
+    Diff(DiffArgs),
+
     /// Manage session lifecycle (start, stop, attach, etc.)
     Session {
         #[command(subcommand)]
         command: SessionCommands,
     },
 
+    /// Manage sandbox containers (snapshots and rollback)
+    Sandbox {
+        #[command(subcommand)]
+        command: SandboxCommands,
+    },
+
     /// Manage groups for organizing sessions
     Group {
         #[command(subcommand)]
@@ -86,6 +98,9 @@ pub enum Commands {
         command: TmuxCommands,
     },
 
+    /// Find and fix leftovers: orphaned worktrees, containers and tmux sessions
+    Doctor(DoctorArgs),
+
     /// Uninstall Agent of Empires
     Uninstall(UninstallArgs),
 }
@@ -0,0 +1,307 @@
+//! `agent-of-empires diff` command implementation
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use similar::ChangeTag;
+
+use crate::git::diff::{compute_changed_files, compute_file_diff, compute_patch, DiffFile};
+use crate::session::{resolve_config, Storage};
+
+const STAT_BAR_WIDTH: usize = 40;
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Branch, tag or commit to diff against (defaults to the diff view's base branch)
+    #[arg(short = 'b', long)]
+    base: Option<String>,
+
+    /// Output format
+    #[arg(short = 'f', long, value_enum, default_value_t = DiffFormat::Patch)]
+    format: DiffFormat,
+
+    /// Write output to a file instead of stdout
+    #[arg(short = 'o', long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiffFormat {
+    /// Unified patch, suitable for `git apply` or `aoe session apply-patch`
+    Patch,
+    /// Per-file summary of changed lines
+    Stat,
+    /// Files and hunks as JSON
+    Json,
+}
+
+#[derive(Serialize)]
+struct DiffJson {
+    session: String,
+    base: String,
+    additions: usize,
+    deletions: usize,
+    files: Vec<FileJson>,
+}
+
+#[derive(Serialize)]
+struct FileJson {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_path: Option<String>,
+    status: &'static str,
+    additions: usize,
+    deletions: usize,
+    binary: bool,
+    hunks: Vec<HunkJson>,
+}
+
+#[derive(Serialize)]
+struct HunkJson {
+    old_start: usize,
+    old_lines: usize,
+    new_start: usize,
+    new_lines: usize,
+    lines: Vec<String>,
+}
+
+pub async fn run(profile: &str, args: DiffArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+
+    let config = resolve_config(profile)?;
+    let repo_path = Path::new(&inst.project_path);
+    let base = args
+        .base
+        .clone()
+        .unwrap_or_else(|| config.diff.base_branch_for(repo_path));
+    let context_lines = config.diff.context_lines;
+
+    let output = match args.format {
+        DiffFormat::Patch => compute_patch(repo_path, &base, context_lines)?,
+        DiffFormat::Stat => {
+            let files = compute_changed_files(repo_path, &base)?;
+            format_stat(&files).into_bytes()
+        }
+        DiffFormat::Json => {
+            let files = compute_changed_files(repo_path, &base)?;
+            let json = build_json(&inst.title, &base, repo_path, &files, context_lines);
+            let mut out = serde_json::to_vec_pretty(&json)?;
+            out.push(b'\n');
+            out
+        }
+    };
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, &output)?;
+            if args.format == DiffFormat::Patch && output.is_empty() {
+                eprintln!("No changes against '{}'", base);
+            }
+        }
+        None => std::io::stdout().write_all(&output)?,
+    }
+
+    Ok(())
+}
+
+fn format_stat(files: &[DiffFile]) -> String {
+    if files.is_empty() {
+        return String::new();
+    }
+
+    let path_width = files
+        .iter()
+        .map(|f| f.path.display().to_string().chars().count())
+        .max()
+        .unwrap_or(0);
+    let max_changes = files
+        .iter()
+        .map(|f| f.additions + f.deletions)
+        .max()
+        .unwrap_or(0);
+    let count_width = max_changes.to_string().len();
+
+    let mut out = String::new();
+    let mut total_additions = 0;
+    let mut total_deletions = 0;
+
+    for file in files {
+        total_additions += file.additions;
+        total_deletions += file.deletions;
+
+        let changes = file.additions + file.deletions;
+        let (plus, minus) = if max_changes > STAT_BAR_WIDTH {
+            let scale = |n: usize| (n * STAT_BAR_WIDTH).div_ceil(max_changes);
+            (scale(file.additions), scale(file.deletions))
+        } else {
+            (file.additions, file.deletions)
+        };
+
+        out.push_str(&format!(
+            " {:<path_width$} | {:>count_width$} {}{}\n",
+            file.path.display(),
+            changes,
+            "+".repeat(plus),
+            "-".repeat(minus),
+        ));
+    }
+
+    out.push_str(&format!(
+        " {} file{} changed, {} insertion{}(+), {} deletion{}(-)\n",
+        files.len(),
+        if files.len() == 1 { "" } else { "s" },
+        total_additions,
+        if total_additions == 1 { "" } else { "s" },
+        total_deletions,
+        if total_deletions == 1 { "" } else { "s" },
+    ));
+    out
+}
+
+fn build_json(
+    session: &str,
+    base: &str,
+    repo_path: &Path,
+    files: &[DiffFile],
+    context_lines: usize,
+) -> DiffJson {
+    let files: Vec<FileJson> = files
+        .iter()
+        .map(|file| {
+            let file_diff = compute_file_diff(repo_path, &file.path, base, context_lines).ok();
+            let hunks = file_diff
+                .as_ref()
+                .map(|d| {
+                    d.hunks
+                        .iter()
+                        .map(|h| HunkJson {
+                            old_start: h.old_start,
+                            old_lines: h.old_lines,
+                            new_start: h.new_start,
+                            new_lines: h.new_lines,
+                            lines: h
+                                .lines
+                                .iter()
+                                .map(|l| {
+                                    let prefix = match l.tag {
+                                        ChangeTag::Insert => '+',
+                                        ChangeTag::Delete => '-',
+                                        ChangeTag::Equal => ' ',
+                                    };
+                                    format!("{}{}", prefix, l.content.trim_end_matches('\n'))
+                                })
+                                .collect(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            FileJson {
+                path: file.path.display().to_string(),
+                old_path: file.old_path.as_ref().map(|p| p.display().to_string()),
+                status: file.status.label(),
+                additions: file.additions,
+                deletions: file.deletions,
+                binary: file_diff.map(|d| d.is_binary).unwrap_or(false),
+                hunks,
+            }
+        })
+        .collect();
+
+    DiffJson {
+        session: session.to_string(),
+        base: base.to_string(),
+        additions: files.iter().map(|f| f.additions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+        files,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::FileStatus;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn file(path: &str, additions: usize, deletions: usize) -> DiffFile {
+        DiffFile {
+            path: PathBuf::from(path),
+            old_path: None,
+            status: FileStatus::Modified,
+            additions,
+            deletions,
+        }
+    }
+
+    #[test]
+    fn test_format_stat_empty() {
+        assert_eq!(format_stat(&[]), "");
+    }
+
+    #[test]
+    fn test_format_stat_aligns_paths_and_counts() {
+        let stat = format_stat(&[file("src/main.rs", 3, 1), file("README.md", 10, 0)]);
+        assert_eq!(
+            stat,
+            " src/main.rs |  4 +++-\n \
+             README.md   | 10 ++++++++++\n \
+             2 files changed, 13 insertions(+), 1 deletion(-)\n"
+        );
+    }
+
+    #[test]
+    fn test_format_stat_scales_long_bars() {
+        let stat = format_stat(&[file("big.rs", 100, 100), file("small.rs", 1, 0)]);
+        let lines: Vec<&str> = stat.lines().collect();
+        let bar = |line: &str| line.rsplit(' ').next().unwrap().len();
+        assert_eq!(bar(lines[0]), STAT_BAR_WIDTH);
+        // Small changes still get a mark
+        assert_eq!(bar(lines[1]), 1);
+        assert_eq!(
+            lines[2],
+            " 2 files changed, 101 insertions(+), 100 deletions(-)"
+        );
+    }
+
+    #[test]
+    fn test_build_json_includes_hunks() {
+        let dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("notes.txt"), "one\ntwo\n").unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        fs::write(dir.path().join("notes.txt"), "one\n2\n").unwrap();
+        let files = compute_changed_files(dir.path(), &base).unwrap();
+        let json = build_json("my-session", &base, dir.path(), &files, 3);
+
+        assert_eq!(json.session, "my-session");
+        assert_eq!(json.base, base);
+        assert_eq!((json.additions, json.deletions), (1, 1));
+        assert_eq!(json.files.len(), 1);
+        let notes = &json.files[0];
+        assert_eq!(notes.path, "notes.txt");
+        assert_eq!(notes.status, "modified");
+        assert!(!notes.binary);
+        assert_eq!(notes.hunks.len(), 1);
+        assert_eq!(notes.hunks[0].lines, vec![" one", "-two", "+2"]);
+
+        let value = serde_json::to_value(&json).unwrap();
+        assert!(value["files"][0].get("old_path").is_none());
+    }
+}
@@ -0,0 +1,670 @@
+//! `agent-of-empires doctor` command implementation
+//!
+//! Cross-references saved sessions (in every profile) against the resources
+//! aoe creates outside of `sessions.json`: git worktrees and branches, sandbox
+//! containers, tmux sessions and the hook trust store.
+
+use anyhow::Result;
+use clap::Args;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::cli::truncate_id;
+use crate::docker::{self, ContainerRuntime, DockerContainer, SANDBOX_CONTAINER_PREFIX};
+use crate::git::template::{resolve_template, TemplateVars};
+use crate::git::GitWorktree;
+use crate::session::{self, repo_config, GroupTree, Instance, Storage};
+use crate::tmux;
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Fix the problems found (prompts for confirmation)
+    #[arg(long)]
+    fix: bool,
+
+    /// Don't ask for confirmation before fixing
+    #[arg(short = 'y', long, requires = "fix")]
+    yes: bool,
+}
+
+/// A session whose project directory no longer exists
+struct MissingPath {
+    profile: String,
+    instance: Instance,
+}
+
+/// A worktree of a session's main repo that no session points at
+struct OrphanedWorktree {
+    repo: PathBuf,
+    path: PathBuf,
+    branch: Option<String>,
+}
+
+/// A sandbox container with no session
+struct OrphanedContainer {
+    runtime: ContainerRuntime,
+    name: String,
+}
+
+/// A merged branch that aoe created for a session and nothing has checked
+/// out anymore
+struct StaleBranch {
+    repo: PathBuf,
+    branch: String,
+}
+
+#[derive(Default)]
+struct Findings {
+    missing_paths: Vec<MissingPath>,
+    orphaned_worktrees: Vec<OrphanedWorktree>,
+    stale_branches: Vec<StaleBranch>,
+    orphaned_containers: Vec<OrphanedContainer>,
+    orphaned_tmux_sessions: Vec<String>,
+    stale_trust_entries: Vec<String>,
+}
+
+impl Findings {
+    fn total(&self) -> usize {
+        self.missing_paths.len()
+            + self.orphaned_worktrees.len()
+            + self.stale_branches.len()
+            + self.orphaned_containers.len()
+            + self.orphaned_tmux_sessions.len()
+            + self.stale_trust_entries.len()
+    }
+}
+
+pub async fn run(args: DoctorArgs) -> Result<()> {
+    let mut profiles = Vec::new();
+    for profile in session::list_profiles()? {
+        let storage = Storage::new(&profile)?;
+        let (instances, groups) = storage.load_with_groups()?;
+        profiles.push((profile, instances, groups));
+    }
+    let all_instances: Vec<&Instance> = profiles.iter().flat_map(|(_, i, _)| i).collect();
+
+    // Session IDs are embedded (truncated) in tmux and container names
+    let short_ids: HashSet<&str> = all_instances
+        .iter()
+        .map(|i| truncate_id(&i.id, 8))
+        .collect();
+
+    let mut findings = Findings::default();
+
+    for (profile, instances, _) in &profiles {
+        for inst in instances {
+            if !Path::new(&inst.project_path).exists() {
+                findings.missing_paths.push(MissingPath {
+                    profile: profile.clone(),
+                    instance: inst.clone(),
+                });
+            }
+        }
+    }
+
+    // Worktree locations aoe may have used, from the global config and every
+    // profile's
+    let mut templates = BTreeSet::new();
+    let configs = std::iter::once(session::Config::load()).chain(
+        profiles
+            .iter()
+            .map(|(profile, _, _)| session::resolve_config(profile)),
+    );
+    for config in configs.flatten() {
+        templates.insert(config.worktree.path_template);
+        templates.insert(config.worktree.bare_repo_path_template);
+    }
+    let templates: Vec<String> = templates.into_iter().collect();
+
+    check_worktrees(&all_instances, &templates, &mut findings);
+
+    // Every runtime a profile is configured with or a session was created
+    // with may hold sandbox containers
+    let mut runtimes: Vec<ContainerRuntime> = Vec::new();
+    let configured = profiles
+        .iter()
+        .map(|(profile, _, _)| ContainerRuntime::for_profile(profile));
+    let recorded = all_instances
+        .iter()
+        .filter_map(|i| i.sandbox_info.as_ref().map(|s| s.runtime));
+    for runtime in configured.chain(recorded) {
+        if !runtimes.contains(&runtime) {
+            runtimes.push(runtime);
+        }
+    }
+    for runtime in runtimes {
+        if !runtime.is_available() || !runtime.is_daemon_running() {
+            println!(
+                "{} not available, skipping its container check",
+                runtime.display_name()
+            );
+            continue;
+        }
+        match docker::list_sandbox_containers(runtime) {
+            Ok(names) => {
+                findings.orphaned_containers.extend(
+                    names
+                        .into_iter()
+                        .filter(|name| {
+                            let id = name.trim_start_matches(SANDBOX_CONTAINER_PREFIX);
+                            !short_ids.contains(id)
+                        })
+                        .map(|name| OrphanedContainer { runtime, name }),
+                );
+            }
+            Err(e) => eprintln!(
+                "⚠ Skipping {} container check: {}",
+                runtime.display_name(),
+                e
+            ),
+        }
+    }
+
+    match tmux::list_aoe_sessions() {
+        Some(names) => {
+            // Names end with `_<short id>` for agent, terminal and container terminal sessions
+            findings.orphaned_tmux_sessions = names
+                .into_iter()
+                .filter(|name| {
+                    name.rsplit_once('_')
+                        .map_or(true, |(_, id)| !short_ids.contains(id))
+                })
+                .collect();
+        }
+        None => println!("tmux not available, skipping tmux session check"),
+    }
+
+    findings.stale_trust_entries = repo_config::trusted_repo_paths()?
+        .into_iter()
+        .filter(|path| !Path::new(path).exists())
+        .collect();
+
+    if findings.total() == 0 {
+        println!("✓ No problems found");
+        return Ok(());
+    }
+
+    print_findings(&findings);
+
+    if !args.fix {
+        println!("Run 'aoe doctor --fix' to clean these up.");
+        return Ok(());
+    }
+
+    if !args.yes {
+        print!("Fix {} problem(s)? (y/N): ", findings.total());
+        io::stdout().flush()?;
+
+        let mut response = String::new();
+        io::stdin().read_line(&mut response)?;
+        let response = response.trim().to_lowercase();
+        if response != "y" && response != "yes" {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    let fixed = fix_findings(&findings, profiles)?;
+    println!("\n✓ Fixed {} of {} problem(s)", fixed, findings.total());
+
+    Ok(())
+}
+
+/// Find worktrees in every repo that sessions were created from which no
+/// session refers to anymore, and merged branches that aoe created for
+/// sessions. Only worktrees on a branch aoe created, or at a path one of the
+/// worktree `templates` gives, count as aoe's; other worktrees and branches
+/// are the user's and are left alone.
+fn check_worktrees(instances: &[&Instance], templates: &[String], findings: &mut Findings) {
+    let repos: BTreeSet<&str> = instances
+        .iter()
+        .filter_map(|i| i.worktree_info.as_ref())
+        .map(|wt| wt.main_repo_path.as_str())
+        .collect();
+
+    let mut managed_branches: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for wt in instances.iter().filter_map(|i| i.worktree_info.as_ref()) {
+        if wt.managed_by_aoe {
+            managed_branches
+                .entry(wt.main_repo_path.as_str())
+                .or_default()
+                .insert(wt.branch.as_str());
+        }
+    }
+
+    let session_paths: HashSet<PathBuf> = instances
+        .iter()
+        .map(|i| canonical(Path::new(&i.project_path)))
+        .collect();
+
+    for repo in repos {
+        let repo_path = Path::new(repo);
+        if !repo_path.exists() {
+            continue;
+        }
+        let Ok(git_wt) = GitWorktree::new(repo_path.to_path_buf()) else {
+            continue;
+        };
+
+        match git_wt.list_worktrees() {
+            Ok(worktrees) => {
+                for wt in worktrees {
+                    if wt.path == git_wt.repo_path || session_paths.contains(&canonical(&wt.path)) {
+                        continue;
+                    }
+                    let Some(branch) = wt.branch.as_deref() else {
+                        continue;
+                    };
+                    let managed = managed_branches
+                        .get(repo)
+                        .is_some_and(|branches| branches.contains(branch));
+                    if !managed && !is_template_path(repo_path, templates, branch, &wt.path) {
+                        continue;
+                    }
+                    findings.orphaned_worktrees.push(OrphanedWorktree {
+                        repo: repo_path.to_path_buf(),
+                        path: wt.path,
+                        branch: wt.branch,
+                    });
+                }
+            }
+            Err(e) => eprintln!("⚠ Could not list worktrees in {}: {}", repo, e),
+        }
+
+        let Some(managed) = managed_branches.get(repo) else {
+            continue;
+        };
+        let base =
+            crate::git::diff::get_default_branch(repo_path).unwrap_or_else(|_| "main".to_string());
+        match git_wt.list_merged_branches(&base) {
+            Ok(branches) => {
+                // Branches of orphaned worktrees get deleted with the worktree
+                findings.stale_branches.extend(
+                    branches
+                        .into_iter()
+                        .filter(|branch| managed.contains(branch.as_str()))
+                        .map(|branch| StaleBranch {
+                            repo: repo_path.to_path_buf(),
+                            branch,
+                        }),
+                );
+            }
+            Err(e) => eprintln!("⚠ Could not list branches in {}: {}", repo, e),
+        }
+    }
+}
+
+fn print_findings(findings: &Findings) {
+    if !findings.missing_paths.is_empty() {
+        println!("Sessions whose project path no longer exists:\n");
+        for missing in &findings.missing_paths {
+            println!(
+                "  • {} ({}) [profile: {}]",
+                missing.instance.title,
+                truncate_id(&missing.instance.id, 8),
+                missing.profile
+            );
+            println!("    Missing path: {}", missing.instance.project_path);
+        }
+        println!();
+    }
+
+    if !findings.orphaned_worktrees.is_empty() {
+        println!("Worktrees with no session:\n");
+        for wt in &findings.orphaned_worktrees {
+            println!("  • {}", wt.path.display());
+            println!(
+                "    Branch: {}",
+                wt.branch.as_deref().unwrap_or("(unknown)")
+            );
+        }
+        println!();
+    }
+
+    if !findings.stale_branches.is_empty() {
+        println!("Merged session branches not checked out anywhere:\n");
+        for stale in &findings.stale_branches {
+            println!("  • {} ({})", stale.branch, stale.repo.display());
+        }
+        println!();
+    }
+
+    if !findings.orphaned_containers.is_empty() {
+        println!("Sandbox containers with no session:\n");
+        for container in &findings.orphaned_containers {
+            println!(
+                "  • {} ({})",
+                container.name,
+                container.runtime.display_name()
+            );
+        }
+        println!();
+    }
+
+    if !findings.orphaned_tmux_sessions.is_empty() {
+        println!("tmux sessions with no session:\n");
+        for name in &findings.orphaned_tmux_sessions {
+            println!("  • {}", name);
+        }
+        println!();
+    }
+
+    if !findings.stale_trust_entries.is_empty() {
+        println!("Trusted repos that no longer exist:\n");
+        for path in &findings.stale_trust_entries {
+            println!("  • {}", path);
+        }
+        println!();
+    }
+}
+
+fn fix_findings(
+    findings: &Findings,
+    profiles: Vec<(String, Vec<Instance>, Vec<session::Group>)>,
+) -> Result<usize> {
+    let mut fixed = 0;
+
+    // Sessions with a vanished project path: drop them along with their
+    // tmux sessions and containers, which would otherwise become orphans.
+    for (profile, mut instances, groups) in profiles {
+        let removed: Vec<&MissingPath> = findings
+            .missing_paths
+            .iter()
+            .filter(|m| m.profile == profile)
+            .collect();
+        if removed.is_empty() {
+            continue;
+        }
+
+        for missing in &removed {
+            let inst = &missing.instance;
+            if let Ok(session) = tmux::Session::new(&inst.id, &inst.title) {
+                if session.exists() {
+                    let _ = session.kill();
+                }
+            }
+            if inst.is_sandboxed() {
+                let _ = DockerContainer::from_session_id(&inst.id, inst.container_runtime())
+                    .remove(true);
+            }
+        }
+
+        instances.retain(|i| !removed.iter().any(|m| m.instance.id == i.id));
+        let storage = Storage::new(&profile)?;
+        let group_tree = GroupTree::new_with_groups(&instances, &groups);
+        storage.save_with_groups(&instances, &group_tree)?;
+
+        for missing in &removed {
+            println!("✓ Removed session: {}", missing.instance.title);
+        }
+        fixed += removed.len();
+    }
+
+    for wt in &findings.orphaned_worktrees {
+        let result = GitWorktree::new(wt.repo.clone()).and_then(|git_wt| {
+            git_wt.remove_worktree(&wt.path)?;
+            if let Some(branch) = &wt.branch {
+                // Only deletes the branch when it is fully merged
+                let _ = git_wt.delete_branch(branch);
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => {
+                println!("✓ Removed worktree: {}", wt.path.display());
+                fixed += 1;
+            }
+            Err(e) => eprintln!("✗ Failed to remove {}: {}", wt.path.display(), e),
+        }
+    }
+
+    for stale in &findings.stale_branches {
+        match GitWorktree::new(stale.repo.clone()).and_then(|g| g.delete_branch(&stale.branch)) {
+            Ok(()) => {
+                println!("✓ Deleted branch: {}", stale.branch);
+                fixed += 1;
+            }
+            Err(e) => eprintln!("✗ Failed to delete branch {}: {}", stale.branch, e),
+        }
+    }
+
+    for orphan in &findings.orphaned_containers {
+        let container = DockerContainer {
+            name: orphan.name.clone(),
+            image: String::new(),
+            runtime: orphan.runtime,
+        };
+        match container.remove(true) {
+            Ok(()) => {
+                println!("✓ Removed container: {}", orphan.name);
+                fixed += 1;
+            }
+            Err(e) => eprintln!("✗ Failed to remove container {}: {}", orphan.name, e),
+        }
+    }
+
+    for name in &findings.orphaned_tmux_sessions {
+        match tmux::kill_session_by_name(name) {
+            Ok(()) => {
+                println!("✓ Killed tmux session: {}", name);
+                fixed += 1;
+            }
+            Err(e) => eprintln!("✗ {}", e),
+        }
+    }
+
+    if !findings.stale_trust_entries.is_empty() {
+        match repo_config::untrust_repos(&findings.stale_trust_entries) {
+            Ok(()) => {
+                println!(
+                    "✓ Removed {} stale trust entr{}",
+                    findings.stale_trust_entries.len(),
+                    if findings.stale_trust_entries.len() == 1 {
+                        "y"
+                    } else {
+                        "ies"
+                    }
+                );
+                fixed += findings.stale_trust_entries.len();
+            }
+            Err(e) => eprintln!("✗ Failed to update trusted repos: {}", e),
+        }
+    }
+
+    Ok(fixed)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether `path` is where one of `templates` puts a worktree of `branch`
+/// in `repo`, for any session id
+fn is_template_path(repo: &Path, templates: &[String], branch: &str, path: &Path) -> bool {
+    // Stands in for the session id, which isn't known here
+    const SESSION_ID: &str = "\u{0}";
+
+    let base = canonical(repo);
+    let vars = TemplateVars {
+        repo_name: repo
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("repo")
+            .to_string(),
+        branch: branch.to_string(),
+        session_id: SESSION_ID.to_string(),
+        base_path: base,
+    };
+    let path = canonical(path);
+    let path = path.to_string_lossy();
+
+    templates.iter().any(|template| {
+        let Ok(expected) = resolve_template(template, &vars) else {
+            return false;
+        };
+        let expected = normalize(&expected);
+        let pattern = expected
+            .to_string_lossy()
+            .split(SESSION_ID)
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join("[0-9a-f]{8}");
+        regex::Regex::new(&format!("^{}$", pattern)).is_ok_and(|re| re.is_match(&path))
+    })
+}
+
+/// Resolve `.` and `..` in `path` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::WorktreeInfo;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    fn session_on_branch(repo: &Path, branch: &str, managed_by_aoe: bool) -> Instance {
+        let mut inst = Instance::new(branch, &repo.to_string_lossy());
+        inst.worktree_info = Some(WorktreeInfo {
+            branch: branch.to_string(),
+            main_repo_path: repo.to_string_lossy().to_string(),
+            managed_by_aoe,
+            created_at: chrono::Utc::now(),
+            cleanup_on_delete: true,
+            sync_conflicts: Vec::new(),
+        });
+        inst
+    }
+
+    #[test]
+    fn test_fix_deletes_only_merged_branches_aoe_created() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        git(repo, &["-c", "init.defaultBranch=main", "init"]);
+        git(repo, &["commit", "--allow-empty", "-m", "initial"]);
+        for branch in ["aoe-feature", "user-feature", "user-topic"] {
+            git(repo, &["branch", branch]);
+        }
+
+        let aoe = session_on_branch(repo, "aoe-feature", true);
+        let user = session_on_branch(repo, "user-feature", false);
+        let mut findings = Findings::default();
+        check_worktrees(&[&aoe, &user], &[], &mut findings);
+
+        let stale: Vec<&str> = findings
+            .stale_branches
+            .iter()
+            .map(|s| s.branch.as_str())
+            .collect();
+        assert_eq!(stale, vec!["aoe-feature"]);
+
+        assert_eq!(fix_findings(&findings, Vec::new()).unwrap(), 1);
+        let branches = git(repo, &["branch", "--format=%(refname:short)"]);
+        let branches: Vec<&str> = branches.lines().collect();
+        assert_eq!(branches, vec!["main", "user-feature", "user-topic"]);
+    }
+
+    #[test]
+    fn test_no_stale_branches_without_aoe_sessions() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        git(repo, &["-c", "init.defaultBranch=main", "init"]);
+        git(repo, &["commit", "--allow-empty", "-m", "initial"]);
+        git(repo, &["branch", "merged"]);
+
+        let user = session_on_branch(repo, "merged", false);
+        let mut findings = Findings::default();
+        check_worktrees(&[&user], &[], &mut findings);
+        assert!(findings.stale_branches.is_empty());
+        assert_eq!(findings.total(), 0);
+    }
+
+    #[test]
+    fn test_fix_keeps_worktrees_aoe_did_not_create() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("app");
+        std::fs::create_dir(&repo).unwrap();
+        git(&repo, &["-c", "init.defaultBranch=main", "init"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        let template = "../{repo-name}-worktrees/{branch}".to_string();
+
+        // An aoe worktree whose session is gone, and one the user made
+        let aoe_path = dir.path().join("app-worktrees/old-feature");
+        let user_path = dir.path().join("scratch");
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                "old-feature",
+                &aoe_path.to_string_lossy(),
+            ],
+        );
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                "experiment",
+                &user_path.to_string_lossy(),
+            ],
+        );
+
+        let session = session_on_branch(&repo, "main", false);
+        let mut findings = Findings::default();
+        check_worktrees(&[&session], &[template], &mut findings);
+
+        let orphaned: Vec<PathBuf> = findings
+            .orphaned_worktrees
+            .iter()
+            .map(|wt| canonical(&wt.path))
+            .collect();
+        assert_eq!(orphaned, vec![canonical(&aoe_path)]);
+
+        fix_findings(&findings, Vec::new()).unwrap();
+        assert!(!aoe_path.exists());
+        assert!(user_path.exists());
+        let branches = git(&repo, &["branch", "--format=%(refname:short)"]);
+        assert!(branches.lines().any(|b| b == "experiment"));
+    }
+
+    #[test]
+    fn test_template_path_matches_any_session_id() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("app");
+        std::fs::create_dir(&repo).unwrap();
+        let templates = vec!["../wt/{branch}-{session-id}".to_string()];
+
+        let matches =
+            |path: &str| is_template_path(&repo, &templates, "feat/x", &dir.path().join(path));
+        assert!(matches("wt/feat-x-1a2b3c4d"));
+        assert!(!matches("wt/feat-x-mine"));
+        assert!(!matches("wt/other-1a2b3c4d"));
+    }
+}
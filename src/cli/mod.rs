@@ -2,11 +2,14 @@
 
 pub mod add;
 pub mod definition;
+pub mod diff;
+pub mod doctor;
 pub mod group;
 pub mod init;
 pub mod list;
 pub mod profile;
 pub mod remove;
+pub mod sandbox;
 pub mod session;
 pub mod status;
 pub mod tmux;
@@ -3,7 +3,7 @@
 use anyhow::{bail, Result};
 use clap::Args;
 
-use crate::docker::DockerContainer;
+use crate::docker::{isolated, snapshot, DockerContainer};
 use crate::session::{Config, GroupTree, Instance, Storage};
 
 #[derive(Args)]
@@ -122,7 +122,7 @@ pub async fn run(profile: &str, args: RemoveArgs) -> Result<()> {
                 if sandbox.enabled && !args.keep_container {
                     let config = Config::load().ok().unwrap_or_default();
                     if config.sandbox.auto_cleanup {
-                        let container = DockerContainer::from_session_id(&inst.id);
+                        let container = DockerContainer::from_session_id(&inst.id, sandbox.runtime);
                         if container.exists().unwrap_or(false) {
                             if let Err(e) = container.remove(true) {
                                 eprintln!("Warning: failed to remove container: {}", e);
@@ -130,6 +130,16 @@ pub async fn run(profile: &str, args: RemoveArgs) -> Result<()> {
                                 println!("✓ Container removed");
                             }
                         }
+                        if let Err(e) = snapshot::remove_all(sandbox.runtime, &container.name) {
+                            eprintln!("Warning: failed to remove snapshots: {}", e);
+                        }
+                        if sandbox.isolated {
+                            if let Err(e) =
+                                isolated::remove_volume(sandbox.runtime, &container.name)
+                            {
+                                eprintln!("Warning: failed to remove workspace copy: {}", e);
+                            }
+                        }
                     } else {
                         println!(
                             "Container preserved: {} (auto_cleanup disabled in config)",
@@ -0,0 +1,275 @@
+//! `agent-of-empires sandbox` subcommands implementation
+
+use anyhow::{bail, Result};
+use clap::Subcommand;
+
+use crate::docker::isolated;
+use crate::session::{GroupTree, Storage};
+
+#[derive(Subcommand)]
+pub enum SandboxCommands {
+    /// Save the state of a session's container as a snapshot
+    Snapshot {
+        /// Session ID or title
+        identifier: String,
+
+        /// Snapshot name (defaults to the current date and time)
+        name: Option<String>,
+    },
+
+    /// List a session's snapshots
+    Snapshots {
+        /// Session ID or title
+        identifier: String,
+    },
+
+    /// Recreate a session's container from a snapshot, keeping the project files
+    Rollback {
+        /// Session ID or title
+        identifier: String,
+
+        /// Snapshot name
+        snapshot: String,
+    },
+
+    /// Delete a snapshot
+    DeleteSnapshot {
+        /// Session ID or title
+        identifier: String,
+
+        /// Snapshot name
+        snapshot: String,
+    },
+
+    /// Show the changes an isolated session made to its copy of the project
+    Diff {
+        /// Session ID or title
+        identifier: String,
+
+        /// List changed files instead of the full diff
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Apply an isolated session's changes to the host project
+    Apply {
+        /// Session ID or title
+        identifier: String,
+
+        /// Files or directories to apply (all changes when omitted)
+        paths: Vec<String>,
+    },
+
+    /// Change a session's CPU or memory limit, applying it to the running container
+    Update {
+        /// Session ID or title
+        identifier: String,
+
+        /// CPU limit, e.g. 2 or 1.5
+        #[arg(long = "cpus", value_name = "CPUS", value_parser = super::add::parse_cpu_limit)]
+        cpu_limit: Option<String>,
+
+        /// Memory limit, e.g. 16g
+        #[arg(long = "memory", value_name = "SIZE", value_parser = super::add::parse_memory_limit)]
+        memory_limit: Option<String>,
+    },
+}
+
+pub async fn run(profile: &str, command: SandboxCommands) -> Result<()> {
+    match command {
+        SandboxCommands::Snapshot { identifier, name } => {
+            create_snapshot(profile, &identifier, name.as_deref()).await
+        }
+        SandboxCommands::Snapshots { identifier } => list_snapshots(profile, &identifier).await,
+        SandboxCommands::Rollback {
+            identifier,
+            snapshot,
+        } => rollback(profile, &identifier, &snapshot).await,
+        SandboxCommands::DeleteSnapshot {
+            identifier,
+            snapshot,
+        } => delete_snapshot(profile, &identifier, &snapshot).await,
+        SandboxCommands::Diff { identifier, stat } => diff(profile, &identifier, stat).await,
+        SandboxCommands::Apply { identifier, paths } => apply(profile, &identifier, &paths).await,
+        SandboxCommands::Update {
+            identifier,
+            cpu_limit,
+            memory_limit,
+        } => update(profile, &identifier, cpu_limit, memory_limit).await,
+    }
+}
+
+/// Load the profile's sessions and find a sandboxed one by identifier.
+fn load_sandboxed(
+    profile: &str,
+    identifier: &str,
+) -> Result<(Storage, Vec<crate::session::Instance>, GroupTree, usize)> {
+    let storage = Storage::new(profile)?;
+    let (instances, groups) = storage.load_with_groups()?;
+    let id = super::resolve_session(identifier, &instances)?.id.clone();
+    let idx = instances
+        .iter()
+        .position(|i| i.id == id)
+        .expect("resolved session is in the list");
+
+    if !instances[idx].is_sandboxed() {
+        bail!("Session '{}' is not sandboxed", instances[idx].title);
+    }
+
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    Ok((storage, instances, group_tree, idx))
+}
+
+async fn create_snapshot(profile: &str, identifier: &str, name: Option<&str>) -> Result<()> {
+    let (_, instances, _, idx) = load_sandboxed(profile, identifier)?;
+    let inst = &instances[idx];
+
+    println!("Snapshotting {}...", inst.title);
+    let snapshot = inst.create_snapshot(name)?;
+    println!("✓ Created snapshot {} ({})", snapshot.name, snapshot.image);
+    println!(
+        "  Roll back with: aoe sandbox rollback {} {}",
+        super::truncate_id(&inst.id, 8),
+        snapshot.name
+    );
+    Ok(())
+}
+
+async fn list_snapshots(profile: &str, identifier: &str) -> Result<()> {
+    let (_, instances, _, idx) = load_sandboxed(profile, identifier)?;
+    let inst = &instances[idx];
+    let snapshots = inst.list_snapshots()?;
+
+    if snapshots.is_empty() {
+        println!("No snapshots for {}.", inst.title);
+        println!(
+            "Create one with: aoe sandbox snapshot {} [name]",
+            super::truncate_id(&inst.id, 8)
+        );
+        return Ok(());
+    }
+
+    let active = inst
+        .sandbox_info
+        .as_ref()
+        .and_then(|s| s.snapshot.as_deref());
+
+    println!("Snapshots of {}:\n", inst.title);
+    println!("  {:<30} {:<20} {:<10}", "NAME", "CREATED", "SIZE");
+    for snapshot in &snapshots {
+        let marker = if active == Some(snapshot.name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{} {:<30} {:<20} {:<10}",
+            marker,
+            super::truncate(&snapshot.name, 30),
+            snapshot.created_short(),
+            snapshot.size
+        );
+    }
+    if active.is_some() {
+        println!("\n* current container was rolled back to this snapshot");
+    }
+    Ok(())
+}
+
+async fn rollback(profile: &str, identifier: &str, snapshot: &str) -> Result<()> {
+    let (storage, mut instances, group_tree, idx) = load_sandboxed(profile, identifier)?;
+
+    println!(
+        "Rolling back {} to snapshot {}...",
+        instances[idx].title, snapshot
+    );
+    instances[idx].rollback(snapshot)?;
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    println!("✓ Container recreated from snapshot {}", snapshot);
+    println!("  Project files were not changed");
+    Ok(())
+}
+
+async fn delete_snapshot(profile: &str, identifier: &str, snapshot: &str) -> Result<()> {
+    let (storage, mut instances, group_tree, idx) = load_sandboxed(profile, identifier)?;
+
+    instances[idx].delete_snapshot(snapshot)?;
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    println!("✓ Deleted snapshot {}", snapshot);
+    Ok(())
+}
+
+async fn update(
+    profile: &str,
+    identifier: &str,
+    cpu_limit: Option<String>,
+    memory_limit: Option<String>,
+) -> Result<()> {
+    if cpu_limit.is_none() && memory_limit.is_none() {
+        bail!("Nothing to update; pass --cpus and/or --memory");
+    }
+
+    let (storage, mut instances, group_tree, idx) = load_sandboxed(profile, identifier)?;
+    let updated = instances[idx].update_limits(cpu_limit, memory_limit)?;
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    let inst = &instances[idx];
+    let sandbox = inst.sandbox_info.as_ref().expect("session is sandboxed");
+    println!("✓ Updated limits of {}", inst.title);
+    if let Some(cpus) = &sandbox.cpu_limit {
+        println!("  CPUs:   {}", cpus);
+    }
+    if let Some(memory) = &sandbox.memory_limit {
+        println!("  Memory: {}", memory);
+    }
+    if !updated {
+        println!("  The container doesn't exist yet; the limits apply when it is created");
+    }
+    Ok(())
+}
+
+async fn diff(profile: &str, identifier: &str, stat: bool) -> Result<()> {
+    let (_, instances, _, idx) = load_sandboxed(profile, identifier)?;
+    let patch = instances[idx].isolated_diff()?;
+
+    if patch.is_empty() {
+        println!("No changes in {}.", instances[idx].title);
+        return Ok(());
+    }
+
+    if !stat {
+        print!("{}", patch);
+        return Ok(());
+    }
+
+    for (file, section) in isolated::split_patch(&patch) {
+        let status = if section.contains("\nnew file mode") {
+            "A"
+        } else if section.contains("\ndeleted file mode") {
+            "D"
+        } else {
+            "M"
+        };
+        println!("{} {}", status, file);
+    }
+    Ok(())
+}
+
+async fn apply(profile: &str, identifier: &str, paths: &[String]) -> Result<()> {
+    let (_, instances, _, idx) = load_sandboxed(profile, identifier)?;
+    let inst = &instances[idx];
+
+    let files = inst.apply_isolated_changes(paths)?;
+    if files.is_empty() {
+        println!("No matching changes to apply.");
+        return Ok(());
+    }
+
+    println!("✓ Applied {} file(s) to {}", files.len(), inst.project_path);
+    for file in &files {
+        println!("  {}", file);
+    }
+    Ok(())
+}
@@ -1,10 +1,14 @@
 //! `agent-of-empires session` subcommands implementation
 
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
 use anyhow::{bail, Result};
 use clap::{Args, Subcommand};
 use serde::Serialize;
 
-use crate::session::{GroupTree, Storage};
+use crate::session::{secrets, GroupTree, SandboxInfo, Storage};
 
 #[derive(Subcommand)]
 pub enum SessionCommands {
@@ -25,6 +29,9 @@ pub enum SessionCommands {
 
     /// Auto-detect current session
     Current(CurrentArgs),
+
+    /// Apply a patch file to a session's working directory
+    ApplyPatch(ApplyPatchArgs),
 }
 
 #[derive(Args)]
@@ -54,6 +61,19 @@ pub struct CurrentArgs {
     json: bool,
 }
 
+#[derive(Args)]
+pub struct ApplyPatchArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Patch file to apply ("-" reads from stdin)
+    patch: PathBuf,
+
+    /// Only check whether the patch applies cleanly
+    #[arg(long)]
+    check: bool,
+}
+
 #[derive(Serialize)]
 struct SessionDetails {
     id: String,
@@ -66,6 +86,33 @@ struct SessionDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     parent_session_id: Option<String>,
     profile: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sandbox: Option<SandboxDetails>,
+}
+
+#[derive(Serialize)]
+struct SandboxDetails {
+    container: String,
+    image: String,
+    /// Session environment values; literal values are masked
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    environment_values: BTreeMap<String, String>,
+}
+
+impl SandboxDetails {
+    fn new(sandbox: &SandboxInfo) -> Self {
+        let environment_values = sandbox
+            .extra_env_values
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.clone(), secrets::mask(value)))
+            .collect();
+        Self {
+            container: sandbox.container_name.clone(),
+            image: sandbox.image.clone(),
+            environment_values,
+        }
+    }
 }
 
 pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
@@ -76,6 +123,7 @@ pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
         SessionCommands::Attach(args) => attach_session(profile, args).await,
         SessionCommands::Show(args) => show_session(profile, args).await,
         SessionCommands::Current(args) => current_session(args).await,
+        SessionCommands::ApplyPatch(args) => apply_patch(profile, args).await,
     }
 }
 
@@ -198,6 +246,7 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
             status: format!("{:?}", inst.status).to_lowercase(),
             parent_session_id: inst.parent_session_id.clone(),
             profile: storage.profile().to_string(),
+            sandbox: inst.sandbox_info.as_ref().map(SandboxDetails::new),
         };
         println!("{}", serde_json::to_string_pretty(&details)?);
     } else {
@@ -212,11 +261,44 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
         if let Some(parent_id) = &inst.parent_session_id {
             println!("  Parent:  {}", parent_id);
         }
+        if let Some(sandbox) = inst.sandbox_info.as_ref().map(SandboxDetails::new) {
+            println!("  Sandbox: {} ({})", sandbox.container, sandbox.image);
+            for (key, value) in &sandbox.environment_values {
+                println!("    {}={}", key, value);
+            }
+        }
     }
 
     Ok(())
 }
 
+async fn apply_patch(profile: &str, args: ApplyPatchArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+
+    let patch = if args.patch == Path::new("-") {
+        let mut buf = Vec::new();
+        std::io::stdin().read_to_end(&mut buf)?;
+        buf
+    } else {
+        std::fs::read(&args.patch)?
+    };
+
+    if patch.is_empty() {
+        bail!("Patch is empty: {}", args.patch.display());
+    }
+
+    crate::git::diff::apply_patch(Path::new(&inst.project_path), &patch, args.check)?;
+
+    if args.check {
+        println!("✓ Patch applies cleanly to session: {}", inst.title);
+    } else {
+        println!("✓ Applied patch to session: {}", inst.title);
+    }
+    Ok(())
+}
+
 async fn current_session(args: CurrentArgs) -> Result<()> {
     // Auto-detect profile and session from tmux
     let current_session = std::env::var("TMUX_PANE")
@@ -1,11 +1,13 @@
 //! `agent-of-empires worktree` command implementation
 
 use anyhow::{bail, Result};
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::git::sync::{self, SyncOutcome, SyncStrategy};
 use crate::git::GitWorktree;
-use crate::session::Storage;
+use crate::session::{Status, Storage};
 
 #[derive(Subcommand)]
 pub enum WorktreeCommands {
@@ -24,6 +26,46 @@ pub enum WorktreeCommands {
         #[arg(short = 'f', long = "force")]
         force: bool,
     },
+
+    /// Fetch and bring every worktree session's branch up to date with its base
+    Sync {
+        /// Only sync sessions in this group (and its subgroups)
+        #[arg(short = 'g', long)]
+        group: Option<String>,
+
+        /// Ref to sync onto (defaults to <remote>/<diff base branch>)
+        #[arg(short = 'b', long)]
+        base: Option<String>,
+
+        /// Remote to fetch from
+        #[arg(long, default_value = "origin")]
+        remote: String,
+
+        /// How to update each branch
+        #[arg(short = 's', long, value_enum, default_value_t = SyncMethod::Rebase)]
+        strategy: SyncMethod,
+
+        /// Skip fetching and sync onto the refs already present locally
+        #[arg(long)]
+        no_fetch: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SyncMethod {
+    /// Rebase the branch onto the base
+    Rebase,
+    /// Merge the base into the branch
+    Merge,
+}
+
+impl From<SyncMethod> for SyncStrategy {
+    fn from(method: SyncMethod) -> Self {
+        match method {
+            SyncMethod::Rebase => SyncStrategy::Rebase,
+            SyncMethod::Merge => SyncStrategy::Merge,
+        }
+    }
 }
 
 pub async fn run(profile: &str, command: WorktreeCommands) -> Result<()> {
@@ -31,6 +73,23 @@ pub async fn run(profile: &str, command: WorktreeCommands) -> Result<()> {
         WorktreeCommands::List => list_worktrees().await,
         WorktreeCommands::Info { identifier } => show_info(profile, &identifier).await,
         WorktreeCommands::Cleanup { force } => cleanup_orphaned(profile, force).await,
+        WorktreeCommands::Sync {
+            group,
+            base,
+            remote,
+            strategy,
+            no_fetch,
+        } => {
+            sync_worktrees(
+                profile,
+                group.as_deref(),
+                base.as_deref(),
+                &remote,
+                strategy.into(),
+                no_fetch,
+            )
+            .await
+        }
     }
 }
 
@@ -257,6 +316,143 @@ async fn cleanup_orphaned(profile: &str, force: bool) -> Result<()> {
     Ok(())
 }
 
+async fn sync_worktrees(
+    profile: &str,
+    group: Option<&str>,
+    base: Option<&str>,
+    remote: &str,
+    strategy: SyncStrategy,
+    no_fetch: bool,
+) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    // Base ref per main repository, resolved (and fetched) once per repo
+    let mut repo_bases: HashMap<String, String> = HashMap::new();
+    let mut rows: Vec<(String, String, String)> = Vec::new();
+    let mut conflict_count = 0;
+    let mut changed = false;
+
+    for inst in instances.iter_mut() {
+        let Some(wt_info) = &inst.worktree_info else {
+            continue;
+        };
+        if let Some(group) = group {
+            let in_group =
+                inst.group_path == group || inst.group_path.starts_with(&format!("{}/", group));
+            if !in_group {
+                continue;
+            }
+        }
+
+        let branch = wt_info.branch.clone();
+        let main_repo = wt_info.main_repo_path.clone();
+
+        inst.update_status();
+        if inst.status == Status::Running {
+            rows.push((inst.title.clone(), branch, "skipped (agent running)".into()));
+            continue;
+        }
+        if !Path::new(&inst.project_path).exists() {
+            rows.push((
+                inst.title.clone(),
+                branch,
+                "skipped (worktree missing)".into(),
+            ));
+            continue;
+        }
+
+        let base_ref = repo_bases
+            .entry(main_repo.clone())
+            .or_insert_with(|| {
+                let repo_path = Path::new(&main_repo);
+                if !no_fetch {
+                    println!("Fetching {} in {}", remote, shorten_path(repo_path));
+                    if let Err(e) = sync::fetch_remote(repo_path, remote) {
+                        eprintln!("✗ {}", e);
+                    }
+                }
+                match base {
+                    Some(base) => base.to_string(),
+                    None => {
+                        let config = crate::session::resolve_config_with_repo(profile, repo_path)
+                            .unwrap_or_default();
+                        let branch = config.diff.base_branch_for(repo_path);
+                        sync::resolve_sync_base(repo_path, remote, &branch)
+                    }
+                }
+            })
+            .clone();
+
+        let result = match sync::sync_worktree(Path::new(&inst.project_path), &base_ref, strategy) {
+            Ok(outcome) => {
+                let conflicts = match &outcome {
+                    SyncOutcome::Conflict { files } => files.clone(),
+                    _ => Vec::new(),
+                };
+                if let Some(wt_info) = inst.worktree_info.as_mut() {
+                    if wt_info.sync_conflicts != conflicts {
+                        wt_info.sync_conflicts = conflicts;
+                        changed = true;
+                    }
+                }
+
+                match outcome {
+                    SyncOutcome::UpToDate => format!("up to date with {}", base_ref),
+                    SyncOutcome::Updated { behind } => format!(
+                        "{} onto {} ({} new commit{})",
+                        match strategy {
+                            SyncStrategy::Rebase => "rebased",
+                            SyncStrategy::Merge => "merged",
+                        },
+                        base_ref,
+                        behind,
+                        if behind == 1 { "" } else { "s" }
+                    ),
+                    SyncOutcome::Conflict { files } => {
+                        conflict_count += 1;
+                        format!("conflict: {}", files.join(", "))
+                    }
+                }
+            }
+            Err(e) => format!("error: {}", e),
+        };
+        rows.push((inst.title.clone(), branch, result));
+    }
+
+    if rows.is_empty() {
+        println!("No worktree sessions to sync");
+        return Ok(());
+    }
+
+    println!();
+    println!("{:<30} {:<30} RESULT", "SESSION", "BRANCH");
+    println!("{}", "=".repeat(80));
+    for (title, branch, result) in &rows {
+        println!("{:<30} {:<30} {}", title, branch, result);
+    }
+
+    if changed {
+        let group_tree = crate::session::GroupTree::new_with_groups(&instances, &groups);
+        storage.save_with_groups(&instances, &group_tree)?;
+    }
+
+    if conflict_count > 0 {
+        bail!(
+            "{} session{} could not be synced because of conflicts; the branch{} left unchanged",
+            conflict_count,
+            if conflict_count == 1 { "" } else { "s" },
+            if conflict_count == 1 {
+                " was"
+            } else {
+                "es were"
+            }
+        );
+    }
+
+    Ok(())
+}
+
 fn shorten_path(path: &Path) -> String {
     let path_str = path.to_string_lossy();
     if let Some(home) = dirs::home_dir() {
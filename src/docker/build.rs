@@ -0,0 +1,382 @@
+//! Building sandbox images from a repository's Dockerfile
+//!
+//! Built images are tagged with a hash of the Dockerfile and the build
+//! context, so an image is only rebuilt when something that goes into it
+//! changes, and worktrees of the same repo share one image.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
+
+use super::error::{DockerError, Result};
+use super::runtime::ContainerRuntime;
+
+/// Repository name of every image aoe builds
+pub const BUILT_IMAGE_REPO: &str = "aoe-build";
+
+/// Number of trailing build output lines included in a build error
+const ERROR_TAIL_LINES: usize = 20;
+
+/// Where to build a sandbox image from. Paths are absolute.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageBuild {
+    pub dockerfile: PathBuf,
+    pub context: PathBuf,
+    /// `--build-arg` values
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+}
+
+impl ImageBuild {
+    /// Resolve the Dockerfile and context, refusing either if it lies outside
+    /// `root`. Both can come from files in the repository, which must not be
+    /// able to bake host files from elsewhere into the image.
+    pub fn confined_to(self, root: &Path) -> Result<Self> {
+        let root = root.canonicalize().map_err(|e| {
+            DockerError::BuildFailed(format!("cannot resolve {}: {}", root.display(), e))
+        })?;
+        let confine = |path: &Path| {
+            let resolved = path.canonicalize().map_err(|e| {
+                DockerError::BuildFailed(format!("cannot resolve {}: {}", path.display(), e))
+            })?;
+            if !resolved.starts_with(&root) {
+                return Err(DockerError::BuildFailed(format!(
+                    "{} is outside the project {}",
+                    path.display(),
+                    root.display()
+                )));
+            }
+            Ok(resolved)
+        };
+        Ok(Self {
+            dockerfile: confine(&self.dockerfile)?,
+            context: confine(&self.context)?,
+            args: self.args,
+        })
+    }
+
+    /// Tag for the image built from the current Dockerfile and context.
+    pub fn image_tag(&self) -> Result<String> {
+        Ok(format!(
+            "{}:{}",
+            BUILT_IMAGE_REPO,
+            &self.content_hash()?[..16]
+        ))
+    }
+
+    /// Hash of the Dockerfile plus every file in the build context, skipping
+    /// `.git` and anything ignored by `.gitignore` or `.dockerignore`.
+    pub fn content_hash(&self) -> Result<String> {
+        let dockerfile = fs::read(&self.dockerfile).map_err(|e| {
+            DockerError::BuildFailed(format!("cannot read {}: {}", self.dockerfile.display(), e))
+        })?;
+        if !self.context.is_dir() {
+            return Err(DockerError::BuildFailed(format!(
+                "build context {} is not a directory",
+                self.context.display()
+            )));
+        }
+
+        let ignores = ContextIgnores::new(&self.context);
+        let mut files = Vec::new();
+        collect_files(&self.context, &ignores, &mut files)?;
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"dockerfile\0");
+        hasher.update(&dockerfile);
+        for (key, value) in &self.args {
+            hasher.update(format!("\0arg\0{}={}", key, value).as_bytes());
+        }
+        for path in &files {
+            let relative = path.strip_prefix(&self.context).unwrap_or(path);
+            hasher.update(b"\0file\0");
+            hasher.update(relative.to_string_lossy().as_bytes());
+            hasher.update(b"\0");
+            let meta = fs::symlink_metadata(path)?;
+            if meta.file_type().is_symlink() {
+                hasher.update(fs::read_link(path)?.to_string_lossy().as_bytes());
+            } else {
+                hasher.update(&fs::read(path)?);
+            }
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    pub(crate) fn build_args(&self, runtime: ContainerRuntime, tag: &str) -> Vec<String> {
+        let mut args = vec!["build".to_string()];
+        if runtime.is_docker() {
+            // BuildKit's default TTY output redraws in place; plain streams lines
+            args.push("--progress=plain".to_string());
+        }
+        for (key, value) in &self.args {
+            args.push("--build-arg".to_string());
+            args.push(format!("{}={}", key, value));
+        }
+        args.extend([
+            "-f".to_string(),
+            self.dockerfile.to_string_lossy().to_string(),
+            "-t".to_string(),
+            tag.to_string(),
+            self.context.to_string_lossy().to_string(),
+        ]);
+        args
+    }
+
+    /// Build the image as `tag`, passing each line of build output to
+    /// `on_output` as it arrives.
+    pub fn build(
+        &self,
+        runtime: ContainerRuntime,
+        tag: &str,
+        mut on_output: impl FnMut(String),
+    ) -> Result<()> {
+        tracing::info!(
+            "Building sandbox image {} from {}",
+            tag,
+            self.dockerfile.display()
+        );
+
+        let mut child = runtime
+            .command()
+            .args(self.build_args(runtime, tag))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Builders write progress to stderr and results to stdout; merge both
+        let (tx, rx) = mpsc::channel::<String>();
+        let readers: Vec<_> = [
+            child
+                .stdout
+                .take()
+                .map(|s| Box::new(s) as Box<dyn Read + Send>),
+            child
+                .stderr
+                .take()
+                .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|stream| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stream).lines().map_while(|l| l.ok()) {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+        drop(tx);
+
+        let mut tail = VecDeque::with_capacity(ERROR_TAIL_LINES);
+        for line in rx {
+            if tail.len() == ERROR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line.clone());
+            on_output(line);
+        }
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        let status = child.wait()?;
+        if !status.success() {
+            let output: Vec<String> = tail.into_iter().collect();
+            return Err(DockerError::BuildFailed(output.join("\n")));
+        }
+
+        Ok(())
+    }
+
+    /// Build the image unless one for the current context already exists.
+    /// Returns the image tag.
+    pub fn ensure(
+        &self,
+        runtime: ContainerRuntime,
+        on_output: impl FnMut(String),
+    ) -> Result<String> {
+        let tag = self.image_tag()?;
+        if runtime.image_exists_locally(&tag) {
+            tracing::info!("Using cached sandbox image {}", tag);
+        } else {
+            self.build(runtime, &tag, on_output)?;
+        }
+        Ok(tag)
+    }
+}
+
+/// Ignore rules for the build context: `.gitignore` files (when the context
+/// is inside a git repository) plus the context's `.dockerignore`.
+struct ContextIgnores {
+    repo: Option<git2::Repository>,
+    workdir: Option<PathBuf>,
+}
+
+impl ContextIgnores {
+    fn new(context: &Path) -> Self {
+        let repo = git2::Repository::discover(context).ok();
+        if let Some(repo) = &repo {
+            if let Ok(rules) = fs::read_to_string(context.join(".dockerignore")) {
+                let _ = repo.add_ignore_rule(&rules);
+            }
+        }
+        let workdir = repo
+            .as_ref()
+            .and_then(|r| r.workdir())
+            .and_then(|w| w.canonicalize().ok());
+        Self { repo, workdir }
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        if path.file_name().is_some_and(|n| n == ".git") {
+            return true;
+        }
+        let (Some(repo), Some(workdir)) = (&self.repo, &self.workdir) else {
+            return false;
+        };
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        canonical
+            .strip_prefix(workdir)
+            .ok()
+            .is_some_and(|relative| repo.is_path_ignored(relative).unwrap_or(false))
+    }
+}
+
+fn collect_files(dir: &Path, ignores: &ContextIgnores, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if ignores.is_ignored(&path) {
+            continue;
+        }
+        let file_type = fs::symlink_metadata(&path)?.file_type();
+        if file_type.is_dir() {
+            collect_files(&path, ignores, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_context() -> (TempDir, ImageBuild) {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Dockerfile"), "FROM alpine\nCOPY . /app\n").unwrap();
+        fs::write(dir.path().join("app.txt"), "v1").unwrap();
+        let build = ImageBuild {
+            dockerfile: dir.path().join("Dockerfile"),
+            context: dir.path().to_path_buf(),
+            args: BTreeMap::new(),
+        };
+        (dir, build)
+    }
+
+    #[test]
+    fn test_hash_is_stable() {
+        let (_dir, build) = setup_context();
+        assert_eq!(build.content_hash().unwrap(), build.content_hash().unwrap());
+        let tag = build.image_tag().unwrap();
+        assert!(tag.starts_with("aoe-build:"));
+        assert_eq!(tag.len(), "aoe-build:".len() + 16);
+    }
+
+    #[test]
+    fn test_hash_changes_with_context() {
+        let (dir, build) = setup_context();
+        let before = build.content_hash().unwrap();
+
+        fs::write(dir.path().join("app.txt"), "v2").unwrap();
+        let after_edit = build.content_hash().unwrap();
+        assert_ne!(before, after_edit);
+
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/new.txt"), "new").unwrap();
+        assert_ne!(after_edit, build.content_hash().unwrap());
+    }
+
+    #[test]
+    fn test_hash_changes_with_dockerfile() {
+        let (dir, build) = setup_context();
+        let before = build.content_hash().unwrap();
+        fs::write(dir.path().join("Dockerfile"), "FROM debian\n").unwrap();
+        assert_ne!(before, build.content_hash().unwrap());
+    }
+
+    #[test]
+    fn test_hash_changes_with_build_args() {
+        let (_dir, mut build) = setup_context();
+        let before = build.content_hash().unwrap();
+        build
+            .args
+            .insert("NODE_VERSION".to_string(), "20".to_string());
+        assert_ne!(before, build.content_hash().unwrap());
+        assert!(build
+            .build_args(ContainerRuntime::Podman, "t")
+            .windows(2)
+            .any(|w| w == ["--build-arg", "NODE_VERSION=20"]));
+    }
+
+    #[test]
+    fn test_hash_skips_ignored_files() {
+        let (dir, build) = setup_context();
+        git2::Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.path().join(".dockerignore"), "*.log\n").unwrap();
+        let before = build.content_hash().unwrap();
+
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("target/out.bin"), "build output").unwrap();
+        fs::write(dir.path().join("debug.log"), "noise").unwrap();
+        assert_eq!(before, build.content_hash().unwrap());
+    }
+
+    #[test]
+    fn test_missing_dockerfile() {
+        let (dir, mut build) = setup_context();
+        build.dockerfile = dir.path().join("Nope.Dockerfile");
+        assert!(matches!(
+            build.content_hash(),
+            Err(DockerError::BuildFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_args() {
+        let build = ImageBuild {
+            dockerfile: PathBuf::from("/repo/.aoe/Dockerfile"),
+            context: PathBuf::from("/repo"),
+            args: BTreeMap::new(),
+        };
+        let args = build.build_args(ContainerRuntime::Docker, "aoe-build:abc");
+        assert_eq!(
+            args,
+            vec![
+                "build",
+                "--progress=plain",
+                "-f",
+                "/repo/.aoe/Dockerfile",
+                "-t",
+                "aoe-build:abc",
+                "/repo"
+            ]
+        );
+
+        let args = build.build_args(ContainerRuntime::Podman, "aoe-build:abc");
+        assert!(!args.contains(&"--progress=plain".to_string()));
+    }
+}
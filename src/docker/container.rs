@@ -1,6 +1,9 @@
+use std::path::Path;
+
 use super::error::{DockerError, Result};
+use super::network::ContainerNetwork;
+use super::runtime::ContainerRuntime;
 use crate::cli::truncate_id;
-use std::process::Command;
 
 pub struct VolumeMount {
     pub host_path: String,
@@ -8,42 +11,91 @@ pub struct VolumeMount {
     pub read_only: bool,
 }
 
+/// A container port published on the host's loopback interface. Without a
+/// host port the runtime picks a free one, so sessions never collide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    pub container_port: u16,
+    pub host_port: Option<u16>,
+}
+
+impl PortMapping {
+    pub fn container(container_port: u16) -> Self {
+        Self {
+            container_port,
+            host_port: None,
+        }
+    }
+
+    /// Parse a `ports` config entry: `"3000"` or `"8080:3000"`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = || DockerError::InvalidPort(spec.to_string());
+        let parse_port = |s: &str| match s.trim().parse::<u16>() {
+            Ok(0) | Err(_) => Err(invalid()),
+            Ok(port) => Ok(port),
+        };
+        match spec.split_once(':') {
+            Some((host, container)) => Ok(Self {
+                container_port: parse_port(container)?,
+                host_port: Some(parse_port(host)?),
+            }),
+            None => Ok(Self::container(parse_port(spec)?)),
+        }
+    }
+
+    fn publish_arg(&self) -> String {
+        match self.host_port {
+            Some(host) => format!("127.0.0.1:{}:{}", host, self.container_port),
+            None => format!("127.0.0.1::{}", self.container_port),
+        }
+    }
+}
+
 pub struct ContainerConfig {
     pub working_dir: String,
     pub volumes: Vec<VolumeMount>,
     pub named_volumes: Vec<(String, String)>,
     pub anonymous_volumes: Vec<String>,
     pub environment: Vec<(String, String)>,
+    pub ports: Vec<PortMapping>,
     pub cpu_limit: Option<String>,
     pub memory_limit: Option<String>,
+    pub network: ContainerNetwork,
 }
 
+/// Name prefix shared by every sandbox container aoe creates
+pub const SANDBOX_CONTAINER_PREFIX: &str = "aoe-sandbox-";
+
 pub struct DockerContainer {
     pub name: String,
     pub image: String,
+    pub runtime: ContainerRuntime,
 }
 
 impl DockerContainer {
-    pub fn new(session_id: &str, image: &str) -> Self {
+    /// A session's container. An existing container is managed by the
+    /// runtime recorded in the session's `SandboxInfo`, which may predate a
+    /// runtime switch.
+    pub fn new(session_id: &str, image: &str, runtime: ContainerRuntime) -> Self {
         Self {
             name: Self::generate_name(session_id),
             image: image.to_string(),
+            runtime,
         }
     }
 
-    pub fn from_session_id(session_id: &str) -> Self {
-        Self {
-            name: Self::generate_name(session_id),
-            image: String::new(),
-        }
+    pub fn from_session_id(session_id: &str, runtime: ContainerRuntime) -> Self {
+        Self::new(session_id, "", runtime)
     }
 
     pub fn generate_name(session_id: &str) -> String {
-        format!("aoe-sandbox-{}", truncate_id(session_id, 8))
+        format!("{}{}", SANDBOX_CONTAINER_PREFIX, truncate_id(session_id, 8))
     }
 
     pub fn exists(&self) -> Result<bool> {
-        let output = Command::new("docker")
+        let output = self
+            .runtime
+            .command()
             .args(["container", "inspect", &self.name])
             .output()?;
 
@@ -51,7 +103,9 @@ impl DockerContainer {
     }
 
     pub fn is_running(&self) -> Result<bool> {
-        let output = Command::new("docker")
+        let output = self
+            .runtime
+            .command()
             .args([
                 "container",
                 "inspect",
@@ -69,9 +123,15 @@ impl DockerContainer {
         Ok(stdout.trim() == "true")
     }
 
-    /// Build the docker run arguments from the container config.
+    /// Build the docker run arguments from the container config, reading the
+    /// environment from `env_file` except for values spanning lines, which
+    /// the runtime takes from its own environment (see `create`).
     /// Separated from `create` to enable unit testing.
-    pub(crate) fn build_create_args(&self, config: &ContainerConfig) -> Vec<String> {
+    pub(crate) fn build_create_args(
+        &self,
+        config: &ContainerConfig,
+        env_file: Option<&Path>,
+    ) -> Vec<String> {
         let mut args = vec![
             "run".to_string(),
             "-d".to_string(),
@@ -93,7 +153,7 @@ impl DockerContainer {
 
         for (vol_name, container_path) in &config.named_volumes {
             args.push("-v".to_string());
-            args.push(format!("{}:{}", vol_name, container_path));
+            args.push(self.runtime.named_volume_mount(vol_name, container_path));
         }
 
         for path in &config.anonymous_volumes {
@@ -101,9 +161,21 @@ impl DockerContainer {
             args.push(path.clone());
         }
 
+        if let Some(env_file) = env_file {
+            args.push("--env-file".to_string());
+            args.push(env_file.to_string_lossy().into_owned());
+        }
+
         for (key, value) in &config.environment {
-            args.push("-e".to_string());
-            args.push(format!("{}={}", key, value));
+            if !crate::session::secrets::fits_env_file(value) {
+                args.push("-e".to_string());
+                args.push(key.clone());
+            }
+        }
+
+        for port in &config.ports {
+            args.push("-p".to_string());
+            args.push(port.publish_arg());
         }
 
         if let Some(cpu) = &config.cpu_limit {
@@ -116,6 +188,11 @@ impl DockerContainer {
             args.push(mem.clone());
         }
 
+        if let Some(network) = config.network.docker_network() {
+            args.push("--network".to_string());
+            args.push(network.to_string());
+        }
+
         args.push(self.image.clone());
         args.push("sleep".to_string());
         args.push("infinity".to_string());
@@ -128,9 +205,33 @@ impl DockerContainer {
             return Err(DockerError::ContainerAlreadyExists(self.name.clone()));
         }
 
-        let args = self.build_create_args(config);
+        // Values go through a private env file so they show up neither in
+        // `ps` nor in the environment of the runtime CLI itself. The env file
+        // can't hold values spanning lines (PEM keys, JSON credentials), so
+        // those are handed to the runtime through its environment instead.
+        let (file_values, multiline): (Vec<_>, Vec<_>) = config
+            .environment
+            .iter()
+            .cloned()
+            .partition(|(_, value)| crate::session::secrets::fits_env_file(value));
+        let env_file = if file_values.is_empty() {
+            None
+        } else {
+            let dir = crate::session::get_app_dir()
+                .map_err(|e| DockerError::CreateFailed(e.to_string()))?
+                .join("secrets");
+            Some(
+                crate::session::secrets::write_env_file(&dir, &file_values)
+                    .map_err(|e| DockerError::CreateFailed(e.to_string()))?,
+            )
+        };
+        let args = self.build_create_args(config, env_file.as_deref());
 
-        let output = Command::new("docker").args(&args).output()?;
+        let output = self.runtime.command().args(&args).envs(multiline).output();
+        if let Some(env_file) = &env_file {
+            let _ = std::fs::remove_file(env_file);
+        }
+        let output = output?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -151,7 +252,9 @@ impl DockerContainer {
     }
 
     pub fn start(&self) -> Result<()> {
-        let output = Command::new("docker")
+        let output = self
+            .runtime
+            .command()
             .args(["start", &self.name])
             .output()?;
 
@@ -164,7 +267,7 @@ impl DockerContainer {
     }
 
     pub fn stop(&self) -> Result<()> {
-        let output = Command::new("docker").args(["stop", &self.name]).output()?;
+        let output = self.runtime.command().args(["stop", &self.name]).output()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -178,13 +281,17 @@ impl DockerContainer {
     }
 
     pub fn remove(&self, force: bool) -> Result<()> {
+        if self.name.starts_with(SANDBOX_CONTAINER_PREFIX) {
+            let _ = super::ports::remove_forwarders(self.runtime, &self.name);
+        }
+
         let mut args = vec!["rm".to_string()];
         if force {
             args.push("-f".to_string());
         }
         args.push(self.name.clone());
 
-        let output = Command::new("docker").args(&args).output()?;
+        let output = self.runtime.command().args(&args).output()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -197,9 +304,49 @@ impl DockerContainer {
         Ok(())
     }
 
+    pub(crate) fn build_update_args(
+        &self,
+        cpu_limit: Option<&str>,
+        memory_limit: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = vec!["update".to_string()];
+        if let Some(cpu) = cpu_limit {
+            args.push("--cpus".to_string());
+            args.push(cpu.to_string());
+        }
+        if let Some(mem) = memory_limit {
+            args.push("--memory".to_string());
+            args.push(mem.to_string());
+            // Swap stays at twice the memory, as `run -m` sets it; leaving it
+            // at the old value would reject raising memory past it
+            if let Some(swap) = double_memory(mem) {
+                args.push("--memory-swap".to_string());
+                args.push(swap);
+            }
+        }
+        args.push(self.name.clone());
+        args
+    }
+
+    /// Change the CPU and memory limits of an existing container in place.
+    pub fn update_limits(&self, cpu_limit: Option<&str>, memory_limit: Option<&str>) -> Result<()> {
+        let args = self.build_update_args(cpu_limit, memory_limit);
+        let output = self.runtime.command().args(&args).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("No such container") || stderr.contains("no such container") {
+                return Err(DockerError::ContainerNotFound(self.name.clone()));
+            }
+            return Err(DockerError::UpdateFailed(stderr.trim().to_string()));
+        }
+
+        Ok(())
+    }
+
     pub fn exec_command(&self) -> Vec<String> {
         vec![
-            "docker".to_string(),
+            self.runtime.binary().to_string(),
             "exec".to_string(),
             "-it".to_string(),
             self.name.clone(),
@@ -210,12 +357,28 @@ impl DockerContainer {
         let mut args = vec!["exec", &self.name];
         args.extend(cmd);
 
-        let output = Command::new("docker").args(&args).output()?;
+        let output = self.runtime.command().args(&args).output()?;
 
         Ok(output)
     }
 }
 
+/// Twice a memory limit such as `512m`, keeping its unit.
+fn double_memory(limit: &str) -> Option<String> {
+    let split = limit
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(limit.len());
+    let (amount, unit) = limit.split_at(split);
+    if !matches!(
+        unit.to_ascii_lowercase().as_str(),
+        "" | "b" | "k" | "m" | "g"
+    ) {
+        return None;
+    }
+    let doubled = amount.parse::<u64>().ok()?.checked_mul(2)?;
+    Some(format!("{}{}", doubled, unit))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,14 +397,66 @@ mod tests {
 
     #[test]
     fn test_exec_command() {
-        let container = DockerContainer::new("test1234567890ab", "ubuntu:latest");
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "ubuntu:latest",
+            ContainerRuntime::Docker,
+        );
         let cmd = container.exec_command();
         assert_eq!(cmd, vec!["docker", "exec", "-it", "aoe-sandbox-test1234"]);
+
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "ubuntu:latest",
+            ContainerRuntime::Podman,
+        );
+        assert_eq!(container.exec_command()[0], "podman");
+    }
+
+    #[test]
+    fn test_podman_named_volumes_are_chowned() {
+        let config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![VolumeMount {
+                host_path: "/home/me/project".to_string(),
+                container_path: "/workspace".to_string(),
+                read_only: false,
+            }],
+            named_volumes: vec![("aoe-claude-auth".to_string(), "/root/.claude".to_string())],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            ports: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: ContainerNetwork::Bridge,
+        };
+
+        let docker = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
+        let args = docker.build_create_args(&config, None);
+        assert!(args.contains(&"aoe-claude-auth:/root/.claude".to_string()));
+
+        let podman = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Podman,
+        );
+        let args = podman.build_create_args(&config, None);
+        assert!(args.contains(&"aoe-claude-auth:/root/.claude:U".to_string()));
+        // Bind mounts keep host ownership
+        assert!(args.contains(&"/home/me/project:/workspace".to_string()));
     }
 
     #[test]
     fn test_anonymous_volumes_in_create_args() {
-        let container = DockerContainer::new("test1234567890ab", "alpine:latest");
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
         let config = ContainerConfig {
             working_dir: "/workspace/myproject".to_string(),
             volumes: vec![],
@@ -251,11 +466,13 @@ mod tests {
                 "/workspace/myproject/node_modules".to_string(),
             ],
             environment: vec![],
+            ports: vec![],
             cpu_limit: None,
             memory_limit: None,
+            network: ContainerNetwork::Bridge,
         };
 
-        let args = container.build_create_args(&config);
+        let args = container.build_create_args(&config, None);
 
         // Find the anonymous volume flags
         let v_positions: Vec<usize> = args
@@ -271,22 +488,207 @@ mod tests {
         assert!(volume_values.contains(&"/workspace/myproject/node_modules"));
     }
 
+    #[test]
+    fn test_environment_comes_from_env_file() {
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
+        let config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![("TOKEN".to_string(), "secret".to_string())],
+            ports: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: ContainerNetwork::Bridge,
+        };
+
+        let args = container.build_create_args(&config, Some(Path::new("/tmp/env-1")));
+        let pos = args.iter().position(|a| a == "--env-file").unwrap();
+        assert_eq!(args[pos + 1], "/tmp/env-1");
+        assert!(!args.iter().any(|a| a == "-e" || a.contains("secret")));
+    }
+
+    #[test]
+    fn test_multiline_environment_comes_from_runtime_env() {
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
+        let config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![
+                ("TOKEN".to_string(), "secret".to_string()),
+                (
+                    "SSH_KEY".to_string(),
+                    "-----BEGIN KEY-----\nsecret\n-----END KEY-----".to_string(),
+                ),
+            ],
+            ports: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: ContainerNetwork::Bridge,
+        };
+
+        let args = container.build_create_args(&config, Some(Path::new("/tmp/env-1")));
+        let flags: Vec<&str> = args
+            .windows(2)
+            .filter(|w| w[0] == "-e")
+            .map(|w| w[1].as_str())
+            .collect();
+        assert_eq!(flags, vec!["SSH_KEY"]);
+        assert!(!args.iter().any(|a| a.contains("secret")));
+    }
+
     #[test]
     fn test_no_anonymous_volumes_when_empty() {
-        let container = DockerContainer::new("test1234567890ab", "alpine:latest");
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
         let config = ContainerConfig {
             working_dir: "/workspace".to_string(),
             volumes: vec![],
             named_volumes: vec![],
             anonymous_volumes: vec![],
             environment: vec![],
+            ports: vec![],
             cpu_limit: None,
             memory_limit: None,
+            network: ContainerNetwork::Bridge,
         };
 
-        let args = container.build_create_args(&config);
+        let args = container.build_create_args(&config, None);
 
         // No -v flags at all
         assert!(!args.contains(&"-v".to_string()));
     }
+
+    #[test]
+    fn test_network_in_create_args() {
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
+        let mut config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            ports: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: ContainerNetwork::Bridge,
+        };
+
+        let args = container.build_create_args(&config, None);
+        assert!(!args.contains(&"--network".to_string()));
+
+        config.network = ContainerNetwork::Disabled;
+        let args = container.build_create_args(&config, None);
+        let idx = args.iter().position(|a| a == "--network").unwrap();
+        assert_eq!(args[idx + 1], "none");
+        // Options must come before the image
+        assert!(idx < args.iter().position(|a| a == "alpine:latest").unwrap());
+    }
+
+    #[test]
+    fn test_parse_port_mapping() {
+        assert_eq!(
+            PortMapping::parse("3000").unwrap(),
+            PortMapping::container(3000)
+        );
+        assert_eq!(
+            PortMapping::parse("8080:3000").unwrap(),
+            PortMapping {
+                container_port: 3000,
+                host_port: Some(8080),
+            }
+        );
+        for bad in ["", "0", "http", "70000", "1:2:3", ":3000"] {
+            assert!(
+                matches!(PortMapping::parse(bad), Err(DockerError::InvalidPort(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ports_in_create_args() {
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
+        let config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            ports: vec![
+                PortMapping::container(3000),
+                PortMapping {
+                    container_port: 5173,
+                    host_port: Some(8080),
+                },
+            ],
+            cpu_limit: None,
+            memory_limit: None,
+            network: ContainerNetwork::Bridge,
+        };
+
+        let args = container.build_create_args(&config, None);
+        let published: Vec<&String> = args
+            .windows(2)
+            .filter(|w| w[0] == "-p")
+            .map(|w| &w[1])
+            .collect();
+        assert_eq!(published, vec!["127.0.0.1::3000", "127.0.0.1:8080:5173"]);
+    }
+
+    #[test]
+    fn test_update_args() {
+        let container = DockerContainer::new(
+            "test1234567890ab",
+            "alpine:latest",
+            ContainerRuntime::Docker,
+        );
+
+        assert_eq!(
+            container.build_update_args(Some("2"), None),
+            vec!["update", "--cpus", "2", "aoe-sandbox-test1234"]
+        );
+        assert_eq!(
+            container.build_update_args(None, Some("16g")),
+            vec![
+                "update",
+                "--memory",
+                "16g",
+                "--memory-swap",
+                "32g",
+                "aoe-sandbox-test1234"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_double_memory() {
+        assert_eq!(double_memory("512m").as_deref(), Some("1024m"));
+        assert_eq!(double_memory("3G").as_deref(), Some("6G"));
+        assert_eq!(double_memory("1000000").as_deref(), Some("2000000"));
+        assert_eq!(double_memory("1.5g"), None);
+        assert_eq!(double_memory("g"), None);
+    }
 }
@@ -43,6 +43,39 @@ pub enum DockerError {
     #[error("Failed to remove container: {0}")]
     RemoveFailed(String),
 
+    #[error("Failed to update container limits: {0}")]
+    UpdateFailed(String),
+
+    #[error("Invalid network allowlist entry {0}")]
+    InvalidNetworkRule(String),
+
+    #[error("Failed to set up sandbox network: {0}")]
+    NetworkFailed(String),
+
+    #[error("Invalid port {0} (expected PORT or HOST_PORT:CONTAINER_PORT)")]
+    InvalidPort(String),
+
+    #[error("Failed to forward port: {0}")]
+    PortForwardFailed(String),
+
+    #[error("Failed to build sandbox image:\n{0}")]
+    BuildFailed(String),
+
+    #[error("Invalid snapshot name {0} (letters, digits, '_', '.' and '-', not starting with '.' or '-')")]
+    InvalidSnapshotName(String),
+
+    #[error("Snapshot already exists: {0}")]
+    SnapshotExists(String),
+
+    #[error("Snapshot not found: {0}")]
+    SnapshotNotFound(String),
+
+    #[error("Failed to snapshot container: {0}")]
+    SnapshotFailed(String),
+
+    #[error("Isolated workspace operation failed: {0}")]
+    IsolationFailed(String),
+
     #[error("Docker command failed: {0}")]
     CommandFailed(String),
 
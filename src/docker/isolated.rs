@@ -0,0 +1,274 @@
+//! Isolated workspaces for sandbox containers
+//!
+//! An isolated session mounts the project read-only at [`HOST_MOUNT`] and
+//! gives the agent a copy of it in a named volume, `aoe-workspace-<session>`.
+//! The host checkout only changes when the user applies the copy's diff.
+//!
+//! When the copy is made, its tree is recorded in a private git directory,
+//! [`BASELINE_DIR`], at the root of the volume. Diffs compare the copy with
+//! that baseline rather than the live host tree, so edits made on the host in
+//! the meantime are neither shown nor reverted. Staging through git honors the
+//! project's `.gitignore` and never compares `.git` itself.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::container::SANDBOX_CONTAINER_PREFIX;
+use super::error::{DockerError, Result};
+use super::runtime::ContainerRuntime;
+
+/// Where the read-only host project is mounted inside the container
+pub const HOST_MOUNT: &str = "/aoe-host";
+
+/// Volume name prefix of workspace copies
+pub const WORKSPACE_VOLUME_PREFIX: &str = "aoe-workspace-";
+
+/// Git directory holding the baseline, at the root of the copy. Its presence
+/// also tells a recreated container not to copy the host tree again.
+pub const BASELINE_DIR: &str = ".aoe-isolated";
+
+const SEED_SCRIPT: &str = r#"set -e
+host="$1"; base="$2"; work="$3"; shift 3
+export GIT_DIR="$base/.aoe-isolated"
+[ -e "$GIT_DIR" ] && exit 0
+cp -a "$host/." "$base/"
+git init -q
+mkdir -p "$GIT_DIR/info"
+printf '%s\n' "$@" > "$GIT_DIR/info/exclude"
+(cd "$work" && git --work-tree=. add -A . >/dev/null 2>&1 && git --work-tree=. write-tree) > "$GIT_DIR/aoe-base"
+rm -f "$GIT_DIR/index""#;
+
+const DIFF_SCRIPT: &str = r#"set -e
+export GIT_DIR="$1/.aoe-isolated"; work="$2"; shift 2
+export GIT_INDEX_FILE="$GIT_DIR/aoe-diff-index"
+trap 'rm -f "$GIT_INDEX_FILE"' EXIT
+printf '%s\n' "$@" > "$GIT_DIR/info/exclude"
+rm -f "$GIT_INDEX_FILE"
+changed=$(cd "$work" && git --work-tree=. add -A . >/dev/null 2>&1 && git --work-tree=. write-tree)
+git -c core.quotepath=off diff --binary --no-color --no-renames "$(cat "$GIT_DIR/aoe-base")" "$changed""#;
+
+/// Volume holding the workspace copy of a sandbox container.
+pub fn workspace_volume(container_name: &str) -> String {
+    let suffix = container_name
+        .strip_prefix(SANDBOX_CONTAINER_PREFIX)
+        .unwrap_or(container_name);
+    format!("{}{}", WORKSPACE_VOLUME_PREFIX, suffix)
+}
+
+/// Patterns left out of baselines and diffs, in gitignore syntax.
+pub fn excludes(volume_ignores: &[String]) -> Vec<String> {
+    let mut excludes = vec![format!("/{}", BASELINE_DIR)];
+    excludes.extend(volume_ignores.iter().cloned());
+    excludes
+}
+
+/// Copy the read-only host tree into the volume at `base_dir` and record the
+/// baseline of `workspace_dir`, unless an earlier container already did.
+pub fn seed(
+    runtime: ContainerRuntime,
+    container_name: &str,
+    base_dir: &str,
+    workspace_dir: &str,
+    excludes: &[String],
+) -> Result<()> {
+    let mut args = vec![
+        "exec".to_string(),
+        container_name.to_string(),
+        "sh".to_string(),
+        "-c".to_string(),
+        SEED_SCRIPT.to_string(),
+        "sh".to_string(),
+        HOST_MOUNT.to_string(),
+        base_dir.to_string(),
+        workspace_dir.to_string(),
+    ];
+    args.extend(excludes.iter().cloned());
+
+    let output = runtime.command().args(&args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::IsolationFailed(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+/// Patch from the baseline recorded by [`seed`] to the current state of
+/// `workspace_dir`.
+pub fn diff(
+    runtime: ContainerRuntime,
+    container_name: &str,
+    base_dir: &str,
+    workspace_dir: &str,
+    excludes: &[String],
+) -> Result<String> {
+    let mut args = vec![
+        "exec".to_string(),
+        container_name.to_string(),
+        "sh".to_string(),
+        "-c".to_string(),
+        DIFF_SCRIPT.to_string(),
+        "sh".to_string(),
+        base_dir.to_string(),
+        workspace_dir.to_string(),
+    ];
+    args.extend(excludes.iter().cloned());
+
+    let output = runtime.command().args(&args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("No such container") || stderr.contains("no such container") {
+            return Err(DockerError::ContainerNotFound(container_name.to_string()));
+        }
+        return Err(DockerError::IsolationFailed(stderr.trim().to_string()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Split a patch into its per-file sections, keyed by path.
+pub fn split_patch(patch: &str) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    for line in patch.split_inclusive('\n') {
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            files.push((header_path(paths.trim_end()), String::new()));
+        }
+        if let Some((_, section)) = files.last_mut() {
+            section.push_str(line);
+        }
+    }
+    files
+}
+
+/// Path named by a `diff --git a/<path> b/<path>` header. Renames are
+/// disabled, so both sides name the same file.
+fn header_path(paths: &str) -> String {
+    let paths = paths.replace('"', "");
+    let half = paths.len().saturating_sub(1) / 2;
+    paths
+        .get(..half)
+        .and_then(|a| a.strip_prefix("a/"))
+        .unwrap_or(&paths)
+        .to_string()
+}
+
+/// Keep the sections of `patch` touching `paths` (files, or directories
+/// matching everything below them). An empty `paths` keeps everything.
+pub fn filter_patch(patch: &str, paths: &[String]) -> String {
+    split_patch(patch)
+        .into_iter()
+        .filter(|(file, _)| {
+            paths.is_empty()
+                || paths.iter().any(|p| {
+                    let p = p.trim_end_matches('/');
+                    file == p || file.starts_with(&format!("{}/", p))
+                })
+        })
+        .map(|(_, section)| section)
+        .collect()
+}
+
+/// Apply a patch to the host project at `project_dir`.
+pub fn apply(project_dir: &Path, patch: &str) -> Result<()> {
+    // Inside a repository git apply resolves paths from its top level
+    let prefix = Command::new("git")
+        .args(["rev-parse", "--show-prefix"])
+        .current_dir(project_dir)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    let mut cmd = Command::new("git");
+    cmd.args(["apply", "--binary"]);
+    if !prefix.is_empty() {
+        cmd.arg(format!("--directory={}", prefix));
+    }
+    let mut child = cmd
+        .arg("-")
+        .current_dir(project_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::IsolationFailed(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+pub fn remove_volume(runtime: ContainerRuntime, container_name: &str) -> Result<()> {
+    let volume = workspace_volume(container_name);
+    let output = runtime.command().args(["volume", "rm", &volume]).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no such volume") || stderr.contains("No such volume") {
+            return Ok(());
+        }
+        return Err(DockerError::CommandFailed(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "diff --git a/src/main.rs b/src/main.rs\n\
+                         index 1111111..2222222 100644\n\
+                         --- a/src/main.rs\n\
+                         +++ b/src/main.rs\n\
+                         @@ -1 +1 @@\n\
+                         -old\n\
+                         +new\n\
+                         diff --git a/docs/my notes.md b/docs/my notes.md\n\
+                         new file mode 100644\n\
+                         index 0000000..3333333\n\
+                         --- /dev/null\n\
+                         +++ b/docs/my notes.md\n\
+                         @@ -0,0 +1 @@\n\
+                         +hello\n";
+
+    #[test]
+    fn test_workspace_volume() {
+        assert_eq!(
+            workspace_volume("aoe-sandbox-abcd1234"),
+            "aoe-workspace-abcd1234"
+        );
+    }
+
+    #[test]
+    fn test_split_patch() {
+        let files = split_patch(PATCH);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "src/main.rs");
+        assert!(files[0].1.ends_with("+new\n"));
+        assert_eq!(files[1].0, "docs/my notes.md");
+        assert!(files[1].1.starts_with("diff --git a/docs/my notes.md"));
+        assert!(split_patch("").is_empty());
+    }
+
+    #[test]
+    fn test_filter_patch() {
+        assert_eq!(filter_patch(PATCH, &[]), PATCH);
+
+        let src = filter_patch(PATCH, &["src/".to_string()]);
+        assert!(src.contains("src/main.rs"));
+        assert!(!src.contains("my notes"));
+
+        let doc = filter_patch(PATCH, &["docs/my notes.md".to_string()]);
+        assert!(doc.starts_with("diff --git a/docs/my notes.md"));
+
+        assert!(filter_patch(PATCH, &["sr".to_string()]).is_empty());
+    }
+}
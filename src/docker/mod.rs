@@ -1,10 +1,23 @@
+pub mod build;
 pub mod container;
 pub mod error;
-
-pub use container::{ContainerConfig, DockerContainer, VolumeMount};
+pub mod isolated;
+pub mod network;
+pub mod ports;
+pub mod runtime;
+pub mod snapshot;
+pub mod stats;
+
+pub use build::ImageBuild;
+pub use container::{
+    ContainerConfig, DockerContainer, PortMapping, VolumeMount, SANDBOX_CONTAINER_PREFIX,
+};
 pub use error::{DockerError, Result};
-
-use std::process::Command;
+pub use network::{ContainerNetwork, EgressProxy};
+pub use ports::{PortForwarder, SessionPort};
+pub use runtime::ContainerRuntime;
+pub use snapshot::Snapshot;
+pub use stats::ContainerStats;
 
 pub const CLAUDE_AUTH_VOLUME: &str = "aoe-claude-auth";
 pub const OPENCODE_AUTH_VOLUME: &str = "aoe-opencode-auth";
@@ -12,88 +25,36 @@ pub const VIBE_AUTH_VOLUME: &str = "aoe-vibe-auth";
 pub const CODEX_AUTH_VOLUME: &str = "aoe-codex-auth";
 pub const GEMINI_AUTH_VOLUME: &str = "aoe-gemini-auth";
 
-pub fn is_docker_available() -> bool {
-    Command::new("docker")
-        .arg("--version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
-}
-
-pub fn is_daemon_running() -> bool {
-    Command::new("docker")
-        .args(["info"])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
-}
-
-pub fn get_docker_version() -> Result<String> {
-    let output = Command::new("docker").arg("--version").output()?;
-
-    if !output.status.success() {
-        return Err(DockerError::NotInstalled);
-    }
-
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-}
-
-pub fn image_exists_locally(image: &str) -> bool {
-    Command::new("docker")
-        .args(["image", "inspect", image])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
-}
-
-pub fn pull_image(image: &str) -> Result<()> {
-    let output = Command::new("docker").args(["pull", image]).output()?;
+// Runtimes come from the profile's config (`ContainerRuntime::for_profile`),
+// resolved once by the caller. Code that operates on an existing session's
+// container should use the runtime recorded in its `SandboxInfo` instead.
+
+/// Names of all aoe sandbox containers of a runtime, running or stopped.
+pub fn list_sandbox_containers(runtime: ContainerRuntime) -> Result<Vec<String>> {
+    let output = runtime
+        .command()
+        .args([
+            "ps",
+            "-a",
+            "--filter",
+            &format!("name={}", SANDBOX_CONTAINER_PREFIX),
+            "--format",
+            "{{.Names}}",
+        ])
+        .output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(DockerError::ImageNotFound(format!(
-            "{}: {}",
-            image,
-            stderr.trim()
-        )));
+        return Err(DockerError::CommandFailed(stderr.trim().to_string()));
     }
 
-    Ok(())
-}
-
-/// Ensure an image is available locally.
-/// If the image exists locally, uses it as-is (supports local-only images).
-/// If not, attempts to pull from the registry.
-pub fn ensure_image(image: &str) -> Result<()> {
-    if image_exists_locally(image) {
-        tracing::info!("Using local Docker image '{}'", image);
-        return Ok(());
-    }
-
-    tracing::info!("Pulling Docker image '{}'", image);
-    pull_image(image)
-}
-
-pub fn ensure_named_volume(name: &str) -> Result<()> {
-    let check = Command::new("docker")
-        .args(["volume", "inspect", name])
-        .output()?;
-
-    if !check.status.success() {
-        let create = Command::new("docker")
-            .args(["volume", "create", name])
-            .output()?;
-
-        if !create.status.success() {
-            let stderr = String::from_utf8_lossy(&create.stderr);
-            return Err(DockerError::CommandFailed(format!(
-                "Failed to create volume {}: {}",
-                name, stderr
-            )));
-        }
-    }
-
-    Ok(())
+    // The name filter matches substrings, so re-check the prefix
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|name| name.starts_with(SANDBOX_CONTAINER_PREFIX))
+        .map(String::from)
+        .collect())
 }
 
 /// The hardcoded fallback sandbox image.
@@ -112,9 +73,12 @@ pub fn effective_default_image() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::process::Command;
+
+    const DOCKER: ContainerRuntime = ContainerRuntime::Docker;
 
     fn skip_if_no_docker() -> bool {
-        !is_docker_available() || !is_daemon_running()
+        !DOCKER.is_available() || !DOCKER.is_daemon_running()
     }
 
     #[test]
@@ -128,7 +92,7 @@ mod tests {
             .args(["pull", "hello-world"])
             .output();
 
-        assert!(image_exists_locally("hello-world"));
+        assert!(DOCKER.image_exists_locally("hello-world"));
     }
 
     #[test]
@@ -137,9 +101,7 @@ mod tests {
             return;
         }
 
-        assert!(!image_exists_locally(
-            "nonexistent-image-that-does-not-exist:v999"
-        ));
+        assert!(!DOCKER.image_exists_locally("nonexistent-image-that-does-not-exist:v999"));
     }
 
     #[test]
@@ -154,7 +116,7 @@ mod tests {
             .output();
 
         // Should succeed without pulling since image exists
-        let result = ensure_image("hello-world");
+        let result = DOCKER.ensure_image("hello-world");
         assert!(result.is_ok());
     }
 
@@ -165,7 +127,7 @@ mod tests {
         }
 
         // Should fail since image doesn't exist locally or remotely
-        let result = ensure_image("nonexistent-image-that-does-not-exist:v999");
+        let result = DOCKER.ensure_image("nonexistent-image-that-does-not-exist:v999");
         assert!(result.is_err());
     }
 }
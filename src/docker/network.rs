@@ -0,0 +1,454 @@
+//! Network isolation for sandbox containers
+//!
+//! In allowlist mode sandboxes join an internal Docker network that has no
+//! route to the outside world. The only way out is a small tinyproxy
+//! container attached to both that network and the default bridge, which
+//! forwards requests to allowlisted hosts and refuses everything else.
+//! Sandboxes sharing the same allowlist share one network and one proxy.
+
+use super::container::DockerContainer;
+use super::error::{DockerError, Result};
+use super::runtime::ContainerRuntime;
+use sha2::{Digest, Sha256};
+use std::net::Ipv4Addr;
+
+/// Name prefix shared by egress networks and proxy containers
+pub const EGRESS_PREFIX: &str = "aoe-egress-";
+
+/// Image the filtering proxy runs in unless configured otherwise. Fully
+/// qualified so Podman doesn't need to resolve a short name.
+pub const DEFAULT_PROXY_IMAGE: &str = "docker.io/library/alpine:3.20";
+
+/// Port tinyproxy listens on inside the egress network
+pub const PROXY_PORT: u16 = 8888;
+
+/// Installs tinyproxy and runs it with a default-deny host filter built from
+/// the newline-separated regexes in `$AOE_ALLOW`. CONNECT tunnels are limited
+/// to HTTPS; without a `ConnectPort` line tinyproxy tunnels to any port.
+const PROXY_SCRIPT: &str = r#"set -e
+apk add --no-cache tinyproxy >/dev/null
+printf '%s\n' "$AOE_ALLOW" | grep -v '^$' > /etc/tinyproxy/filter || true
+cat > /etc/tinyproxy/aoe.conf <<CONF
+Port 8888
+Listen 0.0.0.0
+Timeout 600
+MaxClients 100
+Filter "/etc/tinyproxy/filter"
+FilterType ere
+FilterURLs Off
+FilterDefaultDeny Yes
+ConnectPort 443
+CONF
+exec tinyproxy -d -c /etc/tinyproxy/aoe.conf"#;
+
+/// How a sandbox container is connected to the network
+pub enum ContainerNetwork {
+    /// Docker's default bridge network, unrestricted
+    Bridge,
+    /// No network interfaces besides loopback
+    Disabled,
+    /// An internal network whose only way out is a filtering proxy
+    Egress(EgressProxy),
+}
+
+impl ContainerNetwork {
+    /// Value for `docker run --network`, if the default isn't used
+    pub fn docker_network(&self) -> Option<&str> {
+        match self {
+            ContainerNetwork::Bridge => None,
+            ContainerNetwork::Disabled => Some("none"),
+            ContainerNetwork::Egress(proxy) => Some(&proxy.network),
+        }
+    }
+}
+
+/// An internal network plus the proxy container that filters its egress
+pub struct EgressProxy {
+    pub network: String,
+    pub container_name: String,
+    pub image: String,
+    pub runtime: ContainerRuntime,
+    rules: Vec<String>,
+}
+
+impl EgressProxy {
+    /// Validate `allowlist` and derive the network and proxy names from it.
+    /// Names are stable for a given set of rules and image, so sandboxes
+    /// with the same policy reuse the same proxy.
+    pub fn new(allowlist: &[String], image: &str, runtime: ContainerRuntime) -> Result<Self> {
+        let rules = allowlist_filter(allowlist)?;
+
+        let mut patterns: Vec<&str> = rules.iter().map(String::as_str).collect();
+        patterns.sort_unstable();
+        patterns.dedup();
+
+        // Hash what the proxy enforces, so a changed filter or proxy setup
+        // starts a new proxy instead of reusing one with the old rules
+        let mut hasher = Sha256::new();
+        hasher.update(image.as_bytes());
+        hasher.update(PROXY_SCRIPT.as_bytes());
+        for pattern in &patterns {
+            hasher.update(b"\n");
+            hasher.update(pattern.as_bytes());
+        }
+        let hash = format!("{:x}", hasher.finalize());
+        let hash = &hash[..8];
+
+        Ok(Self {
+            network: format!("{}{}", EGRESS_PREFIX, hash),
+            container_name: format!("{}proxy-{}", EGRESS_PREFIX, hash),
+            image: image.to_string(),
+            runtime,
+            rules,
+        })
+    }
+
+    pub fn proxy_url(&self) -> String {
+        format!("http://{}:{}", self.container_name, PROXY_PORT)
+    }
+
+    /// Environment variables that point tools inside the sandbox at the proxy
+    pub fn environment(&self) -> Vec<(String, String)> {
+        let url = self.proxy_url();
+        let no_proxy = "localhost,127.0.0.1".to_string();
+        vec![
+            ("HTTP_PROXY".to_string(), url.clone()),
+            ("HTTPS_PROXY".to_string(), url.clone()),
+            ("http_proxy".to_string(), url.clone()),
+            ("https_proxy".to_string(), url),
+            ("NO_PROXY".to_string(), no_proxy.clone()),
+            ("no_proxy".to_string(), no_proxy),
+        ]
+    }
+
+    /// Create the internal network and start the proxy if they don't exist yet.
+    pub fn ensure(&self) -> Result<()> {
+        self.ensure_network()?;
+
+        let proxy = DockerContainer {
+            name: self.container_name.clone(),
+            image: self.image.clone(),
+            runtime: self.runtime,
+        };
+        if proxy.is_running()? {
+            return Ok(());
+        }
+        if proxy.exists()? {
+            return proxy.start();
+        }
+
+        self.runtime.ensure_image(&self.image)?;
+
+        // Start on the default bridge so the proxy can install tinyproxy and
+        // reach the internet, then join the internal network to serve sandboxes.
+        let output = self
+            .runtime
+            .command()
+            .args(self.build_proxy_args())
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(DockerError::NetworkFailed(format!(
+                "could not start proxy {}: {}",
+                self.container_name,
+                stderr.trim()
+            )));
+        }
+
+        let output = self
+            .runtime
+            .command()
+            .args(["network", "connect", &self.network, &self.container_name])
+            .output()?;
+        if !output.status.success() {
+            let _ = proxy.remove(true);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(DockerError::NetworkFailed(format!(
+                "could not attach proxy to {}: {}",
+                self.network,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_network(&self) -> Result<()> {
+        let check = self
+            .runtime
+            .command()
+            .args(["network", "inspect", &self.network])
+            .output()?;
+        if check.status.success() {
+            return Ok(());
+        }
+
+        let create = self
+            .runtime
+            .command()
+            .args(["network", "create", "--internal", &self.network])
+            .output()?;
+        if !create.status.success() {
+            let stderr = String::from_utf8_lossy(&create.stderr);
+            return Err(DockerError::NetworkFailed(format!(
+                "could not create network {}: {}",
+                self.network,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn build_proxy_args(&self) -> Vec<String> {
+        vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--name".to_string(),
+            self.container_name.clone(),
+            "--restart".to_string(),
+            "unless-stopped".to_string(),
+            "-e".to_string(),
+            format!("AOE_ALLOW={}", self.rules.join("\n")),
+            self.image.clone(),
+            "sh".to_string(),
+            "-c".to_string(),
+            PROXY_SCRIPT.to_string(),
+        ]
+    }
+}
+
+/// Convert allowlist entries into tinyproxy host filter regexes.
+///
+/// Accepted entries are hostnames (`api.anthropic.com`), wildcard domains
+/// (`*.npmjs.org`, which also matches `npmjs.org` itself), IPv4 addresses
+/// and IPv4 CIDRs on octet boundaries (`/8`, `/16`, `/24`, `/32`).
+pub fn allowlist_filter(allowlist: &[String]) -> Result<Vec<String>> {
+    allowlist
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(entry_to_pattern)
+        .collect()
+}
+
+fn entry_to_pattern(entry: &str) -> Result<String> {
+    let invalid = |reason: &str| DockerError::InvalidNetworkRule(format!("{}: {}", entry, reason));
+
+    if let Some((addr, prefix)) = entry.split_once('/') {
+        let addr: Ipv4Addr = addr
+            .parse()
+            .map_err(|_| invalid("only IPv4 CIDRs are supported"))?;
+        let prefix: u32 = prefix.parse().map_err(|_| invalid("bad prefix length"))?;
+        if !matches!(prefix, 8 | 16 | 24 | 32) {
+            return Err(invalid("prefix length must be 8, 16, 24 or 32"));
+        }
+        if prefix < 32 && u32::from(addr) & (u32::MAX >> prefix) != 0 {
+            return Err(invalid("address has host bits set"));
+        }
+
+        // The filter is matched against hostnames too, so the free octets
+        // must complete a dotted quad: `^10\.` alone would let `10.evil.com` through
+        let kept = (prefix / 8) as usize;
+        let octets: Vec<String> = addr.octets()[..kept]
+            .iter()
+            .map(|o| o.to_string())
+            .chain(std::iter::repeat("[0-9]{1,3}".to_string()).take(4 - kept))
+            .collect();
+        return Ok(format!("^{}$", octets.join("\\.")));
+    }
+
+    if let Ok(addr) = entry.parse::<Ipv4Addr>() {
+        return Ok(format!("^{}$", addr.to_string().replace('.', "\\.")));
+    }
+
+    let (wildcard, host) = match entry.strip_prefix("*.") {
+        Some(rest) => (true, rest),
+        None => (false, entry),
+    };
+    let valid_host = !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if !valid_host {
+        return Err(invalid("not a hostname, IPv4 address or CIDR"));
+    }
+
+    let escaped = host.to_ascii_lowercase().replace('.', "\\.");
+    Ok(if wildcard {
+        format!("^(.+\\.)?{}$", escaped)
+    } else {
+        format!("^{}$", escaped)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_hostname_patterns() {
+        let rules = allowlist_filter(&strings(&[
+            "api.anthropic.com",
+            "*.npmjs.org",
+            "Registry.Example",
+        ]))
+        .unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                "^api\\.anthropic\\.com$",
+                "^(.+\\.)?npmjs\\.org$",
+                "^registry\\.example$",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ip_and_cidr_patterns() {
+        let rules = allowlist_filter(&strings(&[
+            "10.0.0.0/8",
+            "172.16.0.0/16",
+            "192.168.1.0/24",
+            "1.2.3.4/32",
+            "8.8.8.8",
+        ]))
+        .unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                "^10\\.[0-9]{1,3}\\.[0-9]{1,3}\\.[0-9]{1,3}$",
+                "^172\\.16\\.[0-9]{1,3}\\.[0-9]{1,3}$",
+                "^192\\.168\\.1\\.[0-9]{1,3}$",
+                "^1\\.2\\.3\\.4$",
+                "^8\\.8\\.8\\.8$",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cidr_patterns_only_match_addresses() {
+        let rules = allowlist_filter(&strings(&["10.0.0.0/8", "192.168.1.0/24"])).unwrap();
+        let allowed = |host: &str| {
+            rules
+                .iter()
+                .any(|rule| regex::Regex::new(rule).unwrap().is_match(host))
+        };
+
+        assert!(allowed("10.1.2.3"));
+        assert!(allowed("192.168.1.20"));
+        assert!(!allowed("10.evil.com"));
+        assert!(!allowed("192.168.1.attacker.net"));
+        assert!(!allowed("192.168.1.20.attacker.net"));
+        assert!(!allowed("192.168.10.1"));
+    }
+
+    #[test]
+    fn test_invalid_entries_rejected() {
+        for entry in [
+            "10.0.0.0/12",
+            "10.1.0.0/8",
+            "fd00::/8",
+            "http://example.com",
+            "exa mple.com",
+            "*.",
+            "-bad.example.com",
+        ] {
+            let result = allowlist_filter(&strings(&[entry]));
+            assert!(
+                matches!(result, Err(DockerError::InvalidNetworkRule(_))),
+                "{} should be rejected",
+                entry
+            );
+        }
+    }
+
+    #[test]
+    fn test_blank_entries_ignored() {
+        let rules = allowlist_filter(&strings(&["", "  ", "github.com"])).unwrap();
+        assert_eq!(rules, vec!["^github\\.com$"]);
+    }
+
+    #[test]
+    fn test_proxy_names_stable_across_order() {
+        let a = EgressProxy::new(
+            &strings(&["github.com", "pypi.org"]),
+            DEFAULT_PROXY_IMAGE,
+            ContainerRuntime::Docker,
+        )
+        .unwrap();
+        let b = EgressProxy::new(
+            &strings(&["pypi.org", "github.com"]),
+            DEFAULT_PROXY_IMAGE,
+            ContainerRuntime::Docker,
+        )
+        .unwrap();
+        let c = EgressProxy::new(
+            &strings(&["github.com"]),
+            DEFAULT_PROXY_IMAGE,
+            ContainerRuntime::Docker,
+        )
+        .unwrap();
+
+        assert_eq!(a.network, b.network);
+        assert_ne!(a.network, c.network);
+        assert!(a.network.starts_with(EGRESS_PREFIX));
+        assert!(a.container_name.starts_with(EGRESS_PREFIX));
+        assert_ne!(a.network, a.container_name);
+    }
+
+    #[test]
+    fn test_proxy_environment() {
+        let proxy = EgressProxy::new(
+            &strings(&["github.com"]),
+            DEFAULT_PROXY_IMAGE,
+            ContainerRuntime::Docker,
+        )
+        .unwrap();
+        let env = proxy.environment();
+        let url = format!("http://{}:{}", proxy.container_name, PROXY_PORT);
+        assert!(env.contains(&("HTTPS_PROXY".to_string(), url.clone())));
+        assert!(env.contains(&("http_proxy".to_string(), url)));
+        assert!(env.iter().any(|(k, _)| k == "NO_PROXY"));
+    }
+
+    #[test]
+    fn test_build_proxy_args() {
+        let proxy = EgressProxy::new(
+            &strings(&["github.com", "*.pypi.org"]),
+            "alpine:3.20",
+            ContainerRuntime::Docker,
+        )
+        .unwrap();
+        let args = proxy.build_proxy_args();
+
+        let name_idx = args.iter().position(|a| a == "--name").unwrap();
+        assert_eq!(args[name_idx + 1], proxy.container_name);
+        assert!(args.contains(&"AOE_ALLOW=^github\\.com$\n^(.+\\.)?pypi\\.org$".to_string()));
+        assert!(args.contains(&"alpine:3.20".to_string()));
+        let script = args.last().unwrap();
+        assert!(script.contains("FilterDefaultDeny Yes"));
+        assert!(script.contains("ConnectPort 443"));
+        // Proxy starts on the default bridge; it joins the internal network afterwards
+        assert!(!args.contains(&"--network".to_string()));
+    }
+
+    #[test]
+    fn test_container_network_flag() {
+        let proxy = EgressProxy::new(&[], DEFAULT_PROXY_IMAGE, ContainerRuntime::Docker).unwrap();
+        let network = proxy.network.clone();
+
+        assert_eq!(ContainerNetwork::Bridge.docker_network(), None);
+        assert_eq!(ContainerNetwork::Disabled.docker_network(), Some("none"));
+        assert_eq!(
+            ContainerNetwork::Egress(proxy).docker_network(),
+            Some(network.as_str())
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! Reaching services that run inside sandbox containers
+//!
+//! Ports from `[sandbox] ports` (and devcontainer `forwardPorts`) are
+//! published when the container is created. A container's published ports
+//! can't change after that, so ports added on the fly get a small forwarder
+//! container running socat, which publishes the port on 127.0.0.1 and relays
+//! connections to the sandbox over its network.
+
+use std::collections::BTreeMap;
+
+use super::container::{DockerContainer, SANDBOX_CONTAINER_PREFIX};
+use super::error::{DockerError, Result};
+use super::runtime::ContainerRuntime;
+
+/// Name prefix of port forwarder containers
+pub const FORWARDER_PREFIX: &str = "aoe-port-";
+
+/// Image the port forwarders run
+pub const FORWARDER_IMAGE: &str = "docker.io/alpine/socat:latest";
+
+/// A port of a session's container and how to reach it from the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionPort {
+    pub container_port: u16,
+    /// Host address (e.g. `127.0.0.1:49153`) when the port is published
+    pub host_address: Option<String>,
+    /// Published by a forwarder added after the container was created
+    pub forwarded: bool,
+    /// Something in the container is listening on the port
+    pub listening: bool,
+    /// The listener only accepts connections from inside the container, so
+    /// it isn't reachable through a published port
+    pub loopback_only: bool,
+}
+
+impl SessionPort {
+    pub fn url(&self) -> Option<String> {
+        self.host_address
+            .as_ref()
+            .map(|address| format!("http://{}", address))
+    }
+}
+
+/// A TCP port something is listening on inside a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListeningPort {
+    pub port: u16,
+    pub loopback_only: bool,
+}
+
+/// Parse `ss -ltn` output into listening ports, one entry per port.
+pub fn parse_ss_output(output: &str) -> Vec<ListeningPort> {
+    let mut ports: BTreeMap<u16, bool> = BTreeMap::new();
+    for line in output.lines() {
+        let mut fields = line.split_whitespace();
+        if fields.next() != Some("LISTEN") {
+            continue;
+        }
+        let Some(local) = fields.nth(2) else {
+            continue;
+        };
+        let Some((address, port)) = local.rsplit_once(':') else {
+            continue;
+        };
+        // Docker's embedded DNS resolver, not something the agent started
+        if address == "127.0.0.11" {
+            continue;
+        }
+        let Ok(port) = port.parse::<u16>() else {
+            continue;
+        };
+        let loopback = address.starts_with("127.") || address == "[::1]";
+        let entry = ports.entry(port).or_insert(true);
+        *entry = *entry && loopback;
+    }
+    ports
+        .into_iter()
+        .map(|(port, loopback_only)| ListeningPort {
+            port,
+            loopback_only,
+        })
+        .collect()
+}
+
+/// Parse `docker port` output into (container port, host address) pairs.
+/// IPv6 bindings are skipped when an IPv4 one exists for the same port.
+pub fn parse_port_output(output: &str) -> Vec<(u16, String)> {
+    let mut ports: BTreeMap<u16, String> = BTreeMap::new();
+    for line in output.lines() {
+        let Some((container, host)) = line.split_once(" -> ") else {
+            continue;
+        };
+        let Some(port) = container
+            .split('/')
+            .next()
+            .and_then(|p| p.trim().parse::<u16>().ok())
+        else {
+            continue;
+        };
+        let host = host.trim().replace("0.0.0.0:", "127.0.0.1:");
+        if host.starts_with('[') && ports.contains_key(&port) {
+            continue;
+        }
+        ports.insert(port, host);
+    }
+    ports.into_iter().collect()
+}
+
+/// Ports something is listening on inside a running container.
+pub fn detect_listening_ports(
+    runtime: ContainerRuntime,
+    container_name: &str,
+) -> Result<Vec<ListeningPort>> {
+    let output = runtime
+        .command()
+        .args(["exec", container_name, "ss", "-ltn"])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::CommandFailed(format!(
+            "ss -ltn failed in {} (is iproute2 installed in the image?): {}",
+            container_name,
+            stderr.trim()
+        )));
+    }
+    Ok(parse_ss_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Ports a container publishes, as (container port, host address) pairs.
+pub fn published_ports(runtime: ContainerRuntime, container_name: &str) -> Vec<(u16, String)> {
+    runtime
+        .command()
+        .args(["port", container_name])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_port_output(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// Everything known about a session container's ports: published at
+/// creation, forwarded later, and currently listening.
+pub fn session_ports(
+    runtime: ContainerRuntime,
+    container_name: &str,
+    forwarded: &[u16],
+) -> Result<Vec<SessionPort>> {
+    let mut ports: BTreeMap<u16, SessionPort> = BTreeMap::new();
+    let entry = |ports: &mut BTreeMap<u16, SessionPort>, port: u16| {
+        ports.entry(port).or_insert_with(|| SessionPort {
+            container_port: port,
+            host_address: None,
+            forwarded: false,
+            listening: false,
+            loopback_only: false,
+        });
+    };
+
+    for (port, address) in published_ports(runtime, container_name) {
+        entry(&mut ports, port);
+        if let Some(p) = ports.get_mut(&port) {
+            p.host_address = Some(address);
+        }
+    }
+
+    for &port in forwarded {
+        let forwarder = PortForwarder::new(runtime, container_name, port);
+        entry(&mut ports, port);
+        if let Some(p) = ports.get_mut(&port) {
+            p.forwarded = true;
+            if p.host_address.is_none() {
+                p.host_address = forwarder.host_address();
+            }
+        }
+    }
+
+    for listening in detect_listening_ports(runtime, container_name)? {
+        entry(&mut ports, listening.port);
+        if let Some(p) = ports.get_mut(&listening.port) {
+            p.listening = true;
+            p.loopback_only = listening.loopback_only;
+        }
+    }
+
+    Ok(ports.into_values().collect())
+}
+
+/// A socat container publishing one port of a sandbox on 127.0.0.1.
+pub struct PortForwarder {
+    pub name: String,
+    pub runtime: ContainerRuntime,
+    sandbox_container: String,
+    container_port: u16,
+}
+
+impl PortForwarder {
+    pub fn new(runtime: ContainerRuntime, sandbox_container: &str, container_port: u16) -> Self {
+        Self {
+            name: forwarder_name(sandbox_container, container_port),
+            runtime,
+            sandbox_container: sandbox_container.to_string(),
+            container_port,
+        }
+    }
+
+    fn container(&self) -> DockerContainer {
+        DockerContainer {
+            name: self.name.clone(),
+            image: FORWARDER_IMAGE.to_string(),
+            runtime: self.runtime,
+        }
+    }
+
+    /// (Re)create the forwarder. The sandbox must be running: the forwarder
+    /// relays to its current IP address, so it's recreated whenever the
+    /// sandbox container restarts.
+    pub fn start(&self) -> Result<()> {
+        let (network, ip) = self
+            .sandbox_networks()?
+            .into_iter()
+            .find(|(network, ip)| network != "none" && !ip.is_empty())
+            .ok_or_else(|| {
+                DockerError::PortForwardFailed(format!(
+                    "{} has no network (is the sandbox network mode \"none\"?)",
+                    self.sandbox_container
+                ))
+            })?;
+
+        self.runtime.ensure_image(FORWARDER_IMAGE)?;
+        let container = self.container();
+        let _ = container.remove(true);
+
+        let output = self
+            .runtime
+            .command()
+            .args(self.build_run_args(&ip))
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(DockerError::PortForwardFailed(stderr.trim().to_string()));
+        }
+
+        // Sandboxes on an egress-filtered network are unreachable from the
+        // default bridge; published ports only work there, so join both
+        if !is_default_network(self.runtime, &network) {
+            let output = self
+                .runtime
+                .command()
+                .args(["network", "connect", &network, &self.name])
+                .output()?;
+            if !output.status.success() {
+                let _ = container.remove(true);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(DockerError::PortForwardFailed(format!(
+                    "could not attach to {}: {}",
+                    network,
+                    stderr.trim()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        match self.container().remove(true) {
+            Ok(()) | Err(DockerError::ContainerNotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn host_address(&self) -> Option<String> {
+        published_ports(self.runtime, &self.name)
+            .into_iter()
+            .find(|(port, _)| *port == self.container_port)
+            .map(|(_, address)| address)
+    }
+
+    pub(crate) fn build_run_args(&self, target_ip: &str) -> Vec<String> {
+        vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--name".to_string(),
+            self.name.clone(),
+            "-p".to_string(),
+            format!("127.0.0.1::{}", self.container_port),
+            FORWARDER_IMAGE.to_string(),
+            format!("TCP-LISTEN:{},fork,reuseaddr", self.container_port),
+            format!("TCP:{}:{}", target_ip, self.container_port),
+        ]
+    }
+
+    /// (network, IP address) pairs the sandbox container is attached to.
+    fn sandbox_networks(&self) -> Result<Vec<(String, String)>> {
+        let output = self
+            .runtime
+            .command()
+            .args([
+                "inspect",
+                "-f",
+                "{{range $k, $v := .NetworkSettings.Networks}}{{$k}}={{$v.IPAddress}} {{end}}",
+                &self.sandbox_container,
+            ])
+            .output()?;
+        if !output.status.success() {
+            return Err(DockerError::ContainerNotFound(
+                self.sandbox_container.clone(),
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(network, ip)| (network.to_string(), ip.to_string()))
+            .collect())
+    }
+}
+
+fn forwarder_prefix(sandbox_container: &str) -> String {
+    format!(
+        "{}{}-",
+        FORWARDER_PREFIX,
+        sandbox_container
+            .strip_prefix(SANDBOX_CONTAINER_PREFIX)
+            .unwrap_or(sandbox_container)
+    )
+}
+
+fn forwarder_name(sandbox_container: &str, container_port: u16) -> String {
+    format!("{}{}", forwarder_prefix(sandbox_container), container_port)
+}
+
+fn is_default_network(runtime: ContainerRuntime, network: &str) -> bool {
+    match runtime {
+        ContainerRuntime::Docker => network == "bridge",
+        ContainerRuntime::Podman => network == "podman",
+    }
+}
+
+/// Remove every port forwarder belonging to a sandbox container.
+pub fn remove_forwarders(runtime: ContainerRuntime, sandbox_container: &str) -> Result<()> {
+    let prefix = forwarder_prefix(sandbox_container);
+    let output = runtime
+        .command()
+        .args([
+            "ps",
+            "-a",
+            "--filter",
+            &format!("name=^{}", prefix),
+            "--format",
+            "{{.Names}}",
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Ok(());
+    }
+    for name in String::from_utf8_lossy(&output.stdout).lines() {
+        let name = name.trim();
+        if name.starts_with(&prefix) {
+            let _ = runtime.command().args(["rm", "-f", name]).output();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ss_output() {
+        let output = "\
+State  Recv-Q Send-Q Local Address:Port  Peer Address:Port Process
+LISTEN 0      511          0.0.0.0:3000       0.0.0.0:*
+LISTEN 0      511             [::]:3000          [::]:*
+LISTEN 0      4096      127.0.0.11:41567      0.0.0.0:*
+LISTEN 0      128        127.0.0.1:5432       0.0.0.0:*
+LISTEN 0      128                *:8080             *:*
+";
+        assert_eq!(
+            parse_ss_output(output),
+            vec![
+                ListeningPort {
+                    port: 3000,
+                    loopback_only: false
+                },
+                ListeningPort {
+                    port: 5432,
+                    loopback_only: true
+                },
+                ListeningPort {
+                    port: 8080,
+                    loopback_only: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ss_output_empty() {
+        assert!(parse_ss_output("").is_empty());
+        assert!(parse_ss_output("State Recv-Q Send-Q Local Address:Port").is_empty());
+    }
+
+    #[test]
+    fn test_parse_port_output() {
+        let output = "\
+3000/tcp -> 127.0.0.1:49153
+5173/tcp -> 0.0.0.0:8080
+5173/tcp -> [::]:8080
+";
+        assert_eq!(
+            parse_port_output(output),
+            vec![
+                (3000, "127.0.0.1:49153".to_string()),
+                (5173, "127.0.0.1:8080".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forwarder_name() {
+        let forwarder = PortForwarder::new(ContainerRuntime::Docker, "aoe-sandbox-abcd1234", 3000);
+        assert_eq!(forwarder.name, "aoe-port-abcd1234-3000");
+    }
+
+    #[test]
+    fn test_forwarder_run_args() {
+        let forwarder = PortForwarder::new(ContainerRuntime::Docker, "aoe-sandbox-abcd1234", 3000);
+        let args = forwarder.build_run_args("172.17.0.5");
+        assert_eq!(
+            args,
+            vec![
+                "run",
+                "-d",
+                "--name",
+                "aoe-port-abcd1234-3000",
+                "-p",
+                "127.0.0.1::3000",
+                FORWARDER_IMAGE,
+                "TCP-LISTEN:3000,fork,reuseaddr",
+                "TCP:172.17.0.5:3000",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_session_port_url() {
+        let port = SessionPort {
+            container_port: 3000,
+            host_address: Some("127.0.0.1:49153".to_string()),
+            forwarded: false,
+            listening: true,
+            loopback_only: false,
+        };
+        assert_eq!(port.url().as_deref(), Some("http://127.0.0.1:49153"));
+    }
+}
@@ -0,0 +1,183 @@
+//! Container runtime selection (Docker or Podman)
+//!
+//! Both runtimes accept the same CLI for everything aoe does, so the runtime
+//! mostly decides which binary is invoked. The exception is volume ownership:
+//! rootless Podman maps container root to the invoking user, and named volumes
+//! are mounted with `:U` so Podman chowns them to the container user instead
+//! of relying on the one-off `chown` the Docker volume migration performs.
+
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+use super::error::{DockerError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    /// The runtime selected by `[sandbox] runtime` for a profile, with the
+    /// profile's overrides applied. Resolve it once and pass it along.
+    pub fn for_profile(profile: &str) -> Self {
+        crate::session::resolve_config(profile)
+            .map(|c| c.sandbox.runtime)
+            .unwrap_or_default()
+    }
+
+    pub fn is_docker(&self) -> bool {
+        *self == ContainerRuntime::Docker
+    }
+
+    /// Name of the CLI binary
+    pub fn binary(self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+
+    /// Human-readable name for messages
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "Docker",
+            ContainerRuntime::Podman => "Podman",
+        }
+    }
+
+    pub fn command(self) -> Command {
+        Command::new(self.binary())
+    }
+
+    /// `-v` argument mounting a named volume at `container_path`.
+    pub fn named_volume_mount(self, volume: &str, container_path: &str) -> String {
+        match self {
+            ContainerRuntime::Docker => format!("{}:{}", volume, container_path),
+            // Chown the volume to the container's user on mount. Bind mounts
+            // don't need this: rootless Podman already maps container root
+            // to the host user, so project files keep their host ownership
+            // (the same effect `--userns=keep-id` has for non-root images).
+            ContainerRuntime::Podman => format!("{}:{}:U", volume, container_path),
+        }
+    }
+
+    pub fn is_available(self) -> bool {
+        self.command()
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Whether the runtime can run containers right now. Podman has no daemon,
+    /// but `podman info` still fails when its machine/VM isn't running.
+    pub fn is_daemon_running(self) -> bool {
+        self.command()
+            .arg("info")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    pub fn version(self) -> Result<String> {
+        let output = self.command().arg("--version").output()?;
+
+        if !output.status.success() {
+            return Err(DockerError::NotInstalled);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub fn image_exists_locally(self, image: &str) -> bool {
+        self.command()
+            .args(["image", "inspect", image])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    pub fn pull_image(self, image: &str) -> Result<()> {
+        let output = self.command().args(["pull", image]).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(DockerError::ImageNotFound(format!(
+                "{}: {}",
+                image,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Ensure an image is available locally.
+    /// If the image exists locally, uses it as-is (supports local-only images).
+    /// If not, attempts to pull from the registry.
+    pub fn ensure_image(self, image: &str) -> Result<()> {
+        if self.image_exists_locally(image) {
+            tracing::info!("Using local {} image '{}'", self.display_name(), image);
+            return Ok(());
+        }
+
+        tracing::info!("Pulling {} image '{}'", self.display_name(), image);
+        self.pull_image(image)
+    }
+
+    pub fn ensure_named_volume(self, name: &str) -> Result<()> {
+        let check = self.command().args(["volume", "inspect", name]).output()?;
+
+        if !check.status.success() {
+            let create = self.command().args(["volume", "create", name]).output()?;
+
+            if !create.status.success() {
+                let stderr = String::from_utf8_lossy(&create.stderr);
+                return Err(DockerError::CommandFailed(format!(
+                    "Failed to create volume {}: {}",
+                    name, stderr
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_deserialize() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            runtime: ContainerRuntime,
+        }
+        let w: Wrapper = toml::from_str(r#"runtime = "podman""#).unwrap();
+        assert_eq!(w.runtime, ContainerRuntime::Podman);
+        let w: Wrapper = toml::from_str(r#"runtime = "docker""#).unwrap();
+        assert_eq!(w.runtime, ContainerRuntime::Docker);
+    }
+
+    #[test]
+    fn test_binary() {
+        assert_eq!(ContainerRuntime::Docker.binary(), "docker");
+        assert_eq!(ContainerRuntime::Podman.binary(), "podman");
+    }
+
+    #[test]
+    fn test_named_volume_mount() {
+        assert_eq!(
+            ContainerRuntime::Docker.named_volume_mount("aoe-claude-auth", "/root/.claude"),
+            "aoe-claude-auth:/root/.claude"
+        );
+        assert_eq!(
+            ContainerRuntime::Podman.named_volume_mount("aoe-claude-auth", "/root/.claude"),
+            "aoe-claude-auth:/root/.claude:U"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Snapshots of sandbox containers
+//!
+//! A snapshot is an image committed from a session's container. Bind-mounted
+//! paths (the project, auth volumes) aren't part of it, so rolling back
+//! restores installed packages and other container state without touching
+//! the workspace. Each session's snapshots share one image repository,
+//! `aoe-snapshot-<session>`, and the snapshot name is the tag.
+
+use super::container::SANDBOX_CONTAINER_PREFIX;
+use super::error::{DockerError, Result};
+use super::runtime::ContainerRuntime;
+
+/// Repository name prefix of snapshot images
+pub const SNAPSHOT_REPO_PREFIX: &str = "aoe-snapshot-";
+
+/// Longest tag docker accepts
+const MAX_NAME_LEN: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub name: String,
+    pub image: String,
+    /// Creation time as reported by the runtime
+    pub created: String,
+    pub size: String,
+}
+
+impl Snapshot {
+    /// Creation time without fractional seconds and timezone.
+    pub fn created_short(&self) -> &str {
+        self.created.get(..19).unwrap_or(&self.created)
+    }
+}
+
+/// Image repository holding the snapshots of a sandbox container.
+pub fn snapshot_repo(container_name: &str) -> String {
+    let suffix = container_name
+        .strip_prefix(SANDBOX_CONTAINER_PREFIX)
+        .unwrap_or(container_name);
+    format!("{}{}", SNAPSHOT_REPO_PREFIX, suffix)
+}
+
+pub fn snapshot_image(container_name: &str, name: &str) -> String {
+    format!("{}:{}", snapshot_repo(container_name), name)
+}
+
+/// Snapshot names become image tags, so they follow docker's tag rules.
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(DockerError::InvalidSnapshotName(name.to_string()))
+    }
+}
+
+/// Name for a snapshot taken without one, based on the current time.
+pub fn default_name() -> String {
+    chrono::Local::now().format("%Y%m%d-%H%M%S").to_string()
+}
+
+/// Parse `images --format "{{.Repository}}\t{{.Tag}}\t{{.CreatedAt}}\t{{.Size}}"`
+/// output, keeping images of `repo`. Newest first.
+pub fn parse_image_list(output: &str, repo: &str) -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let repository = fields.next()?.trim();
+            let tag = fields.next()?.trim();
+            let created = fields.next()?.trim();
+            let size = fields.next().unwrap_or("").trim();
+            // Podman qualifies local images with `localhost/`
+            let matches_repo = repository == repo
+                || repository
+                    .strip_suffix(repo)
+                    .is_some_and(|prefix| prefix.ends_with('/'));
+            if !matches_repo || tag.is_empty() || tag == "<none>" {
+                return None;
+            }
+            Some(Snapshot {
+                name: tag.to_string(),
+                image: format!("{}:{}", repo, tag),
+                created: created.to_string(),
+                size: size.to_string(),
+            })
+        })
+        .collect();
+    snapshots.sort_by(|a, b| b.created.cmp(&a.created));
+    snapshots
+}
+
+/// Snapshots of a sandbox container, newest first.
+pub fn list(runtime: ContainerRuntime, container_name: &str) -> Result<Vec<Snapshot>> {
+    let repo = snapshot_repo(container_name);
+    let output = runtime
+        .command()
+        .args([
+            "images",
+            &repo,
+            "--format",
+            "{{.Repository}}\t{{.Tag}}\t{{.CreatedAt}}\t{{.Size}}",
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::CommandFailed(stderr.trim().to_string()));
+    }
+
+    Ok(parse_image_list(
+        &String::from_utf8_lossy(&output.stdout),
+        &repo,
+    ))
+}
+
+pub fn exists(runtime: ContainerRuntime, container_name: &str, name: &str) -> bool {
+    runtime.image_exists_locally(&snapshot_image(container_name, name))
+}
+
+/// Commit the container's current filesystem as snapshot `name`.
+pub fn create(runtime: ContainerRuntime, container_name: &str, name: &str) -> Result<Snapshot> {
+    validate_name(name)?;
+    if exists(runtime, container_name, name) {
+        return Err(DockerError::SnapshotExists(name.to_string()));
+    }
+
+    let image = snapshot_image(container_name, name);
+    let output = runtime
+        .command()
+        .args(["commit", container_name, &image])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("No such container") || stderr.contains("no such container") {
+            return Err(DockerError::ContainerNotFound(container_name.to_string()));
+        }
+        return Err(DockerError::SnapshotFailed(stderr.trim().to_string()));
+    }
+
+    list(runtime, container_name)?
+        .into_iter()
+        .find(|s| s.name == name)
+        .ok_or_else(|| DockerError::SnapshotNotFound(name.to_string()))
+}
+
+pub fn remove(runtime: ContainerRuntime, container_name: &str, name: &str) -> Result<()> {
+    let output = runtime
+        .command()
+        .args(["rmi", &snapshot_image(container_name, name)])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("No such image") || stderr.contains("image not known") {
+            return Err(DockerError::SnapshotNotFound(name.to_string()));
+        }
+        return Err(DockerError::SnapshotFailed(stderr.trim().to_string()));
+    }
+
+    Ok(())
+}
+
+/// Remove every snapshot of a sandbox container, e.g. when its session is
+/// deleted.
+pub fn remove_all(runtime: ContainerRuntime, container_name: &str) -> Result<()> {
+    for snapshot in list(runtime, container_name)? {
+        remove(runtime, container_name, &snapshot.name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_repo() {
+        assert_eq!(
+            snapshot_repo("aoe-sandbox-abcd1234"),
+            "aoe-snapshot-abcd1234"
+        );
+        assert_eq!(
+            snapshot_image("aoe-sandbox-abcd1234", "before-upgrade"),
+            "aoe-snapshot-abcd1234:before-upgrade"
+        );
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("before-upgrade").is_ok());
+        assert!(validate_name("v1.2_ok").is_ok());
+        assert!(validate_name(&default_name()).is_ok());
+
+        assert!(validate_name("").is_err());
+        assert!(validate_name("-leading-dash").is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name("slash/name").is_err());
+        assert!(validate_name(&"a".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn test_parse_image_list() {
+        let output = "aoe-snapshot-abcd1234\tfirst\t2025-01-10 09:00:00 +0000 UTC\t1.2GB\n\
+                      localhost/aoe-snapshot-abcd1234\tsecond\t2025-01-11 10:30:00.123 +0000 UTC\t1.3GB\n\
+                      aoe-snapshot-abcd1234\t<none>\t2025-01-09 08:00:00 +0000 UTC\t1.1GB\n\
+                      aoe-snapshot-abcd12345\tother\t2025-01-12 08:00:00 +0000 UTC\t1GB\n";
+        let snapshots = parse_image_list(output, "aoe-snapshot-abcd1234");
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].name, "second");
+        assert_eq!(snapshots[0].image, "aoe-snapshot-abcd1234:second");
+        assert_eq!(snapshots[0].created_short(), "2025-01-11 10:30:00");
+        assert_eq!(snapshots[1].name, "first");
+        assert_eq!(snapshots[1].size, "1.2GB");
+    }
+}
@@ -0,0 +1,160 @@
+//! Resource usage of sandbox containers
+//!
+//! One `stats --no-stream` call covers every running container, so usage for
+//! all sessions is collected in a single batch per runtime.
+
+use super::container::SANDBOX_CONTAINER_PREFIX;
+use super::error::{DockerError, Result};
+use super::runtime::ContainerRuntime;
+
+/// Memory use, as a percentage of the limit, at which a container is
+/// considered close to being OOM-killed
+pub const MEMORY_WARNING_PERCENT: f64 = 90.0;
+
+const STATS_FORMAT: &str = "{{.Name}}\t{{.CPUPerc}}\t{{.MemUsage}}\t{{.PIDs}}";
+
+/// A snapshot of one container's resource usage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerStats {
+    pub name: String,
+    /// Percentage of one CPU; exceeds 100 when using several cores
+    pub cpu_percent: f64,
+    pub memory_used: u64,
+    /// Memory limit in bytes; the host's memory when the container has none
+    pub memory_limit: u64,
+    pub pids: u32,
+}
+
+impl ContainerStats {
+    pub fn memory_percent(&self) -> f64 {
+        if self.memory_limit == 0 {
+            return 0.0;
+        }
+        self.memory_used as f64 / self.memory_limit as f64 * 100.0
+    }
+
+    pub fn near_memory_limit(&self) -> bool {
+        self.memory_percent() >= MEMORY_WARNING_PERCENT
+    }
+}
+
+/// Parse a size like `1.5GiB`, `512MB` or `0B` into bytes.
+pub fn parse_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: f64 = match unit.trim() {
+        "" | "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "KiB" => 1024.0,
+        "MB" => 1e6,
+        "MiB" => 1024.0 * 1024.0,
+        "GB" => 1e9,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TB" => 1e12,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+/// Short binary size for display, e.g. `1.5G` or `512M`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 || value >= 10.0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Parse `stats --no-stream --format` output, keeping sandbox containers.
+pub fn parse_stats_output(output: &str) -> Vec<ContainerStats> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.trim();
+            if !name.starts_with(SANDBOX_CONTAINER_PREFIX) {
+                return None;
+            }
+            let cpu_percent = fields.next()?.trim().trim_end_matches('%').parse().ok()?;
+            let (used, limit) = fields.next()?.split_once('/')?;
+            // Podman prints `--` for values it can't read
+            let pids = fields.next().and_then(|p| p.trim().parse().ok());
+            Some(ContainerStats {
+                name: name.to_string(),
+                cpu_percent,
+                memory_used: parse_size(used)?,
+                memory_limit: parse_size(limit)?,
+                pids: pids.unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// Current usage of every running sandbox container managed by `runtime`.
+pub fn collect_sandbox_stats(runtime: ContainerRuntime) -> Result<Vec<ContainerStats>> {
+    let output = runtime
+        .command()
+        .args(["stats", "--no-stream", "--format", STATS_FORMAT])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::CommandFailed(stderr.trim().to_string()));
+    }
+
+    Ok(parse_stats_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("0B"), Some(0));
+        assert_eq!(parse_size("512KiB"), Some(512 * 1024));
+        assert_eq!(parse_size("1.5GiB"), Some(1024 * 1024 * 1024 * 3 / 2));
+        assert_eq!(parse_size("2GB"), Some(2_000_000_000));
+        assert_eq!(parse_size(" 100MB "), Some(100_000_000));
+        assert_eq!(parse_size("--"), None);
+        assert_eq!(parse_size("12XB"), None);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512B");
+        assert_eq!(format_size(1536 * 1024 * 1024), "1.5G");
+        assert_eq!(format_size(300 * 1024 * 1024), "300M");
+    }
+
+    #[test]
+    fn test_parse_stats_output() {
+        let output = "aoe-sandbox-abcd1234\t12.50%\t1.5GiB / 2GiB\t42\n\
+                      postgres\t3.00%\t100MiB / 7.7GiB\t10\n\
+                      aoe-sandbox-ef567890\t0.00%\t7.2GB / 8GB\t--\n\
+                      aoe-sandbox-bad\tnot-a-number\t1GiB / 2GiB\t1\n";
+        let stats = parse_stats_output(output);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "aoe-sandbox-abcd1234");
+        assert_eq!(stats[0].cpu_percent, 12.5);
+        assert_eq!(stats[0].pids, 42);
+        assert_eq!(stats[0].memory_percent(), 75.0);
+        assert!(!stats[0].near_memory_limit());
+
+        assert_eq!(stats[1].pids, 0);
+        assert!(stats[1].near_memory_limit());
+    }
+}
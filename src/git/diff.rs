@@ -101,6 +101,30 @@ pub struct FileDiff {
     pub is_binary: bool,
 }
 
+/// At-a-glance summary of a working directory's state relative to a base branch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChangeSummary {
+    /// Commits on HEAD that are not on the base branch
+    pub ahead: usize,
+    /// Commits on the base branch that are not on HEAD
+    pub behind: usize,
+    /// Files with uncommitted changes (including untracked files)
+    pub dirty_files: usize,
+    /// Files that differ from the base branch
+    pub changed_files: usize,
+    /// Lines added relative to the base branch
+    pub additions: usize,
+    /// Lines deleted relative to the base branch
+    pub deletions: usize,
+}
+
+impl ChangeSummary {
+    /// Whether the working directory has produced anything relative to the base
+    pub fn has_changes(&self) -> bool {
+        self.ahead > 0 || self.changed_files > 0 || self.dirty_files > 0
+    }
+}
+
 /// Compute the list of changed files between a base branch and the working directory
 pub fn compute_changed_files(repo_path: &Path, base_branch: &str) -> Result<Vec<DiffFile>> {
     let repo = git2::Repository::discover(repo_path)?;
@@ -181,27 +205,115 @@ pub fn compute_changed_files(repo_path: &Path, base_branch: &str) -> Result<Vec<
     Ok(files)
 }
 
+/// Render the changes between a base branch and the working directory as a
+/// unified patch (the same set of files reported by `compute_changed_files`).
+///
+/// Untracked files are included as new files and binary files are emitted as
+/// git binary patches, so the result can be applied with `git apply` or
+/// `apply_patch`.
+pub fn compute_patch(repo_path: &Path, base_branch: &str, context_lines: usize) -> Result<Vec<u8>> {
+    let repo = git2::Repository::discover(repo_path)?;
+    let base_tree = get_tree_from_ref(&repo, base_branch)?;
+
+    let mut opts = git2::DiffOptions::new();
+    opts.include_untracked(true);
+    opts.recurse_untracked_dirs(true);
+    opts.show_untracked_content(true);
+    opts.show_binary(true);
+    opts.context_lines(context_lines as u32);
+
+    let mut diff = repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))?;
+
+    let mut find_opts = git2::DiffFindOptions::new();
+    find_opts.renames(true);
+    find_opts.copies(true);
+    diff.find_similar(Some(&mut find_opts))?;
+
+    let mut patch = Vec::new();
+    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin() as u8);
+        }
+        patch.extend_from_slice(line.content());
+        true
+    })?;
+
+    Ok(patch)
+}
+
+/// Apply a unified patch to the working directory of the repository at `repo_path`.
+///
+/// With `check_only` set, the patch is validated against the working directory
+/// without modifying any files.
+pub fn apply_patch(repo_path: &Path, patch: &[u8], check_only: bool) -> Result<()> {
+    let repo = git2::Repository::discover(repo_path)?;
+    if repo.workdir().is_none() {
+        return Err(GitError::NotAGitRepo);
+    }
+
+    let diff = git2::Diff::from_buffer(patch)
+        .map_err(|e| GitError::PatchFailed(e.message().to_string()))?;
+
+    let mut opts = git2::ApplyOptions::new();
+    opts.check(check_only);
+
+    repo.apply(&diff, git2::ApplyLocation::WorkDir, Some(&mut opts))
+        .map_err(|e| GitError::PatchFailed(e.message().to_string()))
+}
+
+/// Compute ahead/behind counts, dirty files and line totals against a base branch
+pub fn compute_change_summary(repo_path: &Path, base_branch: &str) -> Result<ChangeSummary> {
+    let repo = git2::Repository::discover(repo_path)?;
+    let base_commit = get_commit_from_ref(&repo, base_branch)?;
+
+    let (ahead, behind) = match repo.head().and_then(|h| h.peel_to_commit()) {
+        Ok(head) => repo.graph_ahead_behind(head.id(), base_commit.id())?,
+        Err(_) => (0, 0),
+    };
+
+    let mut status_opts = git2::StatusOptions::new();
+    status_opts.include_untracked(true);
+    status_opts.recurse_untracked_dirs(true);
+    status_opts.include_ignored(false);
+    let dirty_files = repo.statuses(Some(&mut status_opts))?.len();
+
+    let files = compute_changed_files(repo_path, base_branch)?;
+
+    Ok(ChangeSummary {
+        ahead,
+        behind,
+        dirty_files,
+        changed_files: files.len(),
+        additions: files.iter().map(|f| f.additions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+    })
+}
+
 /// Get a git tree from a reference (branch name, tag, or commit)
 fn get_tree_from_ref<'a>(repo: &'a git2::Repository, reference: &str) -> Result<git2::Tree<'a>> {
+    Ok(get_commit_from_ref(repo, reference)?.tree()?)
+}
+
+/// Get a commit from a reference (branch name, tag, or commit)
+fn get_commit_from_ref<'a>(
+    repo: &'a git2::Repository,
+    reference: &str,
+) -> Result<git2::Commit<'a>> {
     // Try as a branch first
     if let Ok(branch) = repo.find_branch(reference, git2::BranchType::Local) {
-        let commit = branch.get().peel_to_commit()?;
-        return Ok(commit.tree()?);
+        return Ok(branch.get().peel_to_commit()?);
     }
 
     // Try as a remote branch
     let remote_ref = format!("origin/{}", reference);
     if let Ok(branch) = repo.find_branch(&remote_ref, git2::BranchType::Remote) {
-        let commit = branch.get().peel_to_commit()?;
-        return Ok(commit.tree()?);
+        return Ok(branch.get().peel_to_commit()?);
     }
 
     // Try as a reference/commit
     let obj = repo.revparse_single(reference)?;
-    let commit = obj
-        .peel_to_commit()
-        .map_err(|_| GitError::BranchNotFound(reference.to_string()))?;
-    Ok(commit.tree()?)
+    obj.peel_to_commit()
+        .map_err(|_| GitError::BranchNotFound(reference.to_string()))
 }
 
 /// Compute the full diff for a specific file
@@ -407,6 +519,36 @@ pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
     Ok(branches)
 }
 
+/// List the refs a new branch can start from: local branches, ordered as by
+/// `list_branches`, then remote branches and tags.
+pub fn list_base_refs(repo_path: &Path) -> Result<Vec<String>> {
+    let repo = git2::Repository::discover(repo_path)?;
+    let mut refs = list_branches(repo_path)?;
+
+    let mut remote = Vec::new();
+    for branch in repo.branches(Some(git2::BranchType::Remote))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()? {
+            if !name.ends_with("/HEAD") {
+                remote.push(name.to_string());
+            }
+        }
+    }
+    remote.sort();
+    refs.extend(remote);
+
+    let mut tags: Vec<String> = repo
+        .tag_names(None)?
+        .iter()
+        .flatten()
+        .map(String::from)
+        .collect();
+    tags.sort();
+    refs.extend(tags);
+
+    Ok(refs)
+}
+
 /// Get the default branch name (main or master)
 pub fn get_default_branch(repo_path: &Path) -> Result<String> {
     let repo = git2::Repository::discover(repo_path)?;
@@ -516,6 +658,100 @@ mod tests {
         assert!(diff.file.additions > 0);
     }
 
+    #[test]
+    fn test_compute_patch_round_trip() {
+        let (dir, _repo) = setup_test_repo();
+        fs::write(
+            dir.path().join("test.txt"),
+            "line 1\nline 2 changed\nline 3\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("new.txt"), "brand new\n").unwrap();
+
+        let patch = compute_patch(dir.path(), "HEAD", 3).unwrap();
+        let text = String::from_utf8(patch.clone()).unwrap();
+        assert!(text.contains("+line 2 changed"));
+        assert!(text.contains("+brand new"));
+
+        let (other, _other_repo) = setup_test_repo();
+        apply_patch(other.path(), &patch, false).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(other.path().join("test.txt")).unwrap(),
+            "line 1\nline 2 changed\nline 3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(other.path().join("new.txt")).unwrap(),
+            "brand new\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_check_only_leaves_files_untouched() {
+        let (dir, _repo) = setup_test_repo();
+        fs::write(dir.path().join("test.txt"), "changed\n").unwrap();
+        let patch = compute_patch(dir.path(), "HEAD", 3).unwrap();
+
+        let (other, _other_repo) = setup_test_repo();
+        apply_patch(other.path(), &patch, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(other.path().join("test.txt")).unwrap(),
+            "line 1\nline 2\nline 3\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_conflict() {
+        let (dir, _repo) = setup_test_repo();
+        fs::write(dir.path().join("test.txt"), "changed\n").unwrap();
+        let patch = compute_patch(dir.path(), "HEAD", 3).unwrap();
+
+        let (other, _other_repo) = setup_test_repo();
+        fs::write(other.path().join("test.txt"), "something else entirely\n").unwrap();
+        let result = apply_patch(other.path(), &patch, false);
+        assert!(matches!(result, Err(GitError::PatchFailed(_))));
+    }
+
+    #[test]
+    fn test_compute_change_summary() {
+        let (dir, repo) = setup_test_repo();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let summary = compute_change_summary(dir.path(), &base).unwrap();
+        assert_eq!(summary, ChangeSummary::default());
+        assert!(!summary.has_changes());
+
+        // Commit a change on a feature branch, then leave an uncommitted edit
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &head, false).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        fs::write(
+            dir.path().join("test.txt"),
+            "line 1\nline 2\nline 3\nline 4\n",
+        )
+        .unwrap();
+        {
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("test.txt")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "Add line 4", &tree, &[&head])
+                .unwrap();
+        }
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+
+        let summary = compute_change_summary(dir.path(), &base).unwrap();
+        assert_eq!(summary.ahead, 1);
+        assert_eq!(summary.behind, 0);
+        assert_eq!(summary.dirty_files, 1);
+        assert_eq!(summary.changed_files, 2);
+        // Untracked files are listed but their lines are not counted
+        assert_eq!(summary.additions, 1);
+        assert_eq!(summary.deletions, 0);
+        assert!(summary.has_changes());
+    }
+
     #[test]
     fn test_list_branches() {
         let (dir, repo) = setup_test_repo();
@@ -529,6 +765,21 @@ mod tests {
         assert!(branches.len() >= 1);
     }
 
+    #[test]
+    fn test_list_base_refs_includes_tags() {
+        let (dir, repo) = setup_test_repo();
+
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &commit, false).unwrap();
+        repo.tag_lightweight("v1.0", commit.as_object(), false)
+            .unwrap();
+
+        let refs = list_base_refs(dir.path()).unwrap();
+        let feature = refs.iter().position(|r| r == "feature").unwrap();
+        let tag = refs.iter().position(|r| r == "v1.0").unwrap();
+        assert!(feature < tag);
+    }
+
     #[test]
     fn test_get_default_branch() {
         let (dir, _repo) = setup_test_repo();
@@ -15,6 +15,21 @@ pub enum GitError {
     #[error("Branch '{0}' not found")]
     BranchNotFound(String),
 
+    #[error("Ref '{0}' not found")]
+    RefNotFound(String),
+
+    #[error("Git fetch failed: {0}")]
+    FetchFailed(String),
+
+    #[error("Sync failed: {0}")]
+    SyncFailed(String),
+
+    #[error("Land failed: {0}")]
+    LandFailed(String),
+
+    #[error("Patch does not apply: {0}")]
+    PatchFailed(String),
+
     #[error("Git error: {0}")]
     Git2Error(#[from] git2::Error),
 
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 
 pub mod diff;
 pub mod error;
+pub mod sync;
 pub mod template;
 
 use error::{GitError, Result};
@@ -148,6 +149,60 @@ impl GitWorktree {
         Ok(())
     }
 
+    /// Create a worktree on a new branch that starts at `base` instead of HEAD.
+    /// `base` may be anything git can resolve to a commit: a local or remote
+    /// branch, a tag, a full ref name or a commit id.
+    pub fn create_worktree_from_base(&self, branch: &str, path: &Path, base: &str) -> Result<()> {
+        if path.exists() {
+            return Err(GitError::WorktreeAlreadyExists(path.to_path_buf()));
+        }
+
+        let repo = git2::Repository::discover(&self.repo_path)?;
+        let commit = repo
+            .revparse_single(base)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| GitError::RefNotFound(base.to_string()))?;
+
+        let mut created = repo.branch(branch, &commit, false)?;
+
+        if let Err(e) = self.create_worktree(branch, path, false) {
+            // Don't leave the new branch behind when the checkout fails
+            if let Err(delete_err) = created.delete() {
+                tracing::warn!("Failed to delete branch '{}': {}", branch, delete_err);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Fetch a pull request's head commit from `remote` and return the local
+    /// ref it was stored under (`refs/remotes/<remote>/pr/<number>`).
+    ///
+    /// `ref_template` is the ref on the remote with `{number}` substituted,
+    /// e.g. "refs/pull/{number}/head" for GitHub.
+    pub fn fetch_pull_request(
+        &self,
+        remote: &str,
+        number: u64,
+        ref_template: &str,
+    ) -> Result<String> {
+        let remote_ref = ref_template.replace("{number}", &number.to_string());
+        let local_ref = format!("refs/remotes/{}/pr/{}", remote, number);
+
+        let output = std::process::Command::new("git")
+            .args(["fetch", remote, &format!("+{}:{}", remote_ref, local_ref)])
+            .current_dir(&self.repo_path)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(GitError::FetchFailed(stderr));
+        }
+
+        Ok(local_ref)
+    }
+
     /// Prune stale worktree entries whose directories no longer exist on disk.
     fn prune_worktrees(&self) -> Result<()> {
         let output = std::process::Command::new("git")
@@ -285,6 +340,33 @@ impl GitWorktree {
         Ok(())
     }
 
+    /// Local branches fully merged into `base` that are not checked out in any
+    /// worktree. These are safe to delete with `git branch -d`.
+    pub fn list_merged_branches(&self, base: &str) -> Result<Vec<String>> {
+        let output = std::process::Command::new("git")
+            .args(["branch", "--merged", base, "--format=%(refname:short)"])
+            .current_dir(&self.repo_path)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(GitError::WorktreeCommandFailed(stderr));
+        }
+
+        let checked_out: Vec<String> = self
+            .list_worktrees()?
+            .into_iter()
+            .filter_map(|wt| wt.branch)
+            .collect();
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|b| !b.is_empty() && *b != base && !checked_out.iter().any(|c| c == b))
+            .map(String::from)
+            .collect())
+    }
+
     /// Delete a local git branch.
     /// Returns an error if the branch doesn't exist or is currently checked out.
     pub fn delete_branch(&self, branch: &str) -> Result<()> {
@@ -428,6 +510,132 @@ mod tests {
             .is_ok());
     }
 
+    fn commit_on_head(repo: &git2::Repository, message: &str) -> git2::Oid {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = parent.tree().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_worktree_from_base_starts_at_base() {
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+
+        let initial = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("release", &initial, false).unwrap();
+        let head_oid = commit_on_head(&repo, "Second commit");
+        assert_ne!(head_oid, initial.id());
+
+        let wt_path = dir.path().join("from-release");
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        git_wt
+            .create_worktree_from_base("feat", &wt_path, "release")
+            .unwrap();
+
+        assert!(wt_path.exists());
+        let branch = repo.find_branch("feat", git2::BranchType::Local).unwrap();
+        assert_eq!(branch.get().peel_to_commit().unwrap().id(), initial.id());
+    }
+
+    #[test]
+    fn test_create_worktree_from_base_rejects_unknown_ref() {
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+
+        let wt_path = dir.path().join("missing-base");
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        let result = git_wt.create_worktree_from_base("feat", &wt_path, "no-such-ref");
+
+        assert!(matches!(result, Err(GitError::RefNotFound(_))));
+        assert!(!wt_path.exists());
+        assert!(repo.find_branch("feat", git2::BranchType::Local).is_err());
+    }
+
+    #[test]
+    fn test_create_worktree_from_base_failure_leaves_no_branch() {
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+
+        // A file where the worktree's parent directory should be
+        let blocker = dir.path().join("blocker");
+        std::fs::write(&blocker, "").unwrap();
+        let wt_path = blocker.join("feat");
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        let result = git_wt.create_worktree_from_base("feat", &wt_path, "HEAD");
+
+        assert!(matches!(result, Err(GitError::WorktreeCommandFailed(_))));
+        assert!(repo.find_branch("feat", git2::BranchType::Local).is_err());
+    }
+
+    #[test]
+    fn test_fetch_pull_request_into_worktree() {
+        let (_upstream_dir, upstream) = setup_test_repo();
+        let pr_oid = commit_on_head(&upstream, "PR commit");
+        upstream
+            .reference("refs/pull/7/head", pr_oid, false, "test PR ref")
+            .unwrap();
+
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        repo.remote("origin", upstream.path().to_str().unwrap())
+            .unwrap();
+
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        let local_ref = git_wt
+            .fetch_pull_request("origin", 7, "refs/pull/{number}/head")
+            .unwrap();
+        assert_eq!(local_ref, "refs/remotes/origin/pr/7");
+
+        let wt_path = dir.path().join("pr-7");
+        git_wt
+            .create_worktree_from_base("pr-7", &wt_path, &local_ref)
+            .unwrap();
+
+        let branch = repo.find_branch("pr-7", git2::BranchType::Local).unwrap();
+        assert_eq!(branch.get().peel_to_commit().unwrap().id(), pr_oid);
+    }
+
+    #[test]
+    fn test_list_merged_branches_skips_unmerged_and_checked_out() {
+        let (dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        let base = GitWorktree::get_current_branch(repo_path).unwrap();
+
+        let initial = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("merged", &initial, false).unwrap();
+        repo.branch("in-use", &initial, false).unwrap();
+
+        let unmerged_oid = commit_on_head(&repo, "Unmerged work");
+        repo.branch("unmerged", &repo.find_commit(unmerged_oid).unwrap(), false)
+            .unwrap();
+        repo.reset(initial.as_object(), git2::ResetType::Hard, None)
+            .unwrap();
+
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        git_wt
+            .create_worktree("in-use", &dir.path().join("in-use-wt"), false)
+            .unwrap();
+
+        let branches = git_wt.list_merged_branches(&base).unwrap();
+        assert_eq!(branches, vec!["merged".to_string()]);
+    }
+
+    #[test]
+    fn test_fetch_pull_request_missing_ref_fails() {
+        let (_upstream_dir, upstream) = setup_test_repo();
+        let (_dir, repo) = setup_test_repo();
+        let repo_path = repo.path().parent().unwrap();
+        repo.remote("origin", upstream.path().to_str().unwrap())
+            .unwrap();
+
+        let git_wt = GitWorktree::new(repo_path.to_path_buf()).unwrap();
+        let result = git_wt.fetch_pull_request("origin", 99, "refs/pull/{number}/head");
+
+        assert!(matches!(result, Err(GitError::FetchFailed(_))));
+    }
+
     #[test]
     fn test_list_worktrees_returns_main_and_additional() {
         let (dir, repo) = setup_test_repo();
@@ -0,0 +1,417 @@
+//! Keeping worktree branches up to date with their base branch, and landing
+//! them back onto it once the work is done.
+//!
+//! Fetches and rebases/merges are done through the git CLI so that hooks,
+//! autostash and the user's merge configuration behave exactly like they do
+//! on the command line.
+
+use std::path::Path;
+use std::process::Command;
+
+use super::error::{GitError, Result};
+
+/// How a worktree branch is brought up to date with its base
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncStrategy {
+    #[default]
+    Rebase,
+    Merge,
+}
+
+/// Result of syncing a single worktree branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// The branch already contains every commit of the base
+    UpToDate,
+    /// The branch was rebased onto (or merged with) `behind` new base commits
+    Updated { behind: usize },
+    /// The rebase or merge stopped on conflicts and was aborted, leaving the
+    /// worktree as it was before the sync
+    Conflict { files: Vec<String> },
+}
+
+/// Result of landing a worktree branch on its base
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LandOutcome {
+    /// The base already contains every commit of the branch
+    AlreadyLanded,
+    /// The base was fast-forwarded by `commits` commits of the branch
+    Landed { commits: usize },
+    /// The base has `behind` commits the branch doesn't have; the branch has
+    /// to be synced before it can be landed
+    Diverged { behind: usize },
+}
+
+/// Fetch `remote` for the repository at `repo_path`.
+pub fn fetch_remote(repo_path: &Path, remote: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["fetch", "--quiet", remote])
+        .current_dir(repo_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(GitError::FetchFailed(stderr));
+    }
+
+    Ok(())
+}
+
+/// Pick the ref worktrees should be synced onto. The remote-tracking branch
+/// is preferred so that a fresh fetch is picked up without having to
+/// fast-forward the local branch; the local branch is used when the remote
+/// has no such branch.
+pub fn resolve_sync_base(repo_path: &Path, remote: &str, branch: &str) -> String {
+    let remote_ref = format!("{}/{}", remote, branch);
+    let has_remote = git2::Repository::discover(repo_path)
+        .ok()
+        .is_some_and(|repo| {
+            repo.find_branch(&remote_ref, git2::BranchType::Remote)
+                .is_ok()
+        });
+
+    if has_remote {
+        remote_ref
+    } else {
+        branch.to_string()
+    }
+}
+
+/// Bring the branch checked out at `worktree_path` up to date with `base`.
+///
+/// Uncommitted changes are stashed for the duration of the sync and restored
+/// afterwards. On conflicts the operation is aborted and the conflicting
+/// paths are returned instead of an error.
+pub fn sync_worktree(
+    worktree_path: &Path,
+    base: &str,
+    strategy: SyncStrategy,
+) -> Result<SyncOutcome> {
+    let repo = git2::Repository::open(worktree_path)?;
+    let head = repo.head()?.peel_to_commit()?.id();
+    let base_commit = repo
+        .revparse_single(base)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|_| GitError::RefNotFound(base.to_string()))?
+        .id();
+
+    let (_, behind) = repo.graph_ahead_behind(head, base_commit)?;
+    if behind == 0 {
+        return Ok(SyncOutcome::UpToDate);
+    }
+
+    let args: &[&str] = match strategy {
+        SyncStrategy::Rebase => &["rebase", "--autostash", base],
+        SyncStrategy::Merge => &["merge", "--autostash", "--no-edit", base],
+    };
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(worktree_path)
+        .output()?;
+
+    if output.status.success() {
+        return Ok(SyncOutcome::Updated { behind });
+    }
+
+    let files = conflicted_files(&repo);
+    let abort = match strategy {
+        SyncStrategy::Rebase => ["rebase", "--abort"],
+        SyncStrategy::Merge => ["merge", "--abort"],
+    };
+    let _ = Command::new("git")
+        .args(abort)
+        .current_dir(worktree_path)
+        .output();
+
+    if files.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(GitError::SyncFailed(stderr));
+    }
+
+    Ok(SyncOutcome::Conflict { files })
+}
+
+/// Fast-forward the local `base` branch of the repository at `repo_path` to
+/// `branch`.
+///
+/// Only fast-forwards are done, so nothing is ever merged implicitly: a branch
+/// that is behind its base is reported as diverged. When `base` is checked out
+/// in the main repository its working tree is updated as well; git refuses the
+/// update if `base` is checked out in another worktree.
+pub fn land_branch(repo_path: &Path, branch: &str, base: &str) -> Result<LandOutcome> {
+    let repo = git2::Repository::open(repo_path)?;
+    let branch_commit = repo
+        .find_branch(branch, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(branch.to_string()))?
+        .get()
+        .peel_to_commit()?
+        .id();
+    let base_commit = repo
+        .find_branch(base, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(base.to_string()))?
+        .get()
+        .peel_to_commit()?
+        .id();
+
+    let (ahead, behind) = repo.graph_ahead_behind(branch_commit, base_commit)?;
+    if behind > 0 {
+        return Ok(LandOutcome::Diverged { behind });
+    }
+    if ahead == 0 {
+        return Ok(LandOutcome::AlreadyLanded);
+    }
+
+    let base_checked_out = repo
+        .head()
+        .ok()
+        .is_some_and(|head| head.is_branch() && head.shorthand() == Some(base));
+    let output = if base_checked_out {
+        Command::new("git")
+            .args(["merge", "--ff-only", "--quiet", branch])
+            .current_dir(repo_path)
+            .output()?
+    } else {
+        let refspec = format!("refs/heads/{}:refs/heads/{}", branch, base);
+        Command::new("git")
+            .args(["fetch", "--quiet", ".", &refspec])
+            .current_dir(repo_path)
+            .output()?
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(GitError::LandFailed(stderr));
+    }
+
+    Ok(LandOutcome::Landed { commits: ahead })
+}
+
+fn conflicted_files(repo: &git2::Repository) -> Vec<String> {
+    let Ok(index) = repo.index() else {
+        return Vec::new();
+    };
+    let Ok(conflicts) = index.conflicts() else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<String> = conflicts
+        .filter_map(|c| c.ok())
+        .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::GitWorktree;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_test_repo() -> (TempDir, git2::Repository) {
+        let dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        commit_file(dir.path(), "shared.txt", "base\n", "Initial commit");
+        (dir, repo)
+    }
+
+    fn commit_file(path: &Path, file: &str, content: &str, message: &str) {
+        let repo = git2::Repository::open(path).unwrap();
+        fs::write(path.join(file), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+    }
+
+    fn setup_worktree(dir: &TempDir, repo: &git2::Repository) -> std::path::PathBuf {
+        let repo_path = repo.path().parent().unwrap();
+        let wt_path = dir.path().join("feature-wt");
+        GitWorktree::new(repo_path.to_path_buf())
+            .unwrap()
+            .create_worktree("feature", &wt_path, true)
+            .unwrap();
+        wt_path
+    }
+
+    fn main_branch(repo: &git2::Repository) -> String {
+        repo.head().unwrap().shorthand().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_sync_up_to_date() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+
+        let outcome = sync_worktree(&wt_path, &main_branch(&repo), SyncStrategy::Rebase).unwrap();
+        assert_eq!(outcome, SyncOutcome::UpToDate);
+    }
+
+    #[test]
+    fn test_sync_rebases_onto_new_base_commits() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let base = main_branch(&repo);
+
+        commit_file(&wt_path, "feature.txt", "feature\n", "Feature work");
+        commit_file(dir.path(), "main.txt", "main\n", "Main moves on");
+
+        let outcome = sync_worktree(&wt_path, &base, SyncStrategy::Rebase).unwrap();
+        assert_eq!(outcome, SyncOutcome::Updated { behind: 1 });
+
+        assert!(wt_path.join("main.txt").exists());
+        assert!(wt_path.join("feature.txt").exists());
+        let wt_repo = git2::Repository::open(&wt_path).unwrap();
+        let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 1, "rebase should keep history linear");
+
+        let outcome = sync_worktree(&wt_path, &base, SyncStrategy::Rebase).unwrap();
+        assert_eq!(outcome, SyncOutcome::UpToDate);
+    }
+
+    #[test]
+    fn test_sync_merge_creates_merge_commit() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let base = main_branch(&repo);
+
+        commit_file(&wt_path, "feature.txt", "feature\n", "Feature work");
+        commit_file(dir.path(), "main.txt", "main\n", "Main moves on");
+
+        let outcome = sync_worktree(&wt_path, &base, SyncStrategy::Merge).unwrap();
+        assert_eq!(outcome, SyncOutcome::Updated { behind: 1 });
+
+        let wt_repo = git2::Repository::open(&wt_path).unwrap();
+        let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+    }
+
+    #[test]
+    fn test_sync_preserves_uncommitted_changes() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let base = main_branch(&repo);
+
+        commit_file(dir.path(), "main.txt", "main\n", "Main moves on");
+        fs::write(wt_path.join("shared.txt"), "base\nwork in progress\n").unwrap();
+
+        let outcome = sync_worktree(&wt_path, &base, SyncStrategy::Rebase).unwrap();
+        assert_eq!(outcome, SyncOutcome::Updated { behind: 1 });
+        assert_eq!(
+            fs::read_to_string(wt_path.join("shared.txt")).unwrap(),
+            "base\nwork in progress\n"
+        );
+    }
+
+    #[test]
+    fn test_sync_conflict_is_aborted() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let base = main_branch(&repo);
+
+        commit_file(&wt_path, "shared.txt", "feature\n", "Feature edit");
+        commit_file(dir.path(), "shared.txt", "main\n", "Main edit");
+
+        let outcome = sync_worktree(&wt_path, &base, SyncStrategy::Rebase).unwrap();
+        assert_eq!(
+            outcome,
+            SyncOutcome::Conflict {
+                files: vec!["shared.txt".to_string()]
+            }
+        );
+
+        // The rebase was aborted and the branch is untouched
+        assert_eq!(
+            fs::read_to_string(wt_path.join("shared.txt")).unwrap(),
+            "feature\n"
+        );
+        let wt_repo = git2::Repository::open(&wt_path).unwrap();
+        assert_eq!(wt_repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_sync_unknown_base() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+
+        let result = sync_worktree(&wt_path, "no-such-branch", SyncStrategy::Rebase);
+        assert!(matches!(result, Err(GitError::RefNotFound(_))));
+    }
+
+    #[test]
+    fn test_land_fast_forwards_checked_out_base() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let base = main_branch(&repo);
+
+        commit_file(&wt_path, "feature.txt", "feature\n", "Feature work");
+        commit_file(&wt_path, "more.txt", "more\n", "More work");
+
+        let outcome = land_branch(dir.path(), "feature", &base).unwrap();
+        assert_eq!(outcome, LandOutcome::Landed { commits: 2 });
+        assert!(dir.path().join("more.txt").exists());
+
+        let outcome = land_branch(dir.path(), "feature", &base).unwrap();
+        assert_eq!(outcome, LandOutcome::AlreadyLanded);
+    }
+
+    #[test]
+    fn test_land_updates_base_that_is_not_checked_out() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("release", &head, false).unwrap();
+
+        commit_file(&wt_path, "feature.txt", "feature\n", "Feature work");
+
+        let outcome = land_branch(dir.path(), "feature", "release").unwrap();
+        assert_eq!(outcome, LandOutcome::Landed { commits: 1 });
+        let release = repo
+            .find_branch("release", git2::BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        assert_eq!(release.summary(), Some("Feature work"));
+        assert!(!dir.path().join("feature.txt").exists());
+    }
+
+    #[test]
+    fn test_land_refuses_diverged_branch() {
+        let (dir, repo) = setup_test_repo();
+        let wt_path = setup_worktree(&dir, &repo);
+        let base = main_branch(&repo);
+
+        commit_file(&wt_path, "feature.txt", "feature\n", "Feature work");
+        commit_file(dir.path(), "main.txt", "main\n", "Main moves on");
+
+        let outcome = land_branch(dir.path(), "feature", &base).unwrap();
+        assert_eq!(outcome, LandOutcome::Diverged { behind: 1 });
+        assert!(!dir.path().join("feature.txt").exists());
+    }
+
+    #[test]
+    fn test_resolve_sync_base_falls_back_to_local_branch() {
+        let (dir, repo) = setup_test_repo();
+        let base = main_branch(&repo);
+        assert_eq!(resolve_sync_base(dir.path(), "origin", &base), base);
+    }
+}
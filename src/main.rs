@@ -20,12 +20,14 @@ async fn main() -> Result<()> {
     let profile = cli.profile.unwrap_or_default();
 
     match cli.command {
-        Some(Commands::Add(args)) => cli::add::run(&profile, args).await,
+        Some(Commands::Add(args)) => cli::add::run(&profile, *args).await,
         Some(Commands::Init(args)) => cli::init::run(args).await,
         Some(Commands::List(args)) => cli::list::run(&profile, args).await,
         Some(Commands::Remove(args)) => cli::remove::run(&profile, args).await,
         Some(Commands::Status(args)) => cli::status::run(&profile, args).await,
+        Some(Commands::Diff(args)) => cli::diff::run(&profile, args).await,
         Some(Commands::Session { command }) => cli::session::run(&profile, command).await,
+        Some(Commands::Sandbox { command }) => cli::sandbox::run(&profile, command).await,
         Some(Commands::Group { command }) => cli::group::run(&profile, command).await,
         Some(Commands::Profile { command }) => cli::profile::run(command).await,
         Some(Commands::Worktree { command }) => cli::worktree::run(&profile, command).await,
@@ -35,6 +37,7 @@ async fn main() -> Result<()> {
                 TmuxCommands::Status(args) => cli::tmux::run_status(args),
             }
         }
+        Some(Commands::Doctor(args)) => cli::doctor::run(args).await,
         Some(Commands::Uninstall(args)) => cli::uninstall::run(args).await,
         None => tui::run(&profile).await,
     }
@@ -4,25 +4,30 @@
 //! used by both synchronous (TUI operations) and asynchronous (background poller) code paths.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
 use chrono::Utc;
 
-use crate::docker::DockerContainer;
+use crate::docker::{ContainerRuntime, DockerContainer, ImageBuild};
 use crate::git::GitWorktree;
 
-use super::{civilizations, Config, Instance, SandboxInfo, WorktreeInfo};
+use super::devcontainer::DevContainer;
+use super::{civilizations, repo_config, Config, Instance, SandboxInfo, WorktreeInfo};
 
 /// Parameters for creating a new session instance.
 #[derive(Debug, Clone)]
 pub struct InstanceParams {
+    /// Profile the session is created in; its config applies to the session
+    pub profile: String,
     pub title: String,
     pub path: String,
     pub group: String,
     pub tool: String,
     pub worktree_branch: Option<String>,
     pub create_new_branch: bool,
+    /// Ref a newly created worktree branch starts from. Defaults to HEAD.
+    pub base_ref: Option<String>,
     pub sandbox: bool,
     /// The sandbox image to use. Required when sandbox is true.
     pub sandbox_image: String,
@@ -31,6 +36,12 @@ pub struct InstanceParams {
     pub extra_env_keys: Vec<String>,
     /// Additional KEY=VALUE environment variables to inject into the container.
     pub extra_env_values: Vec<String>,
+    /// CPU limit for this session's container (config default when None).
+    pub cpu_limit: Option<String>,
+    /// Memory limit for this session's container (config default when None).
+    pub memory_limit: Option<String>,
+    /// Mount the project read-only and let the agent work on a copy.
+    pub isolated: bool,
 }
 
 /// Result of building an instance, tracking what was created for cleanup purposes.
@@ -52,12 +63,21 @@ pub struct CreatedWorktree {
 /// separately via `instance.start()`. This separation allows for proper cleanup
 /// if starting fails.
 pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Result<BuildResult> {
+    let runtime = ContainerRuntime::for_profile(&params.profile);
     if params.sandbox {
-        if !crate::docker::is_docker_available() {
-            bail!("Docker is not installed. Please install Docker to use sandbox mode.");
+        if !runtime.is_available() {
+            bail!(
+                "{} is not installed. Please install {} to use sandbox mode.",
+                runtime.display_name(),
+                runtime.display_name()
+            );
         }
-        if !crate::docker::is_daemon_running() {
-            bail!("Docker daemon is not running. Please start Docker to use sandbox mode.");
+        if !runtime.is_daemon_running() {
+            bail!(
+                "{} is not running. Please start {} to use sandbox mode.",
+                runtime.display_name(),
+                runtime.display_name()
+            );
         }
     }
 
@@ -102,6 +122,7 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                     managed_by_aoe: false,
                     created_at: Utc::now(),
                     cleanup_on_delete: false,
+                    sync_conflicts: Vec::new(),
                 });
             } else {
                 let session_id = uuid::Uuid::new_v4().to_string();
@@ -120,6 +141,7 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                     managed_by_aoe: true,
                     created_at: Utc::now(),
                     cleanup_on_delete: true,
+                    sync_conflicts: Vec::new(),
                 });
             }
         } else {
@@ -130,7 +152,10 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                 bail!("Worktree already exists at {}", worktree_path.display());
             }
 
-            git_wt.create_worktree(branch, &worktree_path, true)?;
+            match &params.base_ref {
+                Some(base) => git_wt.create_worktree_from_base(branch, &worktree_path, base)?,
+                None => git_wt.create_worktree(branch, &worktree_path, true)?,
+            }
 
             final_path = worktree_path.to_string_lossy().to_string();
             created_worktree = Some(CreatedWorktree {
@@ -143,6 +168,7 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                 managed_by_aoe: true,
                 created_at: Utc::now(),
                 cleanup_on_delete: true,
+                sync_conflicts: Vec::new(),
             });
         }
     }
@@ -154,6 +180,7 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
     };
 
     let mut instance = Instance::new(&final_title, &final_path);
+    instance.profile = params.profile.clone();
     instance.group_path = params.group;
     instance.tool = params.tool.clone();
     instance.command = match params.tool.as_str() {
@@ -164,10 +191,23 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
     instance.worktree_info = worktree_info;
 
     if params.sandbox {
+        // An image picked for the session wins over a Dockerfile, as
+        // with `aoe add --sandbox-image`
+        let build = if params.sandbox_image == crate::docker::effective_default_image() {
+            match resolve_image_build(&params.profile, Path::new(&final_path)) {
+                Ok(build) => build,
+                Err(e) => {
+                    cleanup_instance(&instance, created_worktree.as_ref());
+                    return Err(e);
+                }
+            }
+        } else {
+            None
+        };
         instance.sandbox_info = Some(SandboxInfo {
             enabled: true,
             container_id: None,
-            image: params.sandbox_image.clone(),
+            image: resolve_sandbox_image(&params.sandbox_image, Path::new(&final_path)),
             container_name: DockerContainer::generate_name(&instance.id),
             created_at: None,
             yolo_mode: if params.yolo_mode { Some(true) } else { None },
@@ -192,6 +232,14 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                     Some(map)
                 }
             },
+            network: None,
+            runtime,
+            build,
+            forwarded_ports: Vec::new(),
+            snapshot: None,
+            cpu_limit: params.cpu_limit.clone(),
+            memory_limit: params.memory_limit.clone(),
+            isolated: params.isolated,
         });
     }
 
@@ -201,6 +249,34 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
     })
 }
 
+/// Dockerfile build for a project: the profile's config or the repo's
+/// `.aoe/config.toml` first, then the repo's devcontainer.json. Fails if the
+/// build would read files outside the project.
+pub fn resolve_image_build(profile: &str, project_path: &Path) -> Result<Option<ImageBuild>> {
+    let configured = match repo_config::resolve_config_with_repo(profile, project_path) {
+        Ok(config) => config.sandbox.image_build(project_path)?,
+        Err(_) => None,
+    };
+    match configured {
+        Some(build) => Ok(Some(build)),
+        None => match DevContainer::load_or_warn(project_path) {
+            Some(devcontainer) => devcontainer.image_build(),
+            None => Ok(None),
+        },
+    }
+}
+
+/// Image for a new sandbox. A devcontainer.json `image` replaces the
+/// configured default, but not an image picked explicitly for the session.
+fn resolve_sandbox_image(requested: &str, project_path: &Path) -> String {
+    if requested == crate::docker::effective_default_image() {
+        if let Some(image) = DevContainer::load_or_warn(project_path).and_then(|d| d.image) {
+            return image;
+        }
+    }
+    requested.to_string()
+}
+
 /// Clean up resources created during a failed or cancelled instance build.
 ///
 /// This handles:
@@ -218,7 +294,7 @@ pub fn cleanup_instance(instance: &Instance, created_worktree: Option<&CreatedWo
 
     if let Some(sandbox) = &instance.sandbox_info {
         if sandbox.enabled {
-            let container = DockerContainer::from_session_id(&instance.id);
+            let container = DockerContainer::from_session_id(&instance.id, sandbox.runtime);
             if container.exists().unwrap_or(false) {
                 if let Err(e) = container.remove(true) {
                     tracing::warn!("Failed to clean up container: {}", e);
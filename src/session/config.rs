@@ -2,11 +2,12 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use super::get_app_dir;
+use super::{get_app_dir, Status};
+use crate::docker::{ContainerRuntime, ImageBuild};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
@@ -37,6 +38,9 @@ pub struct Config {
     #[serde(default)]
     pub diff: DiffConfig,
 
+    #[serde(default)]
+    pub keybindings: KeybindingsConfig,
+
     #[serde(default)]
     pub app_state: AppStateConfig,
 }
@@ -54,6 +58,91 @@ pub struct AppStateConfig {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub diff_file_list_width: Option<u16>,
+
+    /// Order of the home list; None keeps group tree order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_sort: Option<SessionSort>,
+
+    /// Show the home list without groups, so the sort applies across them
+    #[serde(default)]
+    pub home_flat: bool,
+
+    #[serde(default, skip_serializing_if = "SessionFilters::is_empty")]
+    pub home_filters: SessionFilters,
+}
+
+/// Order of the sessions in the home list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSort {
+    /// Waiting first, then errors, running and idle sessions
+    Status,
+    /// Most recently attached first
+    LastAccessed,
+    /// Newest first
+    Created,
+    Title,
+    /// Grouped by agent, by title within each agent
+    Tool,
+    /// Sessions with git changes first, most changed lines first
+    Changes,
+}
+
+/// Filter chips of the home list. Empty lists and unset flags let every
+/// session through.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionFilters {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<Status>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandboxed: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<bool>,
+}
+
+impl SessionFilters {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// TUI key bindings per view, mapping command names to keys. See
+/// `tui::keybindings` for the command names and key syntax.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeybindingsConfig {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub home: BTreeMap<String, KeyList>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub diff: BTreeMap<String, KeyList>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub grid: BTreeMap<String, KeyList>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub settings: BTreeMap<String, KeyList>,
+}
+
+/// One key or a list of keys bound to a command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
 }
 
 /// Session-related configuration defaults
@@ -91,12 +180,24 @@ fn default_context_lines() -> usize {
     3
 }
 
+impl DiffConfig {
+    /// Resolve the branch to diff against: the configured default, then the
+    /// repository's main/master branch, then "main".
+    pub fn base_branch_for(&self, repo_path: &std::path::Path) -> String {
+        self.default_branch
+            .clone()
+            .or_else(|| crate::git::diff::get_default_branch(repo_path).ok())
+            .unwrap_or_else(|| "main".to_string())
+    }
+}
+
 fn default_profile() -> String {
     "default".to_string()
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ThemeConfig {
+    /// Built-in theme or `themes/<name>.toml` in the app dir; empty is the default
     #[serde(default)]
     pub name: String,
 }
@@ -164,6 +265,12 @@ pub struct WorktreeConfig {
     /// Default: false (unchecked in delete dialog)
     #[serde(default)]
     pub delete_branch_on_cleanup: bool,
+
+    /// Remote ref fetched for `aoe add --pr <number>` ({number} is substituted).
+    /// Defaults to GitHub's "refs/pull/{number}/head"; GitLab uses
+    /// "refs/merge-requests/{number}/head".
+    #[serde(default = "default_pull_request_ref")]
+    pub pull_request_ref: String,
 }
 
 impl Default for WorktreeConfig {
@@ -175,10 +282,15 @@ impl Default for WorktreeConfig {
             auto_cleanup: true,
             show_branch_in_tui: true,
             delete_branch_on_cleanup: false,
+            pull_request_ref: default_pull_request_ref(),
         }
     }
 }
 
+fn default_pull_request_ref() -> String {
+    "refs/pull/{number}/head".to_string()
+}
+
 fn default_worktree_template() -> String {
     "../{repo-name}-worktrees/{branch}".to_string()
 }
@@ -199,6 +311,20 @@ pub struct SandboxConfig {
     #[serde(default = "default_sandbox_image")]
     pub default_image: String,
 
+    /// Dockerfile to build the sandbox image from, relative to the project
+    /// root. When set, it takes precedence over `default_image`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+
+    /// Build context for `dockerfile`, relative to the project root.
+    /// Defaults to the directory containing the Dockerfile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_context: Option<String>,
+
+    /// Container runtime used for new sandboxes (docker or podman)
+    #[serde(default)]
+    pub runtime: ContainerRuntime,
+
     #[serde(default)]
     pub extra_volumes: Vec<String>,
 
@@ -208,9 +334,15 @@ pub struct SandboxConfig {
     /// Environment variables with explicit values to inject into sandbox containers.
     /// Unlike `environment` (which passes through host values), these are stored in config
     /// and injected directly. Useful for sandbox-specific credentials like GH_TOKEN.
+    /// Values may reference secrets instead (`file:`, `cmd:`, `keyring:`); see `secrets`.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub environment_values: HashMap<String, String>,
 
+    /// Command looking up `keyring:NAME` values, with `{name}` replaced by the
+    /// quoted name. Defaults to `secret-tool` on Linux and `security` on macOS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyring_command: Option<String>,
+
     #[serde(default = "default_true")]
     pub auto_cleanup: bool,
 
@@ -227,6 +359,15 @@ pub struct SandboxConfig {
     /// Relative directory paths to exclude from the host bind mount via anonymous volumes
     #[serde(default)]
     pub volume_ignores: Vec<String>,
+
+    /// Outbound network access for sandbox containers
+    #[serde(default)]
+    pub network: SandboxNetworkConfig,
+
+    /// Ports to publish on 127.0.0.1: "3000" (host port picked by the
+    /// runtime) or "8080:3000" (host:container)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<String>,
 }
 
 impl Default for SandboxConfig {
@@ -235,18 +376,49 @@ impl Default for SandboxConfig {
             enabled_by_default: false,
             yolo_mode_default: false,
             default_image: default_sandbox_image(),
+            dockerfile: None,
+            build_context: None,
+            runtime: ContainerRuntime::default(),
             extra_volumes: Vec::new(),
             environment: default_sandbox_environment(),
             environment_values: HashMap::new(),
+            keyring_command: None,
             auto_cleanup: true,
             cpu_limit: None,
             memory_limit: None,
             default_terminal_mode: DefaultTerminalMode::default(),
             volume_ignores: Vec::new(),
+            network: SandboxNetworkConfig::default(),
+            ports: Vec::new(),
         }
     }
 }
 
+impl SandboxConfig {
+    /// The image build configured for a project, with paths resolved against
+    /// `project_path`. Fails if either path leads outside the project, since
+    /// both can be set by the repo's `.aoe/config.toml`.
+    pub fn image_build(&self, project_path: &Path) -> Result<Option<ImageBuild>> {
+        let Some(dockerfile) = &self.dockerfile else {
+            return Ok(None);
+        };
+        let dockerfile = project_path.join(dockerfile);
+        let context = match &self.build_context {
+            Some(context) => project_path.join(context),
+            None => dockerfile
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| project_path.to_path_buf()),
+        };
+        let build = ImageBuild {
+            dockerfile,
+            context,
+            args: Default::default(),
+        };
+        Ok(Some(build.confined_to(project_path)?))
+    }
+}
+
 fn default_sandbox_image() -> String {
     crate::docker::default_sandbox_image().to_string()
 }
@@ -271,6 +443,71 @@ pub enum DefaultTerminalMode {
     Container,
 }
 
+/// Network access policy for sandbox containers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkMode {
+    /// Unrestricted access through Docker's default bridge network
+    #[default]
+    Full,
+    /// No network access at all
+    None,
+    /// Only hosts in the allowlist, reached through an aoe-managed filtering proxy
+    Allowlist,
+}
+
+impl NetworkMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkMode::Full => "full",
+            NetworkMode::None => "none",
+            NetworkMode::Allowlist => "allowlist",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxNetworkConfig {
+    #[serde(default)]
+    pub mode: NetworkMode,
+
+    /// Hosts (`api.anthropic.com`, `*.npmjs.org`) and CIDRs (`10.0.0.0/8`)
+    /// sandboxes may reach in allowlist mode
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+
+    /// Image the filtering proxy runs in (needs `apk` to install tinyproxy)
+    #[serde(default = "default_proxy_image")]
+    pub proxy_image: String,
+}
+
+impl Default for SandboxNetworkConfig {
+    fn default() -> Self {
+        Self {
+            mode: NetworkMode::default(),
+            allowlist: Vec::new(),
+            proxy_image: default_proxy_image(),
+        }
+    }
+}
+
+impl SandboxNetworkConfig {
+    /// Short description of the policy, as shown in the preview pane
+    pub fn describe(&self) -> String {
+        match self.mode {
+            NetworkMode::Allowlist if self.allowlist.is_empty() => {
+                "allowlist (nothing allowed)".to_string()
+            }
+            NetworkMode::Allowlist => format!("allowlist ({})", self.allowlist.join(", ")),
+            mode => mode.as_str().to_string(),
+        }
+    }
+}
+
+fn default_proxy_image() -> String {
+    crate::docker::network::DEFAULT_PROXY_IMAGE.to_string()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum TmuxStatusBarMode {
@@ -600,6 +837,32 @@ mod tests {
         assert!(app.last_seen_version.is_none());
     }
 
+    #[test]
+    fn test_app_state_list_controls_roundtrip() {
+        let app = AppStateConfig {
+            home_sort: Some(SessionSort::LastAccessed),
+            home_flat: true,
+            home_filters: SessionFilters {
+                statuses: vec![Status::Waiting, Status::Error],
+                sandboxed: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let toml = toml::to_string(&app).unwrap();
+        assert!(toml.contains("home_sort = \"last_accessed\""));
+        assert!(toml.contains("statuses = [\"waiting\", \"error\"]"));
+
+        let parsed: AppStateConfig = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.home_sort, Some(SessionSort::LastAccessed));
+        assert!(parsed.home_flat);
+        assert_eq!(parsed.home_filters, app.home_filters);
+
+        // Without filters nothing is written for them
+        let toml = toml::to_string(&AppStateConfig::default()).unwrap();
+        assert!(!toml.contains("home_filters"));
+    }
+
     #[test]
     fn test_app_state_config_deserialize() {
         let toml = r#"
@@ -835,4 +1098,81 @@ mod tests {
         assert_eq!(config.diff.default_branch, Some("main".to_string()));
         assert_eq!(config.diff.context_lines, 10);
     }
+
+    // Tests for SandboxNetworkConfig
+    #[test]
+    fn test_sandbox_network_config_default() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.sandbox.network.mode, NetworkMode::Full);
+        assert!(config.sandbox.network.allowlist.is_empty());
+        assert_eq!(config.sandbox.network.describe(), "full");
+    }
+
+    #[test]
+    fn test_sandbox_network_config_deserialize() {
+        let toml = r#"
+            [sandbox.network]
+            mode = "allowlist"
+            allowlist = ["api.anthropic.com", "*.npmjs.org"]
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.sandbox.network.mode, NetworkMode::Allowlist);
+        assert_eq!(
+            config.sandbox.network.describe(),
+            "allowlist (api.anthropic.com, *.npmjs.org)"
+        );
+    }
+
+    #[test]
+    fn test_sandbox_network_config_none_mode() {
+        let toml = r#"
+            [sandbox.network]
+            mode = "none"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.sandbox.network.mode, NetworkMode::None);
+        assert_eq!(config.sandbox.network.describe(), "none");
+    }
+
+    #[test]
+    fn test_sandbox_image_build_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(project.join(".aoe")).unwrap();
+        std::fs::write(project.join(".aoe/Dockerfile"), "FROM alpine\n").unwrap();
+        let mut sandbox = SandboxConfig::default();
+        assert!(sandbox.image_build(&project).unwrap().is_none());
+
+        sandbox.dockerfile = Some(".aoe/Dockerfile".to_string());
+        let build = sandbox.image_build(&project).unwrap().unwrap();
+        assert_eq!(build.dockerfile, project.join(".aoe/Dockerfile"));
+        assert_eq!(build.context, project.join(".aoe"));
+
+        sandbox.build_context = Some(".".to_string());
+        let build = sandbox.image_build(&project).unwrap().unwrap();
+        assert_eq!(build.context, project);
+    }
+
+    #[test]
+    fn test_sandbox_image_build_stays_in_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("repo");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(project.join("Dockerfile"), "FROM alpine\n").unwrap();
+        std::fs::write(dir.path().join("Dockerfile.outside"), "FROM alpine\n").unwrap();
+
+        let mut sandbox = SandboxConfig {
+            dockerfile: Some("Dockerfile".to_string()),
+            ..Default::default()
+        };
+        for context in ["/", "..", "../.."] {
+            sandbox.build_context = Some(context.to_string());
+            let err = sandbox.image_build(&project).unwrap_err();
+            assert!(err.to_string().contains("outside the project"), "{}", err);
+        }
+
+        sandbox.build_context = None;
+        sandbox.dockerfile = Some("../Dockerfile.outside".to_string());
+        assert!(sandbox.image_build(&project).is_err());
+    }
 }
@@ -0,0 +1,668 @@
+//! Dev Container support (`.devcontainer/devcontainer.json`)
+//!
+//! Sandboxed sessions pick up the parts of a repo's dev container definition
+//! that have an aoe equivalent, so agents run in the same environment as the
+//! people working on the repo:
+//!
+//! - `image` / `build` choose the sandbox image (`build` uses [`ImageBuild`])
+//! - `containerEnv`, `mounts` and `forwardPorts` are added to the container;
+//!   `containerEnv` and `mounts` read host variables and files, so they
+//!   apply only once the repo is trusted, like hooks
+//! - `postCreateCommand` runs like an `on_create` hook, inside the container
+//!
+//! Everything else in the file (features, customizations, lifecycle commands
+//! other than `postCreateCommand`) is ignored.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::docker::{ImageBuild, PortMapping};
+
+/// Locations checked for a dev container definition, in order.
+const DEVCONTAINER_PATHS: &[&str] = &[".devcontainer/devcontainer.json", ".devcontainer.json"];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevContainer {
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub build: Option<DevContainerBuild>,
+    /// Pre-`build` spelling of `build.dockerfile`
+    #[serde(default)]
+    docker_file: Option<String>,
+    /// Pre-`build` spelling of `build.context`
+    #[serde(default)]
+    context: Option<String>,
+    #[serde(default)]
+    pub container_env: HashMap<String, String>,
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+    #[serde(default)]
+    pub forward_ports: Vec<PortSpec>,
+    #[serde(default)]
+    pub post_create_command: Option<LifecycleCommand>,
+
+    /// Directory containing the devcontainer.json; build paths are relative to it
+    #[serde(skip)]
+    dir: PathBuf,
+    /// Project the devcontainer.json belongs to; builds can't reach outside it
+    #[serde(skip)]
+    project: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DevContainerBuild {
+    #[serde(default)]
+    pub dockerfile: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+}
+
+/// A `mounts` entry, either in `--mount` string form or as an object.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MountSpec {
+    String(String),
+    Object {
+        #[serde(default)]
+        source: Option<String>,
+        target: String,
+        #[serde(rename = "type", default)]
+        kind: Option<String>,
+    },
+}
+
+/// A `forwardPorts` entry: a port number, or a `"host:port"` string.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PortSpec {
+    Number(u16),
+    String(String),
+}
+
+/// A lifecycle command: a shell string, an argv array, or named commands.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LifecycleCommand {
+    Shell(String),
+    Exec(Vec<String>),
+    Named(BTreeMap<String, LifecycleCommand>),
+}
+
+/// A mount translated for the sandbox container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevContainerMount {
+    Bind(String, String, bool),
+    Volume(String, String),
+}
+
+/// Values for the `${...}` variables devcontainer.json may reference.
+pub struct Substitutions<'a> {
+    pub local_workspace_folder: &'a Path,
+    pub container_workspace_folder: &'a str,
+}
+
+impl DevContainer {
+    /// Load the dev container definition for a project, if it has one.
+    pub fn load(project_path: &Path) -> Result<Option<Self>> {
+        for relative in DEVCONTAINER_PATHS {
+            let path = project_path.join(relative);
+            if !path.is_file() {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let mut devcontainer: DevContainer = serde_json::from_str(&strip_jsonc(&content))
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            devcontainer.dir = path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| project_path.to_path_buf());
+            devcontainer.project = project_path.to_path_buf();
+            return Ok(Some(devcontainer));
+        }
+        Ok(None)
+    }
+
+    /// Like `load`, but logs and ignores a malformed file.
+    pub fn load_or_warn(project_path: &Path) -> Option<Self> {
+        Self::load(project_path).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring devcontainer.json: {:#}", e);
+            None
+        })
+    }
+
+    /// The image build described by `build` (or the legacy `dockerFile`).
+    /// Fails if the Dockerfile or context lies outside the project.
+    pub fn image_build(&self) -> Result<Option<ImageBuild>> {
+        let (dockerfile, context, args) = match &self.build {
+            Some(build) if build.dockerfile.is_some() => (
+                build.dockerfile.as_ref(),
+                build.context.as_ref(),
+                build.args.clone(),
+            ),
+            _ => (
+                self.docker_file.as_ref(),
+                self.context.as_ref(),
+                BTreeMap::new(),
+            ),
+        };
+        let Some(dockerfile) = dockerfile else {
+            return Ok(None);
+        };
+        let build = ImageBuild {
+            dockerfile: self.dir.join(dockerfile),
+            context: self.dir.join(context.map(String::as_str).unwrap_or(".")),
+            args,
+        };
+        Ok(Some(build.confined_to(&self.project)?))
+    }
+
+    /// `containerEnv` with variables substituted. Entries using variables
+    /// that can't be expanded on the host (e.g. `${containerEnv:PATH}`) are
+    /// skipped.
+    pub fn environment(&self, subs: &Substitutions) -> Vec<(String, String)> {
+        let mut env: Vec<(String, String)> = self
+            .container_env
+            .iter()
+            .filter_map(|(key, value)| match subs.apply(value) {
+                Ok(value) => Some((key.clone(), value)),
+                Err(var) => {
+                    tracing::warn!(
+                        "Skipping devcontainer containerEnv {}: can't expand ${{{}}}",
+                        key,
+                        var
+                    );
+                    None
+                }
+            })
+            .collect();
+        env.sort();
+        env
+    }
+
+    /// `containerEnv` and `mounts` as written, for the trust prompt and the
+    /// trusted hooks hash
+    pub fn host_settings(&self) -> Vec<String> {
+        let mut env: Vec<String> = self
+            .container_env
+            .iter()
+            .map(|(key, value)| format!("containerEnv {}={}", key, value))
+            .collect();
+        env.sort();
+        env.extend(self.mounts.iter().map(|spec| format!("mount {}", spec)));
+        env
+    }
+
+    /// Bind and volume mounts. Other mount types (e.g. tmpfs) are skipped.
+    pub fn mounts(&self, subs: &Substitutions) -> Vec<DevContainerMount> {
+        self.mounts
+            .iter()
+            .filter_map(|spec| {
+                let mount = spec.translate(subs);
+                if mount.is_none() {
+                    tracing::debug!("Skipping unsupported devcontainer mount {:?}", spec);
+                }
+                mount
+            })
+            .collect()
+    }
+
+    /// Container ports from `forwardPorts`. Entries naming another host
+    /// (`"db:5432"`, used with Docker Compose) don't apply and are skipped.
+    pub fn ports(&self) -> Vec<PortMapping> {
+        self.forward_ports
+            .iter()
+            .filter_map(|spec| match spec {
+                PortSpec::Number(port) => Some(*port),
+                PortSpec::String(s) => match s.rsplit_once(':') {
+                    Some((host, port)) if host == "localhost" || host == "127.0.0.1" => {
+                        port.parse().ok()
+                    }
+                    Some(_) => None,
+                    None => s.parse().ok(),
+                },
+            })
+            .map(PortMapping::container)
+            .collect()
+    }
+
+    /// `postCreateCommand` as shell commands. Named commands run in name order.
+    pub fn post_create_commands(&self) -> Vec<String> {
+        let mut commands = Vec::new();
+        if let Some(command) = &self.post_create_command {
+            command.flatten_into(&mut commands);
+        }
+        commands
+    }
+}
+
+impl MountSpec {
+    fn translate(&self, subs: &Substitutions) -> Option<DevContainerMount> {
+        let (kind, source, target, read_only) = match self {
+            MountSpec::Object {
+                source,
+                target,
+                kind,
+            } => (
+                kind.clone().unwrap_or_else(|| "bind".to_string()),
+                source.clone()?,
+                target.clone(),
+                false,
+            ),
+            MountSpec::String(s) => {
+                let mut kind = "volume".to_string();
+                let (mut source, mut target, mut read_only) = (None, None, false);
+                for part in s.split(',') {
+                    let (key, value) = part.split_once('=').unwrap_or((part, ""));
+                    match key.trim() {
+                        "type" => kind = value.to_string(),
+                        "source" | "src" => source = Some(value.to_string()),
+                        "target" | "destination" | "dst" => target = Some(value.to_string()),
+                        "readonly" | "ro" => read_only = value.is_empty() || value == "true",
+                        _ => {}
+                    }
+                }
+                (kind, source?, target?, read_only)
+            }
+        };
+
+        let source = subs.apply(&source).ok()?;
+        let target = subs.apply(&target).ok()?;
+        match kind.as_str() {
+            "bind" => Some(DevContainerMount::Bind(source, target, read_only)),
+            "volume" => Some(DevContainerMount::Volume(source, target)),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MountSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MountSpec::String(s) => write!(f, "{}", s),
+            MountSpec::Object {
+                source,
+                target,
+                kind,
+            } => write!(
+                f,
+                "type={},source={},target={}",
+                kind.as_deref().unwrap_or("bind"),
+                source.as_deref().unwrap_or(""),
+                target
+            ),
+        }
+    }
+}
+
+impl LifecycleCommand {
+    fn flatten_into(&self, commands: &mut Vec<String>) {
+        match self {
+            LifecycleCommand::Shell(cmd) if !cmd.trim().is_empty() => commands.push(cmd.clone()),
+            LifecycleCommand::Shell(_) => {}
+            LifecycleCommand::Exec(argv) if !argv.is_empty() => commands.push(
+                argv.iter()
+                    .map(|arg| shell_quote(arg))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            LifecycleCommand::Exec(_) => {}
+            LifecycleCommand::Named(named) => {
+                for command in named.values() {
+                    command.flatten_into(commands);
+                }
+            }
+        }
+    }
+}
+
+impl Substitutions<'_> {
+    /// Expand `${localWorkspaceFolder}`, `${localWorkspaceFolderBasename}`,
+    /// `${containerWorkspaceFolder}`, `${containerWorkspaceFolderBasename}`
+    /// and `${localEnv:NAME[:default]}`. Any other variable is an error,
+    /// returning its name.
+    pub fn apply(&self, value: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            let Some(len) = rest[start..].find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let var = &rest[start + 2..start + len];
+            out.push_str(&self.lookup(var).ok_or_else(|| var.to_string())?);
+            rest = &rest[start + len + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    fn lookup(&self, var: &str) -> Option<String> {
+        let basename = |path: &str| {
+            Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        match var {
+            "localWorkspaceFolder" => {
+                Some(self.local_workspace_folder.to_string_lossy().to_string())
+            }
+            "localWorkspaceFolderBasename" => {
+                Some(basename(&self.local_workspace_folder.to_string_lossy()))
+            }
+            "containerWorkspaceFolder" => Some(self.container_workspace_folder.to_string()),
+            "containerWorkspaceFolderBasename" => Some(basename(self.container_workspace_folder)),
+            _ => {
+                let spec = var
+                    .strip_prefix("localEnv:")
+                    .or_else(|| var.strip_prefix("env:"))?;
+                let (name, default) = spec.split_once(':').unwrap_or((spec, ""));
+                Some(std::env::var(name).unwrap_or_else(|_| default.to_string()))
+            }
+        }
+    }
+}
+
+/// Single-quote an argument for `bash -c`.
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@+,".contains(c))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// Strip the comments and trailing commas JSONC allows, leaving plain JSON.
+fn strip_jsonc(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        out.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            ']' | '}' => {
+                // Drop a comma left dangling before the closing bracket
+                let trimmed_len = out.trim_end().len();
+                if out[..trimmed_len].ends_with(',') {
+                    out.remove(trimmed_len - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn subs(project: &Path) -> Substitutions<'_> {
+        Substitutions {
+            local_workspace_folder: project,
+            container_workspace_folder: "/workspace/app",
+        }
+    }
+
+    fn write_devcontainer(dir: &Path, content: &str) {
+        fs::create_dir_all(dir.join(".devcontainer")).unwrap();
+        fs::write(dir.join(".devcontainer/devcontainer.json"), content).unwrap();
+    }
+
+    #[test]
+    fn test_strip_jsonc() {
+        let input = r#"{
+            // comment with "quotes"
+            "a": "http://example.com", /* block */
+            "b": [1, 2,],
+        }"#;
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(input)).unwrap();
+        assert_eq!(value["a"], "http://example.com");
+        assert_eq!(value["b"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let dir = TempDir::new().unwrap();
+        assert!(DevContainer::load(dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_load_image_and_env() {
+        let dir = TempDir::new().unwrap();
+        write_devcontainer(
+            dir.path(),
+            r#"{
+                "image": "mcr.microsoft.com/devcontainers/rust:1",
+                "containerEnv": {
+                    "APP_ROOT": "${containerWorkspaceFolder}",
+                    "MODE": "dev",
+                    "PATH": "${containerEnv:PATH}:/opt/bin"
+                }
+            }"#,
+        );
+        let dc = DevContainer::load(dir.path()).unwrap().unwrap();
+        assert_eq!(
+            dc.image.as_deref(),
+            Some("mcr.microsoft.com/devcontainers/rust:1")
+        );
+        assert!(dc.image_build().unwrap().is_none());
+        assert_eq!(
+            dc.environment(&subs(dir.path())),
+            vec![
+                ("APP_ROOT".to_string(), "/workspace/app".to_string()),
+                ("MODE".to_string(), "dev".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_settings() {
+        let dc: DevContainer = serde_json::from_str(
+            r#"{
+                "containerEnv": { "B": "2", "A": "${localEnv:HOME}" },
+                "mounts": [
+                    "source=/x,target=/y,type=bind",
+                    { "source": "/d", "target": "/data" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            dc.host_settings(),
+            vec![
+                "containerEnv A=${localEnv:HOME}",
+                "containerEnv B=2",
+                "mount source=/x,target=/y,type=bind",
+                "mount type=bind,source=/d,target=/data",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_paths_relative_to_devcontainer_dir() {
+        let dir = TempDir::new().unwrap();
+        write_devcontainer(
+            dir.path(),
+            r#"{ "build": { "dockerfile": "Dockerfile", "context": "..", "args": { "V": "1" } } }"#,
+        );
+        fs::write(dir.path().join(".devcontainer/Dockerfile"), "FROM alpine\n").unwrap();
+        let build = DevContainer::load(dir.path())
+            .unwrap()
+            .unwrap()
+            .image_build()
+            .unwrap()
+            .unwrap();
+        let project = dir.path().canonicalize().unwrap();
+        assert_eq!(build.dockerfile, project.join(".devcontainer/Dockerfile"));
+        assert_eq!(build.context, project);
+        assert_eq!(build.args.get("V").map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn test_legacy_dockerfile() {
+        let dir = TempDir::new().unwrap();
+        write_devcontainer(dir.path(), r#"{ "dockerFile": "Dockerfile" }"#);
+        fs::write(dir.path().join(".devcontainer/Dockerfile"), "FROM alpine\n").unwrap();
+        let dc = DevContainer::load(dir.path()).unwrap().unwrap();
+        let build = dc.image_build().unwrap().unwrap();
+        let devcontainer_dir = dir.path().join(".devcontainer").canonicalize().unwrap();
+        assert_eq!(build.dockerfile, devcontainer_dir.join("Dockerfile"));
+        assert_eq!(build.context, devcontainer_dir);
+    }
+
+    #[test]
+    fn test_build_paths_stay_in_project() {
+        let dir = TempDir::new().unwrap();
+        let project = dir.path().join("repo");
+        fs::create_dir(&project).unwrap();
+        for build in [
+            r#"{ "build": { "dockerfile": "Dockerfile", "context": "../.." } }"#,
+            r#"{ "build": { "dockerfile": "Dockerfile", "context": "/" } }"#,
+            r#"{ "build": { "dockerfile": "../../Dockerfile.outside" } }"#,
+        ] {
+            write_devcontainer(&project, build);
+            fs::write(project.join(".devcontainer/Dockerfile"), "FROM alpine\n").unwrap();
+            fs::write(dir.path().join("Dockerfile.outside"), "FROM alpine\n").unwrap();
+            let dc = DevContainer::load(&project).unwrap().unwrap();
+            let err = dc.image_build().unwrap_err();
+            assert!(err.to_string().contains("outside the project"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_mounts() {
+        let project = Path::new("/home/me/app");
+        let dc: DevContainer = serde_json::from_str(
+            r#"{ "mounts": [
+                "source=${localWorkspaceFolder}/.cache,target=/cache,type=bind,readonly",
+                "source=app-node-modules,target=/workspace/node_modules,type=volume",
+                { "source": "/tmp/data", "target": "/data", "type": "bind" },
+                "type=tmpfs,target=/scratch"
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            dc.mounts(&subs(project)),
+            vec![
+                DevContainerMount::Bind(
+                    "/home/me/app/.cache".to_string(),
+                    "/cache".to_string(),
+                    true
+                ),
+                DevContainerMount::Volume(
+                    "app-node-modules".to_string(),
+                    "/workspace/node_modules".to_string()
+                ),
+                DevContainerMount::Bind("/tmp/data".to_string(), "/data".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forward_ports() {
+        let dc: DevContainer = serde_json::from_str(
+            r#"{ "forwardPorts": [3000, "8080", "localhost:9229", "db:5432"] }"#,
+        )
+        .unwrap();
+        let ports: Vec<u16> = dc.ports().iter().map(|p| p.container_port).collect();
+        assert_eq!(ports, vec![3000, 8080, 9229]);
+    }
+
+    #[test]
+    fn test_post_create_command_forms() {
+        let dc: DevContainer =
+            serde_json::from_str(r#"{ "postCreateCommand": "npm install && npm run build" }"#)
+                .unwrap();
+        assert_eq!(
+            dc.post_create_commands(),
+            vec!["npm install && npm run build"]
+        );
+
+        let dc: DevContainer =
+            serde_json::from_str(r#"{ "postCreateCommand": ["echo", "hello world"] }"#).unwrap();
+        assert_eq!(dc.post_create_commands(), vec!["echo 'hello world'"]);
+
+        let dc: DevContainer = serde_json::from_str(
+            r#"{ "postCreateCommand": { "server": "npm install", "client": ["pip", "install", "-e", "."] } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            dc.post_create_commands(),
+            vec!["pip install -e .", "npm install"]
+        );
+    }
+
+    #[test]
+    fn test_substitution_local_env() {
+        std::env::set_var("AOE_DEVCONTAINER_TEST_VAR", "value");
+        let s = subs(Path::new("/home/me/app"));
+        assert_eq!(
+            s.apply("${localEnv:AOE_DEVCONTAINER_TEST_VAR}").unwrap(),
+            "value"
+        );
+        assert_eq!(
+            s.apply("${localEnv:AOE_DEVCONTAINER_UNSET_VAR:fallback}")
+                .unwrap(),
+            "fallback"
+        );
+        assert_eq!(s.apply("${localWorkspaceFolderBasename}").unwrap(), "app");
+        assert_eq!(s.apply("a ${unterminated").unwrap(), "a ${unterminated");
+        assert_eq!(
+            s.apply("${containerEnv:PATH}:/opt/bin"),
+            Err("containerEnv:PATH".to_string())
+        );
+    }
+}
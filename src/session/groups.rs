@@ -11,6 +11,10 @@ pub struct Group {
     pub path: String,
     #[serde(default)]
     pub collapsed: bool,
+    /// Position among sibling groups. Groups that were never moved share 0
+    /// and sort by name.
+    #[serde(default)]
+    pub order: u32,
     #[serde(skip)]
     pub children: Vec<Group>,
 }
@@ -21,6 +25,7 @@ impl Group {
             name: name.to_string(),
             path: path.to_string(),
             collapsed: false,
+            order: 0,
             children: Vec::new(),
         }
     }
@@ -91,7 +96,7 @@ impl GroupTree {
             .cloned()
             .collect();
 
-        root_groups.sort_by(|a, b| a.name.cmp(&b.name));
+        sort_siblings(&mut root_groups);
 
         // Build children recursively
         for root in &mut root_groups {
@@ -111,7 +116,7 @@ impl GroupTree {
             .cloned()
             .collect();
 
-        children.sort_by(|a, b| a.name.cmp(&b.name));
+        sort_siblings(&mut children);
 
         for child in &mut children {
             self.build_children(child);
@@ -156,6 +161,41 @@ impl GroupTree {
         &self.roots
     }
 
+    /// Move a group above or below its neighbouring sibling. Returns false
+    /// when it is already first or last.
+    pub fn move_group(&mut self, path: &str, up: bool) -> bool {
+        let parent = parent_path(path);
+        let mut siblings: Vec<Group> = self
+            .groups_by_path
+            .values()
+            .filter(|g| parent_path(&g.path) == parent)
+            .cloned()
+            .collect();
+        sort_siblings(&mut siblings);
+
+        let Some(pos) = siblings.iter().position(|g| g.path == path) else {
+            return false;
+        };
+        let target = if up {
+            pos.checked_sub(1)
+        } else {
+            Some(pos + 1).filter(|&t| t < siblings.len())
+        };
+        let Some(target) = target else {
+            return false;
+        };
+        siblings.swap(pos, target);
+
+        // Number every sibling, so groups that shared an order keep theirs
+        for (order, sibling) in siblings.iter().enumerate() {
+            if let Some(group) = self.groups_by_path.get_mut(&sibling.path) {
+                group.order = order as u32;
+            }
+        }
+        self.rebuild_tree();
+        true
+    }
+
     pub fn toggle_collapsed(&mut self, path: &str) {
         if let Some(group) = self.groups_by_path.get_mut(path) {
             group.collapsed = !group.collapsed;
@@ -164,9 +204,30 @@ impl GroupTree {
     }
 }
 
-/// Item represents either a group or an instance in the flattened tree view
+fn parent_path(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+fn sort_siblings(groups: &mut [Group]) {
+    groups.sort_by(|a, b| a.order.cmp(&b.order).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// Pinned sessions first, each part keeping the order it was given in
+fn pinned_first<'a>(sessions: impl Iterator<Item = &'a Instance>) -> Vec<&'a Instance> {
+    let (mut pinned, rest): (Vec<_>, Vec<_>) = sessions.partition(|i| i.pinned);
+    pinned.extend(rest);
+    pinned
+}
+
+/// Item represents a group, an instance, or the header of the favorites in
+/// the flattened tree view
 #[derive(Debug, Clone)]
 pub enum Item {
+    /// Virtual group listing the favorite sessions, which also stay in their
+    /// own groups
+    Favorites {
+        session_count: usize,
+    },
     Group {
         path: String,
         name: String,
@@ -183,6 +244,7 @@ pub enum Item {
 impl Item {
     pub fn depth(&self) -> usize {
         match self {
+            Item::Favorites { .. } => 0,
             Item::Group { depth, .. } => *depth,
             Item::Session { depth, .. } => *depth,
         }
@@ -192,11 +254,22 @@ impl Item {
 pub fn flatten_tree(group_tree: &GroupTree, instances: &[Instance]) -> Vec<Item> {
     let mut items = Vec::new();
 
-    // Add ungrouped sessions first
-    let ungrouped: Vec<&Instance> = instances
-        .iter()
-        .filter(|i| i.group_path.is_empty())
-        .collect();
+    // Favorites on top
+    let favorites = pinned_first(instances.iter().filter(|i| i.favorite));
+    if !favorites.is_empty() {
+        items.push(Item::Favorites {
+            session_count: favorites.len(),
+        });
+        for inst in favorites {
+            items.push(Item::Session {
+                id: inst.id.clone(),
+                depth: 1,
+            });
+        }
+    }
+
+    // Then ungrouped sessions
+    let ungrouped = pinned_first(instances.iter().filter(|i| i.group_path.is_empty()));
 
     for inst in ungrouped {
         items.push(Item::Session {
@@ -235,10 +308,7 @@ fn flatten_group(
     }
 
     // Add sessions in this group (direct children only)
-    let group_sessions: Vec<&Instance> = instances
-        .iter()
-        .filter(|i| i.group_path == group.path)
-        .collect();
+    let group_sessions = pinned_first(instances.iter().filter(|i| i.group_path == group.path));
 
     for inst in group_sessions {
         items.push(Item::Session {
@@ -554,4 +624,65 @@ mod tests {
         assert_eq!(roots[1].name, "mango");
         assert_eq!(roots[2].name, "zebra");
     }
+
+    #[test]
+    fn test_move_group_reorders_siblings_and_persists() {
+        let instances: Vec<Instance> = ["apple", "mango", "zebra", "mango/kiwi"]
+            .iter()
+            .map(|path| {
+                let mut inst = Instance::new(path, "/tmp");
+                inst.group_path = path.to_string();
+                inst
+            })
+            .collect();
+        let mut tree = GroupTree::new_with_groups(&instances, &[]);
+        let names = |tree: &GroupTree| -> Vec<String> {
+            tree.get_roots().iter().map(|g| g.name.clone()).collect()
+        };
+
+        assert!(!tree.move_group("apple", true));
+        assert!(tree.move_group("zebra", true));
+        assert_eq!(names(&tree), vec!["apple", "zebra", "mango"]);
+        assert!(!tree.move_group("mango", false));
+        assert!(!tree.move_group("mango/kiwi", true));
+
+        // The order survives a reload from the saved groups
+        let reloaded = GroupTree::new_with_groups(&instances, &tree.get_all_groups());
+        assert_eq!(names(&reloaded), vec!["apple", "zebra", "mango"]);
+        assert_eq!(reloaded.get_roots()[2].children[0].name, "kiwi");
+    }
+
+    #[test]
+    fn test_flatten_lists_favorites_and_pinned_sessions_first() {
+        let mut first = Instance::new("first", "/tmp/1");
+        first.group_path = "work".to_string();
+        let mut second = Instance::new("second", "/tmp/2");
+        second.group_path = "work".to_string();
+        second.pinned = true;
+        let mut loose = Instance::new("loose", "/tmp/3");
+        loose.favorite = true;
+        let instances = vec![first.clone(), second.clone(), loose.clone()];
+        let tree = GroupTree::new_with_groups(&instances, &[]);
+
+        let items = flatten_tree(&tree, &instances);
+        let rows: Vec<(String, usize)> = items
+            .iter()
+            .map(|item| match item {
+                Item::Favorites { session_count } => (format!("favorites {}", session_count), 0),
+                Item::Group { path, depth, .. } => (path.clone(), *depth),
+                Item::Session { id, depth } => (id.clone(), *depth),
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("favorites 1".to_string(), 0),
+                (loose.id.clone(), 1),
+                (loose.id.clone(), 0),
+                ("work".to_string(), 0),
+                (second.id.clone(), 1),
+                (first.id.clone(), 1),
+            ]
+        );
+    }
 }
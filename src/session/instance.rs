@@ -6,12 +6,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::docker::{
-    self, ContainerConfig, DockerContainer, VolumeMount, CLAUDE_AUTH_VOLUME, CODEX_AUTH_VOLUME,
-    GEMINI_AUTH_VOLUME, OPENCODE_AUTH_VOLUME, VIBE_AUTH_VOLUME,
+    isolated, ports, snapshot, ContainerConfig, ContainerNetwork, ContainerRuntime,
+    DockerContainer, EgressProxy, ImageBuild, PortForwarder, PortMapping, SessionPort, Snapshot,
+    VolumeMount, CLAUDE_AUTH_VOLUME, CODEX_AUTH_VOLUME, GEMINI_AUTH_VOLUME, OPENCODE_AUTH_VOLUME,
+    VIBE_AUTH_VOLUME,
 };
 use crate::git::GitWorktree;
 use crate::tmux;
 
+use super::config::{Config, NetworkMode};
+use super::devcontainer::{DevContainer, DevContainerMount, Substitutions};
+use super::secrets;
+
 fn default_true() -> bool {
     true
 }
@@ -31,24 +37,57 @@ fn shell_escape(val: &str) -> String {
     format!("\"{}\"", escaped)
 }
 
-/// Resolve an environment_values entry. If the value starts with `$`, read the
-/// named variable from the host environment (use `$$` to escape a literal `$`).
-/// Otherwise return the literal value.
-fn resolve_env_value(val: &str) -> Option<String> {
-    if let Some(rest) = val.strip_prefix("$$") {
-        Some(format!("${}", rest))
-    } else if let Some(var_name) = val.strip_prefix('$') {
-        std::env::var(var_name).ok()
-    } else {
-        Some(val.to_string())
+/// Environment of a `docker exec` session.
+struct ExecEnv {
+    values: Vec<(String, String)>,
+}
+
+impl ExecEnv {
+    /// Write the values to an env file that the exec command opens and
+    /// deletes (see `open_env_file`). Values spanning lines can't go in the
+    /// file; exec sessions inherit them from the container, which got them
+    /// when it was created (see `DockerContainer::create`).
+    fn env_file(&self) -> Result<Option<std::path::PathBuf>> {
+        let values: Vec<(String, String)> = self
+            .values
+            .iter()
+            .filter(|(_, value)| secrets::fits_env_file(value))
+            .cloned()
+            .collect();
+        if values.is_empty() {
+            return Ok(None);
+        }
+        let dir = super::get_app_dir()?.join("secrets");
+        Ok(Some(secrets::write_env_file(&dir, &values)?))
+    }
+}
+
+/// Runtime flags reading the env file that `open_env_file` leaves on fd 3
+fn env_file_flags(env_file: Option<&std::path::Path>) -> &'static str {
+    match env_file {
+        Some(_) => "--env-file /dev/fd/3 ",
+        None => "",
+    }
+}
+
+/// Shell prefix that opens an env file written by `ExecEnv::env_file` on fd 3
+/// and deletes it, for use inside `bash -c '...'`. The runtime reads the
+/// values from the descriptor, so they are never on a command line, in the
+/// shell's environment or in that of the runtime CLI.
+fn open_env_file(env_file: Option<&std::path::Path>) -> String {
+    match env_file {
+        Some(path) => {
+            let path = shell_escape(&path.to_string_lossy());
+            format!("exec 3< {}; rm -f {}; ", path, path)
+        }
+        None => String::new(),
     }
 }
 
-/// Build docker exec environment flags from config and optional per-session extra keys.
-/// Used for `docker exec` commands (shell string interpolation, hence shell-escaping).
-/// Container creation uses `ContainerConfig.environment` (separate args, no escaping needed).
-fn build_docker_env_args(sandbox: &SandboxInfo) -> String {
-    let config = super::config::Config::load().unwrap_or_default();
+/// Build docker exec environment from config and optional per-session extra keys.
+/// Container creation uses `ContainerConfig.environment` instead.
+fn build_docker_exec_env(sandbox: &SandboxInfo, config: &Config) -> ExecEnv {
+    let keyring_command = config.sandbox.keyring_command.as_deref();
 
     // Start with default terminal variables (always included for proper UI)
     let mut env_keys: Vec<String> = DEFAULT_TERMINAL_ENV_VARS
@@ -72,32 +111,28 @@ fn build_docker_env_args(sandbox: &SandboxInfo) -> String {
         }
     }
 
-    let mut args: Vec<String> = env_keys
+    let mut values: Vec<(String, String)> = env_keys
         .iter()
-        .filter_map(|key| {
-            std::env::var(key)
-                .ok()
-                .map(|val| format!("-e {}={}", key, shell_escape(&val)))
-        })
+        .filter_map(|key| std::env::var(key).ok().map(|val| (key.clone(), val)))
         .collect();
 
     // Inject environment_values (AOE-managed, used for docker exec sessions)
     for (key, val) in &config.sandbox.environment_values {
-        if let Some(resolved) = resolve_env_value(val) {
-            args.push(format!("-e {}={}", key, shell_escape(&resolved)));
+        if let Some(resolved) = secrets::resolve(key, val, keyring_command) {
+            values.push((key.clone(), resolved));
         }
     }
 
     // Inject per-session extra env values
     if let Some(extra_vals) = &sandbox.extra_env_values {
         for (key, val) in extra_vals {
-            if let Some(resolved) = resolve_env_value(val) {
-                args.push(format!("-e {}={}", key, shell_escape(&resolved)));
+            if let Some(resolved) = secrets::resolve(key, val, keyring_command) {
+                values.push((key.clone(), resolved));
             }
         }
     }
 
-    args.join(" ")
+    ExecEnv { values }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -128,6 +163,10 @@ pub struct WorktreeInfo {
     pub created_at: DateTime<Utc>,
     #[serde(default = "default_true")]
     pub cleanup_on_delete: bool,
+    /// Files that conflicted the last time `aoe worktree sync` tried to bring
+    /// this branch up to date. Cleared by the next successful sync.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sync_conflicts: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,6 +186,32 @@ pub struct SandboxInfo {
     /// Additional KEY=VALUE environment variables (session-specific overrides)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub extra_env_values: Option<std::collections::HashMap<String, String>>,
+    /// Network policy the container was created with (e.g. "none", "allowlist (...)")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    /// Runtime that manages the container, fixed when the session is created
+    #[serde(default, skip_serializing_if = "ContainerRuntime::is_docker")]
+    pub runtime: ContainerRuntime,
+    /// Dockerfile the image is built from; `image` then holds the last built tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<ImageBuild>,
+    /// Container ports published after creation through port forwarders
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forwarded_ports: Vec<u16>,
+    /// Snapshot the container was rolled back to. A recreated container
+    /// starts from it instead of `image`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
+    /// Session-specific CPU limit, overriding `[sandbox] cpu_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<String>,
+    /// Session-specific memory limit, overriding `[sandbox] memory_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<String>,
+    /// Project mounted read-only; the agent works on a copy in a volume and
+    /// changes reach the host only when applied
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub isolated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -167,6 +232,12 @@ pub struct Instance {
     pub created_at: DateTime<Utc>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_accessed_at: Option<DateTime<Utc>>,
+    /// Listed before the other sessions of its group
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Also listed in the Favorites group at the top of the list
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub favorite: bool,
 
     // Git worktree integration
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -187,12 +258,18 @@ pub struct Instance {
     pub last_start_time: Option<std::time::Instant>,
     #[serde(skip)]
     pub last_error: Option<String>,
+    /// What the agent is waiting for, while `status` is `Waiting`
+    #[serde(skip)]
+    pub waiting_reason: Option<tmux::WaitingReason>,
 
     // Search optimization: pre-computed lowercase strings (not serialized)
     #[serde(skip)]
     pub title_lower: String,
     #[serde(skip)]
     pub project_path_lower: String,
+    /// Profile the session belongs to, set when it is created or loaded
+    #[serde(skip)]
+    pub profile: String,
 }
 
 impl Instance {
@@ -208,17 +285,32 @@ impl Instance {
             status: Status::Idle,
             created_at: Utc::now(),
             last_accessed_at: None,
+            pinned: false,
+            favorite: false,
             worktree_info: None,
             sandbox_info: None,
             terminal_info: None,
             last_error_check: None,
             last_start_time: None,
             last_error: None,
+            waiting_reason: None,
             title_lower: title.to_lowercase(),
             project_path_lower: project_path.to_lowercase(),
+            profile: String::new(),
         }
     }
 
+    /// Effective config of the session's profile
+    pub fn config(&self) -> Config {
+        super::resolve_config(&self.profile).unwrap_or_default()
+    }
+
+    /// Paths an isolated workspace never reports as changed: its own baseline
+    /// and the directories shadowed by anonymous volumes.
+    fn isolated_excludes(&self) -> Vec<String> {
+        isolated::excludes(&self.config().sandbox.volume_ignores)
+    }
+
     /// Update the pre-computed lowercase fields for search optimization.
     /// Call this after loading instances from disk or modifying title/path.
     pub fn update_search_cache(&mut self) {
@@ -234,6 +326,14 @@ impl Instance {
         self.sandbox_info.as_ref().is_some_and(|s| s.enabled)
     }
 
+    /// Runtime managing this session's container (Docker for unsandboxed sessions)
+    pub fn container_runtime(&self) -> ContainerRuntime {
+        self.sandbox_info
+            .as_ref()
+            .map(|s| s.runtime)
+            .unwrap_or_default()
+    }
+
     pub fn is_yolo_mode(&self) -> bool {
         self.sandbox_info
             .as_ref()
@@ -321,25 +421,29 @@ impl Instance {
         self.ensure_container_running()?;
         let sandbox = self.sandbox_info.as_ref().unwrap();
 
-        let env_args = build_docker_env_args(sandbox);
-        let env_part = if env_args.is_empty() {
-            String::new()
-        } else {
-            format!("{} ", env_args)
-        };
-
         // Get workspace path inside container (handles bare repo worktrees correctly)
         let project_path = std::path::Path::new(&self.project_path);
         let (_, _, container_workdir) = self.compute_volume_paths(project_path)?;
 
-        let cmd = format!(
-            "docker exec -it -w {} {}{} /bin/bash",
-            container_workdir, env_part, sandbox.container_name
-        );
-
         let session = self.container_terminal_tmux_session()?;
         let is_new = !session.exists();
         if is_new {
+            let env_file = build_docker_exec_env(sandbox, &self.config()).env_file()?;
+            let exec_cmd = format!(
+                "{} exec -it -w {} {}{} /bin/bash",
+                sandbox.runtime.binary(),
+                container_workdir,
+                env_file_flags(env_file.as_deref()),
+                sandbox.container_name
+            );
+            let cmd = match env_file {
+                Some(env_file) => format!(
+                    "bash -c '{}exec {}'",
+                    open_env_file(Some(&env_file)),
+                    exec_cmd
+                ),
+                None => exec_cmd,
+            };
             session.create_with_size(&self.project_path, Some(&cmd), size)?;
             self.apply_container_terminal_tmux_options();
         }
@@ -421,6 +525,7 @@ impl Instance {
                     let workdir = self.container_workdir();
                     if let Err(e) = super::repo_config::execute_hooks_in_container(
                         hook_cmds,
+                        sandbox.runtime,
                         &sandbox.container_name,
                         &workdir,
                     ) {
@@ -440,16 +545,17 @@ impl Instance {
             } else {
                 self.get_tool_command().to_string()
             };
-            let env_args = build_docker_env_args(sandbox);
-            let env_part = if env_args.is_empty() {
-                String::new()
-            } else {
-                format!("{} ", env_args)
-            };
-            Some(wrap_command_ignore_suspend(&format!(
-                "docker exec -it {}{} {}",
-                env_part, sandbox.container_name, tool_cmd
-            )))
+            let env_file = build_docker_exec_env(sandbox, &self.config()).env_file()?;
+            Some(wrap_command_with_env(
+                &format!(
+                    "{} exec -it {}{} {}",
+                    sandbox.runtime.binary(),
+                    env_file_flags(env_file.as_deref()),
+                    sandbox.container_name,
+                    tool_cmd
+                ),
+                env_file.as_deref(),
+            ))
         } else {
             // Run on_launch hooks on host for non-sandboxed sessions
             if let Some(ref hook_cmds) = on_launch_hooks {
@@ -524,46 +630,310 @@ impl Instance {
         apply_all_tmux_options(&session_name, &terminal_title, branch, sandbox.as_ref());
     }
 
+    /// Make the sandbox image available locally. Sessions with a Dockerfile
+    /// get an image built from their current build context (reusing a cached
+    /// build when nothing changed); others pull `image` if it's missing.
+    /// Build output is passed to `on_output` line by line.
+    pub fn prepare_sandbox_image(&mut self, on_output: impl FnMut(String)) -> Result<()> {
+        let sandbox = self
+            .sandbox_info
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Cannot prepare image for non-sandboxed session"))?;
+
+        match &sandbox.build {
+            Some(build) => sandbox.image = build.ensure(sandbox.runtime, on_output)?,
+            None => sandbox.runtime.ensure_image(&sandbox.image)?,
+        }
+
+        Ok(())
+    }
+
     pub fn ensure_container_running(&mut self) -> Result<()> {
         let sandbox = self
             .sandbox_info
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Cannot ensure container for non-sandboxed session"))?;
 
-        let image = &sandbox.image;
-        let container = DockerContainer::new(&self.id, image);
+        let runtime = sandbox.runtime;
+        let existing = DockerContainer::from_session_id(&self.id, runtime);
 
-        if container.is_running()? {
+        if existing.is_running()? {
             return Ok(());
         }
 
-        if container.exists()? {
-            container.start()?;
+        if existing.exists()? {
+            existing.start()?;
+            self.restore_port_forwards();
             return Ok(());
         }
 
-        // Ensure image is available (always pulls to get latest)
-        docker::ensure_image(image)?;
+        let image = match self.rollback_image() {
+            Some(image) => image,
+            None => {
+                self.prepare_sandbox_image(|line| tracing::debug!("{}", line))?;
+                self.sandbox_info
+                    .as_ref()
+                    .map(|s| s.image.clone())
+                    .unwrap_or_default()
+            }
+        };
+        let container = DockerContainer::new(&self.id, &image, runtime);
 
-        docker::ensure_named_volume(CLAUDE_AUTH_VOLUME)?;
-        docker::ensure_named_volume(OPENCODE_AUTH_VOLUME)?;
-        docker::ensure_named_volume(VIBE_AUTH_VOLUME)?;
-        docker::ensure_named_volume(CODEX_AUTH_VOLUME)?;
-        docker::ensure_named_volume(GEMINI_AUTH_VOLUME)?;
+        runtime.ensure_named_volume(CLAUDE_AUTH_VOLUME)?;
+        runtime.ensure_named_volume(OPENCODE_AUTH_VOLUME)?;
+        runtime.ensure_named_volume(VIBE_AUTH_VOLUME)?;
+        runtime.ensure_named_volume(CODEX_AUTH_VOLUME)?;
+        runtime.ensure_named_volume(GEMINI_AUTH_VOLUME)?;
 
-        crate::migrations::run_lazy_docker_migrations();
+        // Podman mounts named volumes with `:U`, which fixes ownership on its own
+        if runtime.is_docker() {
+            crate::migrations::run_lazy_docker_migrations();
+        }
 
-        let config = self.build_container_config()?;
+        let (config, network_policy) = self.build_container_config()?;
+        if let ContainerNetwork::Egress(proxy) = &config.network {
+            proxy.ensure()?;
+        }
         let container_id = container.create(&config)?;
 
+        if self.is_isolated() {
+            let project_path = std::path::Path::new(&self.project_path);
+            let (_, container_base_path, workspace_path) =
+                self.compute_volume_paths(project_path)?;
+            isolated::seed(
+                runtime,
+                &container.name,
+                &container_base_path,
+                &workspace_path,
+                &self.isolated_excludes(),
+            )?;
+        }
+
         if let Some(ref mut sandbox) = self.sandbox_info {
             sandbox.container_id = Some(container_id);
             sandbox.created_at = Some(Utc::now());
+            sandbox.network = Some(network_policy);
+        }
+        self.restore_port_forwards();
+
+        Ok(())
+    }
+
+    /// Image of the snapshot the session was rolled back to, if it still
+    /// exists. A snapshot that was removed is forgotten.
+    fn rollback_image(&mut self) -> Option<String> {
+        let sandbox = self.sandbox_info.as_mut()?;
+        let name = sandbox.snapshot.clone()?;
+        if snapshot::exists(sandbox.runtime, &sandbox.container_name, &name) {
+            return Some(snapshot::snapshot_image(&sandbox.container_name, &name));
+        }
+        tracing::warn!(
+            "Snapshot {} of {} no longer exists, using the session image",
+            name,
+            sandbox.container_name
+        );
+        sandbox.snapshot = None;
+        None
+    }
+
+    /// Commit the container's current state as a snapshot. Without a name
+    /// the snapshot is named after the current time.
+    pub fn create_snapshot(&self, name: Option<&str>) -> Result<Snapshot> {
+        let sandbox = self
+            .sandbox_info
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have snapshots"))?;
+        let name = name.map_or_else(snapshot::default_name, String::from);
+        Ok(snapshot::create(
+            sandbox.runtime,
+            &sandbox.container_name,
+            &name,
+        )?)
+    }
+
+    /// Snapshots of the session's container, newest first.
+    pub fn list_snapshots(&self) -> Result<Vec<Snapshot>> {
+        let sandbox = self
+            .sandbox_info
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have snapshots"))?;
+        Ok(snapshot::list(sandbox.runtime, &sandbox.container_name)?)
+    }
+
+    pub fn delete_snapshot(&mut self, name: &str) -> Result<()> {
+        let sandbox = self
+            .sandbox_info
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have snapshots"))?;
+        snapshot::remove(sandbox.runtime, &sandbox.container_name, name)?;
+        if sandbox.snapshot.as_deref() == Some(name) {
+            sandbox.snapshot = None;
+        }
+        Ok(())
+    }
+
+    /// Replace the container with one created from a snapshot. The project
+    /// and other mounts are untouched. A running agent is restarted in the
+    /// new container.
+    pub fn rollback(&mut self, name: &str) -> Result<()> {
+        let sandbox = self
+            .sandbox_info
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have snapshots"))?;
+        if !snapshot::exists(sandbox.runtime, &sandbox.container_name, name) {
+            return Err(crate::docker::DockerError::SnapshotNotFound(name.to_string()).into());
+        }
+
+        let container = DockerContainer::from_session_id(&self.id, sandbox.runtime);
+        sandbox.snapshot = Some(name.to_string());
+        sandbox.container_id = None;
+
+        let was_running = self.tmux_session()?.exists();
+        self.kill()?;
+        let _ = self.kill_container_terminal();
+        if container.exists()? {
+            container.remove(true)?;
+        }
+
+        self.ensure_container_running()?;
+        if was_running {
+            self.start()?;
+        }
+        Ok(())
+    }
+
+    pub fn is_isolated(&self) -> bool {
+        self.sandbox_info.as_ref().is_some_and(|s| s.isolated)
+    }
+
+    /// Changes the agent made to its copy of an isolated workspace, as a
+    /// patch against the host project.
+    pub fn isolated_diff(&self) -> Result<String> {
+        let sandbox = self
+            .sandbox_info
+            .as_ref()
+            .filter(|s| s.isolated)
+            .ok_or_else(|| anyhow::anyhow!("Session '{}' is not isolated", self.title))?;
+
+        let project_path = std::path::Path::new(&self.project_path);
+        let (_, container_base_path, workspace_path) = self.compute_volume_paths(project_path)?;
+
+        let container = DockerContainer::from_session_id(&self.id, sandbox.runtime);
+        if !container.is_running()? {
+            container.start()?;
+        }
+
+        Ok(isolated::diff(
+            sandbox.runtime,
+            &container.name,
+            &container_base_path,
+            &workspace_path,
+            &self.isolated_excludes(),
+        )?)
+    }
+
+    /// Apply the isolated workspace's changes under `paths` (all changes
+    /// when empty) to the host project. Returns the files that changed.
+    pub fn apply_isolated_changes(&self, paths: &[String]) -> Result<Vec<String>> {
+        let patch = isolated::filter_patch(&self.isolated_diff()?, paths);
+        let files: Vec<String> = isolated::split_patch(&patch)
+            .into_iter()
+            .map(|(file, _)| file)
+            .collect();
+        if !files.is_empty() {
+            isolated::apply(std::path::Path::new(&self.project_path), &patch)?;
+        }
+        Ok(files)
+    }
+
+    /// Set the session's CPU and/or memory limit. An existing container is
+    /// updated in place; otherwise the limits apply when it is created.
+    /// Returns whether a container was updated.
+    pub fn update_limits(
+        &mut self,
+        cpu_limit: Option<String>,
+        memory_limit: Option<String>,
+    ) -> Result<bool> {
+        let sandbox = self
+            .sandbox_info
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have resource limits"))?;
+
+        let container = DockerContainer::from_session_id(&self.id, sandbox.runtime);
+        let updated = container.exists()?;
+        if updated {
+            container.update_limits(cpu_limit.as_deref(), memory_limit.as_deref())?;
+        }
+
+        if cpu_limit.is_some() {
+            sandbox.cpu_limit = cpu_limit;
+        }
+        if memory_limit.is_some() {
+            sandbox.memory_limit = memory_limit;
         }
+        Ok(updated)
+    }
 
+    /// Ports of the session's container, with host addresses where published.
+    pub fn list_ports(&self) -> Result<Vec<SessionPort>> {
+        let sandbox = self
+            .sandbox_info
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have ports"))?;
+        Ok(ports::session_ports(
+            sandbox.runtime,
+            &sandbox.container_name,
+            &sandbox.forwarded_ports,
+        )?)
+    }
+
+    /// Publish a container port on the host while the container is running.
+    pub fn forward_port(&mut self, port: u16) -> Result<()> {
+        let sandbox = self
+            .sandbox_info
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have ports"))?;
+        if ports::published_ports(sandbox.runtime, &sandbox.container_name)
+            .iter()
+            .any(|(p, _)| *p == port)
+        {
+            anyhow::bail!("Port {} is already published", port);
+        }
+
+        PortForwarder::new(sandbox.runtime, &sandbox.container_name, port).start()?;
+        if !sandbox.forwarded_ports.contains(&port) {
+            sandbox.forwarded_ports.push(port);
+            sandbox.forwarded_ports.sort_unstable();
+        }
         Ok(())
     }
 
+    /// Stop publishing a port added with `forward_port`.
+    pub fn unforward_port(&mut self, port: u16) -> Result<()> {
+        let sandbox = self
+            .sandbox_info
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Only sandboxed sessions have ports"))?;
+        PortForwarder::new(sandbox.runtime, &sandbox.container_name, port).stop()?;
+        sandbox.forwarded_ports.retain(|p| *p != port);
+        Ok(())
+    }
+
+    /// Recreate port forwarders after the container restarts, since its
+    /// address may have changed.
+    fn restore_port_forwards(&self) {
+        let Some(sandbox) = &self.sandbox_info else {
+            return;
+        };
+        for &port in &sandbox.forwarded_ports {
+            if let Err(e) =
+                PortForwarder::new(sandbox.runtime, &sandbox.container_name, port).start()
+            {
+                tracing::warn!("Failed to restore port forward {}: {}", port, e);
+            }
+        }
+    }
+
     /// Compute volume mount paths for Docker container.
     ///
     /// For bare repo worktrees, mounts the entire bare repo and sets working_dir to the worktree.
@@ -634,7 +1004,8 @@ impl Instance {
             .unwrap_or_else(|_| "/workspace".to_string())
     }
 
-    fn build_container_config(&self) -> Result<ContainerConfig> {
+    /// Returns the container config along with a description of its network policy.
+    fn build_container_config(&self) -> Result<(ContainerConfig, String)> {
         let home =
             dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
 
@@ -646,10 +1017,17 @@ impl Instance {
         let (mount_host_path, container_base_path, workspace_path) =
             self.compute_volume_paths(project_path)?;
 
+        // An isolated session sees the project read-only; its working copy
+        // is a volume mounted where the project would be
+        let is_isolated = self.is_isolated();
         let mut volumes = vec![VolumeMount {
             host_path: mount_host_path,
-            container_path: container_base_path,
-            read_only: false,
+            container_path: if is_isolated {
+                isolated::HOST_MOUNT.to_string()
+            } else {
+                container_base_path.clone()
+            },
+            read_only: is_isolated,
         }];
 
         const CONTAINER_HOME: &str = "/root";
@@ -719,10 +1097,60 @@ impl Instance {
             ));
         }
 
-        let sandbox_config = super::config::Config::load()
-            .ok()
-            .map(|c| c.sandbox)
-            .unwrap_or_default();
+        if let Some(sandbox) = self.sandbox_info.as_ref().filter(|s| s.isolated) {
+            named_volumes.push((
+                isolated::workspace_volume(&sandbox.container_name),
+                container_base_path,
+            ));
+        }
+
+        let sandbox_config = self.config().sandbox;
+
+        let devcontainer = DevContainer::load_or_warn(project_path);
+        // containerEnv and mounts reach host variables and files, so like
+        // hooks they wait for the repo to be trusted
+        let trusted = matches!(
+            super::repo_config::check_hook_trust(project_path),
+            Ok(super::repo_config::HookTrustStatus::Trusted(_))
+        );
+        let host_settings = devcontainer.as_ref().filter(|dc| {
+            let ignored = !trusted && !dc.host_settings().is_empty();
+            if ignored {
+                tracing::warn!(
+                    "Ignoring containerEnv and mounts of devcontainer.json: repo is not trusted"
+                );
+            }
+            !ignored
+        });
+        let subs = Substitutions {
+            local_workspace_folder: project_path,
+            container_workspace_folder: &workspace_path,
+        };
+        let mut ports = Vec::new();
+        if let Some(devcontainer) = host_settings {
+            for mount in devcontainer.mounts(&subs) {
+                match mount {
+                    DevContainerMount::Bind(host_path, container_path, read_only) => {
+                        volumes.push(VolumeMount {
+                            host_path,
+                            container_path,
+                            read_only,
+                        })
+                    }
+                    DevContainerMount::Volume(name, container_path) => {
+                        named_volumes.push((name, container_path))
+                    }
+                }
+            }
+        }
+        if let Some(devcontainer) = &devcontainer {
+            ports = devcontainer.ports();
+        }
+        for spec in &sandbox_config.ports {
+            let mapping = PortMapping::parse(spec)?;
+            ports.retain(|p: &PortMapping| p.container_port != mapping.container_port);
+            ports.push(mapping);
+        }
 
         // Start with default terminal variables (always included for proper UI)
         let mut env_keys: Vec<String> = DEFAULT_TERMINAL_ENV_VARS
@@ -760,9 +1188,15 @@ impl Instance {
             format!("{}/.claude", CONTAINER_HOME),
         ));
 
+        // devcontainer.json containerEnv, overridable by aoe's own settings below
+        if let Some(devcontainer) = host_settings {
+            environment.extend(devcontainer.environment(&subs));
+        }
+
         // Inject environment_values (AOE-managed, used for container creation via separate args)
+        let keyring_command = sandbox_config.keyring_command.as_deref();
         for (key, val) in &sandbox_config.environment_values {
-            if let Some(resolved) = resolve_env_value(val) {
+            if let Some(resolved) = secrets::resolve(key, val, keyring_command) {
                 environment.push((key.clone(), resolved));
             }
         }
@@ -774,7 +1208,7 @@ impl Instance {
             .and_then(|s| s.extra_env_values.as_ref())
         {
             for (key, val) in extra_vals {
-                if let Some(resolved) = resolve_env_value(val) {
+                if let Some(resolved) = secrets::resolve(key, val, keyring_command) {
                     environment.push((key.clone(), resolved));
                 }
             }
@@ -793,15 +1227,44 @@ impl Instance {
             .map(|ignore| format!("{}/{}", workspace_path, ignore))
             .collect();
 
-        Ok(ContainerConfig {
-            working_dir: workspace_path,
-            volumes,
-            named_volumes,
-            anonymous_volumes,
-            environment,
-            cpu_limit: sandbox_config.cpu_limit,
-            memory_limit: sandbox_config.memory_limit,
-        })
+        // Per-session limits win over the configured defaults
+        let session_sandbox = self.sandbox_info.as_ref();
+        let cpu_limit = session_sandbox
+            .and_then(|s| s.cpu_limit.clone())
+            .or(sandbox_config.cpu_limit.clone());
+        let memory_limit = session_sandbox
+            .and_then(|s| s.memory_limit.clone())
+            .or(sandbox_config.memory_limit.clone());
+
+        let network_config = &sandbox_config.network;
+        let network = match network_config.mode {
+            NetworkMode::Full => ContainerNetwork::Bridge,
+            NetworkMode::None => ContainerNetwork::Disabled,
+            NetworkMode::Allowlist => {
+                let proxy = EgressProxy::new(
+                    &network_config.allowlist,
+                    &network_config.proxy_image,
+                    self.container_runtime(),
+                )?;
+                environment.extend(proxy.environment());
+                ContainerNetwork::Egress(proxy)
+            }
+        };
+
+        Ok((
+            ContainerConfig {
+                working_dir: workspace_path,
+                volumes,
+                named_volumes,
+                anonymous_volumes,
+                environment,
+                ports,
+                cpu_limit,
+                memory_limit,
+                network,
+            },
+            network_config.describe(),
+        ))
     }
 
     pub fn restart(&mut self) -> Result<()> {
@@ -829,7 +1292,19 @@ impl Instance {
         Ok(())
     }
 
+    /// Send a prompt to the running agent
+    pub fn send_prompt(&self, prompt: &str) -> Result<()> {
+        self.tmux_session()?.send_text(prompt)
+    }
+
+    /// Press a key in the agent's pane; see `tmux::Session::send_key`
+    pub fn send_key(&self, key: &str, literal: bool) -> Result<()> {
+        self.tmux_session()?.send_key(key, literal)
+    }
+
     pub fn update_status(&mut self) {
+        self.waiting_reason = None;
+
         // Skip expensive checks for recently errored sessions
         if self.status == Status::Error {
             if let Some(last_check) = self.last_error_check {
@@ -863,9 +1338,9 @@ impl Instance {
         }
 
         // Detect status from pane content
-        self.status = match session.detect_status(&self.tool) {
-            Ok(status) => status,
-            Err(_) => Status::Idle,
+        (self.status, self.waiting_reason) = match session.detect_status(&self.tool) {
+            Ok(detection) => (detection.status, detection.waiting_reason),
+            Err(_) => (Status::Idle, None),
         };
     }
 
@@ -893,7 +1368,17 @@ fn generate_id() -> String {
 ///
 /// Uses POSIX-standard `stty susp undef` which works on both Linux and macOS.
 fn wrap_command_ignore_suspend(cmd: &str) -> String {
-    format!("bash -c 'stty susp undef; exec {}'", cmd)
+    wrap_command_with_env(cmd, None)
+}
+
+/// Like `wrap_command_ignore_suspend`, first opening an env file for the
+/// runtime to read (see `open_env_file`).
+fn wrap_command_with_env(cmd: &str, env_file: Option<&std::path::Path>) -> String {
+    format!(
+        "bash -c 'stty susp undef; {}exec {}'",
+        open_env_file(env_file),
+        cmd
+    )
 }
 
 /// All supported coding tools.
@@ -971,6 +1456,14 @@ mod tests {
             yolo_mode: Some(true),
             extra_env_keys: None,
             extra_env_values: None,
+            network: None,
+            runtime: Default::default(),
+            build: None,
+            forwarded_ports: Vec::new(),
+            snapshot: None,
+            cpu_limit: None,
+            memory_limit: None,
+            isolated: false,
         });
         assert!(inst.is_yolo_mode());
 
@@ -1000,6 +1493,14 @@ mod tests {
             yolo_mode: None,
             extra_env_keys: None,
             extra_env_values: None,
+            network: None,
+            runtime: Default::default(),
+            build: None,
+            forwarded_ports: Vec::new(),
+            snapshot: None,
+            cpu_limit: None,
+            memory_limit: None,
+            isolated: false,
         });
         assert!(!inst.is_sandboxed());
     }
@@ -1016,6 +1517,14 @@ mod tests {
             yolo_mode: None,
             extra_env_keys: None,
             extra_env_values: None,
+            network: None,
+            runtime: Default::default(),
+            build: None,
+            forwarded_ports: Vec::new(),
+            snapshot: None,
+            cpu_limit: None,
+            memory_limit: None,
+            isolated: false,
         });
         assert!(inst.is_sandboxed());
     }
@@ -1117,6 +1626,7 @@ mod tests {
             managed_by_aoe: true,
             created_at: Utc::now(),
             cleanup_on_delete: true,
+            sync_conflicts: Vec::new(),
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1136,6 +1646,21 @@ mod tests {
         assert!(info.cleanup_on_delete);
     }
 
+    #[test]
+    fn test_worktree_info_sync_conflicts_roundtrip() {
+        let json = r#"{"branch":"test","main_repo_path":"/path","managed_by_aoe":true,"created_at":"2024-01-01T00:00:00Z"}"#;
+        let mut info: WorktreeInfo = serde_json::from_str(json).unwrap();
+        assert!(info.sync_conflicts.is_empty());
+        assert!(!serde_json::to_string(&info)
+            .unwrap()
+            .contains("sync_conflicts"));
+
+        info.sync_conflicts = vec!["src/main.rs".to_string()];
+        let json = serde_json::to_string(&info).unwrap();
+        let deserialized: WorktreeInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.sync_conflicts, vec!["src/main.rs".to_string()]);
+    }
+
     // Tests for SandboxInfo
     #[test]
     fn test_sandbox_info_serialization() {
@@ -1148,6 +1673,14 @@ mod tests {
             yolo_mode: Some(true),
             extra_env_keys: Some(vec!["MY_VAR".to_string(), "OTHER_VAR".to_string()]),
             extra_env_values: None,
+            network: None,
+            runtime: Default::default(),
+            build: None,
+            forwarded_ports: Vec::new(),
+            snapshot: None,
+            cpu_limit: None,
+            memory_limit: None,
+            isolated: false,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1161,6 +1694,23 @@ mod tests {
         assert_eq!(info.extra_env_keys, deserialized.extra_env_keys);
     }
 
+    #[test]
+    fn test_sandbox_info_runtime_serialization() {
+        let legacy = r#"{"enabled":true,"image":"img","container_name":"test"}"#;
+        let info: SandboxInfo = serde_json::from_str(legacy).unwrap();
+        assert_eq!(info.runtime, ContainerRuntime::Docker);
+        assert!(!serde_json::to_string(&info).unwrap().contains("runtime"));
+
+        let podman = SandboxInfo {
+            runtime: ContainerRuntime::Podman,
+            ..info
+        };
+        let json = serde_json::to_string(&podman).unwrap();
+        assert!(json.contains(r#""runtime":"podman""#));
+        let deserialized: SandboxInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.runtime, ContainerRuntime::Podman);
+    }
+
     #[test]
     fn test_sandbox_info_minimal_serialization() {
         // Required fields: enabled, image, container_name
@@ -1218,6 +1768,7 @@ mod tests {
             managed_by_aoe: true,
             created_at: Utc::now(),
             cleanup_on_delete: true,
+            sync_conflicts: Vec::new(),
         });
 
         let json = serde_json::to_string(&inst).unwrap();
@@ -1420,4 +1971,27 @@ mod tests {
             assert!(!working_dir.is_empty());
         }
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_isolated_excludes_follow_profile_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+
+        let profile = crate::session::ProfileConfig {
+            sandbox: Some(crate::session::SandboxConfigOverride {
+                volume_ignores: Some(vec!["target".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        crate::session::save_profile_config("work", &profile).unwrap();
+
+        let mut inst = Instance::new("test", "/tmp/test");
+        assert!(!inst.isolated_excludes().contains(&"target".to_string()));
+        inst.profile = "work".to_string();
+        assert!(inst.isolated_excludes().contains(&"target".to_string()));
+    }
 }
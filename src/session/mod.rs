@@ -3,15 +3,18 @@
 pub mod builder;
 pub mod civilizations;
 pub mod config;
+pub mod devcontainer;
 mod groups;
 mod instance;
 pub mod profile_config;
 pub mod repo_config;
+pub mod secrets;
 mod storage;
 
 pub use config::{
     get_claude_config_dir, get_update_settings, load_config, save_config, ClaudeConfig, Config,
-    DefaultTerminalMode, SandboxConfig, SessionConfig, ThemeConfig, TmuxMouseMode,
+    DefaultTerminalMode, KeyList, KeybindingsConfig, NetworkMode, SandboxConfig,
+    SandboxNetworkConfig, SessionConfig, SessionFilters, SessionSort, ThemeConfig, TmuxMouseMode,
     TmuxStatusBarMode, UpdatesConfig, WorktreeConfig,
 };
 pub use groups::{flatten_tree, Group, GroupTree, Item};
@@ -21,9 +24,10 @@ pub use instance::{
 };
 pub use profile_config::{
     load_profile_config, merge_configs, resolve_config, save_profile_config,
-    validate_check_interval, validate_memory_limit, validate_path_exists, validate_volume_format,
-    ClaudeConfigOverride, ProfileConfig, SandboxConfigOverride, SessionConfigOverride,
-    ThemeConfigOverride, TmuxConfigOverride, UpdatesConfigOverride, WorktreeConfigOverride,
+    validate_check_interval, validate_cpu_limit, validate_memory_limit, validate_path_exists,
+    validate_volume_format, ClaudeConfigOverride, ProfileConfig, SandboxConfigOverride,
+    SandboxNetworkConfigOverride, SessionConfigOverride, ThemeConfigOverride, TmuxConfigOverride,
+    UpdatesConfigOverride, WorktreeConfigOverride,
 };
 pub use repo_config::{
     check_hook_trust, execute_hooks, execute_hooks_in_container, load_repo_config,
@@ -8,7 +8,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 
-use super::config::{Config, DefaultTerminalMode, TmuxMouseMode, TmuxStatusBarMode};
+use crate::docker::ContainerRuntime;
+
+use super::config::{Config, DefaultTerminalMode, NetworkMode, TmuxMouseMode, TmuxStatusBarMode};
 use super::get_profile_dir;
 
 /// Profile-specific settings. All fields are Option<T> - None means "inherit from global"
@@ -82,6 +84,9 @@ pub struct WorktreeConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub delete_branch_on_cleanup: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -95,6 +100,15 @@ pub struct SandboxConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_image: Option<String>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_context: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<ContainerRuntime>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub extra_volumes: Option<Vec<String>>,
 
@@ -104,6 +118,9 @@ pub struct SandboxConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub environment_values: Option<HashMap<String, String>>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyring_command: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auto_cleanup: Option<bool>,
 
@@ -118,6 +135,38 @@ pub struct SandboxConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub volume_ignores: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<SandboxNetworkConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SandboxNetworkConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<NetworkMode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowlist: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_image: Option<String>,
+}
+
+impl SandboxNetworkConfigOverride {
+    pub fn apply_to(&self, network: &mut super::config::SandboxNetworkConfig) {
+        if let Some(mode) = self.mode {
+            network.mode = mode;
+        }
+        if let Some(ref allowlist) = self.allowlist {
+            network.allowlist = allowlist.clone();
+        }
+        if let Some(ref proxy_image) = self.proxy_image {
+            network.proxy_image = proxy_image.clone();
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -232,6 +281,9 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(delete_branch_on_cleanup) = worktree_override.delete_branch_on_cleanup {
             global.worktree.delete_branch_on_cleanup = delete_branch_on_cleanup;
         }
+        if let Some(ref pull_request_ref) = worktree_override.pull_request_ref {
+            global.worktree.pull_request_ref = pull_request_ref.clone();
+        }
     }
 
     // Sandbox
@@ -245,15 +297,30 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(ref default_image) = sandbox_override.default_image {
             global.sandbox.default_image = default_image.clone();
         }
+        if let Some(ref dockerfile) = sandbox_override.dockerfile {
+            global.sandbox.dockerfile = Some(dockerfile.clone());
+        }
+        if let Some(ref build_context) = sandbox_override.build_context {
+            global.sandbox.build_context = Some(build_context.clone());
+        }
         if let Some(ref extra_volumes) = sandbox_override.extra_volumes {
             global.sandbox.extra_volumes = extra_volumes.clone();
         }
+        if let Some(ref ports) = sandbox_override.ports {
+            global.sandbox.ports = ports.clone();
+        }
+        if let Some(runtime) = sandbox_override.runtime {
+            global.sandbox.runtime = runtime;
+        }
         if let Some(ref environment) = sandbox_override.environment {
             global.sandbox.environment = environment.clone();
         }
         if let Some(ref environment_values) = sandbox_override.environment_values {
             global.sandbox.environment_values = environment_values.clone();
         }
+        if let Some(ref keyring_command) = sandbox_override.keyring_command {
+            global.sandbox.keyring_command = Some(keyring_command.clone());
+        }
         if let Some(auto_cleanup) = sandbox_override.auto_cleanup {
             global.sandbox.auto_cleanup = auto_cleanup;
         }
@@ -269,6 +336,9 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(ref volume_ignores) = sandbox_override.volume_ignores {
             global.sandbox.volume_ignores = volume_ignores.clone();
         }
+        if let Some(ref network) = sandbox_override.network {
+            network.apply_to(&mut global.sandbox.network);
+        }
     }
 
     // Tmux
@@ -346,6 +416,18 @@ pub fn validate_memory_limit(limit: &str) -> Result<(), String> {
     }
 }
 
+/// Validate Docker CPU limit format (e.g., "2", "1.5")
+pub fn validate_cpu_limit(limit: &str) -> Result<(), String> {
+    if limit.is_empty() {
+        return Ok(());
+    }
+
+    match limit.parse::<f64>() {
+        Ok(cpus) if cpus > 0.0 && cpus.is_finite() => Ok(()),
+        _ => Err("CPU limit must be a positive number of CPUs (e.g. 2 or 1.5)".to_string()),
+    }
+}
+
 /// Validate check interval is positive
 pub fn validate_check_interval(hours: u64) -> Result<(), String> {
     if hours == 0 {
@@ -485,6 +567,16 @@ mod tests {
         assert!(validate_memory_limit("512mb").is_err());
     }
 
+    #[test]
+    fn test_validate_cpu_limit() {
+        assert!(validate_cpu_limit("").is_ok());
+        assert!(validate_cpu_limit("2").is_ok());
+        assert!(validate_cpu_limit("1.5").is_ok());
+        assert!(validate_cpu_limit("0").is_err());
+        assert!(validate_cpu_limit("-1").is_err());
+        assert!(validate_cpu_limit("two").is_err());
+    }
+
     #[test]
     fn test_validate_check_interval() {
         assert!(validate_check_interval(1).is_ok());
@@ -583,6 +675,27 @@ mod tests {
         assert!(merged.sandbox.enabled_by_default);
     }
 
+    #[test]
+    fn test_merge_configs_with_network_override() {
+        let mut global = Config::default();
+        global.sandbox.network.allowlist = vec!["github.com".to_string()];
+
+        let profile = ProfileConfig {
+            sandbox: Some(SandboxConfigOverride {
+                network: Some(SandboxNetworkConfigOverride {
+                    mode: Some(NetworkMode::Allowlist),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let merged = merge_configs(global, &profile);
+        assert_eq!(merged.sandbox.network.mode, NetworkMode::Allowlist);
+        assert_eq!(merged.sandbox.network.allowlist, vec!["github.com"]);
+    }
+
     #[test]
     fn test_volume_ignores_override_serialization() {
         let config = ProfileConfig {
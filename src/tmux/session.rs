@@ -3,10 +3,10 @@
 use anyhow::{bail, Result};
 use std::process::Command;
 
+use super::status_detection::Detection;
 use super::{refresh_session_cache, session_exists_from_cache, SESSION_PREFIX};
 use crate::cli::truncate_id;
 use crate::process;
-use crate::session::Status;
 
 pub struct Session {
     name: String,
@@ -106,6 +106,34 @@ impl Session {
         Ok(())
     }
 
+    /// Type `text` into the pane and press Enter, as if it was typed by hand.
+    pub fn send_text(&self, text: &str) -> Result<()> {
+        // Literal keys first so that words like "Enter" aren't taken as key names
+        self.send_key(text, true)?;
+        self.send_key("Enter", false)
+    }
+
+    /// Press a key in the pane. `key` is a tmux key name such as `Enter` or
+    /// `C-c`, or with `literal` set, text that is typed as is.
+    pub fn send_key(&self, key: &str, literal: bool) -> Result<()> {
+        if !self.exists() {
+            bail!("Session is not running");
+        }
+
+        let mut args = vec!["send-keys", "-t", &self.name];
+        if literal {
+            args.push("-l");
+        }
+        args.push(key);
+        let output = Command::new("tmux").args(&args).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to send keys to tmux session: {}", stderr);
+        }
+
+        Ok(())
+    }
+
     pub fn attach(&self) -> Result<()> {
         if !self.exists() {
             bail!("Session does not exist: {}", self.name);
@@ -183,7 +211,7 @@ impl Session {
         process::get_foreground_pid(pane_pid).or(Some(pane_pid))
     }
 
-    pub fn detect_status(&self, tool: &str) -> Result<Status> {
+    pub fn detect_status(&self, tool: &str) -> Result<Detection> {
         let content = self.capture_pane(50)?;
         let fg_pid = self.get_foreground_pid();
         Ok(super::status_detection::detect_status_from_content(
@@ -6,20 +6,325 @@ use super::utils::strip_ansi;
 
 const SPINNER_CHARS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
-pub fn detect_status_from_content(content: &str, tool: &str, _fg_pid: Option<u32>) -> Status {
+/// What status detection found in a pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    pub status: Status,
+    /// Why the agent is waiting; set when `status` is `Waiting`
+    pub waiting_reason: Option<WaitingReason>,
+}
+
+impl Detection {
+    fn running() -> Self {
+        Self {
+            status: Status::Running,
+            waiting_reason: None,
+        }
+    }
+
+    fn idle() -> Self {
+        Self {
+            status: Status::Idle,
+            waiting_reason: None,
+        }
+    }
+
+    fn waiting(reason: WaitingReason) -> Self {
+        Self {
+            status: Status::Waiting,
+            waiting_reason: Some(reason),
+        }
+    }
+}
+
+/// Why an agent is waiting for the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaitingReason {
+    /// Asking for permission to use a tool. `options` may be empty when the
+    /// prompt was recognized but its options couldn't be read.
+    Permission { options: Vec<PromptOption> },
+    /// A question with options that aren't approvals, e.g. a checklist
+    Menu,
+    /// A yes/no question answered by typing y or n
+    YesNo,
+    /// Ready for a new prompt
+    Input,
+}
+
+/// One-key answers offered for a waiting session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAnswer {
+    ApproveOnce,
+    ApproveAlways,
+    Deny,
+}
+
+/// An option of a permission prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptOption {
+    pub label: String,
+    /// What picking this option means, if recognized
+    pub answer: Option<QuickAnswer>,
+    /// tmux key names that pick this option
+    pub keys: Vec<String>,
+}
+
+impl WaitingReason {
+    /// Short description for the session's status
+    pub fn label(&self) -> &'static str {
+        match self {
+            WaitingReason::Permission { .. } => "permission",
+            WaitingReason::Menu => "choice",
+            WaitingReason::YesNo => "yes/no",
+            WaitingReason::Input => "input",
+        }
+    }
+
+    /// tmux key names that give `answer`, if the prompt offers it
+    pub fn answer_keys(&self, answer: QuickAnswer) -> Option<Vec<String>> {
+        match self {
+            WaitingReason::Permission { options } => options
+                .iter()
+                .find(|option| option.answer == Some(answer))
+                .map(|option| option.keys.clone()),
+            WaitingReason::YesNo => match answer {
+                QuickAnswer::ApproveOnce => Some(vec!["y".to_string(), "Enter".to_string()]),
+                QuickAnswer::Deny => Some(vec!["n".to_string(), "Enter".to_string()]),
+                QuickAnswer::ApproveAlways => None,
+            },
+            WaitingReason::Menu | WaitingReason::Input => None,
+        }
+    }
+}
+
+pub fn detect_status_from_content(content: &str, tool: &str, _fg_pid: Option<u32>) -> Detection {
     let content_lower = content.to_lowercase();
 
     match tool {
-        "claude" => detect_claude_status(content),
-        "opencode" => detect_opencode_status(&content_lower),
-        "vibe" => detect_vibe_status(&content_lower),
-        "codex" => detect_codex_status(&content_lower),
-        "gemini" => detect_gemini_status(&content_lower),
-        _ => detect_claude_status(content),
+        "claude" => detect_claude(content),
+        "opencode" => detect_opencode(&content_lower),
+        "vibe" => detect_vibe(&content_lower),
+        "codex" => detect_codex(&content_lower),
+        "gemini" => detect_gemini(&content_lower),
+        _ => detect_claude(content),
     }
 }
 
 pub fn detect_claude_status(content: &str) -> Status {
+    detect_claude(content).status
+}
+
+pub fn detect_opencode_status(content: &str) -> Status {
+    detect_opencode(content).status
+}
+
+pub fn detect_vibe_status(content: &str) -> Status {
+    detect_vibe(content).status
+}
+
+pub fn detect_codex_status(content: &str) -> Status {
+    detect_codex(content).status
+}
+
+pub fn detect_gemini_status(content: &str) -> Status {
+    detect_gemini(content).status
+}
+
+/// An option as listed in a prompt
+struct ListedOption {
+    label: String,
+    /// The prompt's cursor is on this option
+    selected: bool,
+}
+
+/// Text of a prompt line without box drawing, and whether one of `cursors`
+/// marks it as selected
+fn strip_cursor(line: &str, cursors: &[&str]) -> (String, bool) {
+    let clean = strip_ansi(line);
+    let text = clean.trim().trim_start_matches(['│', '┃']).trim();
+    for cursor in cursors {
+        if let Some(rest) = text.strip_prefix(cursor) {
+            return (rest.trim_start().to_string(), true);
+        }
+    }
+    (text.to_string(), false)
+}
+
+/// Number and label of an option line like "2. Yes, allow always"
+fn numbered_label(text: &str) -> Option<(usize, &str)> {
+    let (number, label) = text.split_once(". ")?;
+    if number.is_empty() || number.len() > 2 || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((number.parse().ok()?, label.trim()))
+}
+
+/// Options of the last prompt in `lines`: numbered options ("❯ 1. Yes"), or
+/// else the run of answer-like lines around the cursor
+fn listed_options(lines: &[&str], cursors: &[&str]) -> Vec<ListedOption> {
+    let parsed: Vec<(String, bool)> = lines.iter().map(|l| strip_cursor(l, cursors)).collect();
+
+    let mut numbered: Vec<ListedOption> = Vec::new();
+    for (text, selected) in &parsed {
+        if let Some((number, label)) = numbered_label(text) {
+            if number == 1 {
+                numbered.clear();
+            }
+            if number == numbered.len() + 1 {
+                numbered.push(ListedOption {
+                    label: label.to_string(),
+                    selected: *selected,
+                });
+            }
+        }
+    }
+    if !numbered.is_empty() {
+        return numbered;
+    }
+
+    let Some(cursor) = parsed.iter().rposition(|(_, selected)| *selected) else {
+        return Vec::new();
+    };
+    let is_option = |i: usize| classify_option(&parsed[i].0).is_some();
+    if !is_option(cursor) {
+        return Vec::new();
+    }
+    let start = (0..cursor)
+        .rev()
+        .take_while(|&i| is_option(i))
+        .last()
+        .unwrap_or(cursor);
+    let end = (cursor + 1..parsed.len())
+        .take_while(|&i| is_option(i))
+        .last()
+        .unwrap_or(cursor);
+    parsed[start..=end]
+        .iter()
+        .map(|(label, selected)| ListedOption {
+            label: label.clone(),
+            selected: *selected,
+        })
+        .collect()
+}
+
+/// What an option's label means, e.g. "Yes, and don't ask again" is an
+/// approval that applies from now on
+fn classify_option(label: &str) -> Option<QuickAnswer> {
+    let label = label.to_lowercase();
+    if ["no", "reject", "deny"]
+        .iter()
+        .any(|p| label.starts_with(p))
+    {
+        Some(QuickAnswer::Deny)
+    } else if label.contains("always") || label.contains("don't ask again") {
+        Some(QuickAnswer::ApproveAlways)
+    } else if ["yes", "allow", "approve", "proceed"]
+        .iter()
+        .any(|p| label.starts_with(p))
+    {
+        Some(QuickAnswer::ApproveOnce)
+    } else {
+        None
+    }
+}
+
+/// Options picked by typing their number, as in Claude Code
+fn options_by_number(options: Vec<ListedOption>) -> Vec<PromptOption> {
+    options
+        .into_iter()
+        .enumerate()
+        .map(|(i, option)| PromptOption {
+            answer: classify_option(&option.label),
+            label: option.label,
+            keys: vec![(i + 1).to_string()],
+        })
+        .collect()
+}
+
+/// Options picked by moving the cursor to them and pressing Enter. Without a
+/// visible cursor the first option is taken to be selected.
+fn options_by_cursor(options: Vec<ListedOption>, back: &str, forward: &str) -> Vec<PromptOption> {
+    let current = options.iter().position(|o| o.selected).unwrap_or(0);
+    options
+        .into_iter()
+        .enumerate()
+        .map(|(i, option)| {
+            let (key, count) = if i < current {
+                (back, current - i)
+            } else {
+                (forward, i - current)
+            };
+            let mut keys = vec![key.to_string(); count];
+            keys.push("Enter".to_string());
+            PromptOption {
+                answer: classify_option(&option.label),
+                label: option.label,
+                keys,
+            }
+        })
+        .collect()
+}
+
+/// A permission prompt if any option is an approval or denial, else a menu
+fn prompt_reason(options: Vec<PromptOption>) -> WaitingReason {
+    if options.iter().any(|o| o.answer.is_some()) {
+        WaitingReason::Permission { options }
+    } else {
+        WaitingReason::Menu
+    }
+}
+
+/// Claude Code prompts are answered by number; Esc cancels a tool request
+fn claude_prompt_reason(lines: &[&str], can_cancel: bool) -> WaitingReason {
+    let mut options = options_by_number(listed_options(lines, &["❯"]));
+    let approves = options.iter().any(|o| {
+        matches!(
+            o.answer,
+            Some(QuickAnswer::ApproveOnce | QuickAnswer::ApproveAlways)
+        )
+    });
+    let denies = options.iter().any(|o| o.answer == Some(QuickAnswer::Deny));
+    if approves && !denies && can_cancel {
+        options.push(PromptOption {
+            label: "Esc".to_string(),
+            answer: Some(QuickAnswer::Deny),
+            keys: vec!["Escape".to_string()],
+        });
+    }
+    prompt_reason(options)
+}
+
+/// Prompts of tools whose options are picked with the arrow keys
+fn vertical_prompt_reason(lines: &[&str], cursors: &[&str]) -> WaitingReason {
+    match options_by_cursor(listed_options(lines, cursors), "Up", "Down") {
+        options if options.is_empty() => WaitingReason::Permission { options },
+        options => prompt_reason(options),
+    }
+}
+
+/// OpenCode lists its permission options side by side:
+/// "Allow once   Allow always   Reject      ⇆ select  enter confirm"
+fn opencode_prompt_reason(lines: &[&str]) -> WaitingReason {
+    let buttons = lines.iter().rev().find_map(|line| {
+        let (text, _) = strip_cursor(line, &[]);
+        let lower = text.to_lowercase();
+        (lower.contains("allow once") && lower.contains("reject")).then(|| {
+            text.split("  ")
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .take_while(|t| !t.contains('⇆') && !t.contains("select"))
+                .map(|label| ListedOption {
+                    label: label.to_string(),
+                    selected: false,
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let options = options_by_cursor(buttons.unwrap_or_default(), "Left", "Right");
+    WaitingReason::Permission { options }
+}
+
+fn detect_claude(content: &str) -> Detection {
     let lines: Vec<&str> = content.lines().collect();
     let non_empty_lines: Vec<&str> = lines
         .iter()
@@ -36,23 +341,25 @@ pub fn detect_claude_status(content: &str) -> Status {
         .collect::<Vec<&str>>()
         .join("\n");
     let last_lines_lower = last_lines.to_lowercase();
+    let prompt_lines: Vec<&str> = last_lines.lines().collect();
+    let can_cancel = last_lines_lower.contains("esc to cancel");
 
     if last_lines_lower.contains("esc to interrupt")
         || last_lines_lower.contains("ctrl+c to interrupt")
     {
-        return Status::Running;
+        return Detection::running();
     }
 
     for line in &lines {
         for spinner in SPINNER_CHARS {
             if line.contains(spinner) {
-                return Status::Running;
+                return Detection::running();
             }
         }
     }
 
-    if last_lines_lower.contains("enter to select") || last_lines_lower.contains("esc to cancel") {
-        return Status::Waiting;
+    if last_lines_lower.contains("enter to select") || can_cancel {
+        return Detection::waiting(claude_prompt_reason(&prompt_lines, can_cancel));
     }
 
     let permission_prompts = [
@@ -66,7 +373,12 @@ pub fn detect_claude_status(content: &str) -> Status {
     ];
     for prompt in &permission_prompts {
         if last_lines.contains(prompt) {
-            return Status::Waiting;
+            return Detection::waiting(match claude_prompt_reason(&prompt_lines, can_cancel) {
+                WaitingReason::Menu => WaitingReason::Permission {
+                    options: Vec::new(),
+                },
+                reason => reason,
+            });
         }
     }
 
@@ -75,7 +387,7 @@ pub fn detect_claude_status(content: &str) -> Status {
         if trimmed.starts_with("❯") && trimmed.len() > 2 {
             let rest = &trimmed[3..].trim_start();
             if rest.starts_with("1.") || rest.starts_with("2.") || rest.starts_with("3.") {
-                return Status::Waiting;
+                return Detection::waiting(claude_prompt_reason(&prompt_lines, can_cancel));
             }
         }
     }
@@ -83,13 +395,13 @@ pub fn detect_claude_status(content: &str) -> Status {
     for line in non_empty_lines.iter().rev().take(10) {
         let clean_line = strip_ansi(line).trim().to_string();
         if clean_line == ">" || clean_line == "> " {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
         if clean_line.starts_with("> ")
             && !clean_line.to_lowercase().contains("esc")
             && clean_line.len() < 100
         {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
     }
 
@@ -98,14 +410,14 @@ pub fn detect_claude_status(content: &str) -> Status {
     let question_prompts = ["(Y/n)", "(y/N)", "[Y/n]", "[y/N]"];
     for prompt in &question_prompts {
         if last_lines.contains(prompt) {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::YesNo);
         }
     }
 
-    Status::Idle
+    Detection::idle()
 }
 
-pub fn detect_opencode_status(content: &str) -> Status {
+fn detect_opencode(content: &str) -> Detection {
     let lines: Vec<&str> = content.lines().collect();
     let non_empty_lines: Vec<&str> = lines
         .iter()
@@ -123,17 +435,18 @@ pub fn detect_opencode_status(content: &str) -> Status {
         .collect::<Vec<&str>>()
         .join("\n");
     let last_lines_lower = last_lines.to_lowercase();
+    let prompt_lines: Vec<&str> = last_lines.lines().collect();
 
     // RUNNING: OpenCode shows "esc to interrupt" when busy (same as Claude Code)
     // Only check in last lines to avoid matching comments/code in terminal output
     if last_lines_lower.contains("esc to interrupt") || last_lines_lower.contains("esc interrupt") {
-        return Status::Running;
+        return Detection::running();
     }
 
     for line in &lines {
         for spinner in SPINNER_CHARS {
             if line.contains(spinner) {
-                return Status::Running;
+                return Detection::running();
             }
         }
     }
@@ -141,7 +454,7 @@ pub fn detect_opencode_status(content: &str) -> Status {
     // WAITING: Selection menus (shows "Enter to select" or "Esc to cancel")
     // Only check in last lines to avoid matching comments/code
     if last_lines_lower.contains("enter to select") || last_lines_lower.contains("esc to cancel") {
-        return Status::Waiting;
+        return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["❯"]));
     }
 
     // WAITING: Permission/confirmation prompts
@@ -156,7 +469,10 @@ pub fn detect_opencode_status(content: &str) -> Status {
     ];
     for prompt in &permission_prompts {
         if last_lines_lower.contains(prompt) {
-            return Status::Waiting;
+            return Detection::waiting(match *prompt {
+                "(y/n)" | "[y/n]" => WaitingReason::YesNo,
+                _ => opencode_prompt_reason(&prompt_lines),
+            });
         }
     }
 
@@ -168,27 +484,27 @@ pub fn detect_opencode_status(content: &str) -> Status {
                 || after_cursor.starts_with("2.")
                 || after_cursor.starts_with("3.")
             {
-                return Status::Waiting;
+                return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["❯"]));
             }
         }
     }
     if lines.iter().any(|line| {
         line.contains("❯") && (line.contains(" 1.") || line.contains(" 2.") || line.contains(" 3."))
     }) {
-        return Status::Waiting;
+        return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["❯"]));
     }
 
     for line in non_empty_lines.iter().rev().take(10) {
         let clean_line = strip_ansi(line).trim().to_string();
 
         if clean_line == ">" || clean_line == "> " || clean_line == ">>" {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
         if clean_line.starts_with("> ")
             && !clean_line.to_lowercase().contains("esc")
             && clean_line.len() < 100
         {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
     }
 
@@ -212,15 +528,15 @@ pub fn detect_opencode_status(content: &str) -> Status {
         for line in non_empty_lines.iter().rev().take(10) {
             let clean = strip_ansi(line).trim().to_string();
             if clean == ">" || clean == "> " || clean == ">>" {
-                return Status::Waiting;
+                return Detection::waiting(WaitingReason::Input);
             }
         }
     }
 
-    Status::Idle
+    Detection::idle()
 }
 
-pub fn detect_vibe_status(content: &str) -> Status {
+fn detect_vibe(content: &str) -> Detection {
     let lines: Vec<&str> = content.lines().collect();
     let non_empty_lines: Vec<&str> = lines
         .iter()
@@ -237,6 +553,7 @@ pub fn detect_vibe_status(content: &str) -> Status {
         .collect::<Vec<&str>>()
         .join("\n");
     let last_lines_lower = last_lines.to_lowercase();
+    let prompt_lines: Vec<&str> = last_lines.lines().collect();
 
     // Vibe uses Textual TUI which can render text vertically (one char per line).
     // Join recent single-char lines to reconstruct words for detection.
@@ -258,12 +575,12 @@ pub fn detect_vibe_status(content: &str) -> Status {
         || last_lines_lower.contains("enter select")
         || last_lines_lower.contains("esc reject")
     {
-        return Status::Waiting;
+        return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["›"]));
     }
 
     // WAITING: Tool approval warning (shows "⚠ {tool_name} command")
     if last_lines.contains("⚠") && last_lines_lower.contains("command") {
-        return Status::Waiting;
+        return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["›"]));
     }
 
     // WAITING: Approval options shown by Vibe
@@ -276,7 +593,7 @@ pub fn detect_vibe_status(content: &str) -> Status {
     ];
     for option in &approval_options {
         if last_lines_lower.contains(option) {
-            return Status::Waiting;
+            return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["›"]));
         }
     }
 
@@ -284,7 +601,7 @@ pub fn detect_vibe_status(content: &str) -> Status {
     for line in &lines {
         let trimmed = line.trim();
         if trimmed.starts_with("›") && trimmed.len() > 2 {
-            return Status::Waiting;
+            return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["›"]));
         }
     }
 
@@ -292,7 +609,7 @@ pub fn detect_vibe_status(content: &str) -> Status {
     // Vibe renders vertically so spinner may be on its own line
     for spinner in SPINNER_CHARS {
         if recent_text.contains(spinner) {
-            return Status::Running;
+            return Detection::running();
         }
     }
 
@@ -308,19 +625,19 @@ pub fn detect_vibe_status(content: &str) -> Status {
     ];
     for indicator in &activity_indicators {
         if recent_text_lower.contains(indicator) {
-            return Status::Running;
+            return Detection::running();
         }
     }
 
     // RUNNING: Ellipsis at end often indicates ongoing activity
     if recent_text.ends_with("…") || recent_text.ends_with("...") {
-        return Status::Running;
+        return Detection::running();
     }
 
-    Status::Idle
+    Detection::idle()
 }
 
-pub fn detect_codex_status(content: &str) -> Status {
+fn detect_codex(content: &str) -> Detection {
     let lines: Vec<&str> = content.lines().collect();
     let non_empty_lines: Vec<&str> = lines
         .iter()
@@ -337,6 +654,7 @@ pub fn detect_codex_status(content: &str) -> Status {
         .collect::<Vec<&str>>()
         .join("\n");
     let last_lines_lower = last_lines.to_lowercase();
+    let prompt_lines: Vec<&str> = last_lines.lines().collect();
 
     // RUNNING: Codex shows "esc to interrupt" or similar when processing
     if last_lines_lower.contains("esc to interrupt")
@@ -344,13 +662,13 @@ pub fn detect_codex_status(content: &str) -> Status {
         || last_lines_lower.contains("working")
         || last_lines_lower.contains("thinking")
     {
-        return Status::Running;
+        return Detection::running();
     }
 
     for line in &lines {
         for spinner in SPINNER_CHARS {
             if line.contains(spinner) {
-                return Status::Running;
+                return Detection::running();
             }
         }
     }
@@ -368,13 +686,16 @@ pub fn detect_codex_status(content: &str) -> Status {
     ];
     for prompt in &approval_prompts {
         if last_lines_lower.contains(prompt) {
-            return Status::Waiting;
+            return Detection::waiting(match *prompt {
+                "(y/n)" | "[y/n]" => WaitingReason::YesNo,
+                _ => vertical_prompt_reason(&prompt_lines, &["›", "❯"]),
+            });
         }
     }
 
     // WAITING: Selection menus
     if last_lines_lower.contains("enter to select") || last_lines_lower.contains("esc to cancel") {
-        return Status::Waiting;
+        return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["›", "❯"]));
     }
 
     // WAITING: Numbered selection
@@ -386,7 +707,7 @@ pub fn detect_codex_status(content: &str) -> Status {
                 || after_cursor.starts_with("2.")
                 || after_cursor.starts_with("3.")
             {
-                return Status::Waiting;
+                return Detection::waiting(vertical_prompt_reason(&prompt_lines, &["›", "❯"]));
             }
         }
     }
@@ -395,20 +716,20 @@ pub fn detect_codex_status(content: &str) -> Status {
     for line in non_empty_lines.iter().rev().take(10) {
         let clean_line = strip_ansi(line).trim().to_string();
         if clean_line == ">" || clean_line == "> " || clean_line == "codex>" {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
         if clean_line.starts_with("> ")
             && !clean_line.to_lowercase().contains("esc")
             && clean_line.len() < 100
         {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
     }
 
-    Status::Idle
+    Detection::idle()
 }
 
-pub fn detect_gemini_status(content: &str) -> Status {
+fn detect_gemini(content: &str) -> Detection {
     let lines: Vec<&str> = content.lines().collect();
     let non_empty_lines: Vec<&str> = lines
         .iter()
@@ -425,18 +746,19 @@ pub fn detect_gemini_status(content: &str) -> Status {
         .collect::<Vec<&str>>()
         .join("\n");
     let last_lines_lower = last_lines.to_lowercase();
+    let prompt_lines: Vec<&str> = last_lines.lines().collect();
 
     // RUNNING: Gemini shows activity indicators
     if last_lines_lower.contains("esc to interrupt")
         || last_lines_lower.contains("ctrl+c to interrupt")
     {
-        return Status::Running;
+        return Detection::running();
     }
 
     for line in &lines {
         for spinner in SPINNER_CHARS {
             if line.contains(spinner) {
-                return Status::Running;
+                return Detection::running();
             }
         }
     }
@@ -453,7 +775,10 @@ pub fn detect_gemini_status(content: &str) -> Status {
     ];
     for prompt in &approval_prompts {
         if last_lines_lower.contains(prompt) {
-            return Status::Waiting;
+            return Detection::waiting(match *prompt {
+                "(y/n)" | "[y/n]" => WaitingReason::YesNo,
+                _ => vertical_prompt_reason(&prompt_lines, &["●", "❯", "›"]),
+            });
         }
     }
 
@@ -461,11 +786,11 @@ pub fn detect_gemini_status(content: &str) -> Status {
     for line in non_empty_lines.iter().rev().take(10) {
         let clean_line = strip_ansi(line).trim().to_string();
         if clean_line == ">" || clean_line == "> " {
-            return Status::Waiting;
+            return Detection::waiting(WaitingReason::Input);
         }
     }
 
-    Status::Idle
+    Detection::idle()
 }
 
 #[cfg(test)]
@@ -561,8 +886,8 @@ mod tests {
     #[test]
     fn test_detect_status_from_content_falls_back_to_claude() {
         let content = "Processing ⠋";
-        let status = detect_status_from_content(content, "unknown_tool", None);
-        assert_eq!(status, Status::Running);
+        let detection = detect_status_from_content(content, "unknown_tool", None);
+        assert_eq!(detection.status, Status::Running);
     }
 
     #[test]
@@ -737,4 +1062,85 @@ mod tests {
         assert_eq!(detect_gemini_status("file saved"), Status::Idle);
         assert_eq!(detect_gemini_status("random output text"), Status::Idle);
     }
+
+    fn answer_keys(detection: &Detection, answer: QuickAnswer) -> Option<Vec<String>> {
+        detection.waiting_reason.as_ref()?.answer_keys(answer)
+    }
+
+    #[test]
+    fn test_claude_permission_prompt_answers_by_number() {
+        let content = "Do you want to proceed?\n\
+                       ❯ 1. Yes\n\
+                       \x20 2. Yes, and don't ask again for this command\n\
+                       \x20 3. No, and tell Claude what to do differently (esc)";
+        let detection = detect_claude(content);
+        assert_eq!(detection.status, Status::Waiting);
+        let keys = |answer| answer_keys(&detection, answer);
+        assert_eq!(keys(QuickAnswer::ApproveOnce), Some(vec!["1".to_string()]));
+        assert_eq!(
+            keys(QuickAnswer::ApproveAlways),
+            Some(vec!["2".to_string()])
+        );
+        assert_eq!(keys(QuickAnswer::Deny), Some(vec!["3".to_string()]));
+    }
+
+    #[test]
+    fn test_claude_permission_prompt_denies_with_esc() {
+        let content =
+            "Do you want to proceed?\n❯ 1. Yes\n  2. Type here to tell Claude\n\nEsc to cancel";
+        let detection = detect_claude(content);
+        assert_eq!(
+            answer_keys(&detection, QuickAnswer::Deny),
+            Some(vec!["Escape".to_string()])
+        );
+        assert_eq!(answer_keys(&detection, QuickAnswer::ApproveAlways), None);
+    }
+
+    #[test]
+    fn test_claude_waiting_reasons() {
+        assert_eq!(
+            detect_claude("❯ 1. Planned activities\n  2. Spontaneous").waiting_reason,
+            Some(WaitingReason::Menu)
+        );
+        assert_eq!(
+            detect_claude("Continue? (Y/n)").waiting_reason,
+            Some(WaitingReason::YesNo)
+        );
+        assert_eq!(
+            detect_claude("Done!\n> ").waiting_reason,
+            Some(WaitingReason::Input)
+        );
+        assert_eq!(
+            detect_claude("Allow once").waiting_reason,
+            Some(WaitingReason::Permission {
+                options: Vec::new()
+            })
+        );
+        assert_eq!(detect_claude("Processing ⠋").waiting_reason, None);
+    }
+
+    #[test]
+    fn test_yes_no_answers_type_y_or_n() {
+        let detection = detect_codex("run this command? (y/n)");
+        assert_eq!(
+            answer_keys(&detection, QuickAnswer::ApproveOnce),
+            Some(vec!["y".to_string(), "Enter".to_string()])
+        );
+        assert_eq!(
+            answer_keys(&detection, QuickAnswer::Deny),
+            Some(vec!["n".to_string(), "Enter".to_string()])
+        );
+        assert_eq!(answer_keys(&detection, QuickAnswer::ApproveAlways), None);
+    }
+
+    #[test]
+    fn test_vibe_options_are_picked_with_the_cursor() {
+        let detection = detect_vibe(
+            "⚠ bash command\n  yes\n› yes and always allow bash for this session\n  no and tell the agent",
+        );
+        let keys = |answer| answer_keys(&detection, answer).unwrap();
+        assert_eq!(keys(QuickAnswer::ApproveOnce), vec!["Up", "Enter"]);
+        assert_eq!(keys(QuickAnswer::ApproveAlways), vec!["Enter"]);
+        assert_eq!(keys(QuickAnswer::Deny), vec!["Down", "Enter"]);
+    }
 }
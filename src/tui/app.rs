@@ -8,7 +8,7 @@ use std::time::Duration;
 
 use super::home::{HomeView, TerminalMode};
 use super::styles::Theme;
-use crate::session::{get_update_settings, load_config, save_config, Storage};
+use crate::session::{get_update_settings, load_config, resolve_config, save_config, Storage};
 use crate::tmux::AvailableTools;
 use crate::update::{check_for_update, UpdateInfo};
 
@@ -40,7 +40,7 @@ impl App {
     pub fn new(profile: &str, available_tools: AvailableTools) -> Result<Self> {
         let storage = Storage::new(profile)?;
         let mut home = HomeView::new(storage, available_tools)?;
-        let theme = Theme::default();
+        let theme = load_theme(profile);
 
         // Check if we need to show welcome or changelog dialogs
         let mut config = load_config()?.unwrap_or_default();
@@ -92,8 +92,15 @@ impl App {
 
         let mut last_status_refresh = std::time::Instant::now();
         let mut last_disk_refresh = std::time::Instant::now();
+        let mut last_git_status_refresh: Option<std::time::Instant> = None;
+        let mut last_stats_refresh: Option<std::time::Instant> = None;
+        let mut last_grid_refresh = std::time::Instant::now();
         const STATUS_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
         const DISK_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+        const GIT_STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+        const STATS_REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+        const GRID_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+        const INLINE_INPUT_REFRESH_INTERVAL: Duration = Duration::from_millis(100);
 
         loop {
             // Force full redraw if needed (e.g., after returning from tmux)
@@ -147,6 +154,53 @@ impl App {
                 refresh_needed = true;
             }
 
+            // Git summaries are expensive, so refresh them less often (non-blocking)
+            if last_git_status_refresh.map_or(true, |t| t.elapsed() >= GIT_STATUS_REFRESH_INTERVAL)
+            {
+                self.home.request_git_status_refresh();
+                last_git_status_refresh = Some(std::time::Instant::now());
+            }
+
+            if self.home.apply_git_status_updates() {
+                refresh_needed = true;
+            }
+
+            // Container stats take a second to sample, so they run in the background too
+            if last_stats_refresh.map_or(true, |t| t.elapsed() >= STATS_REFRESH_INTERVAL) {
+                self.home.request_stats_refresh();
+                last_stats_refresh = Some(std::time::Instant::now());
+            }
+
+            if self.home.apply_stats_updates() {
+                refresh_needed = true;
+            }
+
+            // Tiles of the grid view are captured in the background (non-blocking),
+            // more often while typing into one of them
+            let grid_refresh_interval = if self.home.is_inline_input() {
+                INLINE_INPUT_REFRESH_INTERVAL
+            } else {
+                GRID_REFRESH_INTERVAL
+            };
+            if last_grid_refresh.elapsed() >= grid_refresh_interval {
+                self.home.request_grid_refresh();
+                last_grid_refresh = std::time::Instant::now();
+            }
+
+            if self.home.apply_grid_updates() {
+                refresh_needed = true;
+            }
+
+            // Check for and apply bulk action results (non-blocking)
+            if self.home.apply_bulk_results() {
+                refresh_needed = true;
+            }
+
+            // Check for and apply snapshot results (non-blocking)
+            if self.home.apply_container_results() {
+                refresh_needed = true;
+            }
+
             // Check for and apply deletion results (non-blocking)
             if self.home.apply_deletion_results() {
                 refresh_needed = true;
@@ -159,6 +213,11 @@ impl App {
                 refresh_needed = true;
             }
 
+            // Keep the preview of the session being typed into live
+            if self.home.is_inline_input() {
+                refresh_needed = true;
+            }
+
             // Tick the dialog spinner if loading
             if self.home.is_creation_pending() {
                 self.home.tick_dialog();
@@ -186,8 +245,14 @@ impl App {
     }
 
     fn render(&mut self, frame: &mut Frame) {
+        // The settings theme picker previews its selection across the whole UI
+        let theme = self
+            .home
+            .preview_theme()
+            .cloned()
+            .unwrap_or_else(|| self.theme.clone());
         self.home
-            .render(frame, frame.area(), &self.theme, self.update_info.as_ref());
+            .render(frame, frame.area(), &theme, self.update_info.as_ref());
     }
 
     /// Poll for update check result (non-blocking).
@@ -201,6 +266,14 @@ impl App {
     }
 }
 
+/// Theme configured for a profile, or the default if it can't be loaded.
+fn load_theme(profile: &str) -> Theme {
+    let name = resolve_config(profile)
+        .map(|config| config.theme.name)
+        .unwrap_or_default();
+    Theme::load_or_default(&name)
+}
+
 /// Polls the update receiver and returns the new state.
 /// Returns (update_info, update_rx, was_update_received).
 fn poll_update_receiver(
@@ -238,18 +311,21 @@ impl App {
         terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     ) -> Result<()> {
         // Global keybindings
-        match (key.code, key.modifiers) {
-            (KeyCode::Char('c'), KeyModifiers::CONTROL) | (KeyCode::Char('q'), _) => {
-                if !self.home.has_dialog() {
-                    self.should_quit = true;
-                    return Ok(());
-                }
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key.code, key.modifiers) {
+            if !self.home.has_dialog() {
+                self.should_quit = true;
+                return Ok(());
             }
-            _ => {}
         }
 
         // Delegate to home view
-        if let Some(action) = self.home.handle_key(key) {
+        let settings_open = self.home.is_settings_open();
+        let action = self.home.handle_key(key);
+        if settings_open && !self.home.is_settings_open() {
+            // Pick up a theme saved (or previewed and discarded) in settings
+            self.theme = load_theme(self.home.profile());
+        }
+        if let Some(action) = action {
             match action {
                 Action::Quit => self.should_quit = true,
                 Action::AttachSession(id) => {
@@ -262,6 +338,7 @@ impl App {
                     let storage = Storage::new(&profile)?;
                     let tools = self.home.available_tools();
                     self.home = HomeView::new(storage, tools)?;
+                    self.theme = load_theme(&profile);
                 }
                 Action::EditFile(path) => {
                     self.edit_file(&path, terminal)?;
@@ -291,6 +368,7 @@ impl App {
                     let storage = Storage::new(&profile)?;
                     let tools = self.home.available_tools();
                     self.home = HomeView::new(storage, tools)?;
+                    self.theme = load_theme(&profile);
                 }
                 Action::EditFile(path) => {
                     self.edit_file(&path, terminal)?;
@@ -0,0 +1,176 @@
+//! Background worker for actions applied to several marked sessions at once
+
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::git::sync::{self, LandOutcome};
+use crate::session::Instance;
+
+/// Work done per session by the bulk worker. Deleting goes through the
+/// deletion poller and moving sessions is done in place by the home view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkOperation {
+    Stop,
+    Restart,
+    SendPrompt(String),
+    /// Fast-forward the base branch to the session's worktree branch
+    Land,
+}
+
+pub struct BulkRequest {
+    pub instance: Instance,
+    pub operation: BulkOperation,
+    /// Profile the session belongs to, for resolving the base branch
+    pub profile: String,
+}
+
+#[derive(Debug)]
+pub struct BulkResult {
+    pub session_id: String,
+    /// What happened, or why it failed
+    pub outcome: Result<String, String>,
+    /// The session after a restart, to write back into the list
+    pub instance: Option<Instance>,
+}
+
+pub struct BulkPoller {
+    request_tx: mpsc::Sender<BulkRequest>,
+    result_rx: mpsc::Receiver<BulkResult>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl BulkPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<BulkRequest>();
+        let (result_tx, result_rx) = mpsc::channel::<BulkResult>();
+
+        let handle = thread::spawn(move || {
+            while let Ok(request) = request_rx.recv() {
+                if result_tx.send(Self::perform(request)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn perform(request: BulkRequest) -> BulkResult {
+        let BulkRequest {
+            mut instance,
+            operation,
+            profile,
+        } = request;
+        let session_id = instance.id.clone();
+
+        let outcome = match operation {
+            BulkOperation::Stop => match instance.tmux_session() {
+                Ok(session) if !session.exists() => Ok("not running".to_string()),
+                Ok(session) => session
+                    .kill()
+                    .map(|_| "stopped".to_string())
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+            BulkOperation::Restart => {
+                let result = instance
+                    .restart_with_size(crate::terminal::get_size())
+                    .map(|_| "restarted".to_string())
+                    .map_err(|e| e.to_string());
+                return BulkResult {
+                    session_id,
+                    outcome: result,
+                    instance: Some(instance),
+                };
+            }
+            BulkOperation::SendPrompt(prompt) => instance
+                .send_prompt(&prompt)
+                .map(|_| "prompt sent".to_string())
+                .map_err(|e| e.to_string()),
+            BulkOperation::Land => Self::land(&instance, &profile),
+        };
+
+        BulkResult {
+            session_id,
+            outcome,
+            instance: None,
+        }
+    }
+
+    fn land(instance: &Instance, profile: &str) -> Result<String, String> {
+        let Some(wt_info) = &instance.worktree_info else {
+            return Err("not a worktree session".to_string());
+        };
+        let repo_path = Path::new(&wt_info.main_repo_path);
+        let base = crate::session::resolve_config_with_repo(profile, repo_path)
+            .unwrap_or_default()
+            .diff
+            .base_branch_for(repo_path);
+
+        match sync::land_branch(repo_path, &wt_info.branch, &base) {
+            Ok(LandOutcome::AlreadyLanded) => Ok(format!("already in {}", base)),
+            Ok(LandOutcome::Landed { commits }) => Ok(format!(
+                "landed {} commit{} on {}",
+                commits,
+                if commits == 1 { "" } else { "s" },
+                base
+            )),
+            Ok(LandOutcome::Diverged { behind }) => Err(format!(
+                "{} is {} commit{} ahead, sync first",
+                base,
+                behind,
+                if behind == 1 { "" } else { "s" }
+            )),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn request(&self, request: BulkRequest) {
+        let _ = self.request_tx.send(request);
+    }
+
+    pub fn try_recv_result(&self) -> Option<BulkResult> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for BulkPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_land_requires_worktree() {
+        let instance = Instance::new("Test Session", "/tmp/test-project");
+        let result = BulkPoller::perform(BulkRequest {
+            instance,
+            operation: BulkOperation::Land,
+            profile: "default".to_string(),
+        });
+        assert_eq!(result.outcome, Err("not a worktree session".to_string()));
+        assert!(result.instance.is_none());
+    }
+
+    #[test]
+    fn test_stop_session_that_is_not_running() {
+        let instance = Instance::new("Not Running", "/tmp/test-project");
+        let session_id = instance.id.clone();
+        let result = BulkPoller::perform(BulkRequest {
+            instance,
+            operation: BulkOperation::Stop,
+            profile: "default".to_string(),
+        });
+        assert_eq!(result.session_id, session_id);
+        assert_eq!(result.outcome, Ok("not running".to_string()));
+    }
+}
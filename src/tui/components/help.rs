@@ -1,86 +1,122 @@
 //! Help overlay component
+//!
+//! Lists the active key bindings of a view, so the help always matches what
+//! the keys do. Sections flow into more columns on short terminals.
 
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
 use crate::tui::styles::Theme;
 
-const DIALOG_WIDTH: u16 = 50;
-const DIALOG_HEIGHT: u16 = 30;
-#[cfg(test)]
-const BORDER_HEIGHT: u16 = 2;
-#[cfg(test)]
-const BORDER_WIDTH: u16 = 2;
-#[cfg(test)]
-const KEY_COLUMN_WIDTH: usize = 12; // 2 spaces indent + 10 chars for key
-
-fn shortcuts() -> Vec<(&'static str, Vec<(&'static str, &'static str)>)> {
-    vec![
-        (
-            "Navigation",
-            vec![
-                ("j/↓", "Move down"),
-                ("k/↑", "Move up"),
-                ("h/←", "Collapse group"),
-                ("l/→", "Expand group"),
-                ("g/G", "Go to top / bottom"),
-                ("PgUp/Dn", "Move 10 items up / down"),
-            ],
-        ),
-        (
-            "Actions",
-            vec![
-                ("Enter", "Attach to session"),
-                ("n", "New session"),
-                ("d", "Delete session/group"),
-                ("r", "Rename session"),
-            ],
-        ),
-        (
-            "Views",
-            vec![
-                ("t", "Toggle Agent/Terminal view"),
-                ("c", "Toggle container/host (sandbox)"),
-                ("D", "Diff view (git changes)"),
-                ("H/L", "Resize list panel"),
-            ],
-        ),
-        (
-            "Other",
-            vec![
-                ("/", "Search"),
-                ("s", "Settings"),
-                ("P", "Next profile"),
-                ("?", "Toggle help"),
-                ("q", "Quit"),
-            ],
-        ),
-    ]
+/// Sections of (keys, description) pairs, as built by `Keymap::help_sections`
+pub type HelpSections = [(&'static str, Vec<(String, &'static str)>)];
+
+const BORDER: u16 = 2;
+const INDENT: usize = 2;
+const MIN_KEY_WIDTH: usize = 10;
+const COLUMN_GAP: u16 = 2;
+
+pub struct HelpOverlay;
+
+/// Size of the dialog and the sections shown in each column
+#[derive(Debug, PartialEq, Eq)]
+struct HelpLayout {
+    width: u16,
+    height: u16,
+    key_width: usize,
+    column_width: u16,
+    /// Index of the first section of every column after the first
+    splits: Vec<usize>,
 }
 
-#[cfg(test)]
-fn content_line_count() -> usize {
-    let mut count = 0;
-    for (_, keys) in shortcuts() {
-        count += 1; // section header
-        count += keys.len(); // shortcut lines
-        count += 1; // empty line after section
+fn section_height(entries: usize) -> u16 {
+    // header + entries + blank line
+    entries as u16 + 2
+}
+
+/// Split `heights` into `columns` consecutive runs, keeping the tallest run
+/// as short as possible. Returns its height and where each later run starts.
+fn balance(heights: &[u16], columns: usize) -> (u16, Vec<usize>) {
+    if columns <= 1 || heights.len() <= 1 {
+        return (heights.iter().sum(), Vec::new());
     }
-    count
+    (1..heights.len())
+        .map(|split| {
+            let first: u16 = heights[..split].iter().sum();
+            let (rest, splits) = balance(&heights[split..], columns - 1);
+            let splits = std::iter::once(split)
+                .chain(splits.into_iter().map(|s| s + split))
+                .collect();
+            (first.max(rest), splits)
+        })
+        .min_by_key(|(height, _)| *height)
+        .unwrap_or((heights.iter().sum(), Vec::new()))
 }
 
-pub struct HelpOverlay;
+fn layout(sections: &HelpSections, area: Rect) -> HelpLayout {
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, entries)| entries)
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(MIN_KEY_WIDTH);
+    let column_width = sections
+        .iter()
+        .flat_map(|(name, entries)| {
+            std::iter::once(name.chars().count()).chain(
+                entries
+                    .iter()
+                    .map(|(_, desc)| INDENT + key_width + 1 + desc.chars().count()),
+            )
+        })
+        .max()
+        .unwrap_or(0) as u16;
+    let width_for = |columns: u16| column_width * columns + COLUMN_GAP * (columns - 1) + BORDER;
+
+    let heights: Vec<u16> = sections
+        .iter()
+        .map(|(_, entries)| section_height(entries.len()))
+        .collect();
+
+    // Use as few columns as fit the terminal height; when even the widest
+    // layout that fits is too tall, it is clipped
+    let mut best = (heights.iter().sum::<u16>(), Vec::new(), 1);
+    for columns in 2..=sections.len() as u16 {
+        if best.0 + BORDER <= area.height || width_for(columns) > area.width {
+            break;
+        }
+        let (height, splits) = balance(&heights, columns as usize);
+        best = (height, splits, columns);
+    }
+    let (height, splits, columns) = best;
+
+    HelpLayout {
+        width: width_for(columns),
+        height: height + BORDER,
+        key_width,
+        column_width,
+        splits,
+    }
+}
 
 impl HelpOverlay {
-    pub fn render(frame: &mut Frame, area: Rect, theme: &Theme) {
-        let x = area.x + (area.width.saturating_sub(DIALOG_WIDTH)) / 2;
-        let y = area.y + (area.height.saturating_sub(DIALOG_HEIGHT)) / 2;
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        title: &str,
+        sections: &HelpSections,
+    ) {
+        let layout = layout(sections, area);
+        let x = area.x + (area.width.saturating_sub(layout.width)) / 2;
+        let y = area.y + (area.height.saturating_sub(layout.height)) / 2;
 
         let dialog_area = Rect {
             x,
             y,
-            width: DIALOG_WIDTH.min(area.width),
-            height: DIALOG_HEIGHT.min(area.height),
+            width: layout.width.min(area.width),
+            height: layout.height.min(area.height),
         };
 
         frame.render_widget(Clear, dialog_area);
@@ -89,71 +125,100 @@ impl HelpOverlay {
             .style(Style::default().bg(theme.background))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.border))
-            .title(" Keyboard Shortcuts ")
+            .title(format!(" {} ", title))
             .title_style(Style::default().fg(theme.title).bold());
 
         let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
-        let mut lines: Vec<Line> = Vec::new();
-
-        for (section, keys) in shortcuts() {
-            lines.push(Line::from(Span::styled(
-                section,
-                Style::default().fg(theme.accent).bold(),
-            )));
-            for (key, desc) in keys {
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  {:10}", key), Style::default().fg(theme.waiting)),
-                    Span::styled(desc, Style::default().fg(theme.text)),
-                ]));
+        let bounds: Vec<usize> = std::iter::once(0)
+            .chain(layout.splits.iter().copied())
+            .chain(std::iter::once(sections.len()))
+            .collect();
+        let columns = bounds.windows(2).map(|w| &sections[w[0]..w[1]]);
+
+        for (i, column) in columns.enumerate() {
+            let offset = i as u16 * (layout.column_width + COLUMN_GAP);
+            let column_area = Rect {
+                x: inner.x + offset,
+                width: inner.width.saturating_sub(offset).min(layout.column_width),
+                ..inner
+            };
+
+            let mut lines: Vec<Line> = Vec::new();
+            for (section, entries) in column {
+                lines.push(Line::from(Span::styled(
+                    *section,
+                    Style::default().fg(theme.accent).bold(),
+                )));
+                for (keys, desc) in entries {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!(
+                                "{}{:width$} ",
+                                " ".repeat(INDENT),
+                                keys,
+                                width = layout.key_width
+                            ),
+                            Style::default().fg(theme.waiting),
+                        ),
+                        Span::styled(*desc, Style::default().fg(theme.text)),
+                    ]));
+                }
+                lines.push(Line::from(""));
             }
-            lines.push(Line::from(""));
-        }
 
-        let paragraph = Paragraph::new(lines);
-        frame.render_widget(paragraph, inner);
+            frame.render_widget(Paragraph::new(lines), column_area);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tui::keybindings::{HomeCommand, Keymap};
+
+    fn home_sections() -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+        Keymap::<HomeCommand>::default().help_sections()
+    }
 
     #[test]
-    fn help_contains_resize_shortcut() {
-        let all = shortcuts();
-        let views_section = all.iter().find(|(name, _)| *name == "Views");
-        assert!(views_section.is_some(), "Views section should exist");
-        let (_, keys) = views_section.unwrap();
-        assert!(
-            keys.iter().any(|(k, _)| *k == "H/L"),
-            "Views section should contain H/L resize shortcut"
-        );
+    fn help_lists_active_bindings() {
+        let sections = home_sections();
+        let views = sections.iter().find(|(name, _)| *name == "Views");
+        let (_, entries) = views.expect("Views section should exist");
+        assert!(entries
+            .iter()
+            .any(|(keys, desc)| keys == "H" && *desc == "Shrink list panel"));
+
+        let (_, navigation) = &sections[0];
+        assert_eq!(navigation[0], ("j/↓".to_string(), "Move down"));
     }
 
     #[test]
-    fn help_content_fits_in_dialog() {
-        let available_height = (DIALOG_HEIGHT - BORDER_HEIGHT) as usize;
-        let content_lines = content_line_count();
-        assert!(
-            content_lines <= available_height,
-            "Help content ({content_lines} lines) exceeds dialog inner height ({available_height} lines)"
-        );
-
-        let available_width = (DIALOG_WIDTH - BORDER_WIDTH) as usize;
-        for (section, keys) in shortcuts() {
-            assert!(
-                section.len() <= available_width,
-                "Section header '{section}' exceeds dialog width ({available_width} chars)"
-            );
-            for (key, desc) in keys {
-                let line_width = KEY_COLUMN_WIDTH + desc.len();
-                assert!(
-                    line_width <= available_width,
-                    "Shortcut '{key}' description '{desc}' exceeds dialog width ({line_width} > {available_width})"
-                );
-            }
-        }
+    fn help_uses_one_column_when_it_fits() {
+        let sections = home_sections();
+        let layout = layout(&sections, Rect::new(0, 0, 200, 100));
+        assert!(layout.splits.is_empty());
+        let lines: u16 = sections.iter().map(|(_, e)| section_height(e.len())).sum();
+        assert_eq!(layout.height, lines + BORDER);
+        assert!(layout.width <= 60, "help too wide: {}", layout.width);
+    }
+
+    #[test]
+    fn balance_keeps_columns_even() {
+        assert_eq!(balance(&[10, 4, 4, 6], 2), (14, vec![1]));
+        assert_eq!(balance(&[10, 4, 4, 6], 3), (10, vec![1, 3]));
+        assert_eq!(balance(&[5], 3), (5, vec![]));
+    }
+
+    #[test]
+    fn help_splits_into_columns_on_short_terminals() {
+        let sections = home_sections();
+        let area = Rect::new(0, 0, 160, 24);
+        let layout = layout(&sections, area);
+        assert!(!layout.splits.is_empty());
+        assert!(layout.height <= area.height, "height {}", layout.height);
+        assert!(layout.width <= area.width);
     }
 }
@@ -1,6 +1,7 @@
 //! TUI components
 
 mod help;
+pub mod mouse;
 mod preview;
 mod text_input;
 
@@ -0,0 +1,115 @@
+//! Mouse helpers shared by views and dialogs
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+/// Whether the mouse event happened inside `area`
+pub fn hit(area: Rect, mouse: &MouseEvent) -> bool {
+    area.contains(Position::new(mouse.column, mouse.row))
+}
+
+/// Whether the event is a left click inside `area`
+pub fn clicked(area: Rect, mouse: &MouseEvent) -> bool {
+    mouse.kind == MouseEventKind::Down(MouseButton::Left) && hit(area, mouse)
+}
+
+/// Area of a dialog of the given size, centered in `area` the way dialogs
+/// place themselves
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width: width.min(area.width),
+        height: height.min(area.height),
+    }
+}
+
+/// Dragging the right border of a resizable left panel
+#[derive(Debug, Default)]
+pub struct SplitDrag {
+    dragging: bool,
+}
+
+/// What a mouse event did to a split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitEvent {
+    /// The left panel is being dragged to this width
+    Resize(u16),
+    /// The drag ended; time to persist the width
+    Done,
+}
+
+impl SplitDrag {
+    /// Track a drag of the border of `left`. Returns None for events that
+    /// aren't part of a drag, so they can be handled as usual.
+    pub fn handle(&mut self, mouse: &MouseEvent, left: Rect) -> Option<SplitEvent> {
+        let border = left.x + left.width.saturating_sub(1);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left)
+                if mouse.column == border
+                    && mouse.row >= left.y
+                    && mouse.row < left.y + left.height =>
+            {
+                self.dragging = true;
+                Some(SplitEvent::Resize(left.width))
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                Some(SplitEvent::Resize(mouse.column.saturating_sub(left.x) + 1))
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragging => {
+                self.dragging = false;
+                Some(SplitEvent::Done)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_split_drag_follows_the_border() {
+        let left = Rect::new(0, 0, 35, 20);
+        let mut drag = SplitDrag::default();
+        let down = MouseEventKind::Down(MouseButton::Left);
+        let moved = MouseEventKind::Drag(MouseButton::Left);
+        let up = MouseEventKind::Up(MouseButton::Left);
+
+        // Only the border starts a drag
+        assert_eq!(drag.handle(&mouse(down, 10, 5), left), None);
+        assert_eq!(drag.handle(&mouse(moved, 50, 5), left), None);
+
+        assert_eq!(
+            drag.handle(&mouse(down, 34, 5), left),
+            Some(SplitEvent::Resize(35))
+        );
+        assert_eq!(
+            drag.handle(&mouse(moved, 49, 7), left),
+            Some(SplitEvent::Resize(50))
+        );
+        assert_eq!(drag.handle(&mouse(up, 49, 7), left), Some(SplitEvent::Done));
+        assert_eq!(drag.handle(&mouse(up, 49, 7), left), None);
+    }
+
+    #[test]
+    fn test_centered_matches_dialog_placement() {
+        let area = Rect::new(0, 0, 100, 40);
+        assert_eq!(centered(area, 50, 10), Rect::new(25, 15, 50, 10));
+        assert_eq!(
+            centered(Rect::new(0, 0, 30, 5), 50, 10),
+            Rect::new(0, 0, 30, 5)
+        );
+    }
+}
@@ -3,7 +3,8 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use crate::session::Instance;
+use crate::docker::{stats::format_size, ContainerStats};
+use crate::session::{Instance, SandboxInfo};
 use crate::tui::styles::Theme;
 
 pub struct Preview;
@@ -15,10 +16,11 @@ impl Preview {
         instance: &Instance,
         terminal_running: bool,
         cached_output: &str,
+        scrollback: u16,
         theme: &Theme,
     ) {
         let info_height = if instance.sandbox_info.as_ref().is_some_and(|s| s.enabled) {
-            5
+            6
         } else {
             4
         };
@@ -65,6 +67,7 @@ impl Preview {
                     Span::styled("Sandbox: ", Style::default().fg(theme.dimmed)),
                     Span::styled(&sandbox.container_name, Style::default().fg(Color::Magenta)),
                 ]));
+                info_lines.push(network_line(sandbox, theme));
             }
         }
         let paragraph = Paragraph::new(info_lines);
@@ -100,7 +103,7 @@ impl Preview {
             let visible_height = inner.height as usize;
 
             let scroll_offset = if line_count > visible_height {
-                (line_count - visible_height) as u16
+                ((line_count - visible_height) as u16).saturating_sub(scrollback)
             } else {
                 0
             };
@@ -117,15 +120,20 @@ impl Preview {
         frame: &mut Frame,
         area: Rect,
         instance: &Instance,
+        stats: Option<&ContainerStats>,
         cached_output: &str,
+        scrollback: u16,
         theme: &Theme,
     ) {
-        // Adjust height based on whether worktree info is present
-        let info_height = if instance.worktree_info.is_some() {
-            10 // Expanded to show worktree details
-        } else {
-            6 // Standard height
+        // Adjust height based on whether worktree and sandbox info are present
+        let mut info_height = match &instance.worktree_info {
+            Some(wt) if !wt.sync_conflicts.is_empty() => 11, // Worktree details + sync conflicts
+            Some(_) => 10,                                   // Expanded to show worktree details
+            None => 6,                                       // Standard height
         };
+        if instance.sandbox_info.as_ref().is_some_and(|s| s.enabled) {
+            info_height += 5; // Sandbox details
+        }
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -135,11 +143,17 @@ impl Preview {
             ])
             .split(area);
 
-        Self::render_info(frame, chunks[0], instance, theme);
-        Self::render_output_cached(frame, chunks[1], instance, cached_output, theme);
+        Self::render_info(frame, chunks[0], instance, stats, theme);
+        Self::render_output_cached(frame, chunks[1], instance, cached_output, scrollback, theme);
     }
 
-    fn render_info(frame: &mut Frame, area: Rect, instance: &Instance, theme: &Theme) {
+    fn render_info(
+        frame: &mut Frame,
+        area: Rect,
+        instance: &Instance,
+        stats: Option<&ContainerStats>,
+        theme: &Theme,
+    ) {
         let mut info_lines = vec![
             Line::from(vec![
                 Span::styled("Title:   ", Style::default().fg(theme.dimmed)),
@@ -159,7 +173,10 @@ impl Preview {
             Line::from(vec![
                 Span::styled("Status:  ", Style::default().fg(theme.dimmed)),
                 Span::styled(
-                    format!("{:?}", instance.status),
+                    match &instance.waiting_reason {
+                        Some(reason) => format!("{:?} for {}", instance.status, reason.label()),
+                        None => format!("{:?}", instance.status),
+                    },
                     Style::default().fg(match instance.status {
                         crate::session::Status::Running => theme.running,
                         crate::session::Status::Waiting => theme.waiting,
@@ -219,6 +236,31 @@ impl Preview {
                     }),
                 ),
             ]));
+
+            if !wt_info.sync_conflicts.is_empty() {
+                info_lines.push(Line::from(vec![
+                    Span::styled("Sync:    ", Style::default().fg(theme.dimmed)),
+                    Span::styled(
+                        format!("✗ conflicts in {}", wt_info.sync_conflicts.join(", ")),
+                        Style::default().fg(theme.error),
+                    ),
+                ]));
+            }
+        }
+
+        if let Some(sandbox) = instance.sandbox_info.as_ref().filter(|s| s.enabled) {
+            info_lines.push(Line::from(""));
+            info_lines.push(Line::from(vec![
+                Span::styled("─", Style::default().fg(theme.border)),
+                Span::styled(" Sandbox ", Style::default().fg(theme.dimmed)),
+                Span::styled("─", Style::default().fg(theme.border)),
+            ]));
+            info_lines.push(Line::from(vec![
+                Span::styled("Name:    ", Style::default().fg(theme.dimmed)),
+                Span::styled(&sandbox.container_name, Style::default().fg(Color::Magenta)),
+            ]));
+            info_lines.push(network_line(sandbox, theme));
+            info_lines.push(usage_line(stats, theme));
         }
 
         let paragraph = Paragraph::new(info_lines);
@@ -230,6 +272,7 @@ impl Preview {
         area: Rect,
         instance: &Instance,
         cached_output: &str,
+        scrollback: u16,
         theme: &Theme,
     ) {
         let block = Block::default()
@@ -272,9 +315,9 @@ impl Preview {
             let line_count = output_lines.len();
             let visible_height = inner.height as usize;
 
-            // Scroll to show the bottom of the content
+            // Show the bottom of the content, or `scrollback` lines above it
             let scroll_offset = if line_count > visible_height {
-                (line_count - visible_height) as u16
+                ((line_count - visible_height) as u16).saturating_sub(scrollback)
             } else {
                 0
             };
@@ -288,6 +331,69 @@ impl Preview {
     }
 }
 
+/// Network policy the sandbox container was created with. Containers from
+/// before network policies existed always had full access.
+fn network_line<'a>(sandbox: &'a SandboxInfo, theme: &Theme) -> Line<'a> {
+    let policy = sandbox.network.as_deref().unwrap_or("full");
+    Line::from(vec![
+        Span::styled("Network: ", Style::default().fg(theme.dimmed)),
+        Span::styled(
+            policy,
+            Style::default().fg(if policy == "full" {
+                Color::Yellow
+            } else {
+                Color::Green
+            }),
+        ),
+    ])
+}
+
+/// CPU, memory against the container's limit, and process count. Memory
+/// turns red when the container is close to being OOM-killed.
+fn usage_line(stats: Option<&ContainerStats>, theme: &Theme) -> Line<'static> {
+    let label = Span::styled("Usage:   ", Style::default().fg(theme.dimmed));
+    let Some(stats) = stats else {
+        return Line::from(vec![
+            label,
+            Span::styled("not running", Style::default().fg(theme.dimmed)),
+        ]);
+    };
+
+    let memory_style = if stats.near_memory_limit() {
+        Style::default().fg(theme.error).bold()
+    } else {
+        Style::default().fg(theme.text)
+    };
+    let mut spans = vec![
+        label,
+        Span::styled(
+            format!("CPU {:.1}%", stats.cpu_percent),
+            Style::default().fg(theme.text),
+        ),
+        Span::styled("  ", Style::default()),
+        Span::styled(
+            format!(
+                "Mem {} / {} ({:.0}%)",
+                format_size(stats.memory_used),
+                format_size(stats.memory_limit),
+                stats.memory_percent()
+            ),
+            memory_style,
+        ),
+        Span::styled(
+            format!("  PIDs {}", stats.pids),
+            Style::default().fg(theme.text),
+        ),
+    ];
+    if stats.near_memory_limit() {
+        spans.push(Span::styled(
+            "  ⚠ near memory limit",
+            Style::default().fg(theme.error),
+        ));
+    }
+    Line::from(spans)
+}
+
 fn shorten_path(path: &str) -> String {
     let path_buf = std::path::PathBuf::from(path);
 
@@ -0,0 +1,132 @@
+//! Background worker for runtime commands on a session's container that are
+//! too slow for the key handler: committing, recreating and listing
+//! snapshots, and inspecting and forwarding ports
+
+use std::sync::mpsc;
+use std::thread;
+
+use crate::docker::{SessionPort, Snapshot};
+use crate::session::Instance;
+use crate::tui::dialogs::{PortsAction, SnapshotAction};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerOperation {
+    /// List the snapshots for the dialog
+    ListSnapshots,
+    Snapshot(SnapshotAction),
+    Ports(PortsAction),
+}
+
+pub struct ContainerRequest {
+    pub instance: Instance,
+    pub operation: ContainerOperation,
+}
+
+/// What the dialog that asked shows once the work is done
+#[derive(Debug)]
+pub enum ContainerListing {
+    Snapshots {
+        snapshots: Result<Vec<Snapshot>, String>,
+        /// Snapshot the container was rolled back to
+        active: Option<String>,
+    },
+    Ports(Result<Vec<SessionPort>, String>),
+}
+
+#[derive(Debug)]
+pub struct ContainerResult {
+    pub session_id: String,
+    /// Why the requested change failed
+    pub error: Option<String>,
+    pub listing: ContainerListing,
+    /// The session after a change to its sandbox, to write back into the list
+    pub instance: Option<Instance>,
+}
+
+pub struct ContainerPoller {
+    request_tx: mpsc::Sender<ContainerRequest>,
+    result_rx: mpsc::Receiver<ContainerResult>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl ContainerPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<ContainerRequest>();
+        let (result_tx, result_rx) = mpsc::channel::<ContainerResult>();
+
+        let handle = thread::spawn(move || {
+            while let Ok(request) = request_rx.recv() {
+                if result_tx.send(Self::perform(request)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn perform(request: ContainerRequest) -> ContainerResult {
+        let ContainerRequest {
+            mut instance,
+            operation,
+        } = request;
+
+        let (result, changed) = match &operation {
+            ContainerOperation::ListSnapshots => (Ok(()), false),
+            ContainerOperation::Snapshot(SnapshotAction::Create(name)) => {
+                (instance.create_snapshot(name.as_deref()).map(|_| ()), false)
+            }
+            ContainerOperation::Snapshot(SnapshotAction::Rollback(name)) => {
+                (instance.rollback(name), true)
+            }
+            ContainerOperation::Snapshot(SnapshotAction::Delete(name)) => {
+                (instance.delete_snapshot(name), true)
+            }
+            ContainerOperation::Ports(PortsAction::Refresh) => (Ok(()), false),
+            ContainerOperation::Ports(PortsAction::Forward(port)) => {
+                (instance.forward_port(*port), true)
+            }
+            ContainerOperation::Ports(PortsAction::Unforward(port)) => {
+                (instance.unforward_port(*port), true)
+            }
+        };
+
+        let listing = match operation {
+            ContainerOperation::Ports(_) => {
+                ContainerListing::Ports(instance.list_ports().map_err(|e| e.to_string()))
+            }
+            _ => ContainerListing::Snapshots {
+                snapshots: instance.list_snapshots().map_err(|e| e.to_string()),
+                active: instance
+                    .sandbox_info
+                    .as_ref()
+                    .and_then(|s| s.snapshot.clone()),
+            },
+        };
+
+        ContainerResult {
+            session_id: instance.id.clone(),
+            error: result.err().map(|e| e.to_string()),
+            listing,
+            instance: changed.then_some(instance),
+        }
+    }
+
+    pub fn request(&self, request: ContainerRequest) {
+        let _ = self.request_tx.send(request);
+    }
+
+    pub fn try_recv_result(&self) -> Option<ContainerResult> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for ContainerPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,6 @@
 //! Background session creation handler for TUI responsiveness
 //!
-//! This handles the potentially slow Docker operations (image pull, container creation)
+//! This handles the potentially slow Docker operations (image pull or build, container creation)
 //! in a background thread so the UI remains responsive.
 
 use std::sync::mpsc;
@@ -12,6 +12,8 @@ use crate::session::Instance;
 use crate::tui::dialogs::NewSessionData;
 
 pub struct CreationRequest {
+    /// Profile the session is created in
+    pub profile: String,
     pub data: NewSessionData,
     /// Existing instances, used for generating unique titles
     pub existing_instances: Vec<Instance>,
@@ -97,14 +99,19 @@ impl CreationPoller {
             .collect();
 
         let params = InstanceParams {
+            profile: request.profile,
             title: data.title,
             path: data.path.clone(),
             group: data.group,
             tool: data.tool,
             worktree_branch: data.worktree_branch,
             create_new_branch: data.create_new_branch,
+            base_ref: data.base_ref,
             sandbox: data.sandbox,
             sandbox_image: data.sandbox_image,
+            cpu_limit: data.cpu_limit,
+            memory_limit: data.memory_limit,
+            isolated: data.isolated,
             yolo_mode: data.yolo_mode,
             extra_env_keys: data.extra_env_keys,
             extra_env_values: data.extra_env_values,
@@ -118,12 +125,31 @@ impl CreationPoller {
         let mut instance = build_result.instance;
         let created_worktree = build_result.created_worktree;
 
-        let has_on_create = hooks.as_ref().is_some_and(|h| !h.on_create.is_empty());
+        // Build the sandbox image up front so its output shows in the dialog;
+        // ensure_container_running below then finds it already built
+        if instance
+            .sandbox_info
+            .as_ref()
+            .is_some_and(|s| s.build.is_some())
+        {
+            let _ = progress_tx.send(HookProgress::Started("Building sandbox image".to_string()));
+            if let Err(e) = instance.prepare_sandbox_image(|line| {
+                let _ = progress_tx.send(HookProgress::Output(line));
+            }) {
+                builder::cleanup_instance(&instance, created_worktree.as_ref());
+                return CreationResult::Error(e.to_string());
+            }
+        }
+
+        let on_create = hooks
+            .as_ref()
+            .map(|h| h.on_create_commands(data.sandbox))
+            .unwrap_or_default();
+        let has_on_create = !on_create.is_empty();
         let has_on_launch = hooks.as_ref().is_some_and(|h| !h.on_launch.is_empty());
 
         // Execute on_create hooks after worktree setup, before starting
         if has_on_create {
-            let hooks = hooks.as_ref().unwrap();
             if data.sandbox {
                 // Ensure the container is running so we can exec hooks inside it.
                 // Don't create the tmux session yet -- that happens at attach time
@@ -135,7 +161,8 @@ impl CreationPoller {
                 if let Some(ref sandbox) = instance.sandbox_info {
                     let workdir = instance.container_workdir();
                     if let Err(e) = repo_config::execute_hooks_in_container_streamed(
-                        &hooks.on_create,
+                        &on_create,
+                        sandbox.runtime,
                         &sandbox.container_name,
                         &workdir,
                         progress_tx,
@@ -145,7 +172,7 @@ impl CreationPoller {
                     }
                 }
             } else if let Err(e) = repo_config::execute_hooks_streamed(
-                &hooks.on_create,
+                &on_create,
                 std::path::Path::new(&instance.project_path),
                 progress_tx,
             ) {
@@ -165,6 +192,7 @@ impl CreationPoller {
                         let workdir = instance.container_workdir();
                         if let Err(e) = repo_config::execute_hooks_in_container_streamed(
                             &hooks.on_launch,
+                            sandbox.runtime,
                             &sandbox.container_name,
                             &workdir,
                             progress_tx,
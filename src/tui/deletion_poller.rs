@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::mpsc;
 use std::thread;
 
-use crate::docker::DockerContainer;
+use crate::docker::{isolated, snapshot, DockerContainer};
 use crate::git::GitWorktree;
 use crate::session::Instance;
 
@@ -107,12 +107,21 @@ impl DeletionPoller {
         if request.delete_sandbox {
             if let Some(sandbox) = &request.instance.sandbox_info {
                 if sandbox.enabled {
-                    let container = DockerContainer::from_session_id(&request.instance.id);
+                    let container =
+                        DockerContainer::from_session_id(&request.instance.id, sandbox.runtime);
                     if container.exists().unwrap_or(false) {
                         if let Err(e) = container.remove(true) {
                             errors.push(format!("Container: {}", e));
                         }
                     }
+                    if let Err(e) = snapshot::remove_all(sandbox.runtime, &container.name) {
+                        errors.push(format!("Snapshots: {}", e));
+                    }
+                    if sandbox.isolated {
+                        if let Err(e) = isolated::remove_volume(sandbox.runtime, &container.name) {
+                            errors.push(format!("Workspace copy: {}", e));
+                        }
+                    }
                 }
             }
         }
@@ -0,0 +1,398 @@
+//! Menu of actions applied to all marked sessions
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+/// Action picked for the marked sessions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    Stop,
+    Restart,
+    /// Opens the delete dialog for the marked sessions
+    Delete,
+    /// Move to a group; empty removes the sessions from their groups
+    MoveToGroup(String),
+    MoveToProfile(String),
+    SendPrompt(String),
+    /// Fast-forward each worktree's base branch to the session's branch
+    Land,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MenuItem {
+    Stop,
+    Restart,
+    Delete,
+    MoveToGroup,
+    MoveToProfile,
+    SendPrompt,
+    Land,
+}
+
+const MENU: [(MenuItem, &str); 7] = [
+    (MenuItem::SendPrompt, "Send prompt"),
+    (MenuItem::Stop, "Stop"),
+    (MenuItem::Restart, "Restart"),
+    (MenuItem::MoveToGroup, "Move to group"),
+    (MenuItem::MoveToProfile, "Move to profile"),
+    (MenuItem::Land, "Land worktree branches"),
+    (MenuItem::Delete, "Delete"),
+];
+
+enum Stage {
+    Menu,
+    Group(Input),
+    Prompt(Input),
+    Profile(usize),
+    /// Stop, restart or land waiting for `y`
+    Confirm(BulkAction),
+}
+
+pub struct BulkActionsDialog {
+    count: usize,
+    /// Profiles other than the current one
+    profiles: Vec<String>,
+    selected: usize,
+    stage: Stage,
+}
+
+impl BulkActionsDialog {
+    pub fn new(count: usize, current_profile: &str, profiles: Vec<String>) -> Self {
+        Self {
+            count,
+            profiles: profiles
+                .into_iter()
+                .filter(|p| p != current_profile)
+                .collect(),
+            selected: 0,
+            stage: Stage::Menu,
+        }
+    }
+
+    fn sessions_label(&self) -> String {
+        format!(
+            "{} session{}",
+            self.count,
+            if self.count == 1 { "" } else { "s" }
+        )
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<BulkAction> {
+        match &mut self.stage {
+            Stage::Menu => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => DialogResult::Cancel,
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1) % MENU.len();
+                    DialogResult::Continue
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.selected = (self.selected + MENU.len() - 1) % MENU.len();
+                    DialogResult::Continue
+                }
+                KeyCode::Enter => {
+                    self.stage = match MENU[self.selected].0 {
+                        MenuItem::Delete => return DialogResult::Submit(BulkAction::Delete),
+                        MenuItem::Stop => Stage::Confirm(BulkAction::Stop),
+                        MenuItem::Restart => Stage::Confirm(BulkAction::Restart),
+                        MenuItem::Land => Stage::Confirm(BulkAction::Land),
+                        MenuItem::MoveToGroup => Stage::Group(Input::default()),
+                        MenuItem::SendPrompt => Stage::Prompt(Input::default()),
+                        MenuItem::MoveToProfile if self.profiles.is_empty() => Stage::Menu,
+                        MenuItem::MoveToProfile => Stage::Profile(0),
+                    };
+                    DialogResult::Continue
+                }
+                _ => DialogResult::Continue,
+            },
+            Stage::Group(input) => match key.code {
+                KeyCode::Esc => {
+                    self.stage = Stage::Menu;
+                    DialogResult::Continue
+                }
+                KeyCode::Enter => {
+                    let group = input.value().trim().trim_matches('/').to_string();
+                    DialogResult::Submit(BulkAction::MoveToGroup(group))
+                }
+                _ => {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                    DialogResult::Continue
+                }
+            },
+            Stage::Prompt(input) => match key.code {
+                KeyCode::Esc => {
+                    self.stage = Stage::Menu;
+                    DialogResult::Continue
+                }
+                KeyCode::Enter if input.value().trim().is_empty() => DialogResult::Continue,
+                KeyCode::Enter => {
+                    DialogResult::Submit(BulkAction::SendPrompt(input.value().trim().to_string()))
+                }
+                _ => {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                    DialogResult::Continue
+                }
+            },
+            Stage::Profile(index) => match key.code {
+                KeyCode::Esc => {
+                    self.stage = Stage::Menu;
+                    DialogResult::Continue
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    *index = (*index + 1) % self.profiles.len();
+                    DialogResult::Continue
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    *index = (*index + self.profiles.len() - 1) % self.profiles.len();
+                    DialogResult::Continue
+                }
+                KeyCode::Enter => {
+                    DialogResult::Submit(BulkAction::MoveToProfile(self.profiles[*index].clone()))
+                }
+                _ => DialogResult::Continue,
+            },
+            Stage::Confirm(action) => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => DialogResult::Submit(action.clone()),
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.stage = Stage::Menu;
+                    DialogResult::Continue
+                }
+                _ => DialogResult::Continue,
+            },
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let list_len = match self.stage {
+            Stage::Profile(_) => self.profiles.len(),
+            _ => MENU.len(),
+        } as u16;
+        let dialog_width = 56.min(area.width.saturating_sub(4));
+        let dialog_height = (list_len + 6).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect {
+            x,
+            y,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" {} marked ", self.sessions_label()))
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // actions or profiles
+                Constraint::Length(1), // input or confirmation
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        let item = |label: &str, selected: bool| {
+            let style = if selected {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            Line::from(vec![
+                Span::styled(if selected { "  > " } else { "    " }, style),
+                Span::styled(label.to_string(), style),
+            ])
+        };
+
+        let lines: Vec<Line> = match self.stage {
+            Stage::Profile(index) => self
+                .profiles
+                .iter()
+                .enumerate()
+                .map(|(i, profile)| item(profile, i == index))
+                .collect(),
+            _ => MENU
+                .iter()
+                .enumerate()
+                .map(|(i, (menu_item, label))| {
+                    if *menu_item == MenuItem::MoveToProfile && self.profiles.is_empty() {
+                        Line::from(Span::styled(
+                            format!("    {} (no other profiles)", label),
+                            Style::default().fg(theme.dimmed),
+                        ))
+                    } else {
+                        item(label, i == self.selected)
+                    }
+                })
+                .collect(),
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        match &self.stage {
+            Stage::Group(input) => render_text_field(
+                frame,
+                chunks[1],
+                "  Group:",
+                input,
+                true,
+                Some("none"),
+                theme,
+            ),
+            Stage::Prompt(input) => {
+                render_text_field(frame, chunks[1], "  Prompt:", input, true, None, theme)
+            }
+            Stage::Confirm(action) => {
+                let verb = match action {
+                    BulkAction::Stop => "Stop",
+                    BulkAction::Restart => "Restart",
+                    _ => "Land the branches of",
+                };
+                frame.render_widget(
+                    Paragraph::new(format!("  {} {}? (y/n)", verb, self.sessions_label()))
+                        .style(Style::default().fg(theme.waiting)),
+                    chunks[1],
+                );
+            }
+            Stage::Menu | Stage::Profile(_) => {}
+        }
+
+        let hint = |key: &'static str, text: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.hint)),
+                Span::styled(text, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let hints: Vec<Span> = match self.stage {
+            Stage::Menu | Stage::Profile(_) => [
+                hint("j/k", " move  "),
+                hint("Enter", " select  "),
+                hint("Esc", " cancel"),
+            ]
+            .concat(),
+            Stage::Group(_) | Stage::Prompt(_) => {
+                [hint("Enter", " apply  "), hint("Esc", " back")].concat()
+            }
+            Stage::Confirm(_) => [hint("y", " confirm  "), hint("n", " back")].concat(),
+        };
+        frame.render_widget(Paragraph::new(Line::from(hints)), chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn dialog() -> BulkActionsDialog {
+        BulkActionsDialog::new(
+            3,
+            "default",
+            vec!["default".to_string(), "work".to_string()],
+        )
+    }
+
+    fn select(dialog: &mut BulkActionsDialog, item: MenuItem) {
+        while MENU[dialog.selected].0 != item {
+            dialog.handle_key(key(KeyCode::Down));
+        }
+        dialog.handle_key(key(KeyCode::Enter));
+    }
+
+    #[test]
+    fn test_stop_needs_confirmation() {
+        let mut dialog = dialog();
+        select(&mut dialog, MenuItem::Stop);
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('n'))),
+            DialogResult::Continue
+        ));
+        assert!(matches!(dialog.stage, Stage::Menu));
+
+        select(&mut dialog, MenuItem::Stop);
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('y'))),
+            DialogResult::Submit(BulkAction::Stop)
+        ));
+    }
+
+    #[test]
+    fn test_delete_submits_immediately() {
+        let mut dialog = dialog();
+        while MENU[dialog.selected].0 != MenuItem::Delete {
+            dialog.handle_key(key(KeyCode::Down));
+        }
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(BulkAction::Delete)
+        ));
+    }
+
+    #[test]
+    fn test_move_to_group_trims_input() {
+        let mut dialog = dialog();
+        select(&mut dialog, MenuItem::MoveToGroup);
+        for c in " work/api/ ".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(BulkAction::MoveToGroup(group)) => assert_eq!(group, "work/api"),
+            _ => panic!("expected MoveToGroup"),
+        }
+    }
+
+    #[test]
+    fn test_empty_prompt_is_not_sent() {
+        let mut dialog = dialog();
+        select(&mut dialog, MenuItem::SendPrompt);
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        for c in "run the tests".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(BulkAction::SendPrompt(prompt)) => {
+                assert_eq!(prompt, "run the tests")
+            }
+            _ => panic!("expected SendPrompt"),
+        }
+    }
+
+    #[test]
+    fn test_move_to_profile_skips_current_profile() {
+        let mut dialog = dialog();
+        select(&mut dialog, MenuItem::MoveToProfile);
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(BulkAction::MoveToProfile(profile)) => {
+                assert_eq!(profile, "work")
+            }
+            _ => panic!("expected MoveToProfile"),
+        }
+    }
+
+    #[test]
+    fn test_move_to_profile_unavailable_with_single_profile() {
+        let mut dialog = BulkActionsDialog::new(2, "default", vec!["default".to_string()]);
+        select(&mut dialog, MenuItem::MoveToProfile);
+        assert!(matches!(dialog.stage, Stage::Menu));
+    }
+}
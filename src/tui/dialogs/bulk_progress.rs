@@ -0,0 +1,209 @@
+//! Progress and per-session results of a bulk action
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::tui::styles::Theme;
+
+struct Row {
+    session_id: String,
+    title: String,
+    /// `None` while the action is still running for this session
+    outcome: Option<Result<String, String>>,
+}
+
+pub struct BulkProgressDialog {
+    title: String,
+    rows: Vec<Row>,
+    scroll: usize,
+}
+
+impl BulkProgressDialog {
+    /// `sessions` are (id, title) pairs, in list order
+    pub fn new(title: &str, sessions: Vec<(String, String)>) -> Self {
+        Self {
+            title: title.to_string(),
+            rows: sessions
+                .into_iter()
+                .map(|(session_id, title)| Row {
+                    session_id,
+                    title,
+                    outcome: None,
+                })
+                .collect(),
+            scroll: 0,
+        }
+    }
+
+    /// Record the outcome for a session; ignored for sessions not in this run.
+    pub fn set_result(&mut self, session_id: &str, outcome: Result<String, String>) {
+        if let Some(row) = self
+            .rows
+            .iter_mut()
+            .find(|row| row.session_id == session_id && row.outcome.is_none())
+        {
+            row.outcome = Some(outcome);
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.rows.iter().all(|row| row.outcome.is_some())
+    }
+
+    fn counts(&self) -> (usize, usize) {
+        let done = self.rows.iter().filter(|r| r.outcome.is_some()).count();
+        let failed = self
+            .rows
+            .iter()
+            .filter(|r| matches!(r.outcome, Some(Err(_))))
+            .count();
+        (done, failed)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<()> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => DialogResult::Cancel,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = (self.scroll + 1).min(self.rows.len().saturating_sub(1));
+                DialogResult::Continue
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = self.scroll.saturating_sub(1);
+                DialogResult::Continue
+            }
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_width = 72.min(area.width.saturating_sub(4));
+        let dialog_height = (self.rows.len() as u16 + 6).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect {
+            x,
+            y,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" {} ", self.title))
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // sessions
+                Constraint::Length(1), // summary
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        let title_width = self
+            .rows
+            .iter()
+            .map(|row| row.title.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(28);
+        let lines: Vec<Line> = self
+            .rows
+            .iter()
+            .skip(self.scroll)
+            .map(|row| {
+                let (icon, message, color) = match &row.outcome {
+                    None => ("◌", "working...", theme.dimmed),
+                    Some(Ok(message)) => ("✓", message.as_str(), theme.running),
+                    Some(Err(error)) => ("✕", error.as_str(), theme.error),
+                };
+                let title: String = row.title.chars().take(title_width).collect();
+                Line::from(vec![
+                    Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+                    Span::styled(
+                        format!("{:width$}  ", title, width = title_width),
+                        Style::default().fg(theme.text),
+                    ),
+                    Span::styled(message.to_string(), Style::default().fg(color)),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let (done, failed) = self.counts();
+        let mut summary = format!("  {}/{} done", done, self.rows.len());
+        if failed > 0 {
+            summary.push_str(&format!(", {} failed", failed));
+        }
+        let summary_color = if failed > 0 {
+            theme.error
+        } else if self.is_done() {
+            theme.running
+        } else {
+            theme.waiting
+        };
+        frame.render_widget(
+            Paragraph::new(summary).style(Style::default().fg(summary_color)),
+            chunks[1],
+        );
+
+        let hint = if self.is_done() {
+            "Enter/Esc close"
+        } else {
+            "Esc close (keeps running in the background)"
+        };
+        frame.render_widget(
+            Paragraph::new(format!("  {}", hint)).style(Style::default().fg(theme.hint)),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dialog() -> BulkProgressDialog {
+        BulkProgressDialog::new(
+            "Restart",
+            vec![
+                ("a".to_string(), "Alpha".to_string()),
+                ("b".to_string(), "Beta".to_string()),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_done_once_every_session_reported() {
+        let mut dialog = dialog();
+        assert!(!dialog.is_done());
+
+        dialog.set_result("a", Ok("restarted".to_string()));
+        assert!(!dialog.is_done());
+        dialog.set_result("b", Err("tmux failed".to_string()));
+        assert!(dialog.is_done());
+        assert_eq!(dialog.counts(), (2, 1));
+    }
+
+    #[test]
+    fn test_unknown_and_repeated_results_are_ignored() {
+        let mut dialog = dialog();
+        dialog.set_result("other", Ok("stopped".to_string()));
+        assert_eq!(dialog.counts(), (0, 0));
+
+        dialog.set_result("a", Ok("restarted".to_string()));
+        dialog.set_result("a", Err("late".to_string()));
+        assert_eq!(dialog.counts(), (1, 0));
+    }
+}
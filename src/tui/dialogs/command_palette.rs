@@ -0,0 +1,278 @@
+//! Command palette: fuzzy search over the actions of the current view and
+//! anything else worth jumping to
+
+use std::cmp::Reverse;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+/// An entry of the palette
+pub struct PaletteItem<T> {
+    pub target: T,
+    pub title: String,
+    /// Shown dimmed next to the title: the keys of a command, or what kind
+    /// of thing the entry jumps to
+    pub detail: String,
+}
+
+pub struct CommandPalette<T> {
+    items: Vec<PaletteItem<T>>,
+    query: Input,
+    /// Indices into `items` of the entries matching the query, best first
+    matches: Vec<usize>,
+    selected: usize,
+    /// Rows of matches shown at once, set by render
+    visible_rows: usize,
+    matcher: Matcher,
+}
+
+impl<T: Clone> CommandPalette<T> {
+    pub fn new(items: Vec<PaletteItem<T>>) -> Self {
+        let matches = (0..items.len()).collect();
+        Self {
+            items,
+            query: Input::default(),
+            matches,
+            selected: 0,
+            visible_rows: 10,
+            matcher: Matcher::new(Config::DEFAULT),
+        }
+    }
+
+    /// Rank the entries against the query. Ties keep the order the entries
+    /// were given in.
+    fn refilter(&mut self) {
+        let pattern = Pattern::parse(
+            self.query.value(),
+            CaseMatching::Ignore,
+            Normalization::Smart,
+        );
+        let mut buf = Vec::new();
+        let mut scored: Vec<(usize, u32)> = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            let haystack = Utf32Str::new(&item.title, &mut buf);
+            if let Some(score) = pattern.score(haystack, &mut self.matcher) {
+                scored.push((index, score));
+            }
+        }
+        scored.sort_by_key(|(_, score)| Reverse(*score));
+        self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let len = self.matches.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<T> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return DialogResult::Cancel,
+            KeyCode::Enter => {
+                return match self.matches.get(self.selected) {
+                    Some(&index) => DialogResult::Submit(self.items[index].target.clone()),
+                    None => DialogResult::Continue,
+                };
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('p' | 'k') if ctrl => self.move_selection(-1),
+            KeyCode::Char('n' | 'j') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-(self.visible_rows as isize)),
+            KeyCode::PageDown => self.move_selection(self.visible_rows as isize),
+            _ => {
+                let before = self.query.value().to_string();
+                self.query.handle_event(&crossterm::event::Event::Key(key));
+                if self.query.value() != before {
+                    self.refilter();
+                }
+            }
+        }
+        DialogResult::Continue
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_width = 70.min(area.width.saturating_sub(4));
+        let dialog_height = 20.min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect {
+            x,
+            y,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Command Palette ")
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(1)
+            .constraints([
+                Constraint::Length(1), // query
+                Constraint::Length(1), // spacer
+                Constraint::Min(1),    // matches
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        render_text_field(frame, chunks[0], ">", &self.query, true, None, theme);
+
+        let rows = chunks[2].height as usize;
+        self.visible_rows = rows.max(1);
+        // Keep the selection in view
+        let offset = (self.selected + 1).saturating_sub(rows);
+        let width = chunks[2].width as usize;
+
+        let lines: Vec<Line> = if self.matches.is_empty() {
+            vec![Line::from(Span::styled(
+                "  No matches",
+                Style::default().fg(theme.dimmed),
+            ))]
+        } else {
+            self.matches
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(rows)
+                .map(|(pos, &index)| {
+                    let item = &self.items[index];
+                    let selected = pos == self.selected;
+                    let title_style = if selected {
+                        Style::default().fg(theme.accent).bold()
+                    } else {
+                        Style::default().fg(theme.text)
+                    };
+                    let marker = if selected { "▶ " } else { "  " };
+                    let used = 2 + item.title.chars().count();
+                    let detail_width = item.detail.chars().count();
+                    let gap = width.saturating_sub(used + detail_width).max(1);
+                    Line::from(vec![
+                        Span::styled(marker, title_style),
+                        Span::styled(item.title.clone(), title_style),
+                        Span::raw(" ".repeat(gap)),
+                        Span::styled(item.detail.clone(), Style::default().fg(theme.dimmed)),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+        let hint = |key: &'static str, text: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.hint)),
+                Span::styled(text, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let hints: Vec<Span> = [
+            hint("↑/↓", " move  "),
+            hint("Enter", " run  "),
+            hint("Esc", " close"),
+        ]
+        .concat();
+        frame.render_widget(Paragraph::new(Line::from(hints)), chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn palette() -> CommandPalette<&'static str> {
+        let item = |target: &'static str, title: &str| PaletteItem {
+            target,
+            title: title.to_string(),
+            detail: String::new(),
+        };
+        CommandPalette::new(vec![
+            item("new", "New session"),
+            item("delete", "Delete"),
+            item("diff", "Show diff"),
+            item("quit", "Quit"),
+        ])
+    }
+
+    fn type_query(palette: &mut CommandPalette<&'static str>, query: &str) {
+        for c in query.chars() {
+            palette.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    fn submit(palette: &mut CommandPalette<&'static str>) -> Option<&'static str> {
+        match palette.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(target) => Some(target),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_empty_query_lists_everything_in_order() {
+        let mut palette = palette();
+        assert_eq!(palette.matches, vec![0, 1, 2, 3]);
+        assert_eq!(submit(&mut palette), Some("new"));
+    }
+
+    #[test]
+    fn test_query_fuzzy_matches_titles() {
+        let mut palette = palette();
+        type_query(&mut palette, "shdf");
+        assert_eq!(submit(&mut palette), Some("diff"));
+
+        palette.handle_key(key(KeyCode::Backspace));
+        palette.handle_key(key(KeyCode::Backspace));
+        palette.handle_key(key(KeyCode::Backspace));
+        palette.handle_key(key(KeyCode::Backspace));
+        type_query(&mut palette, "zzz");
+        assert!(palette.matches.is_empty());
+        assert_eq!(submit(&mut palette), None);
+    }
+
+    #[test]
+    fn test_selection_wraps_and_resets_on_typing() {
+        let mut palette = palette();
+        palette.handle_key(key(KeyCode::Up));
+        assert_eq!(submit(&mut palette), Some("quit"));
+        palette.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(submit(&mut palette), Some("new"));
+
+        palette.handle_key(key(KeyCode::Down));
+        type_query(&mut palette, "e");
+        assert_eq!(palette.selected, 0);
+    }
+
+    #[test]
+    fn test_escape_cancels() {
+        let mut palette = palette();
+        assert!(matches!(
+            palette.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
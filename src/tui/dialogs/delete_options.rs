@@ -1,10 +1,13 @@
 //! Unified delete dialog
 
-use crossterm::event::{KeyCode, KeyEvent};
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
 use super::DialogResult;
+use crate::tui::components::mouse::{centered, clicked};
 use crate::tui::styles::Theme;
 
 /// Options for what to clean up when deleting a session
@@ -18,6 +21,8 @@ pub struct DeleteOptions {
 /// Configuration for what cleanup options to show in the dialog
 #[derive(Clone, Debug, Default)]
 pub struct DeleteDialogConfig {
+    /// Branch shown next to the worktree options; for several sessions a
+    /// summary such as "2 worktrees"
     pub worktree_branch: Option<String>,
     pub has_sandbox: bool,
 }
@@ -34,7 +39,7 @@ enum FocusElement {
 
 /// Unified delete dialog that adapts based on available cleanup options
 pub struct UnifiedDeleteDialog {
-    session_title: String,
+    message: String,
     config: DeleteDialogConfig,
     options: DeleteOptions,
     focus: FocusElement,
@@ -43,6 +48,16 @@ pub struct UnifiedDeleteDialog {
 
 impl UnifiedDeleteDialog {
     pub fn new(session_title: String, config: DeleteDialogConfig) -> Self {
+        Self::with_message(format!("Delete \"{}\"?", session_title), config)
+    }
+
+    /// Delete dialog for several marked sessions. The options apply to each
+    /// session that has a managed worktree or a container.
+    pub fn for_sessions(count: usize, config: DeleteDialogConfig) -> Self {
+        Self::with_message(format!("Delete {} sessions?", count), config)
+    }
+
+    fn with_message(message: String, config: DeleteDialogConfig) -> Self {
         let user_config = crate::session::Config::load().ok().unwrap_or_default();
 
         let mut focusable_elements = Vec::new();
@@ -74,7 +89,7 @@ impl UnifiedDeleteDialog {
         };
 
         Self {
-            session_title,
+            message,
             config,
             options,
             focus: initial_focus,
@@ -183,40 +198,27 @@ impl UnifiedDeleteDialog {
         }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let has_worktree = self.config.worktree_branch.is_some();
-        let has_sandbox = self.config.has_sandbox;
-        // Count checkboxes: worktree + branch (if worktree exists) + sandbox
-        let checkbox_count = if has_worktree { 2 } else { 0 } + (has_sandbox as u16);
+    fn checkbox_count(&self) -> u16 {
+        // worktree + branch (if worktree exists) + sandbox
+        let worktree = if self.config.worktree_branch.is_some() {
+            2
+        } else {
+            0
+        };
+        worktree + self.config.has_sandbox as u16
+    }
+
+    /// Area of the dialog and of each of its rows
+    fn layout(&self, area: Rect) -> (Rect, Rc<[Rect]>) {
+        let checkbox_count = self.checkbox_count();
 
-        let dialog_width = 55;
         // Add extra height for spacing: 1 after message, 1 before buttons, 1 before hints
         let dialog_height = if checkbox_count > 0 {
             8 + checkbox_count // message + spacer + checkboxes + spacer + buttons + spacer + hints + border
         } else {
             7 // message + spacer + buttons + spacer + hints + border
         };
-
-        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
-        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
-
-        let dialog_area = Rect {
-            x,
-            y,
-            width: dialog_width.min(area.width),
-            height: dialog_height.min(area.height),
-        };
-
-        frame.render_widget(Clear, dialog_area);
-
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme.error))
-            .title(" Delete Session ")
-            .title_style(Style::default().fg(theme.error).bold());
-
-        let inner = block.inner(dialog_area);
-        frame.render_widget(block, dialog_area);
+        let dialog_area = centered(area, 55, dialog_height);
 
         let mut constraints = vec![
             Constraint::Length(1), // message
@@ -237,13 +239,61 @@ impl UnifiedDeleteDialog {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(constraints)
-            .split(inner);
+            .split(Block::default().borders(Borders::ALL).inner(dialog_area));
+        (dialog_area, chunks)
+    }
+
+    /// Handle a mouse event over the dialog rendered in `area`: clicking a
+    /// checkbox toggles it, clicking a button answers the dialog
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) -> DialogResult<DeleteOptions> {
+        let (_, chunks) = self.layout(area);
+        let checkbox_count = self.checkbox_count() as usize;
+
+        // Checkboxes come first among the focusable elements, in row order
+        for (index, rect) in chunks.iter().skip(2).take(checkbox_count).enumerate() {
+            if clicked(*rect, &mouse) {
+                self.focus = self.focusable_elements[index];
+                return self.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+            }
+        }
+
+        let buttons_row = if checkbox_count > 0 {
+            3 + checkbox_count
+        } else {
+            2
+        };
+        let buttons = chunks[buttons_row];
+        if !clicked(buttons, &mouse) {
+            return DialogResult::Continue;
+        }
+        // "  [Yes]    [No]" centered in the row
+        let start = buttons.x + buttons.width.saturating_sub(15) / 2;
+        match mouse.column.saturating_sub(start) {
+            2..=6 => DialogResult::Submit(self.options.clone()),
+            11..=14 => DialogResult::Cancel,
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let has_sandbox = self.config.has_sandbox;
+        let checkbox_count = self.checkbox_count();
+        let (dialog_area, chunks) = self.layout(area);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error))
+            .title(" Delete Session ")
+            .title_style(Style::default().fg(theme.error).bold());
+
+        frame.render_widget(block, dialog_area);
 
         let mut chunk_idx = 0;
 
-        let message = format!("Delete \"{}\"?", self.session_title);
         frame.render_widget(
-            Paragraph::new(message)
+            Paragraph::new(self.message.as_str())
                 .style(Style::default().fg(theme.text))
                 .alignment(Alignment::Center),
             chunks[chunk_idx],
@@ -400,7 +450,7 @@ impl UnifiedDeleteDialog {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crossterm::event::KeyModifiers;
+    use crossterm::event::{MouseButton, MouseEventKind};
 
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
@@ -503,6 +553,47 @@ mod tests {
         assert_eq!(dialog.options.delete_worktree, initial);
     }
 
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_click_toggles_checkbox() {
+        let mut dialog = full_dialog();
+        let area = Rect::new(0, 0, 100, 40);
+        let initial = dialog.options.delete_sandbox;
+
+        // Checkboxes start two rows into the dialog, which sits at row 14
+        let result = dialog.handle_mouse(click(30, 19), area);
+        assert!(matches!(result, DialogResult::Continue));
+        assert_eq!(dialog.focus, FocusElement::SandboxCheckbox);
+        assert_eq!(dialog.options.delete_sandbox, !initial);
+    }
+
+    #[test]
+    fn test_click_buttons() {
+        let mut dialog = simple_dialog();
+        let area = Rect::new(0, 0, 100, 40);
+
+        assert!(matches!(
+            dialog.handle_mouse(click(40, 19), area),
+            DialogResult::Continue
+        ));
+        assert!(matches!(
+            dialog.handle_mouse(click(54, 19), area),
+            DialogResult::Cancel
+        ));
+        assert!(matches!(
+            dialog.handle_mouse(click(45, 19), area),
+            DialogResult::Submit(_)
+        ));
+    }
+
     #[test]
     fn test_esc_cancels() {
         let mut dialog = full_dialog();
@@ -0,0 +1,374 @@
+//! Filter chips for the session list
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::session::{SessionFilters, Status};
+use crate::tui::styles::Theme;
+
+/// Statuses offered as chips; starting and deleting sessions are too
+/// short-lived to filter on
+const STATUSES: [(Status, &str); 4] = [
+    (Status::Waiting, "Waiting"),
+    (Status::Running, "Running"),
+    (Status::Idle, "Idle"),
+    (Status::Error, "Error"),
+];
+
+/// Chips of the yes/no rows, in the order they are shown
+const FLAGS: [(Option<bool>, &str); 3] = [(None, "Any"), (Some(true), "Yes"), (Some(false), "No")];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Status,
+    Tool,
+    Sandboxed,
+    Worktree,
+    Profile,
+}
+
+const ROWS: [(Row, &str); 5] = [
+    (Row::Status, "Status"),
+    (Row::Tool, "Tool"),
+    (Row::Sandboxed, "Sandboxed"),
+    (Row::Worktree, "Worktree"),
+    // Not a filter: shown apart from the chips above it
+    (Row::Profile, "Switch to"),
+];
+
+/// Filters picked in the dialog, and the profile whose sessions to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterSelection {
+    pub filters: SessionFilters,
+    pub profile: String,
+}
+
+pub struct FiltersDialog {
+    selection: FilterSelection,
+    tools: Vec<String>,
+    profiles: Vec<String>,
+    row: usize,
+    chip: usize,
+}
+
+impl FiltersDialog {
+    /// `tools` are the agents to offer chips for; agents filtered on are
+    /// offered even when no session uses them anymore.
+    pub fn new(
+        filters: SessionFilters,
+        mut tools: Vec<String>,
+        current_profile: &str,
+        profiles: Vec<String>,
+    ) -> Self {
+        tools.extend(filters.tools.iter().cloned());
+        tools.sort();
+        tools.dedup();
+        Self {
+            selection: FilterSelection {
+                filters,
+                profile: current_profile.to_string(),
+            },
+            tools,
+            profiles,
+            row: 0,
+            chip: 0,
+        }
+    }
+
+    fn chip_count(&self, row: Row) -> usize {
+        match row {
+            Row::Status => STATUSES.len(),
+            Row::Tool => self.tools.len(),
+            Row::Sandboxed | Row::Worktree => FLAGS.len(),
+            Row::Profile => self.profiles.len(),
+        }
+    }
+
+    /// Chips of a row, with whether each is on
+    fn chips(&self, row: Row) -> Vec<(String, bool)> {
+        let filters = &self.selection.filters;
+        match row {
+            Row::Status => STATUSES
+                .iter()
+                .map(|(status, label)| (label.to_string(), filters.statuses.contains(status)))
+                .collect(),
+            Row::Tool => self
+                .tools
+                .iter()
+                .map(|tool| (tool.clone(), filters.tools.contains(tool)))
+                .collect(),
+            Row::Sandboxed => FLAGS
+                .iter()
+                .map(|(flag, label)| (label.to_string(), filters.sandboxed == *flag))
+                .collect(),
+            Row::Worktree => FLAGS
+                .iter()
+                .map(|(flag, label)| (label.to_string(), filters.worktree == *flag))
+                .collect(),
+            Row::Profile => self
+                .profiles
+                .iter()
+                .map(|profile| (profile.clone(), *profile == self.selection.profile))
+                .collect(),
+        }
+    }
+
+    fn toggle(&mut self) {
+        let filters = &mut self.selection.filters;
+        match ROWS[self.row].0 {
+            Row::Status => {
+                let status = STATUSES[self.chip].0;
+                if let Some(pos) = filters.statuses.iter().position(|s| *s == status) {
+                    filters.statuses.remove(pos);
+                } else {
+                    filters.statuses.push(status);
+                }
+            }
+            Row::Tool => {
+                let Some(tool) = self.tools.get(self.chip) else {
+                    return;
+                };
+                if let Some(pos) = filters.tools.iter().position(|t| t == tool) {
+                    filters.tools.remove(pos);
+                } else {
+                    filters.tools.push(tool.clone());
+                }
+            }
+            Row::Sandboxed => filters.sandboxed = FLAGS[self.chip].0,
+            Row::Worktree => filters.worktree = FLAGS[self.chip].0,
+            Row::Profile => {
+                if let Some(profile) = self.profiles.get(self.chip) {
+                    self.selection.profile = profile.clone();
+                }
+            }
+        }
+    }
+
+    fn move_row(&mut self, row: usize) {
+        self.row = row;
+        let count = self.chip_count(ROWS[row].0);
+        self.chip = self.chip.min(count.saturating_sub(1));
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<FilterSelection> {
+        let chips = self.chip_count(ROWS[self.row].0);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return DialogResult::Cancel,
+            KeyCode::Enter => return DialogResult::Submit(self.selection.clone()),
+            KeyCode::Down | KeyCode::Char('j') => self.move_row((self.row + 1) % ROWS.len()),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_row((self.row + ROWS.len() - 1) % ROWS.len())
+            }
+            KeyCode::Right | KeyCode::Char('l') if chips > 0 => {
+                self.chip = (self.chip + 1) % chips;
+            }
+            KeyCode::Left | KeyCode::Char('h') if chips > 0 => {
+                self.chip = (self.chip + chips - 1) % chips;
+            }
+            KeyCode::Char(' ') if chips > 0 => self.toggle(),
+            KeyCode::Char('c') => self.selection.filters = SessionFilters::default(),
+            _ => {}
+        }
+        DialogResult::Continue
+    }
+
+    fn row_line(&self, row_idx: usize, row: Row, label: &str, theme: &Theme) -> Line<'_> {
+        let focused_row = row_idx == self.row;
+        let label_style = if focused_row {
+            Style::default().fg(theme.accent).bold()
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let mut spans = vec![Span::styled(format!("  {:<11}", label), label_style)];
+        let chips = self.chips(row);
+        if chips.is_empty() {
+            spans.push(Span::styled("none", Style::default().fg(theme.dimmed)));
+        }
+        for (chip_idx, (chip, on)) in chips.into_iter().enumerate() {
+            let mut style = if on {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default().fg(theme.dimmed)
+            };
+            if focused_row && chip_idx == self.chip {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            let marker = if on { "●" } else { "○" };
+            spans.push(Span::styled(format!(" {} {} ", marker, chip), style));
+        }
+        Line::from(spans)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_width = 72.min(area.width.saturating_sub(4));
+        // Chip rows, the line setting the profile row apart, hints and margins
+        let dialog_height = (ROWS.len() as u16 + 6).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect {
+            x,
+            y,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Filter Sessions ")
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // chip rows
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (row_idx, (row, label)) in ROWS.iter().enumerate() {
+            if *row == Row::Profile {
+                lines.push(Line::from(Span::styled(
+                    "  Profile: not a filter, shows another profile's sessions",
+                    Style::default().fg(theme.dimmed),
+                )));
+            }
+            lines.push(self.row_line(row_idx, *row, label, theme));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let hint = |key: &'static str, text: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.hint)),
+                Span::styled(text, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let toggle = if ROWS[self.row].0 == Row::Profile {
+            " pick profile  "
+        } else {
+            " toggle  "
+        };
+        let hints: Vec<Span> = [
+            hint("j/k/h/l", " move  "),
+            hint("Space", toggle),
+            hint("c", " clear  "),
+            hint("Enter", " apply  "),
+            hint("Esc", " cancel"),
+        ]
+        .concat();
+        frame.render_widget(Paragraph::new(Line::from(hints)), chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn dialog() -> FiltersDialog {
+        FiltersDialog::new(
+            SessionFilters::default(),
+            vec!["codex".to_string(), "claude".to_string()],
+            "default",
+            vec!["default".to_string(), "work".to_string()],
+        )
+    }
+
+    fn submit(dialog: &mut FiltersDialog) -> FilterSelection {
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(selection) => selection,
+            _ => panic!("expected Submit"),
+        }
+    }
+
+    #[test]
+    fn test_status_and_tool_chips_toggle() {
+        let mut dialog = dialog();
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        dialog.handle_key(key(KeyCode::Right));
+        dialog.handle_key(key(KeyCode::Right));
+        dialog.handle_key(key(KeyCode::Right));
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        // Tools are sorted, and the chip column stays within the shorter row
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Char(' ')));
+
+        let selection = submit(&mut dialog);
+        assert_eq!(
+            selection.filters.statuses,
+            vec![Status::Waiting, Status::Error]
+        );
+        assert_eq!(selection.filters.tools, vec!["codex".to_string()]);
+
+        // Toggling again turns a chip off
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        assert!(submit(&mut dialog).filters.tools.is_empty());
+    }
+
+    #[test]
+    fn test_flag_rows_pick_one_chip() {
+        let mut dialog = dialog();
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Right));
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Right));
+        dialog.handle_key(key(KeyCode::Char(' ')));
+
+        let selection = submit(&mut dialog);
+        assert_eq!(selection.filters.sandboxed, Some(true));
+        assert_eq!(selection.filters.worktree, Some(false));
+
+        dialog.handle_key(key(KeyCode::Char('c')));
+        assert!(submit(&mut dialog).filters.is_empty());
+    }
+
+    #[test]
+    fn test_profile_chip_picks_profile() {
+        let mut dialog = dialog();
+        dialog.handle_key(key(KeyCode::Up));
+        assert_eq!(submit(&mut dialog).profile, "default");
+        dialog.handle_key(key(KeyCode::Right));
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        assert_eq!(submit(&mut dialog).profile, "work");
+
+        // Clearing the filters leaves the profile alone
+        dialog.handle_key(key(KeyCode::Char('c')));
+        assert_eq!(submit(&mut dialog).profile, "work");
+    }
+
+    #[test]
+    fn test_filtered_tools_stay_offered() {
+        let filters = SessionFilters {
+            tools: vec!["gemini".to_string()],
+            ..Default::default()
+        };
+        let dialog = FiltersDialog::new(filters, vec!["claude".to_string()], "default", vec![]);
+        assert_eq!(dialog.tools, vec!["claude", "gemini"]);
+    }
+
+    #[test]
+    fn test_escape_cancels() {
+        let mut dialog = dialog();
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
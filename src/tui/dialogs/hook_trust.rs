@@ -110,6 +110,28 @@ impl HookTrustDialog {
             for cmd in &self.hooks.on_launch {
                 lines.push(Line::from(format!("  {}", cmd)));
             }
+            lines.push(Line::from(""));
+        }
+
+        if !self.hooks.devcontainer_on_create.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "postCreateCommand (devcontainer.json, sandbox only):",
+                Style::default().bold(),
+            )));
+            for cmd in &self.hooks.devcontainer_on_create {
+                lines.push(Line::from(format!("  {}", cmd)));
+            }
+            lines.push(Line::from(""));
+        }
+
+        if !self.hooks.devcontainer_settings.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "containerEnv and mounts (devcontainer.json, sandbox only):",
+                Style::default().bold(),
+            )));
+            for setting in &self.hooks.devcontainer_settings {
+                lines.push(Line::from(format!("  {}", setting)));
+            }
         }
 
         lines
@@ -215,6 +237,8 @@ mod tests {
             HooksConfig {
                 on_create: vec!["npm install".to_string()],
                 on_launch: vec!["echo start".to_string()],
+                devcontainer_on_create: vec![],
+                devcontainer_settings: vec![],
             },
             "abc123".to_string(),
             "/home/user/project".to_string(),
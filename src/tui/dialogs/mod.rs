@@ -1,23 +1,35 @@
 //! TUI dialog components
 
+mod bulk_actions;
+mod bulk_progress;
 mod changelog;
+mod command_palette;
 mod confirm;
 mod delete_options;
+mod filters;
 mod group_delete_options;
 mod hook_trust;
 mod info;
 mod new_session;
+mod ports;
 mod rename;
+mod snapshots;
 mod welcome;
 
+pub use bulk_actions::{BulkAction, BulkActionsDialog};
+pub use bulk_progress::BulkProgressDialog;
 pub use changelog::ChangelogDialog;
+pub use command_palette::{CommandPalette, PaletteItem};
 pub use confirm::ConfirmDialog;
 pub use delete_options::{DeleteDialogConfig, DeleteOptions, UnifiedDeleteDialog};
+pub use filters::{FilterSelection, FiltersDialog};
 pub use group_delete_options::{GroupDeleteOptions, GroupDeleteOptionsDialog};
 pub use hook_trust::{HookTrustAction, HookTrustDialog};
 pub use info::InfoDialog;
 pub use new_session::{NewSessionData, NewSessionDialog};
+pub use ports::{PortsAction, PortsDialog};
 pub use rename::{RenameData, RenameDialog};
+pub use snapshots::{SnapshotAction, SnapshotsDialog};
 pub use welcome::WelcomeDialog;
 
 pub enum DialogResult<T> {
@@ -5,17 +5,21 @@ mod render;
 #[cfg(test)]
 mod tests;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use render::Row;
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use super::DialogResult;
-use crate::docker;
+use crate::docker::{self, ContainerRuntime};
 use crate::session::repo_config::HookProgress;
 #[cfg(test)]
 use crate::session::Config;
-use crate::session::{civilizations, resolve_config};
+use crate::session::{civilizations, resolve_config, validate_cpu_limit, validate_memory_limit};
 use crate::tmux::AvailableTools;
+use crate::tui::components::mouse::hit;
+use crate::tui::diff::BranchSelectState;
 
 pub(super) struct FieldHelp {
     pub(super) name: &'static str,
@@ -50,6 +54,10 @@ pub(super) const FIELD_HELP: &[FieldHelp] = &[
         description:
             "Checked: create new branch. Unchecked: use existing (creates worktree if needed)",
     },
+    FieldHelp {
+        name: "Base Ref",
+        description: "Branch, tag or commit to start from (empty uses HEAD); Ctrl+b lists refs",
+    },
     FieldHelp {
         name: "Sandbox",
         description: "Run session in Docker container for isolation",
@@ -58,18 +66,31 @@ pub(super) const FIELD_HELP: &[FieldHelp] = &[
         name: "Image",
         description: "Docker image. Edit config.toml [sandbox] default_image to change default",
     },
+    FieldHelp {
+        name: "CPUs",
+        description: "CPU limit for this container, e.g. 2 (empty uses [sandbox] cpu_limit)",
+    },
+    FieldHelp {
+        name: "Memory",
+        description: "Memory limit for this container, e.g. 8g (empty uses [sandbox] memory_limit)",
+    },
     FieldHelp {
         name: "YOLO Mode",
         description:
             "Skip permission prompts for autonomous operation (--dangerously-skip-permissions)",
     },
+    FieldHelp {
+        name: "Isolated",
+        description: "Mount the project read-only; review and apply the agent's changes later",
+    },
     FieldHelp {
         name: "Environment",
         description: "Env var names to pass from host to container (extends global config)",
     },
     FieldHelp {
         name: "Environment Values",
-        description: "Custom KEY=VALUE env vars injected into the sandbox container",
+        description:
+            "KEY=VALUE env vars for the sandbox; values may be $VAR, file:, cmd: or keyring:",
     },
 ];
 
@@ -81,10 +102,18 @@ pub struct NewSessionData {
     pub tool: String,
     pub worktree_branch: Option<String>,
     pub create_new_branch: bool,
+    /// Ref the new worktree branch starts from (HEAD when None).
+    pub base_ref: Option<String>,
     pub sandbox: bool,
     /// The sandbox image to use (always populated from the input field).
     pub sandbox_image: String,
+    /// Session CPU limit; None falls back to the configured default.
+    pub cpu_limit: Option<String>,
+    /// Session memory limit; None falls back to the configured default.
+    pub memory_limit: Option<String>,
     pub yolo_mode: bool,
+    /// Mount the project read-only and let the agent work on a copy.
+    pub isolated: bool,
     /// Additional environment variable keys to pass from host to container.
     pub extra_env_keys: Vec<String>,
     /// Custom KEY=VALUE environment variables to inject into the container.
@@ -105,10 +134,18 @@ pub struct NewSessionDialog {
     pub(super) existing_titles: Vec<String>,
     pub(super) worktree_branch: Input,
     pub(super) create_new_branch: bool,
+    pub(super) base_ref: Input,
+    /// Refs listed for the base ref after pressing Ctrl+b on it
+    pub(super) base_ref_select: Option<BranchSelectState>,
     pub(super) sandbox_enabled: bool,
     pub(super) sandbox_image: Input,
+    pub(super) cpu_limit: Input,
+    pub(super) memory_limit: Input,
     pub(super) docker_available: bool,
+    /// Container runtime of the profile
+    pub(super) runtime: ContainerRuntime,
     pub(super) yolo_mode: bool,
+    pub(super) isolated: bool,
     /// Extra environment variable keys (session-specific)
     pub(super) extra_env_keys: Vec<String>,
     /// Whether the env list is expanded (editing mode)
@@ -135,6 +172,8 @@ pub struct NewSessionDialog {
     pub(super) needs_image_pull: bool,
     /// Whether hooks are being executed during loading
     pub(super) has_hooks: bool,
+    /// Whether the sandbox image is built from a Dockerfile during loading
+    pub(super) builds_image: bool,
     /// The currently running hook command
     pub(super) current_hook: Option<String>,
     /// Accumulated output lines from hook execution
@@ -148,10 +187,10 @@ impl NewSessionDialog {
             .unwrap_or_default();
 
         let available_tools = tools.available_list();
-        let docker_available = docker::is_docker_available();
-
         // Load resolved config (global merged with profile overrides)
         let config = resolve_config(profile).unwrap_or_default();
+        let runtime = config.sandbox.runtime;
+        let docker_available = runtime.is_available();
 
         // Determine default tool index based on config
         let tool_index = if let Some(ref default_tool) = config.session.default_tool {
@@ -191,9 +230,15 @@ impl NewSessionDialog {
             existing_titles,
             worktree_branch: Input::default(),
             create_new_branch: true,
+            base_ref: Input::default(),
+            base_ref_select: None,
             sandbox_enabled,
             sandbox_image: Input::new(docker::effective_default_image()),
+            isolated: false,
+            cpu_limit: Input::default(),
+            memory_limit: Input::default(),
             docker_available,
+            runtime,
             yolo_mode,
             extra_env_keys,
             env_list_expanded: false,
@@ -211,6 +256,7 @@ impl NewSessionDialog {
             spinner_frame: 0,
             needs_image_pull: false,
             has_hooks: false,
+            builds_image: false,
             current_hook: None,
             hook_output: Vec::new(),
         }
@@ -221,6 +267,11 @@ impl NewSessionDialog {
         self.has_hooks = has_hooks;
     }
 
+    /// Set whether the sandbox image will be built from a Dockerfile
+    pub fn set_builds_image(&mut self, builds_image: bool) {
+        self.builds_image = builds_image;
+    }
+
     /// Push a hook progress message into the dialog state
     pub fn push_hook_progress(&mut self, progress: HookProgress) {
         match progress {
@@ -241,7 +292,7 @@ impl NewSessionDialog {
             // Check if image pull will be needed (only relevant for sandbox sessions)
             if self.sandbox_enabled {
                 let image = self.sandbox_image.value().trim();
-                self.needs_image_pull = !docker::image_exists_locally(image);
+                self.needs_image_pull = !self.runtime.image_exists_locally(image);
             }
         }
     }
@@ -278,9 +329,15 @@ impl NewSessionDialog {
             existing_titles: Vec::new(),
             worktree_branch: Input::default(),
             create_new_branch: true,
+            base_ref: Input::default(),
+            base_ref_select: None,
             sandbox_enabled: false,
             sandbox_image: Input::new(docker::effective_default_image()),
+            isolated: false,
+            cpu_limit: Input::default(),
+            memory_limit: Input::default(),
             docker_available: false,
+            runtime: ContainerRuntime::default(),
             yolo_mode: false,
             extra_env_keys: Vec::new(),
             env_list_expanded: false,
@@ -298,6 +355,7 @@ impl NewSessionDialog {
             spinner_frame: 0,
             needs_image_pull: false,
             has_hooks: false,
+            builds_image: false,
             current_hook: None,
             hook_output: Vec::new(),
         }
@@ -316,9 +374,15 @@ impl NewSessionDialog {
             existing_titles: Vec::new(),
             worktree_branch: Input::default(),
             create_new_branch: true,
+            base_ref: Input::default(),
+            base_ref_select: None,
             sandbox_enabled: false,
             sandbox_image: Input::new(docker::effective_default_image()),
+            isolated: false,
+            cpu_limit: Input::default(),
+            memory_limit: Input::default(),
             docker_available: false,
+            runtime: ContainerRuntime::default(),
             yolo_mode: false,
             extra_env_keys: Vec::new(),
             env_list_expanded: false,
@@ -336,6 +400,7 @@ impl NewSessionDialog {
             spinner_frame: 0,
             needs_image_pull: false,
             has_hooks: false,
+            builds_image: false,
             current_hook: None,
             hook_output: Vec::new(),
         }
@@ -365,8 +430,9 @@ impl NewSessionDialog {
         let has_tool_selection = self.available_tools.len() > 1;
         let has_sandbox = self.docker_available;
         let has_worktree = !self.worktree_branch.value().is_empty();
+        let has_base_ref = has_worktree && self.create_new_branch;
         let sandbox_options_visible = has_sandbox && self.sandbox_enabled;
-        // Fields: title(0), path(1), group(2), [tool(3)], worktree(3/4), [new_branch(4/5)], [sandbox(5/6)], [image(6/7)], [yolo(7/8)], [env(8/9)]
+        // Fields: title(0), path(1), group(2), [tool(3)], worktree(3/4), [new_branch], [base_ref], [sandbox], [image], [cpus], [memory], [yolo], [isolated], [env], [env_values]
         let tool_field = if has_tool_selection { 3 } else { usize::MAX };
         let worktree_field = if has_tool_selection { 4 } else { 3 };
         let new_branch_field = if has_worktree {
@@ -374,12 +440,20 @@ impl NewSessionDialog {
         } else {
             usize::MAX
         };
+        let base_ref_field = if has_base_ref {
+            new_branch_field + 1
+        } else {
+            usize::MAX
+        };
+        let last_worktree_field = if has_base_ref {
+            base_ref_field
+        } else if has_worktree {
+            new_branch_field
+        } else {
+            worktree_field
+        };
         let sandbox_field = if has_sandbox {
-            if has_worktree {
-                new_branch_field + 1
-            } else {
-                worktree_field + 1
-            }
+            last_worktree_field + 1
         } else {
             usize::MAX
         };
@@ -388,16 +462,31 @@ impl NewSessionDialog {
         } else {
             usize::MAX
         };
-        let yolo_mode_field = if sandbox_options_visible {
+        let cpu_limit_field = if sandbox_options_visible {
             sandbox_image_field + 1
         } else {
             usize::MAX
         };
-        let env_field = if sandbox_options_visible {
+        let memory_limit_field = if sandbox_options_visible {
+            cpu_limit_field + 1
+        } else {
+            usize::MAX
+        };
+        let yolo_mode_field = if sandbox_options_visible {
+            memory_limit_field + 1
+        } else {
+            usize::MAX
+        };
+        let isolated_field = if sandbox_options_visible {
             yolo_mode_field + 1
         } else {
             usize::MAX
         };
+        let env_field = if sandbox_options_visible {
+            isolated_field + 1
+        } else {
+            usize::MAX
+        };
         let env_values_field = if sandbox_options_visible {
             env_field + 1
         } else {
@@ -407,12 +496,14 @@ impl NewSessionDialog {
             env_values_field + 1
         } else if has_sandbox {
             sandbox_field + 1
-        } else if has_worktree {
-            new_branch_field + 1
         } else {
-            worktree_field + 1
+            last_worktree_field + 1
         };
 
+        if self.base_ref_select.is_some() {
+            return self.handle_base_ref_select_key(key);
+        }
+
         // Handle env list editing mode
         if self.env_list_expanded && self.focused_field == env_field {
             return self.handle_env_list_key(key);
@@ -430,6 +521,13 @@ impl NewSessionDialog {
                 self.error_message = None;
                 DialogResult::Cancel
             }
+            KeyCode::Char('b')
+                if self.focused_field == base_ref_field
+                    && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.open_base_ref_select();
+                DialogResult::Continue
+            }
             KeyCode::Enter if self.focused_field == env_field => {
                 self.env_list_expanded = true;
                 self.env_selected_index = 0;
@@ -455,6 +553,29 @@ impl NewSessionDialog {
                 } else {
                     Some(worktree_value.to_string())
                 };
+                let base_ref_value = self.base_ref.value().trim();
+                let base_ref = if has_base_ref && !base_ref_value.is_empty() {
+                    Some(base_ref_value.to_string())
+                } else {
+                    None
+                };
+                let cpu_limit = self.cpu_limit.value().trim();
+                let memory_limit = self.memory_limit.value().trim();
+                if self.sandbox_enabled {
+                    if let Err(e) = validate_cpu_limit(cpu_limit) {
+                        self.error_message = Some(e);
+                        self.focused_field = cpu_limit_field;
+                        return DialogResult::Continue;
+                    }
+                    if let Err(e) = validate_memory_limit(memory_limit) {
+                        self.error_message = Some(e);
+                        self.focused_field = memory_limit_field;
+                        return DialogResult::Continue;
+                    }
+                }
+                let non_empty = |value: &str| {
+                    (self.sandbox_enabled && !value.is_empty()).then(|| value.to_string())
+                };
                 DialogResult::Submit(NewSessionData {
                     title: final_title,
                     path: self.path.value().trim().to_string(),
@@ -462,9 +583,13 @@ impl NewSessionDialog {
                     tool: self.available_tools[self.tool_index].to_string(),
                     worktree_branch,
                     create_new_branch: self.create_new_branch,
+                    base_ref,
                     sandbox: self.sandbox_enabled,
                     sandbox_image: self.sandbox_image.value().trim().to_string(),
+                    cpu_limit: non_empty(cpu_limit),
+                    memory_limit: non_empty(memory_limit),
                     yolo_mode: self.sandbox_enabled && self.yolo_mode,
+                    isolated: self.sandbox_enabled && self.isolated,
                     extra_env_keys: if self.sandbox_enabled {
                         self.extra_env_keys.clone()
                     } else {
@@ -520,6 +645,7 @@ impl NewSessionDialog {
                         .collect();
                 } else {
                     self.yolo_mode = false;
+                    self.isolated = false;
                     self.extra_env_keys.clear();
                     self.env_list_expanded = false;
                     self.env_editing_input = None;
@@ -538,11 +664,18 @@ impl NewSessionDialog {
                 self.yolo_mode = !self.yolo_mode;
                 DialogResult::Continue
             }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
+                if self.focused_field == isolated_field =>
+            {
+                self.isolated = !self.isolated;
+                DialogResult::Continue
+            }
             _ => {
                 if self.focused_field != tool_field
                     && self.focused_field != new_branch_field
                     && self.focused_field != sandbox_field
                     && self.focused_field != yolo_mode_field
+                    && self.focused_field != isolated_field
                     && self.focused_field != env_field
                     && self.focused_field != env_values_field
                 {
@@ -555,6 +688,112 @@ impl NewSessionDialog {
         }
     }
 
+    /// List the project's refs to pick the base ref from
+    fn open_base_ref_select(&mut self) {
+        let path = std::path::Path::new(self.path.value().trim());
+        match crate::git::diff::list_base_refs(path) {
+            Ok(branches) if branches.is_empty() => {
+                self.error_message = Some("No branches or tags to start from".to_string());
+            }
+            Ok(branches) => {
+                let current = self.base_ref.value().trim();
+                let selected = branches.iter().position(|b| b == current).unwrap_or(0);
+                self.base_ref_select = Some(BranchSelectState { branches, selected });
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list refs: {}", e));
+            }
+        }
+    }
+
+    fn handle_base_ref_select_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
+        let Some(state) = &mut self.base_ref_select else {
+            return DialogResult::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.base_ref_select = None;
+            }
+            KeyCode::Enter => {
+                if let Some(branch) = state.branches.get(state.selected) {
+                    self.base_ref = Input::new(branch.clone());
+                }
+                self.base_ref_select = None;
+            }
+            KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                state.selected -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if state.selected < state.branches.len().saturating_sub(1) =>
+            {
+                state.selected += 1;
+            }
+            _ => {}
+        }
+        DialogResult::Continue
+    }
+
+    /// Handle a mouse event over the dialog rendered in `area`: a click
+    /// focuses the field under it, and toggles checkboxes or picks the tool
+    /// clicked on.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) {
+        // Keys belong to the loading overlay, the help, the ref list and
+        // open env lists
+        if self.loading
+            || self.show_help
+            || self.base_ref_select.is_some()
+            || self.env_list_expanded
+            || self.env_values_list_expanded
+            || mouse.kind != MouseEventKind::Down(MouseButton::Left)
+        {
+            return;
+        }
+        let has_tool_selection = self.available_tools.len() > 1;
+        let (_, rows) = self.layout(area);
+
+        // Fields are numbered in row order, skipping the read-only tool row
+        let mut field = 0;
+        for (row, rect) in rows {
+            if row == Row::Hints {
+                break;
+            }
+            if row == Row::Tool && !has_tool_selection {
+                continue;
+            }
+            if hit(rect, &mouse) {
+                self.focused_field = field;
+                match row {
+                    Row::Checkbox => {
+                        self.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+                    }
+                    Row::Tool => {
+                        if let Some(index) = self.tool_at(mouse.column.saturating_sub(rect.x)) {
+                            self.tool_index = index;
+                        }
+                    }
+                    _ => {}
+                }
+                return;
+            }
+            field += 1;
+        }
+    }
+
+    /// Tool shown at a column of the tool row, laid out as
+    /// "Tool: ● claude  ○ opencode"
+    fn tool_at(&self, column: u16) -> Option<usize> {
+        let mut start = "Tool: ".len() as u16;
+        for (index, tool) in self.available_tools.iter().enumerate() {
+            let end = start + 2 + tool.chars().count() as u16;
+            if (start..end).contains(&column) {
+                return Some(index);
+            }
+            start = end + 2;
+        }
+        None
+    }
+
     /// Handle key events when the env list is expanded
     fn handle_env_list_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
         // Handle text input mode (editing or adding)
@@ -720,6 +959,7 @@ impl NewSessionDialog {
     fn current_input_mut(&mut self) -> &mut Input {
         let has_tool_selection = self.available_tools.len() > 1;
         let has_worktree = !self.worktree_branch.value().is_empty();
+        let has_base_ref = has_worktree && self.create_new_branch;
 
         let worktree_field = if has_tool_selection { 4 } else { 3 };
         let new_branch_field = if has_worktree {
@@ -727,8 +967,15 @@ impl NewSessionDialog {
         } else {
             usize::MAX
         };
+        let base_ref_field = if has_base_ref {
+            new_branch_field + 1
+        } else {
+            usize::MAX
+        };
         let sandbox_field = if self.docker_available {
-            if has_worktree {
+            if has_base_ref {
+                base_ref_field + 1
+            } else if has_worktree {
                 new_branch_field + 1
             } else {
                 worktree_field + 1
@@ -741,13 +988,22 @@ impl NewSessionDialog {
         } else {
             usize::MAX
         };
+        let (cpu_limit_field, memory_limit_field) = if self.docker_available && self.sandbox_enabled
+        {
+            (sandbox_field + 2, sandbox_field + 3)
+        } else {
+            (usize::MAX, usize::MAX)
+        };
 
         match self.focused_field {
             0 => &mut self.title,
             1 => &mut self.path,
             2 => &mut self.group,
             n if n == worktree_field => &mut self.worktree_branch,
+            n if n == base_ref_field => &mut self.base_ref,
             n if n == sandbox_image_field => &mut self.sandbox_image,
+            n if n == cpu_limit_field => &mut self.cpu_limit,
+            n if n == memory_limit_field => &mut self.memory_limit,
             _ => &mut self.title,
         }
     }
@@ -4,85 +4,115 @@ use ratatui::prelude::*;
 use ratatui::widgets::*;
 
 use super::{NewSessionDialog, FIELD_HELP, HELP_DIALOG_WIDTH, SPINNER_FRAMES};
+use crate::session::secrets;
+use crate::tui::components::mouse::centered;
 use crate::tui::components::render_text_field;
 use crate::tui::styles::Theme;
 
-impl NewSessionDialog {
-    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        // If loading, render the loading overlay instead
-        if self.loading {
-            self.render_loading(frame, area, theme);
-            return;
-        }
+/// What a row of the dialog holds, for telling what a click landed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Row {
+    Text,
+    Tool,
+    Checkbox,
+    EnvList,
+    Hints,
+}
 
-        let has_tool_selection = self.available_tools.len() > 1;
+impl NewSessionDialog {
+    /// Rows of the dialog with their heights, in the order they are shown
+    fn rows(&self) -> Vec<(Row, u16)> {
         let has_sandbox = self.docker_available;
         let has_worktree = !self.worktree_branch.value().is_empty();
+        let has_base_ref = has_worktree && self.create_new_branch;
         let sandbox_options_visible = has_sandbox && self.sandbox_enabled;
-        let dialog_width = 80;
-        // Calculate env list heights based on expanded state and number of items
-        let env_list_height: u16 = if sandbox_options_visible {
-            if self.env_list_expanded {
-                (2 + self.extra_env_keys.len() as u16).clamp(4, 8)
-            } else {
-                2
-            }
-        } else {
-            0
-        };
-        let env_values_list_height: u16 = if sandbox_options_visible {
-            if self.env_values_list_expanded {
-                (2 + self.extra_env_values.len() as u16).clamp(4, 8)
+        // Env lists grow with their items while expanded
+        let list_height = |expanded: bool, len: usize| {
+            if expanded {
+                (2 + len as u16).clamp(4, 8)
             } else {
                 2
             }
-        } else {
-            0
         };
 
-        // Build constraints dynamically based on visible fields only
-        let mut constraints = vec![
-            Constraint::Length(2), // Title
-            Constraint::Length(2), // Path
-            Constraint::Length(2), // Group
-            Constraint::Length(2), // Tool (always shown, interactive or not)
-            Constraint::Length(2), // Worktree Branch
+        // Tool is always shown, interactive or not
+        let mut rows = vec![
+            (Row::Text, 2), // Title
+            (Row::Text, 2), // Path
+            (Row::Text, 2), // Group
+            (Row::Tool, 2),
+            (Row::Text, 2), // Worktree Branch
         ];
         if has_worktree {
-            constraints.push(Constraint::Length(2)); // New Branch checkbox
+            rows.push((Row::Checkbox, 2)); // New Branch
+        }
+        if has_base_ref {
+            rows.push((Row::Text, 2)); // Base Ref
         }
         if has_sandbox {
-            constraints.push(Constraint::Length(2)); // Sandbox checkbox
+            rows.push((Row::Checkbox, 2)); // Sandbox
         }
         if sandbox_options_visible {
-            constraints.push(Constraint::Length(2)); // Image field
-            constraints.push(Constraint::Length(2)); // YOLO mode checkbox
-            constraints.push(Constraint::Length(env_list_height)); // Env vars field
-            constraints.push(Constraint::Length(env_values_list_height)); // Env values field
+            rows.push((Row::Text, 2)); // Image
+            rows.push((Row::Text, 2)); // CPUs
+            rows.push((Row::Text, 2)); // Memory
+            rows.push((Row::Checkbox, 2)); // YOLO Mode
+            rows.push((Row::Checkbox, 2)); // Isolated
+            rows.push((
+                Row::EnvList,
+                list_height(self.env_list_expanded, self.extra_env_keys.len()),
+            ));
+            rows.push((
+                Row::EnvList,
+                list_height(self.env_values_list_expanded, self.extra_env_values.len()),
+            ));
         }
-        constraints.push(Constraint::Min(1)); // Hints/errors
+        rows.push((Row::Hints, 1));
+        rows
+    }
+
+    /// Area of the dialog and of each of its rows
+    pub(super) fn layout(&self, area: Rect) -> (Rect, Vec<(Row, Rect)>) {
+        let rows = self.rows();
+        // border (2) + margin (2) + sum of row heights
+        let dialog_height = rows.iter().map(|(_, height)| height).sum::<u16>() + 4;
+        let dialog_area = centered(area, 80, dialog_height);
 
-        // Compute dialog height from actual constraints
-        // border (2) + margin (2) + sum of field heights + hint line (2)
-        let fields_height: u16 = constraints
+        // The hints row takes whatever room is left
+        let constraints: Vec<Constraint> = rows
             .iter()
-            .map(|c| match c {
-                Constraint::Length(n) => *n,
-                Constraint::Min(n) => *n,
-                _ => 0,
+            .map(|(row, height)| match row {
+                Row::Hints => Constraint::Min(*height),
+                _ => Constraint::Length(*height),
             })
-            .sum();
-        let dialog_height = fields_height + 4; // +2 border, +2 margin
+            .collect();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(constraints)
+            .split(Block::default().borders(Borders::ALL).inner(dialog_area));
 
-        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
-        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let rows = rows
+            .iter()
+            .zip(chunks.iter())
+            .map(|((row, _), rect)| (*row, *rect))
+            .collect();
+        (dialog_area, rows)
+    }
 
-        let dialog_area = Rect {
-            x,
-            y,
-            width: dialog_width.min(area.width),
-            height: dialog_height.min(area.height),
-        };
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // If loading, render the loading overlay instead
+        if self.loading {
+            self.render_loading(frame, area, theme);
+            return;
+        }
+
+        let has_tool_selection = self.available_tools.len() > 1;
+        let has_sandbox = self.docker_available;
+        let has_worktree = !self.worktree_branch.value().is_empty();
+        let has_base_ref = has_worktree && self.create_new_branch;
+        let sandbox_options_visible = has_sandbox && self.sandbox_enabled;
+        let (dialog_area, rows) = self.layout(area);
 
         let clear = Clear;
         frame.render_widget(clear, dialog_area);
@@ -93,14 +123,9 @@ impl NewSessionDialog {
             .title(" New Session ")
             .title_style(Style::default().fg(theme.title).bold());
 
-        let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints(constraints)
-            .split(inner);
+        let chunks: Vec<Rect> = rows.iter().map(|(_, rect)| *rect).collect();
 
         // Render fields sequentially, tracking chunk index to match dynamic constraints
         let mut ci = 0; // chunk index
@@ -214,9 +239,26 @@ impl NewSessionDialog {
             ci += 1;
         }
 
+        // Base Ref (only when creating a new worktree branch)
+        let base_ref_field = new_branch_field + 1;
+        if has_base_ref {
+            render_text_field(
+                frame,
+                chunks[ci],
+                "Base Ref:",
+                &self.base_ref,
+                self.focused_field == base_ref_field,
+                Some("(HEAD; Ctrl+b lists refs)"),
+                theme,
+            );
+            ci += 1;
+        }
+
         // Sandbox checkbox (only when Docker available)
         if has_sandbox {
-            let sandbox_field = if has_worktree {
+            let sandbox_field = if has_base_ref {
+                base_ref_field + 1
+            } else if has_worktree {
                 new_branch_field + 1
             } else {
                 worktree_field + 1
@@ -265,8 +307,33 @@ impl NewSessionDialog {
                 );
                 ci += 1;
 
+                // Resource limits (empty falls back to the config)
+                let cpu_limit_field = sandbox_image_field + 1;
+                render_text_field(
+                    frame,
+                    chunks[ci],
+                    "  CPUs:",
+                    &self.cpu_limit,
+                    self.focused_field == cpu_limit_field,
+                    Some("(config default)"),
+                    theme,
+                );
+                ci += 1;
+
+                let memory_limit_field = cpu_limit_field + 1;
+                render_text_field(
+                    frame,
+                    chunks[ci],
+                    "  Memory:",
+                    &self.memory_limit,
+                    self.focused_field == memory_limit_field,
+                    Some("(config default)"),
+                    theme,
+                );
+                ci += 1;
+
                 // YOLO Mode checkbox
-                let yolo_mode_field = sandbox_image_field + 1;
+                let yolo_mode_field = memory_limit_field + 1;
                 let is_yolo_focused = self.focused_field == yolo_mode_field;
                 let yolo_label_style = if is_yolo_focused {
                     Style::default().fg(theme.accent).underlined()
@@ -297,8 +364,36 @@ impl NewSessionDialog {
                 frame.render_widget(Paragraph::new(yolo_line), chunks[ci]);
                 ci += 1;
 
+                // Isolated workspace checkbox
+                let isolated_field = yolo_mode_field + 1;
+                let isolated_label_style = if self.focused_field == isolated_field {
+                    Style::default().fg(theme.accent).underlined()
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let (isolated_checkbox, isolated_style) = if self.isolated {
+                    ("[x]", Style::default().fg(theme.accent).bold())
+                } else {
+                    ("[ ]", Style::default().fg(theme.dimmed))
+                };
+                let isolated_line = Line::from(vec![
+                    Span::styled("  Isolated:", isolated_label_style),
+                    Span::raw(" "),
+                    Span::styled(isolated_checkbox, isolated_style),
+                    Span::styled(
+                        " Project read-only, apply changes later",
+                        if self.isolated {
+                            Style::default().fg(theme.accent)
+                        } else {
+                            Style::default().fg(theme.dimmed)
+                        },
+                    ),
+                ]);
+                frame.render_widget(Paragraph::new(isolated_line), chunks[ci]);
+                ci += 1;
+
                 // Environment variables field
-                let env_field = yolo_mode_field + 1;
+                let env_field = isolated_field + 1;
                 self.render_env_field(frame, chunks[ci], env_field, theme);
                 ci += 1;
 
@@ -346,11 +441,69 @@ impl NewSessionDialog {
             frame.render_widget(Paragraph::new(hint), chunks[hint_chunk]);
         }
 
+        if self.base_ref_select.is_some() {
+            self.render_base_ref_select(frame, area, theme);
+        }
+
         if self.show_help {
             self.render_help_overlay(frame, area, theme);
         }
     }
 
+    fn render_base_ref_select(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(state) = &self.base_ref_select else {
+            return;
+        };
+
+        let dialog_width = 40u16.min(area.width);
+        let dialog_height = (state.branches.len() as u16 + 2).min(20).min(area.height);
+        let dialog_area = Rect {
+            x: area.x + (area.width.saturating_sub(dialog_width)) / 2,
+            y: area.y + (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(" Base Ref ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.background));
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        // Keep the selection in view when there are more refs than rows
+        let visible = inner.height as usize;
+        let offset = state.selected.saturating_sub(visible.saturating_sub(1));
+        let items: Vec<ListItem> = state
+            .branches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, branch)| {
+                let is_selected = i == state.selected;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let prefix = if is_selected { "> " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(branch.as_str(), style),
+                ]))
+            })
+            .collect();
+
+        frame.render_widget(List::new(items), inner);
+    }
+
     fn render_env_field(&self, frame: &mut Frame, area: Rect, env_field: usize, theme: &Theme) {
         let is_focused = self.focused_field == env_field;
         let label_style = if is_focused {
@@ -520,7 +673,7 @@ impl NewSessionDialog {
                             "    "
                         };
                         lines.push(Line::from(Span::styled(
-                            format!("{}{}", prefix, entry),
+                            format!("{}{}", prefix, secrets::mask_entry(entry)),
                             Style::default().fg(theme.text),
                         )));
                     }
@@ -544,7 +697,7 @@ impl NewSessionDialog {
                             lines.push(input_line);
                         } else {
                             lines.push(Line::from(Span::styled(
-                                format!("    {}", entry),
+                                format!("    {}", secrets::mask_entry(entry)),
                                 Style::default().fg(theme.text),
                             )));
                         }
@@ -565,7 +718,7 @@ impl NewSessionDialog {
                         Style::default().fg(theme.text)
                     };
                     lines.push(Line::from(Span::styled(
-                        format!("{}{}", prefix, entry),
+                        format!("{}{}", prefix, secrets::mask_entry(entry)),
                         style,
                     )));
                 }
@@ -581,7 +734,7 @@ impl NewSessionDialog {
         let show_sandbox_options_help = has_sandbox && self.sandbox_enabled;
 
         let dialog_width: u16 = HELP_DIALOG_WIDTH;
-        let base_height: u16 = 17;
+        let base_height: u16 = 20;
         let dialog_height: u16 = base_height
             + if has_tool_selection { 3 } else { 0 }
             + if has_sandbox { 3 } else { 0 }
@@ -614,10 +767,7 @@ impl NewSessionDialog {
             if idx == 3 && !has_tool_selection {
                 continue;
             }
-            if idx == 6 && !has_sandbox {
-                continue;
-            }
-            if idx == 7 && !show_sandbox_options_help {
+            if idx == 7 && !has_sandbox {
                 continue;
             }
             if idx == 8 && !show_sandbox_options_help {
@@ -629,6 +779,9 @@ impl NewSessionDialog {
             if idx == 10 && !show_sandbox_options_help {
                 continue;
             }
+            if idx == 11 && !show_sandbox_options_help {
+                continue;
+            }
 
             lines.push(Line::from(Span::styled(
                 help.name,
@@ -654,7 +807,7 @@ impl NewSessionDialog {
 
     fn render_loading(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let needs_extra_line = self.sandbox_enabled && self.needs_image_pull;
-        let show_hook_output = self.has_hooks;
+        let show_hook_output = self.has_hooks || self.builds_image;
         let max_output_lines: usize = 6;
 
         let dialog_width: u16 = if show_hook_output {
@@ -685,7 +838,9 @@ impl NewSessionDialog {
 
         frame.render_widget(Clear, dialog_area);
 
-        let title = if show_hook_output {
+        let title = if self.builds_image {
+            " Preparing Session "
+        } else if show_hook_output {
             " Running Hooks "
         } else {
             " Creating Session "
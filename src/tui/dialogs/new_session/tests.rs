@@ -1,6 +1,7 @@
 use super::*;
 use crate::session::{merge_configs, Config, ProfileConfig, SessionConfigOverride};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
 
 fn key(code: KeyCode) -> KeyEvent {
     KeyEvent::new(code, KeyModifiers::NONE)
@@ -105,6 +106,9 @@ fn test_tab_cycles_fields_single_tool_with_worktree() {
     dialog.handle_key(key(KeyCode::Tab));
     assert_eq!(dialog.focused_field, 4); // new branch checkbox (now visible)
 
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(dialog.focused_field, 5); // base ref (new branch checked)
+
     dialog.handle_key(key(KeyCode::Tab));
     assert_eq!(dialog.focused_field, 0); // wrap to start
 }
@@ -315,6 +319,79 @@ fn test_submit_respects_create_new_branch() {
     }
 }
 
+#[test]
+fn test_submit_includes_base_ref() {
+    let mut dialog = single_tool_dialog();
+    dialog.worktree_branch = Input::new("feature-branch".to_string());
+    dialog.focused_field = 5; // base ref field
+    for c in "origin/release".chars() {
+        dialog.handle_key(key(KeyCode::Char(c)));
+    }
+    assert_eq!(dialog.base_ref.value(), "origin/release");
+
+    let result = dialog.handle_key(key(KeyCode::Enter));
+    match result {
+        DialogResult::Submit(data) => {
+            assert_eq!(data.base_ref.as_deref(), Some("origin/release"));
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_pick_base_ref_from_list() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let repo = git2::Repository::init(dir.path()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+        .unwrap();
+    let commit = repo.find_commit(oid).unwrap();
+    repo.tag_lightweight("v1.0", commit.as_object(), false)
+        .unwrap();
+
+    let mut dialog =
+        NewSessionDialog::new_with_tools(vec!["claude"], dir.path().display().to_string());
+    dialog.worktree_branch = Input::new("feature-branch".to_string());
+    dialog.focused_field = 5; // base ref field
+    dialog.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL));
+    let refs = dialog.base_ref_select.as_ref().unwrap().branches.clone();
+    assert_eq!(refs.last().map(String::as_str), Some("v1.0"));
+
+    // Keys move through the list instead of editing the field
+    for _ in 0..refs.len() {
+        dialog.handle_key(key(KeyCode::Char('j')));
+    }
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    assert!(dialog.base_ref_select.is_none());
+    assert_eq!(dialog.base_ref.value(), "v1.0");
+}
+
+#[test]
+fn test_base_ref_hidden_for_existing_branch() {
+    let mut dialog = single_tool_dialog();
+    dialog.worktree_branch = Input::new("feature-branch".to_string());
+    dialog.base_ref = Input::new("origin/release".to_string());
+    dialog.focused_field = 4;
+    dialog.handle_key(key(KeyCode::Char(' '))); // Attach to existing branch
+
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(dialog.focused_field, 0); // base ref skipped, wrap to start
+
+    let result = dialog.handle_key(key(KeyCode::Enter));
+    match result {
+        DialogResult::Submit(data) => {
+            assert_eq!(data.base_ref, None);
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
 #[test]
 fn test_new_branch_field_hidden_without_worktree() {
     let mut dialog = single_tool_dialog();
@@ -351,21 +428,31 @@ fn test_tab_includes_sandbox_options_when_sandbox_enabled() {
     dialog.docker_available = true;
     dialog.sandbox_enabled = true;
 
-    // Tab through all fields including sandbox image, yolo mode, env keys, and env values
-    // 0: title, 1: path, 2: group, 3: tool, 4: worktree, 5: sandbox, 6: image, 7: yolo, 8: env keys, 9: env values
+    // Tab through all fields including sandbox image, limits, yolo mode, env keys, and env values
+    // 0: title, 1: path, 2: group, 3: tool, 4: worktree, 5: sandbox, 6: image, 7: cpus, 8: memory,
+    // 9: yolo, 10: isolated, 11: env keys, 12: env values
     for _ in 0..6 {
         dialog.handle_key(key(KeyCode::Tab));
     }
     assert_eq!(dialog.focused_field, 6); // sandbox image field
 
     dialog.handle_key(key(KeyCode::Tab));
-    assert_eq!(dialog.focused_field, 7); // yolo mode field
+    assert_eq!(dialog.focused_field, 7); // cpus field
+
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(dialog.focused_field, 8); // memory field
+
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(dialog.focused_field, 9); // yolo mode field
+
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(dialog.focused_field, 10); // isolated field
 
     dialog.handle_key(key(KeyCode::Tab));
-    assert_eq!(dialog.focused_field, 8); // env keys field
+    assert_eq!(dialog.focused_field, 11); // env keys field
 
     dialog.handle_key(key(KeyCode::Tab));
-    assert_eq!(dialog.focused_field, 9); // env values field
+    assert_eq!(dialog.focused_field, 12); // env values field
 
     dialog.handle_key(key(KeyCode::Tab));
     assert_eq!(dialog.focused_field, 0); // wrap to start
@@ -490,7 +577,7 @@ fn test_yolo_mode_toggle() {
     let mut dialog = multi_tool_dialog();
     dialog.docker_available = true;
     dialog.sandbox_enabled = true;
-    dialog.focused_field = 7; // yolo mode field
+    dialog.focused_field = 9; // yolo mode field
     assert!(!dialog.yolo_mode);
 
     dialog.handle_key(key(KeyCode::Char(' ')));
@@ -536,6 +623,71 @@ fn test_submit_yolo_mode_false_when_sandbox_disabled() {
     }
 }
 
+#[test]
+fn test_submit_with_resource_limits() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.focused_field = 7; // cpus field
+    dialog.handle_key(key(KeyCode::Char('2')));
+    dialog.focused_field = 8; // memory field
+    for c in "16g".chars() {
+        dialog.handle_key(key(KeyCode::Char(c)));
+    }
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            assert_eq!(data.cpu_limit.as_deref(), Some("2"));
+            assert_eq!(data.memory_limit.as_deref(), Some("16g"));
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_empty_resource_limits_use_config() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            assert!(data.cpu_limit.is_none());
+            assert!(data.memory_limit.is_none());
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_invalid_memory_limit_blocks_submit() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.memory_limit = Input::new("lots".to_string());
+
+    let result = dialog.handle_key(key(KeyCode::Enter));
+    assert!(matches!(result, DialogResult::Continue));
+    assert!(dialog.error_message.is_some());
+    assert_eq!(dialog.focused_field, 8); // memory field
+}
+
+#[test]
+fn test_isolated_toggle_and_submit() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.focused_field = 10; // isolated field
+
+    dialog.handle_key(key(KeyCode::Char(' ')));
+    assert!(dialog.isolated);
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert!(data.isolated),
+        _ => panic!("Expected Submit"),
+    }
+}
+
 #[test]
 fn test_disabling_sandbox_resets_yolo_mode() {
     let mut dialog = multi_tool_dialog();
@@ -623,3 +775,54 @@ fn test_profile_override_beats_global_default_tool() {
     );
     assert_eq!(dialog.available_tools[dialog.tool_index], "opencode");
 }
+
+fn click(column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+#[test]
+fn test_click_focuses_field_and_picks_tool() {
+    let mut dialog = multi_tool_dialog();
+    let area = Rect::new(0, 0, 100, 40);
+
+    // Rows of two lines start at (12, 14)
+    dialog.handle_mouse(click(20, 22), area);
+    assert_eq!(dialog.focused_field, 4);
+
+    // "Tool: ● claude  ○ opencode"
+    dialog.handle_mouse(click(12 + 18, 20), area);
+    assert_eq!(dialog.focused_field, 3);
+    assert_eq!(dialog.tool_index, 1);
+    dialog.handle_mouse(click(12 + 7, 20), area);
+    assert_eq!(dialog.tool_index, 0);
+}
+
+#[test]
+fn test_click_skips_read_only_tool_row() {
+    let mut dialog = single_tool_dialog();
+    let area = Rect::new(0, 0, 100, 40);
+
+    dialog.handle_mouse(click(20, 20), area);
+    assert_eq!(dialog.focused_field, 0);
+    dialog.handle_mouse(click(20, 22), area);
+    assert_eq!(dialog.focused_field, 3);
+}
+
+#[test]
+fn test_click_toggles_checkbox() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.yolo_mode = false;
+    let area = Rect::new(0, 0, 100, 40);
+
+    // The taller dialog's rows start at row 6; YOLO mode is the tenth
+    dialog.handle_mouse(click(20, 24), area);
+    assert_eq!(dialog.focused_field, 9);
+    assert!(dialog.yolo_mode);
+}
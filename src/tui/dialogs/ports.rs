@@ -0,0 +1,383 @@
+//! Ports dialog for sandboxed sessions
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::docker::SessionPort;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+/// Changes requested from the ports dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortsAction {
+    Forward(u16),
+    Unforward(u16),
+    Refresh,
+}
+
+pub struct PortsDialog {
+    session_id: String,
+    session_title: String,
+    ports: Vec<SessionPort>,
+    error: Option<String>,
+    selected: usize,
+    /// Port number being typed after pressing `a`
+    adding: Option<Input>,
+    /// What the background worker is doing; keys other than close wait
+    busy: Option<String>,
+}
+
+impl PortsDialog {
+    pub fn new(session_id: &str, session_title: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            session_title: session_title.to_string(),
+            ports: Vec::new(),
+            error: None,
+            selected: 0,
+            adding: None,
+            busy: None,
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn set_ports(&mut self, ports: anyhow::Result<Vec<SessionPort>>) {
+        match ports {
+            Ok(ports) => {
+                self.ports = ports;
+                self.error = None;
+            }
+            Err(e) => {
+                self.ports.clear();
+                self.error = Some(e.to_string());
+            }
+        }
+        self.selected = self.selected.min(self.ports.len().saturating_sub(1));
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn set_busy(&mut self, busy: Option<String>) {
+        self.busy = busy;
+    }
+
+    fn selected_port(&self) -> Option<&SessionPort> {
+        self.ports.get(self.selected)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<PortsAction> {
+        if self.busy.is_some() {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('p') => DialogResult::Cancel,
+                _ => DialogResult::Continue,
+            };
+        }
+
+        if let Some(input) = &mut self.adding {
+            match key.code {
+                KeyCode::Esc => self.adding = None,
+                KeyCode::Enter => match input.value().trim().parse::<u16>() {
+                    Ok(port) if port > 0 => {
+                        self.adding = None;
+                        return DialogResult::Submit(PortsAction::Forward(port));
+                    }
+                    _ => self.error = Some(format!("Invalid port: {}", input.value())),
+                },
+                KeyCode::Char(c) if !c.is_ascii_digit() => {}
+                _ => {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                }
+            }
+            return DialogResult::Continue;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('p') => DialogResult::Cancel,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                DialogResult::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.ports.len() {
+                    self.selected += 1;
+                }
+                DialogResult::Continue
+            }
+            KeyCode::Char('r') => DialogResult::Submit(PortsAction::Refresh),
+            KeyCode::Char('a') => {
+                self.adding = Some(Input::default());
+                DialogResult::Continue
+            }
+            KeyCode::Enter => match self.selected_port() {
+                Some(port) if port.host_address.is_none() => {
+                    DialogResult::Submit(PortsAction::Forward(port.container_port))
+                }
+                _ => DialogResult::Continue,
+            },
+            KeyCode::Char('d') | KeyCode::Char('x') => match self.selected_port() {
+                Some(port) if port.forwarded => {
+                    DialogResult::Submit(PortsAction::Unforward(port.container_port))
+                }
+                _ => DialogResult::Continue,
+            },
+            _ => DialogResult::Continue,
+        }
+    }
+
+    fn port_line(&self, index: usize, port: &SessionPort, theme: &Theme) -> Line<'_> {
+        let is_selected = index == self.selected;
+        let marker = if is_selected { "> " } else { "  " };
+        let base = if is_selected {
+            Style::default().fg(theme.accent).bold()
+        } else {
+            Style::default().fg(theme.text)
+        };
+
+        let mut spans = vec![
+            Span::styled(marker, base),
+            Span::styled(format!("{:<6}", port.container_port), base),
+        ];
+        match port.url() {
+            Some(url) => spans.push(Span::styled(
+                format!("{:<28}", url),
+                Style::default().fg(theme.running),
+            )),
+            None => spans.push(Span::styled(
+                format!("{:<28}", "not published"),
+                Style::default().fg(theme.dimmed),
+            )),
+        }
+
+        let status = if port.loopback_only {
+            Span::styled("localhost only", Style::default().fg(theme.waiting))
+        } else if port.listening {
+            Span::styled("listening", Style::default().fg(theme.text))
+        } else {
+            Span::styled("idle", Style::default().fg(theme.dimmed))
+        };
+        spans.push(status);
+        if port.forwarded {
+            spans.push(Span::styled(" (added)", Style::default().fg(theme.dimmed)));
+        }
+        Line::from(spans)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let list_height = self.ports.len().max(1) as u16;
+        let dialog_width = 64.min(area.width.saturating_sub(4));
+        let dialog_height = (list_height + 9).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect {
+            x,
+            y,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Ports: {} ", self.session_title))
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // ports
+                Constraint::Length(1), // error or input
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        let lines: Vec<Line> =
+            if self.ports.is_empty() && self.error.is_none() && self.busy.is_none() {
+                vec![Line::from(Span::styled(
+                    "  Nothing is listening and no ports are published",
+                    Style::default().fg(theme.dimmed),
+                ))]
+            } else {
+                self.ports
+                    .iter()
+                    .enumerate()
+                    .map(|(i, port)| self.port_line(i, port, theme))
+                    .collect()
+            };
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        if let Some(input) = &self.adding {
+            render_text_field(
+                frame,
+                chunks[1],
+                "  Forward port:",
+                input,
+                true,
+                None,
+                theme,
+            );
+        } else if let Some(busy) = &self.busy {
+            frame.render_widget(
+                Paragraph::new(format!("  {}", busy)).style(Style::default().fg(theme.waiting)),
+                chunks[1],
+            );
+        } else if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(format!("  {}", error)).style(Style::default().fg(theme.error)),
+                chunks[1],
+            );
+        }
+
+        let hint = |key: &'static str, text: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.hint)),
+                Span::styled(text, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let hints: Vec<Span> = if self.busy.is_some() {
+            hint("Esc", " close (keeps running)").to_vec()
+        } else if self.adding.is_some() {
+            [hint("Enter", " forward  "), hint("Esc", " cancel")].concat()
+        } else {
+            [
+                hint("Enter", " publish  "),
+                hint("a", " add  "),
+                hint("d", " remove  "),
+                hint("r", " refresh  "),
+                hint("Esc", " close"),
+            ]
+            .concat()
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(hints)).alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn port(container_port: u16, host: Option<&str>, forwarded: bool) -> SessionPort {
+        SessionPort {
+            container_port,
+            host_address: host.map(String::from),
+            forwarded,
+            listening: true,
+            loopback_only: false,
+        }
+    }
+
+    fn dialog_with_ports() -> PortsDialog {
+        let mut dialog = PortsDialog::new("id", "session");
+        dialog.set_ports(Ok(vec![
+            port(3000, Some("127.0.0.1:49153"), false),
+            port(5173, None, false),
+            port(8080, Some("127.0.0.1:49200"), true),
+        ]));
+        dialog
+    }
+
+    #[test]
+    fn test_esc_closes() {
+        let mut dialog = dialog_with_ports();
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_enter_publishes_unpublished_port() {
+        let mut dialog = dialog_with_ports();
+        // Already published: nothing to do
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        dialog.handle_key(key(KeyCode::Char('j')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(PortsAction::Forward(5173))
+        ));
+    }
+
+    #[test]
+    fn test_remove_only_forwarded_ports() {
+        let mut dialog = dialog_with_ports();
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('d'))),
+            DialogResult::Continue
+        ));
+        dialog.handle_key(key(KeyCode::Char('j')));
+        dialog.handle_key(key(KeyCode::Char('j')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('d'))),
+            DialogResult::Submit(PortsAction::Unforward(8080))
+        ));
+    }
+
+    #[test]
+    fn test_add_port() {
+        let mut dialog = dialog_with_ports();
+        dialog.handle_key(key(KeyCode::Char('a')));
+        for c in "9x229".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(PortsAction::Forward(9229))
+        ));
+        // Back to the list afterwards
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_busy_dialog_only_closes() {
+        let mut dialog = dialog_with_ports();
+        dialog.set_busy(Some("Forwarding port 5173...".to_string()));
+        dialog.handle_key(key(KeyCode::Char('a')));
+        assert!(dialog.adding.is_none());
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('r'))),
+            DialogResult::Continue
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_selection_clamped_after_refresh() {
+        let mut dialog = dialog_with_ports();
+        dialog.handle_key(key(KeyCode::Char('j')));
+        dialog.handle_key(key(KeyCode::Char('j')));
+        dialog.set_ports(Ok(vec![port(3000, None, false)]));
+        assert_eq!(dialog.selected, 0);
+    }
+}
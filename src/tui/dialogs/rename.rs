@@ -1,12 +1,15 @@
 //! Rename session dialog
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use super::DialogResult;
+use crate::tui::components::mouse::{centered, clicked};
 use crate::tui::components::render_text_field;
 use crate::tui::styles::Theme;
 
@@ -158,31 +161,9 @@ impl RenameDialog {
         }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let dialog_width = 50;
-        let dialog_height = 15;
-        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
-        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
-
-        let dialog_area = Rect {
-            x,
-            y,
-            width: dialog_width.min(area.width),
-            height: dialog_height.min(area.height),
-        };
-
-        let clear = Clear;
-        frame.render_widget(clear, dialog_area);
-
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme.accent))
-            .title(" Edit Session ")
-            .title_style(Style::default().fg(theme.title).bold());
-
-        let inner = block.inner(dialog_area);
-        frame.render_widget(block, dialog_area);
-
+    /// Area of the dialog and of each of its rows
+    fn layout(area: Rect) -> (Rect, Rc<[Rect]>) {
+        let dialog_area = centered(area, 50, 15);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
@@ -197,7 +178,45 @@ impl RenameDialog {
                 Constraint::Length(1), // Spacer
                 Constraint::Min(1),    // Hint
             ])
-            .split(inner);
+            .split(Block::default().borders(Borders::ALL).inner(dialog_area));
+        (dialog_area, chunks)
+    }
+
+    /// Handle a mouse event over the dialog rendered in `area`: a click
+    /// focuses the field under it, and clicking the profile's arrows cycles
+    /// the profile
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) {
+        let (_, chunks) = Self::layout(area);
+        let Some(field) = (4..=6).find(|&row| clicked(chunks[row], &mouse)) else {
+            return;
+        };
+        self.focused_field = field - 4;
+        if self.focused_field != 2 {
+            return;
+        }
+        // "Profile:    < name >"
+        let column = mouse.column - chunks[6].x;
+        let name_end = 14 + self.selected_profile().chars().count() as u16;
+        if (12..14).contains(&column) {
+            self.handle_key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
+        } else if (name_end..name_end + 2).contains(&column) {
+            self.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let (dialog_area, chunks) = Self::layout(area);
+
+        let clear = Clear;
+        frame.render_widget(clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Edit Session ")
+            .title_style(Style::default().fg(theme.title).bold());
+
+        frame.render_widget(block, dialog_area);
 
         // Current title
         let current_title_line = Line::from(vec![
@@ -287,7 +306,7 @@ impl RenameDialog {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
 
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::empty())
@@ -776,4 +795,32 @@ mod tests {
             _ => panic!("Expected Submit"),
         }
     }
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_click_focuses_fields_and_cycles_profile() {
+        let mut dialog = RenameDialog::new("Test", "", "default", multi_profiles());
+        let area = Rect::new(0, 0, 100, 35);
+        // The dialog's rows start at (27, 12), and the fields at its fifth row
+        dialog.handle_mouse(click(40, 17), area);
+        assert_eq!(dialog.focused_field, 1);
+
+        dialog.handle_mouse(click(45, 18), area);
+        assert_eq!(dialog.focused_field, 2);
+        assert_eq!(dialog.selected_profile(), "default");
+
+        // "Profile:    < default >"
+        dialog.handle_mouse(click(27 + 22, 18), area);
+        assert_eq!(dialog.selected_profile(), "work");
+        dialog.handle_mouse(click(27 + 12, 18), area);
+        assert_eq!(dialog.selected_profile(), "default");
+    }
 }
@@ -0,0 +1,380 @@
+//! Snapshots dialog for sandboxed sessions
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::docker::Snapshot;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+/// Changes requested from the snapshots dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotAction {
+    /// Take a snapshot; `None` uses a timestamp name
+    Create(Option<String>),
+    Rollback(String),
+    Delete(String),
+}
+
+pub struct SnapshotsDialog {
+    session_id: String,
+    session_title: String,
+    snapshots: Vec<Snapshot>,
+    /// Snapshot the current container was rolled back to
+    active: Option<String>,
+    error: Option<String>,
+    selected: usize,
+    /// Name being typed after pressing `n`
+    naming: Option<Input>,
+    /// Rollback or delete waiting for `y`
+    pending: Option<SnapshotAction>,
+    /// What the background worker is doing; keys other than close wait
+    busy: Option<String>,
+}
+
+impl SnapshotsDialog {
+    pub fn new(session_id: &str, session_title: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            session_title: session_title.to_string(),
+            snapshots: Vec::new(),
+            active: None,
+            error: None,
+            selected: 0,
+            naming: None,
+            pending: None,
+            busy: None,
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn set_snapshots(&mut self, snapshots: anyhow::Result<Vec<Snapshot>>) {
+        match snapshots {
+            Ok(snapshots) => {
+                self.snapshots = snapshots;
+                self.error = None;
+            }
+            Err(e) => {
+                self.snapshots.clear();
+                self.error = Some(e.to_string());
+            }
+        }
+        self.selected = self.selected.min(self.snapshots.len().saturating_sub(1));
+    }
+
+    pub fn set_active(&mut self, active: Option<String>) {
+        self.active = active;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn set_busy(&mut self, busy: Option<String>) {
+        self.busy = busy;
+    }
+
+    fn selected_name(&self) -> Option<String> {
+        self.snapshots.get(self.selected).map(|s| s.name.clone())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<SnapshotAction> {
+        if self.busy.is_some() {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => DialogResult::Cancel,
+                _ => DialogResult::Continue,
+            };
+        }
+
+        if let Some(action) = self.pending.take() {
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                return DialogResult::Submit(action);
+            }
+            return DialogResult::Continue;
+        }
+
+        if let Some(input) = &mut self.naming {
+            match key.code {
+                KeyCode::Esc => self.naming = None,
+                KeyCode::Enter => {
+                    let name = input.value().trim().to_string();
+                    self.naming = None;
+                    let name = if name.is_empty() { None } else { Some(name) };
+                    return DialogResult::Submit(SnapshotAction::Create(name));
+                }
+                _ => {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                }
+            }
+            return DialogResult::Continue;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => DialogResult::Cancel,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                DialogResult::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.snapshots.len() {
+                    self.selected += 1;
+                }
+                DialogResult::Continue
+            }
+            KeyCode::Char('n') => {
+                self.naming = Some(Input::default());
+                DialogResult::Continue
+            }
+            KeyCode::Enter => {
+                self.pending = self.selected_name().map(SnapshotAction::Rollback);
+                DialogResult::Continue
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') => {
+                self.pending = self.selected_name().map(SnapshotAction::Delete);
+                DialogResult::Continue
+            }
+            _ => DialogResult::Continue,
+        }
+    }
+
+    fn snapshot_line(&self, index: usize, snapshot: &Snapshot, theme: &Theme) -> Line<'_> {
+        let is_selected = index == self.selected;
+        let marker = if is_selected { "> " } else { "  " };
+        let name_style = if is_selected {
+            Style::default().fg(theme.accent).bold()
+        } else {
+            Style::default().fg(theme.text)
+        };
+
+        let mut spans = vec![
+            Span::styled(marker, name_style),
+            Span::styled(format!("{:<28}", snapshot.name), name_style),
+            Span::styled(
+                format!("{:<21}", snapshot.created_short()),
+                Style::default().fg(theme.dimmed),
+            ),
+            Span::styled(snapshot.size.clone(), Style::default().fg(theme.dimmed)),
+        ];
+        if self.active.as_deref() == Some(snapshot.name.as_str()) {
+            spans.push(Span::styled(
+                " (current)",
+                Style::default().fg(theme.running),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let list_height = self.snapshots.len().max(1) as u16;
+        let dialog_width = 72.min(area.width.saturating_sub(4));
+        let dialog_height = (list_height + 9).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect {
+            x,
+            y,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Snapshots: {} ", self.session_title))
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // snapshots
+                Constraint::Length(1), // prompt, input or error
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        let lines: Vec<Line> = if self.snapshots.is_empty() && self.error.is_none() {
+            vec![Line::from(Span::styled(
+                "  No snapshots yet. Press n to save the container's current state.",
+                Style::default().fg(theme.dimmed),
+            ))]
+        } else {
+            self.snapshots
+                .iter()
+                .enumerate()
+                .map(|(i, snapshot)| self.snapshot_line(i, snapshot, theme))
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        if let Some(action) = &self.pending {
+            let prompt = match action {
+                SnapshotAction::Rollback(name) => format!(
+                    "  Recreate the container from {}? Project files are kept. (y/n)",
+                    name
+                ),
+                SnapshotAction::Delete(name) => format!("  Delete snapshot {}? (y/n)", name),
+                SnapshotAction::Create(_) => String::new(),
+            };
+            frame.render_widget(
+                Paragraph::new(prompt).style(Style::default().fg(theme.waiting)),
+                chunks[1],
+            );
+        } else if let Some(input) = &self.naming {
+            render_text_field(
+                frame,
+                chunks[1],
+                "  Name:",
+                input,
+                true,
+                Some("date and time"),
+                theme,
+            );
+        } else if let Some(busy) = &self.busy {
+            frame.render_widget(
+                Paragraph::new(format!("  {}", busy)).style(Style::default().fg(theme.waiting)),
+                chunks[1],
+            );
+        } else if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(format!("  {}", error)).style(Style::default().fg(theme.error)),
+                chunks[1],
+            );
+        }
+
+        let hint = |key: &'static str, text: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(theme.hint)),
+                Span::styled(text, Style::default().fg(theme.dimmed)),
+            ]
+        };
+        let hints: Vec<Span> = if self.busy.is_some() {
+            hint("Esc", " close (keeps running)").to_vec()
+        } else if self.naming.is_some() {
+            [hint("Enter", " snapshot  "), hint("Esc", " cancel")].concat()
+        } else {
+            [
+                hint("n", " new  "),
+                hint("Enter", " roll back  "),
+                hint("d", " delete  "),
+                hint("Esc", " close"),
+            ]
+            .concat()
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(hints)).alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn snapshot(name: &str) -> Snapshot {
+        Snapshot {
+            name: name.to_string(),
+            image: format!("aoe-snapshot-abcd1234:{}", name),
+            created: "2025-01-10 09:00:00 +0000 UTC".to_string(),
+            size: "1.2GB".to_string(),
+        }
+    }
+
+    fn dialog_with_snapshots() -> SnapshotsDialog {
+        let mut dialog = SnapshotsDialog::new("id", "session");
+        dialog.set_snapshots(Ok(vec![snapshot("newest"), snapshot("oldest")]));
+        dialog
+    }
+
+    #[test]
+    fn test_rollback_needs_confirmation() {
+        let mut dialog = dialog_with_snapshots();
+        dialog.handle_key(key(KeyCode::Char('j')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('y'))),
+            DialogResult::Submit(SnapshotAction::Rollback(name)) if name == "oldest"
+        ));
+    }
+
+    #[test]
+    fn test_declined_confirmation_keeps_dialog_open() {
+        let mut dialog = dialog_with_snapshots();
+        dialog.handle_key(key(KeyCode::Char('d')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('n'))),
+            DialogResult::Continue
+        ));
+        assert!(dialog.pending.is_none());
+        assert!(dialog.naming.is_none());
+    }
+
+    #[test]
+    fn test_create_with_and_without_name() {
+        let mut dialog = dialog_with_snapshots();
+        dialog.handle_key(key(KeyCode::Char('n')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(SnapshotAction::Create(None))
+        ));
+
+        dialog.handle_key(key(KeyCode::Char('n')));
+        for c in "pre-upgrade".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(SnapshotAction::Create(Some(name))) if name == "pre-upgrade"
+        ));
+    }
+
+    #[test]
+    fn test_busy_dialog_only_closes() {
+        let mut dialog = dialog_with_snapshots();
+        dialog.set_busy(Some("Creating snapshot...".to_string()));
+        dialog.handle_key(key(KeyCode::Char('n')));
+        assert!(dialog.naming.is_none());
+        dialog.handle_key(key(KeyCode::Char('d')));
+        assert!(dialog.pending.is_none());
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+
+        dialog.set_busy(None);
+        dialog.handle_key(key(KeyCode::Char('d')));
+        assert!(dialog.pending.is_some());
+    }
+
+    #[test]
+    fn test_no_action_without_snapshots() {
+        let mut dialog = SnapshotsDialog::new("id", "session");
+        dialog.handle_key(key(KeyCode::Enter));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('y'))),
+            DialogResult::Continue
+        ));
+    }
+}
@@ -2,9 +2,12 @@
 
 use std::path::PathBuf;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::widgets::Block;
 
 use super::DiffView;
+use crate::tui::components::mouse::{hit, SplitEvent};
+use crate::tui::keybindings::DiffCommand;
 
 /// Result of handling a key event in the diff view
 pub enum DiffAction {
@@ -24,11 +27,8 @@ impl DiffView {
 
         // Handle help overlay
         if self.show_help {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('?') => {
-                    self.show_help = false;
-                }
-                _ => {}
+            if key.code == KeyCode::Esc || self.keymap.is(&key, DiffCommand::Help) {
+                self.show_help = false;
             }
             return DiffAction::Continue;
         }
@@ -43,87 +43,57 @@ impl DiffView {
     }
 
     fn handle_normal_key(&mut self, key: KeyEvent) -> DiffAction {
-        match (key.code, key.modifiers) {
-            // Close view
-            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => DiffAction::Close,
-
-            // File navigation (j/k always navigate between files)
-            (KeyCode::Up, _) | (KeyCode::Char('k'), _) => {
-                self.prev_file();
-                DiffAction::Continue
-            }
-            (KeyCode::Down, _) | (KeyCode::Char('j'), _) => {
-                self.next_file();
-                DiffAction::Continue
-            }
+        let Some(command) = self.keymap.command(&key) else {
+            return DiffAction::Continue;
+        };
+        self.run_command(command)
+    }
+
+    /// Carry out a command, from its key or the command palette
+    pub fn run_command(&mut self, command: DiffCommand) -> DiffAction {
+        match command {
+            DiffCommand::Close => return DiffAction::Close,
+
+            // File navigation (always moves between files)
+            DiffCommand::PrevFile => self.prev_file(),
+            DiffCommand::NextFile => self.next_file(),
 
             // Diff scrolling
-            (KeyCode::PageUp, _) => {
-                self.page_up();
-                DiffAction::Continue
-            }
-            (KeyCode::PageDown, _) => {
-                self.page_down();
-                DiffAction::Continue
-            }
-            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-                self.half_page_up();
-                DiffAction::Continue
-            }
-            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
-                self.half_page_down();
-                DiffAction::Continue
-            }
-            (KeyCode::Home, _) | (KeyCode::Char('g'), _) => {
-                self.scroll_offset = 0;
-                DiffAction::Continue
-            }
-            (KeyCode::End, _) | (KeyCode::Char('G'), _) => {
+            DiffCommand::PageUp => self.page_up(),
+            DiffCommand::PageDown => self.page_down(),
+            DiffCommand::HalfPageUp => self.half_page_up(),
+            DiffCommand::HalfPageDown => self.half_page_down(),
+            DiffCommand::Top => self.scroll_offset = 0,
+            DiffCommand::Bottom => {
                 self.scroll_offset = self.total_lines.saturating_sub(self.visible_lines);
-                DiffAction::Continue
             }
 
             // Open external editor
-            (KeyCode::Char('e'), _) | (KeyCode::Enter, _) => {
+            DiffCommand::Edit => {
                 if let Some(file) = self.selected_file() {
                     let full_path = self.repo_path.join(&file.path);
                     return DiffAction::EditFile(full_path);
                 }
-                DiffAction::Continue
             }
 
-            // Branch selection
-            (KeyCode::Char('b'), _) => {
-                self.open_branch_select();
-                DiffAction::Continue
-            }
+            DiffCommand::SelectBranch => self.open_branch_select(),
 
-            // Refresh
-            (KeyCode::Char('r'), _) => {
+            DiffCommand::Refresh => {
                 if let Err(e) = self.refresh_files() {
                     self.error_message = Some(format!("Failed to refresh: {}", e));
                 }
-                DiffAction::Continue
             }
 
             // Resize file list panel
-            (KeyCode::Char('h'), _) | (KeyCode::Left, _) => {
-                self.shrink_file_list();
-                DiffAction::Continue
-            }
-            (KeyCode::Char('l'), _) | (KeyCode::Right, _) => {
-                self.grow_file_list();
-                DiffAction::Continue
-            }
+            DiffCommand::ShrinkFileList => self.shrink_file_list(),
+            DiffCommand::GrowFileList => self.grow_file_list(),
 
-            // Help
-            (KeyCode::Char('?'), _) => {
-                self.show_help = true;
-                DiffAction::Continue
-            }
+            DiffCommand::Help => self.show_help = true,
 
-            _ => DiffAction::Continue,
+            // The home screen opens the palette over the diff
+            DiffCommand::CommandPalette => {}
         }
+        DiffAction::Continue
     }
 
     fn handle_branch_select_key(&mut self, key: KeyEvent) -> DiffAction {
@@ -156,24 +126,38 @@ impl DiffView {
         DiffAction::Continue
     }
 
-    /// Handle a mouse event
+    /// Handle a mouse event: the wheel scrolls the diff, or moves between
+    /// files over the file list; a click selects a file; dragging the border
+    /// of the file list resizes it
     pub fn handle_mouse(&mut self, mouse: MouseEvent) -> DiffAction {
         // Don't handle mouse in help overlay or branch select dialog
         if self.show_help || self.branch_select.is_some() {
             return DiffAction::Continue;
         }
 
-        // Mouse scroll always scrolls the diff content
-        match mouse.kind {
-            MouseEventKind::ScrollUp => {
-                self.scroll_up(3);
-                DiffAction::Continue
+        if let Some(event) = self.split_drag.handle(&mouse, self.file_list_area) {
+            match event {
+                SplitEvent::Resize(width) => self.file_list_width = width.clamp(5, 80),
+                SplitEvent::Done => self.save_file_list_width(),
             }
-            MouseEventKind::ScrollDown => {
-                self.scroll_down(3);
-                DiffAction::Continue
+            return DiffAction::Continue;
+        }
+
+        let over_files = hit(self.file_list_area, &mouse);
+        match mouse.kind {
+            MouseEventKind::ScrollUp if over_files => self.prev_file(),
+            MouseEventKind::ScrollDown if over_files => self.next_file(),
+            MouseEventKind::ScrollUp => self.scroll_up(3),
+            MouseEventKind::ScrollDown => self.scroll_down(3),
+            MouseEventKind::Down(MouseButton::Left) => {
+                // One row per file inside the border
+                let rows = Block::bordered().inner(self.file_list_area);
+                if hit(rows, &mouse) {
+                    self.select_file((mouse.row - rows.y) as usize);
+                }
             }
-            _ => DiffAction::Continue,
+            _ => {}
         }
+        DiffAction::Continue
     }
 }
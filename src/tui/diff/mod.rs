@@ -6,11 +6,15 @@ mod render;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use ratatui::layout::Rect;
+
 use crate::git::diff::{
-    compute_changed_files, compute_file_diff, get_default_branch, list_branches, DiffFile, FileDiff,
+    compute_changed_files, compute_file_diff, list_branches, DiffFile, FileDiff,
 };
 use crate::session::config::{load_config, save_config};
 use crate::session::Config;
+use crate::tui::components::mouse::SplitDrag;
+use crate::tui::keybindings::{DiffCommand, Keymap};
 
 pub use input::DiffAction;
 
@@ -62,22 +66,26 @@ pub struct DiffView {
     /// Show help overlay
     pub(crate) show_help: bool,
 
-    /// Width of the file list panel (resizable with h/l)
+    /// Width of the file list panel (resizable with h/l or by dragging)
     pub(crate) file_list_width: u16,
+
+    /// Areas of the file list and the diff (set during render)
+    pub(crate) file_list_area: Rect,
+    pub(crate) diff_area: Rect,
+
+    /// Drag of the border between the file list and the diff
+    pub(crate) split_drag: SplitDrag,
+
+    /// Active key bindings
+    pub(crate) keymap: Keymap<DiffCommand>,
 }
 
 impl DiffView {
     /// Create a new diff view for a repository
-    pub fn new(repo_path: PathBuf) -> anyhow::Result<Self> {
+    pub fn new(repo_path: PathBuf, keymap: Keymap<DiffCommand>) -> anyhow::Result<Self> {
         let config = Config::load().unwrap_or_default();
 
-        // Determine base branch
-        let base_branch = config
-            .diff
-            .default_branch
-            .clone()
-            .or_else(|| get_default_branch(&repo_path).ok())
-            .unwrap_or_else(|| "main".to_string());
+        let base_branch = config.diff.base_branch_for(&repo_path);
 
         let context_lines = config.diff.context_lines;
 
@@ -96,6 +104,10 @@ impl DiffView {
             context_lines,
             show_help: false,
             file_list_width: config.app_state.diff_file_list_width.unwrap_or(35),
+            file_list_area: Rect::default(),
+            diff_area: Rect::default(),
+            split_drag: SplitDrag::default(),
+            keymap,
         };
 
         view.refresh_files()?;
@@ -215,6 +227,14 @@ impl DiffView {
         self.scroll_up(self.visible_lines / 2);
     }
 
+    /// Select a file by its index in the list
+    pub fn select_file(&mut self, index: usize) {
+        if index < self.files.len() && index != self.selected_file {
+            self.selected_file = index;
+            self.scroll_offset = 0;
+        }
+    }
+
     /// Shrink the file list panel
     pub fn shrink_file_list(&mut self) {
         self.file_list_width = self.file_list_width.saturating_sub(5).max(5);
@@ -227,7 +247,7 @@ impl DiffView {
         self.save_file_list_width();
     }
 
-    fn save_file_list_width(&self) {
+    pub(crate) fn save_file_list_width(&self) {
         if let Ok(mut config) = load_config().map(|c| c.unwrap_or_default()) {
             config.app_state.diff_file_list_width = Some(self.file_list_width);
             let _ = save_config(&config);
@@ -14,6 +14,8 @@ use similar::ChangeTag;
 
 use super::DiffView;
 use crate::git::diff::FileStatus;
+use crate::tui::components::HelpOverlay;
+use crate::tui::keybindings::DiffCommand;
 use crate::tui::styles::Theme;
 
 /// Truncate a string from the left, adding an ellipsis prefix if it doesn't fit.
@@ -111,6 +113,8 @@ impl DiffView {
             ])
             .split(area);
 
+        self.file_list_area = layout[0];
+        self.diff_area = layout[1];
         self.render_file_list(frame, layout[0], theme);
         self.render_diff_content(frame, layout[1], theme);
     }
@@ -327,22 +331,41 @@ impl DiffView {
         } else if let Some(ref success) = self.success_message {
             Line::from(Span::styled(success, Style::default().fg(Color::Green)))
         } else {
-            Line::from(vec![
-                Span::styled("j/k", Style::default().fg(theme.accent)),
-                Span::styled(": files  ", Style::default().fg(theme.dimmed)),
-                Span::styled("h/l", Style::default().fg(theme.accent)),
-                Span::styled(": resize  ", Style::default().fg(theme.dimmed)),
-                Span::styled("scroll", Style::default().fg(theme.accent)),
-                Span::styled(": diff  ", Style::default().fg(theme.dimmed)),
-                Span::styled("e/Enter", Style::default().fg(theme.accent)),
-                Span::styled(": edit  ", Style::default().fg(theme.dimmed)),
-                Span::styled("b", Style::default().fg(theme.accent)),
-                Span::styled(": branch  ", Style::default().fg(theme.dimmed)),
-                Span::styled("?", Style::default().fg(theme.accent)),
-                Span::styled(": help  ", Style::default().fg(theme.dimmed)),
-                Span::styled("q/Esc", Style::default().fg(theme.accent)),
-                Span::styled(": close", Style::default().fg(theme.dimmed)),
-            ])
+            let keys = &self.keymap;
+            let hints = [
+                (
+                    format!(
+                        "{}/{}",
+                        keys.short_label(DiffCommand::NextFile),
+                        keys.short_label(DiffCommand::PrevFile)
+                    ),
+                    ": files  ",
+                ),
+                (
+                    format!(
+                        "{}/{}",
+                        keys.short_label(DiffCommand::ShrinkFileList),
+                        keys.short_label(DiffCommand::GrowFileList)
+                    ),
+                    ": resize  ",
+                ),
+                ("scroll".to_string(), ": diff  "),
+                (keys.label(DiffCommand::Edit), ": edit  "),
+                (keys.label(DiffCommand::SelectBranch), ": branch  "),
+                (keys.label(DiffCommand::Help), ": help  "),
+                (keys.label(DiffCommand::Close), ": close"),
+            ];
+            Line::from(
+                hints
+                    .into_iter()
+                    .flat_map(|(key, desc)| {
+                        [
+                            Span::styled(key, Style::default().fg(theme.accent)),
+                            Span::styled(desc, Style::default().fg(theme.dimmed)),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            )
         };
 
         let paragraph = Paragraph::new(content).alignment(ratatui::layout::Alignment::Center);
@@ -425,79 +448,12 @@ impl DiffView {
     }
 
     fn render_help(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let dialog_width = 55u16;
-        let dialog_height = 19u16;
-
-        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
-        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
-
-        let dialog_area = Rect {
-            x,
-            y,
-            width: dialog_width.min(area.width),
-            height: dialog_height.min(area.height),
-        };
-
-        frame.render_widget(Clear, dialog_area);
-
-        let block = Block::default()
-            .style(Style::default().bg(theme.background))
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme.border))
-            .title(" Diff View Help ")
-            .title_style(
-                Style::default()
-                    .fg(theme.accent)
-                    .add_modifier(Modifier::BOLD),
-            );
-
-        let inner = block.inner(dialog_area);
-        frame.render_widget(block, dialog_area);
-
-        let shortcuts = vec![
-            (
-                "Navigation",
-                vec![
-                    ("j/k, ↑/↓", "Navigate between files"),
-                    ("PgUp/Dn", "Page up / down in diff"),
-                    ("Ctrl+u/d", "Half-page up / down"),
-                    ("g/G", "Go to top / bottom of diff"),
-                    ("h/l, ←/→", "Shrink / grow file list"),
-                ],
-            ),
-            (
-                "Actions",
-                vec![
-                    ("e/Enter", "Edit file in external editor"),
-                    ("b", "Select base branch"),
-                    ("r", "Refresh diff"),
-                ],
-            ),
-            (
-                "Other",
-                vec![("?", "Toggle this help"), ("q/Esc", "Close diff view")],
-            ),
-        ];
-
-        let mut lines: Vec<Line> = Vec::new();
-
-        for (section, keys) in shortcuts {
-            lines.push(Line::from(Span::styled(
-                section,
-                Style::default()
-                    .fg(theme.accent)
-                    .add_modifier(Modifier::BOLD),
-            )));
-            for (key, desc) in keys {
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  {:14}", key), Style::default().fg(Color::Yellow)),
-                    Span::styled(desc, Style::default().fg(theme.text)),
-                ]));
-            }
-            lines.push(Line::from(""));
-        }
-
-        let paragraph = Paragraph::new(lines);
-        frame.render_widget(paragraph, inner);
+        HelpOverlay::render(
+            frame,
+            area,
+            theme,
+            "Diff View Help",
+            &self.keymap.help_sections(),
+        );
     }
 }
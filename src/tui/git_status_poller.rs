@@ -0,0 +1,96 @@
+//! Background git status polling for the home list
+//!
+//! Computing diffs against a base branch touches every file in a worktree, so
+//! this runs on its own thread and only hands finished summaries to the UI.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::git::diff::{compute_change_summary, get_default_branch, ChangeSummary};
+
+/// A session whose working directory should be summarized
+#[derive(Debug, Clone)]
+pub struct GitStatusRequest {
+    pub id: String,
+    pub project_path: PathBuf,
+    /// Configured base branch; auto-detected per repository when unset
+    pub base_branch: Option<String>,
+}
+
+/// Result of a git status check for a single session
+#[derive(Debug)]
+pub struct GitStatusUpdate {
+    pub id: String,
+    /// None when the session's path is not a git repository or the base
+    /// branch could not be resolved
+    pub summary: Option<ChangeSummary>,
+}
+
+/// Background thread that computes git change summaries without blocking the UI
+pub struct GitStatusPoller {
+    request_tx: mpsc::Sender<Vec<GitStatusRequest>>,
+    result_rx: mpsc::Receiver<Vec<GitStatusUpdate>>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl GitStatusPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<Vec<GitStatusRequest>>();
+        let (result_tx, result_rx) = mpsc::channel::<Vec<GitStatusUpdate>>();
+
+        let handle = thread::spawn(move || {
+            Self::polling_loop(request_rx, result_tx);
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn polling_loop(
+        request_rx: mpsc::Receiver<Vec<GitStatusRequest>>,
+        result_tx: mpsc::Sender<Vec<GitStatusUpdate>>,
+    ) {
+        while let Ok(requests) = request_rx.recv() {
+            let updates: Vec<GitStatusUpdate> = requests
+                .into_iter()
+                .map(|req| {
+                    let summary = req
+                        .base_branch
+                        .or_else(|| get_default_branch(&req.project_path).ok())
+                        .and_then(|base| compute_change_summary(&req.project_path, &base).ok());
+
+                    GitStatusUpdate {
+                        id: req.id,
+                        summary,
+                    }
+                })
+                .collect();
+
+            if result_tx.send(updates).is_err() {
+                // Receiver dropped, exit the loop
+                break;
+            }
+        }
+    }
+
+    /// Request a git status refresh for the given sessions (non-blocking).
+    pub fn request_refresh(&self, requests: Vec<GitStatusRequest>) {
+        let _ = self.request_tx.send(requests);
+    }
+
+    /// Try to receive git status updates without blocking.
+    /// Returns None if no updates are available yet.
+    pub fn try_recv_updates(&self) -> Option<Vec<GitStatusUpdate>> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for GitStatusPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,357 @@
+//! Multi-select and bulk actions for HomeView
+
+use std::collections::HashSet;
+
+use crate::session::{list_profiles, GroupTree, Item, Status, Storage};
+use crate::tui::bulk_poller::{BulkOperation, BulkRequest};
+use crate::tui::deletion_poller::DeletionRequest;
+use crate::tui::dialogs::{
+    BulkAction, BulkActionsDialog, BulkProgressDialog, DeleteDialogConfig, DeleteOptions,
+    InfoDialog, UnifiedDeleteDialog,
+};
+
+use super::HomeView;
+
+impl HomeView {
+    /// Sessions covered by the range being marked, if any
+    fn range_ids(&self) -> Vec<&str> {
+        let Some(anchor) = self.mark_anchor else {
+            return Vec::new();
+        };
+        // Like the cursor, the anchor is a position in the displayed list
+        let (start, end) = (anchor.min(self.cursor), anchor.max(self.cursor));
+        (start..=end)
+            .filter_map(|display_idx| match &self.filtered_items {
+                Some(filtered) => filtered.get(display_idx).copied(),
+                None => Some(display_idx),
+            })
+            .filter_map(|idx| self.flat_items.get(idx))
+            .filter_map(|item| match item {
+                Item::Session { id, .. } => Some(id.as_str()),
+                Item::Group { .. } | Item::Favorites { .. } => None,
+            })
+            .collect()
+    }
+
+    pub(super) fn is_marked(&self, id: &str) -> bool {
+        self.marked_sessions.contains(id) || self.range_ids().contains(&id)
+    }
+
+    /// Marked sessions (including a range being marked) in list order,
+    /// leaving out sessions that are being deleted
+    pub(super) fn marked_ids(&self) -> Vec<String> {
+        let range = self.range_ids();
+        self.instances
+            .iter()
+            .filter(|inst| inst.status != Status::Deleting)
+            .filter(|inst| {
+                self.marked_sessions.contains(&inst.id) || range.contains(&inst.id.as_str())
+            })
+            .map(|inst| inst.id.clone())
+            .collect()
+    }
+
+    pub(super) fn has_marks(&self) -> bool {
+        !self.marked_sessions.is_empty() || self.mark_anchor.is_some()
+    }
+
+    /// Toggle the mark on the selected session. On a group, marks every
+    /// session in it, or unmarks them if they are all marked already.
+    pub(super) fn toggle_mark(&mut self) {
+        self.commit_mark_range();
+        if let Some(id) = self.selected_session.clone() {
+            if !self.marked_sessions.remove(&id) {
+                self.marked_sessions.insert(id);
+            }
+        } else if let Some(group) = self.selected_group.clone() {
+            let prefix = format!("{}/", group);
+            let ids: Vec<String> = self
+                .instances
+                .iter()
+                .filter(|i| i.group_path == group || i.group_path.starts_with(&prefix))
+                .map(|i| i.id.clone())
+                .collect();
+            if ids.iter().all(|id| self.marked_sessions.contains(id)) {
+                for id in &ids {
+                    self.marked_sessions.remove(id);
+                }
+            } else {
+                self.marked_sessions.extend(ids);
+            }
+        }
+    }
+
+    /// Start marking a range at the cursor, or keep the range marked so far
+    pub(super) fn toggle_mark_range(&mut self) {
+        if self.mark_anchor.is_some() {
+            self.commit_mark_range();
+        } else {
+            self.mark_anchor = Some(self.cursor);
+        }
+    }
+
+    pub(super) fn commit_mark_range(&mut self) {
+        let range: Vec<String> = self.range_ids().into_iter().map(String::from).collect();
+        self.marked_sessions.extend(range);
+        self.mark_anchor = None;
+    }
+
+    /// Mark every session the list currently shows, i.e. everything that
+    /// matches the search and the changes filter
+    pub(super) fn mark_visible(&mut self) {
+        self.commit_mark_range();
+        let visible = self.visible_session_ids();
+        self.marked_sessions.extend(visible);
+    }
+
+    /// Sessions matching the search and the changes filter, or every
+    /// session when no filter is active. Favorites are listed twice but
+    /// counted once.
+    pub(super) fn visible_session_ids(&self) -> Vec<String> {
+        match &self.filtered_items {
+            Some(indices) => {
+                let mut seen = HashSet::new();
+                indices
+                    .iter()
+                    .filter_map(|&i| self.flat_items.get(i))
+                    .filter_map(|item| match item {
+                        Item::Session { id, .. } => Some(id.clone()),
+                        Item::Group { .. } | Item::Favorites { .. } => None,
+                    })
+                    .filter(|id| seen.insert(id.clone()))
+                    .collect()
+            }
+            None => self.instances.iter().map(|i| i.id.clone()).collect(),
+        }
+    }
+
+    pub(super) fn clear_marks(&mut self) {
+        self.marked_sessions.clear();
+        self.mark_anchor = None;
+    }
+
+    pub(super) fn open_bulk_actions(&mut self) {
+        let count = self.marked_ids().len();
+        if count == 0 {
+            self.info_dialog = Some(InfoDialog::new(
+                "No Sessions Marked",
+                "Mark sessions with Space, a range with v, or everything shown with *.",
+            ));
+            return;
+        }
+        let current_profile = self.storage.profile().to_string();
+        let profiles = list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
+        self.bulk_actions_dialog = Some(BulkActionsDialog::new(count, &current_profile, profiles));
+    }
+
+    /// Open the delete dialog for the marked sessions, offering the cleanup
+    /// options that apply to at least one of them
+    pub(super) fn open_bulk_delete(&mut self) {
+        let ids = self.marked_ids();
+        if ids.is_empty() {
+            return;
+        }
+        let marked: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.instance_map.get(id))
+            .collect();
+        let worktrees = marked
+            .iter()
+            .filter(|i| i.worktree_info.as_ref().is_some_and(|wt| wt.managed_by_aoe))
+            .count();
+        let config = DeleteDialogConfig {
+            worktree_branch: (worktrees > 0).then(|| {
+                format!(
+                    "{} worktree{}",
+                    worktrees,
+                    if worktrees == 1 { "" } else { "s" }
+                )
+            }),
+            has_sandbox: marked
+                .iter()
+                .any(|i| i.sandbox_info.as_ref().is_some_and(|s| s.enabled)),
+        };
+        self.unified_delete_dialog = Some(UnifiedDeleteDialog::for_sessions(ids.len(), config));
+        self.bulk_delete_ids = ids;
+    }
+
+    pub(super) fn apply_bulk_action(&mut self, action: BulkAction) {
+        let ids = self.marked_ids();
+        let (title, operation) = match action {
+            BulkAction::Delete => {
+                self.open_bulk_delete();
+                return;
+            }
+            BulkAction::MoveToGroup(group) => {
+                self.show_bulk_progress("Move to Group", &ids);
+                let outcome = self
+                    .move_sessions_to_group(&ids, &group)
+                    .map(|_| match group.as_str() {
+                        "" => "removed from group".to_string(),
+                        group => format!("moved to {}", group),
+                    })
+                    .map_err(|e| e.to_string());
+                self.report_all(&ids, outcome);
+                self.clear_marks();
+                return;
+            }
+            BulkAction::MoveToProfile(profile) => {
+                self.show_bulk_progress("Move to Profile", &ids);
+                let outcome = self
+                    .move_sessions_to_profile(&ids, &profile)
+                    .map(|_| format!("moved to profile {}", profile))
+                    .map_err(|e| e.to_string());
+                self.report_all(&ids, outcome);
+                self.clear_marks();
+                return;
+            }
+            BulkAction::Stop => ("Stop", BulkOperation::Stop),
+            BulkAction::Restart => ("Restart", BulkOperation::Restart),
+            BulkAction::SendPrompt(prompt) => ("Send Prompt", BulkOperation::SendPrompt(prompt)),
+            BulkAction::Land => ("Land Worktrees", BulkOperation::Land),
+        };
+
+        self.show_bulk_progress(title, &ids);
+        for id in &ids {
+            if let Some(inst) = self.instance_map.get(id) {
+                self.bulk_poller.request(BulkRequest {
+                    instance: inst.clone(),
+                    operation: operation.clone(),
+                    profile: self.storage.profile().to_string(),
+                });
+            }
+        }
+        self.clear_marks();
+    }
+
+    fn show_bulk_progress(&mut self, title: &str, ids: &[String]) {
+        let sessions = ids
+            .iter()
+            .filter_map(|id| self.instance_map.get(id))
+            .map(|inst| (inst.id.clone(), inst.title.clone()))
+            .collect();
+        self.bulk_progress_dialog = Some(BulkProgressDialog::new(title, sessions));
+    }
+
+    fn report_all(&mut self, ids: &[String], outcome: Result<String, String>) {
+        if let Some(dialog) = &mut self.bulk_progress_dialog {
+            for id in ids {
+                dialog.set_result(id, outcome.clone());
+            }
+        }
+    }
+
+    /// Queue the marked sessions for deletion, applying each cleanup option
+    /// only where it makes sense for the session
+    pub(super) fn delete_marked(&mut self, options: &DeleteOptions) {
+        let ids = std::mem::take(&mut self.bulk_delete_ids);
+        self.show_bulk_progress("Delete", &ids);
+
+        for id in &ids {
+            if let Some(inst) = self.instance_map.get_mut(id) {
+                inst.status = Status::Deleting;
+            }
+            if let Some(inst) = self.instances.iter_mut().find(|i| i.id == *id) {
+                inst.status = Status::Deleting;
+            }
+
+            if let Some(inst) = self.instance_map.get(id) {
+                let managed_worktree = inst
+                    .worktree_info
+                    .as_ref()
+                    .is_some_and(|wt| wt.managed_by_aoe);
+                let sandbox = inst.sandbox_info.as_ref().is_some_and(|s| s.enabled);
+                self.deletion_poller.request_deletion(DeletionRequest {
+                    session_id: id.clone(),
+                    instance: inst.clone(),
+                    delete_worktree: options.delete_worktree && managed_worktree,
+                    delete_branch: options.delete_branch && managed_worktree,
+                    delete_sandbox: options.delete_sandbox && sandbox,
+                });
+            }
+        }
+        self.clear_marks();
+    }
+
+    pub(super) fn move_sessions_to_group(
+        &mut self,
+        ids: &[String],
+        group: &str,
+    ) -> anyhow::Result<()> {
+        for inst in self.instances.iter_mut().filter(|i| ids.contains(&i.id)) {
+            inst.group_path = group.to_string();
+        }
+
+        self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
+        if !group.is_empty() {
+            self.group_tree.create_group(group);
+        }
+        self.storage
+            .save_with_groups(&self.instances, &self.group_tree)?;
+
+        self.reload()
+    }
+
+    pub(super) fn move_sessions_to_profile(
+        &mut self,
+        ids: &[String],
+        profile: &str,
+    ) -> anyhow::Result<()> {
+        if profile == self.storage.profile() {
+            return Ok(());
+        }
+        if !list_profiles()?.iter().any(|p| p == profile) {
+            anyhow::bail!("Profile '{}' does not exist", profile);
+        }
+
+        let target_storage = Storage::new(profile)?;
+        let (mut target_instances, target_groups) = target_storage.load_with_groups()?;
+        let moved: Vec<_> = self
+            .instances
+            .iter()
+            .filter(|i| ids.contains(&i.id))
+            .cloned()
+            .collect();
+        target_instances.extend(moved);
+        let target_tree = GroupTree::new_with_groups(&target_instances, &target_groups);
+        target_storage.save_with_groups(&target_instances, &target_tree)?;
+
+        self.instances.retain(|i| !ids.contains(&i.id));
+        self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
+        self.storage
+            .save_with_groups(&self.instances, &self.group_tree)?;
+
+        if self
+            .selected_session
+            .as_ref()
+            .is_some_and(|id| ids.contains(id))
+        {
+            self.selected_session = None;
+        }
+        self.reload()
+    }
+
+    /// Apply finished stop/restart/prompt/land results (non-blocking)
+    pub fn apply_bulk_results(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(result) = self.bulk_poller.try_recv_result() {
+            if let Some(updated) = result.instance {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == updated.id) {
+                    *inst = updated.clone();
+                }
+                self.instance_map.insert(updated.id.clone(), updated);
+                if let Err(e) = self
+                    .storage
+                    .save_with_groups(&self.instances, &self.group_tree)
+                {
+                    tracing::error!("Failed to save after restart: {}", e);
+                }
+            }
+            if let Some(dialog) = &mut self.bulk_progress_dialog {
+                dialog.set_result(&result.session_id, result.outcome);
+            }
+            changed = true;
+        }
+        changed
+    }
+}
@@ -0,0 +1,482 @@
+//! Grid of live previews of several sessions at once
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::render::status_icon;
+use super::{HomeView, PreviewCache};
+use crate::session::{Instance, Status};
+use crate::tmux::QuickAnswer;
+use crate::tui::app::Action;
+use crate::tui::dialogs::InfoDialog;
+use crate::tui::keybindings::GridCommand;
+use crate::tui::preview_poller::PreviewRequest;
+use crate::tui::styles::Theme;
+
+/// Most tiles shown at once; the rest of the scope is left out
+pub(super) const MAX_TILES: usize = 16;
+const MIN_TILE_WIDTH: u16 = 24;
+const MIN_TILE_HEIGHT: u16 = 6;
+
+/// Which sessions the grid shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum GridScope {
+    Marked,
+    /// Sessions in a group and its subgroups
+    Group(String),
+    Waiting,
+    /// Everything the list shows, respecting the search and changes filter
+    Shown,
+}
+
+impl GridScope {
+    fn label(&self) -> &str {
+        match self {
+            GridScope::Marked => "Marked",
+            GridScope::Group(path) => path,
+            GridScope::Waiting => "Waiting",
+            GridScope::Shown => "Shown",
+        }
+    }
+}
+
+pub struct GridView {
+    pub(super) scope: GridScope,
+    /// Focused session, kept by ID so focus stays put as tiles come and go
+    pub(super) focused: Option<String>,
+    /// Latest capture of each tile's session
+    pub(super) previews: HashMap<String, PreviewCache>,
+    /// Layout at the last render, for moving focus and sizing captures
+    pub(super) columns: usize,
+    pub(super) capacity: usize,
+    pub(super) tile_size: (u16, u16),
+    pub(super) pending_refresh: bool,
+}
+
+impl GridView {
+    fn new(scope: GridScope, focused: Option<String>) -> Self {
+        Self {
+            scope,
+            focused,
+            previews: HashMap::new(),
+            columns: 1,
+            capacity: MAX_TILES,
+            tile_size: (0, 0),
+            pending_refresh: false,
+        }
+    }
+}
+
+/// Columns and rows for `count` tiles, and how many tiles fit in `area`.
+/// Tiles are kept roughly square in count, within the minimum tile size.
+pub(super) fn grid_layout(count: usize, area: Rect) -> (usize, usize, usize) {
+    let max_cols = ((area.width / MIN_TILE_WIDTH) as usize).max(1);
+    let max_rows = ((area.height / MIN_TILE_HEIGHT) as usize).max(1);
+    let count = count.min(MAX_TILES).min(max_cols * max_rows);
+    if count == 0 {
+        return (1, 0, 0);
+    }
+
+    let mut cols = 1;
+    while cols * cols < count {
+        cols += 1;
+    }
+    let cols = cols.max(count.div_ceil(max_rows)).min(max_cols);
+    (cols, count.div_ceil(cols), count)
+}
+
+impl HomeView {
+    /// Sessions in a grid scope, in list order
+    pub(super) fn grid_session_ids(&self, scope: &GridScope) -> Vec<String> {
+        let in_scope = |inst: &Instance| match scope {
+            GridScope::Marked | GridScope::Shown => true,
+            GridScope::Group(path) => {
+                inst.group_path == *path || inst.group_path.starts_with(&format!("{}/", path))
+            }
+            GridScope::Waiting => inst.status == Status::Waiting,
+        };
+        let candidates = match scope {
+            GridScope::Marked => self.marked_ids(),
+            GridScope::Shown => self.visible_session_ids(),
+            GridScope::Group(_) | GridScope::Waiting => {
+                self.instances.iter().map(|i| i.id.clone()).collect()
+            }
+        };
+        candidates
+            .into_iter()
+            .filter(|id| {
+                self.instance_map
+                    .get(id)
+                    .is_some_and(|inst| inst.status != Status::Deleting && in_scope(inst))
+            })
+            .collect()
+    }
+
+    /// Sessions with a tile, i.e. as much of the scope as fits
+    pub(super) fn grid_tiles(&self) -> Vec<String> {
+        let Some(grid) = &self.grid else {
+            return Vec::new();
+        };
+        let mut ids = self.grid_session_ids(&grid.scope);
+        ids.truncate(grid.capacity);
+        ids
+    }
+
+    /// Scopes the grid can cycle through right now
+    fn grid_scopes(&self) -> Vec<GridScope> {
+        let mut scopes = Vec::new();
+        if self.has_marks() {
+            scopes.push(GridScope::Marked);
+        }
+        let group = self.selected_group.clone().or_else(|| {
+            self.selected_session
+                .as_ref()
+                .and_then(|id| self.instance_map.get(id))
+                .map(|inst| inst.group_path.clone())
+                .filter(|path| !path.is_empty())
+        });
+        if let Some(group) = group {
+            scopes.push(GridScope::Group(group));
+        }
+        scopes.push(GridScope::Waiting);
+        scopes.push(GridScope::Shown);
+        scopes
+    }
+
+    /// Open the grid on the marked sessions, else the selected group, else
+    /// everything shown
+    pub(super) fn open_grid(&mut self) {
+        let scope = self
+            .grid_scopes()
+            .into_iter()
+            .find(|scope| *scope != GridScope::Waiting)
+            .unwrap_or(GridScope::Shown);
+        if self.grid_session_ids(&scope).is_empty() {
+            self.info_dialog = Some(InfoDialog::new(
+                "No Sessions",
+                "There are no sessions to show in the grid.",
+            ));
+            return;
+        }
+        self.grid = Some(GridView::new(scope, self.selected_session.clone()));
+    }
+
+    fn cycle_grid_scope(&mut self) {
+        let Some(current) = self.grid.as_ref().map(|g| g.scope.clone()) else {
+            return;
+        };
+        let scopes = self.grid_scopes();
+        let next = scopes
+            .iter()
+            .position(|scope| *scope == current)
+            .map_or(0, |i| (i + 1) % scopes.len());
+        if let Some(grid) = &mut self.grid {
+            grid.scope = scopes[next].clone();
+            grid.previews.clear();
+        }
+    }
+
+    /// Index of the focused tile, falling back to the first one
+    fn grid_focus_index(&self, tiles: &[String]) -> usize {
+        self.grid
+            .as_ref()
+            .and_then(|grid| grid.focused.as_ref())
+            .and_then(|id| tiles.iter().position(|t| t == id))
+            .unwrap_or(0)
+    }
+
+    pub(super) fn handle_grid_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let command = self.keybindings.grid.command(&key)?;
+        self.run_grid_command(command)
+    }
+
+    /// Carry out a command of the grid, from its key or the command palette
+    pub(super) fn run_grid_command(&mut self, command: GridCommand) -> Option<Action> {
+        let tiles = self.grid_tiles();
+        let focus = self.grid_focus_index(&tiles);
+        let columns = self.grid.as_ref().map_or(1, |g| g.columns.max(1));
+
+        let target = match command {
+            GridCommand::Close => {
+                self.grid = None;
+                return None;
+            }
+            GridCommand::NextScope => {
+                self.cycle_grid_scope();
+                return None;
+            }
+            GridCommand::Attach => {
+                return tiles.get(focus).map(|id| Action::AttachSession(id.clone()));
+            }
+            GridCommand::InlineInput => {
+                self.start_inline_input(tiles.get(focus).cloned());
+                return None;
+            }
+            GridCommand::ApproveOnce => {
+                self.quick_answer(tiles.get(focus).cloned(), QuickAnswer::ApproveOnce);
+                return None;
+            }
+            GridCommand::ApproveAlways => {
+                self.quick_answer(tiles.get(focus).cloned(), QuickAnswer::ApproveAlways);
+                return None;
+            }
+            GridCommand::Deny => {
+                self.quick_answer(tiles.get(focus).cloned(), QuickAnswer::Deny);
+                return None;
+            }
+            GridCommand::CommandPalette => {
+                self.open_palette();
+                return None;
+            }
+            GridCommand::Left => focus.saturating_sub(1),
+            GridCommand::Right => focus + 1,
+            GridCommand::Up => focus.checked_sub(columns).unwrap_or(focus),
+            GridCommand::Down => focus + columns,
+        };
+        let target = if target >= tiles.len() { focus } else { target };
+        if let Some(grid) = &mut self.grid {
+            grid.focused = tiles.get(target).cloned();
+        }
+        None
+    }
+
+    /// Request fresh captures of every tile in the background (non-blocking).
+    /// Call `apply_grid_updates` to check for and apply results.
+    pub fn request_grid_refresh(&mut self) {
+        let tiles = self.grid_tiles();
+        let Some(grid) = &self.grid else {
+            return;
+        };
+        let (width, height) = grid.tile_size;
+        if grid.pending_refresh || width == 0 || height == 0 {
+            return;
+        }
+        let requests = tiles
+            .iter()
+            .filter_map(|id| self.instance_map.get(id))
+            .map(|inst| PreviewRequest {
+                instance: inst.clone(),
+                width,
+                height,
+            })
+            .collect();
+        self.preview_poller.request_refresh(requests);
+        if let Some(grid) = &mut self.grid {
+            grid.pending_refresh = true;
+        }
+    }
+
+    /// Apply any captures from the background poller.
+    /// Returns true if updates were applied.
+    pub fn apply_grid_updates(&mut self) -> bool {
+        let Some(updates) = self.preview_poller.try_recv_updates() else {
+            return false;
+        };
+        let Some(grid) = &mut self.grid else {
+            return false;
+        };
+        grid.previews.clear();
+        for update in updates {
+            grid.previews.insert(
+                update.session_id.clone(),
+                PreviewCache {
+                    session_id: Some(update.session_id),
+                    content: update.content,
+                    last_refresh: Instant::now(),
+                    dimensions: update.dimensions,
+                },
+            );
+        }
+        grid.pending_refresh = false;
+        true
+    }
+
+    pub(super) fn render_grid(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let total = self
+            .grid
+            .as_ref()
+            .map_or(0, |g| self.grid_session_ids(&g.scope).len());
+        let (columns, rows, capacity) = grid_layout(total, area);
+        let tile_height = area.height / rows.max(1) as u16;
+        let tile_width = area.width / columns as u16;
+        if let Some(grid) = &mut self.grid {
+            grid.columns = columns;
+            grid.capacity = capacity;
+            // Output area of a tile, inside its border
+            grid.tile_size = (tile_width.saturating_sub(2), tile_height.saturating_sub(2));
+        }
+
+        let tiles = self.grid_tiles();
+        if tiles.is_empty() {
+            let hint = Paragraph::new("No sessions in this scope")
+                .style(Style::default().fg(theme.dimmed))
+                .alignment(Alignment::Center);
+            frame.render_widget(hint, area);
+            return;
+        }
+
+        let focus = self.grid_focus_index(&tiles);
+        let row_areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+            .split(area);
+        for (row, row_area) in row_areas.iter().enumerate() {
+            let tile_areas = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+                .split(*row_area);
+            for (col, tile_area) in tile_areas.iter().enumerate() {
+                let index = row * columns + col;
+                let Some(inst) = tiles.get(index).and_then(|id| self.instance_map.get(id)) else {
+                    continue;
+                };
+                self.render_tile(frame, *tile_area, inst, index == focus, theme);
+            }
+        }
+    }
+
+    fn render_tile(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        inst: &Instance,
+        focused: bool,
+        theme: &Theme,
+    ) {
+        let (icon, color) = status_icon(inst.status, theme);
+        let border_color = if self.inline_input.as_ref() == Some(&inst.id) {
+            theme.waiting
+        } else if focused {
+            theme.accent
+        } else {
+            theme.border
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title(Line::from(vec![
+                Span::styled(format!(" {} ", icon), Style::default().fg(color)),
+                Span::styled(
+                    format!("{} ", inst.title),
+                    Style::default().fg(theme.title).bold(),
+                ),
+            ]));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if let Some(error) = &inst.last_error {
+            let paragraph = Paragraph::new(error.as_str())
+                .style(Style::default().fg(theme.error))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(paragraph, inner);
+            return;
+        }
+
+        let content = self
+            .grid
+            .as_ref()
+            .and_then(|grid| grid.previews.get(&inst.id))
+            .map_or("", |cache| cache.content.as_str());
+        let lines: Vec<&str> = content.trim_end().lines().collect();
+        if lines.is_empty() {
+            let hint = Paragraph::new("No output")
+                .style(Style::default().fg(theme.dimmed))
+                .alignment(Alignment::Center);
+            frame.render_widget(hint, inner);
+            return;
+        }
+
+        // Show the bottom of the output, where the agent is
+        let visible = lines.len().saturating_sub(inner.height as usize);
+        let output: Vec<Line> = lines[visible..]
+            .iter()
+            .map(|line| Line::from(Span::raw(*line)))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(output).style(Style::default().fg(theme.text)),
+            inner,
+        );
+    }
+
+    pub(super) fn render_grid_status_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(grid) = &self.grid else {
+            return;
+        };
+        let key_style = Style::default().fg(theme.accent).bold();
+        let desc_style = Style::default().fg(theme.dimmed);
+        let sep_style = Style::default().fg(theme.border);
+        let keys = &self.keybindings.grid;
+
+        let total = self.grid_session_ids(&grid.scope).len();
+        let shown = total.min(grid.capacity);
+        let count = if shown < total {
+            format!(" {} ({} of {}) ", grid.scope.label(), shown, total)
+        } else {
+            format!(" {} ({}) ", grid.scope.label(), total)
+        };
+
+        let mut spans = vec![
+            Span::styled(" [Grid] ", Style::default().fg(theme.waiting).bold()),
+            Span::styled("│", sep_style),
+            Span::styled(count, Style::default().fg(theme.accent).bold()),
+            Span::styled("│", sep_style),
+        ];
+        let tiles = self.grid_tiles();
+        spans.extend(self.quick_answer_hints(
+            tiles.get(self.grid_focus_index(&tiles)),
+            |answer| {
+                keys.short_label(match answer {
+                    QuickAnswer::ApproveOnce => GridCommand::ApproveOnce,
+                    QuickAnswer::ApproveAlways => GridCommand::ApproveAlways,
+                    QuickAnswer::Deny => GridCommand::Deny,
+                })
+            },
+            theme,
+        ));
+        spans.extend([
+            Span::styled(
+                format!(
+                    " {}/{}/{}/{}",
+                    keys.short_label(GridCommand::Left),
+                    keys.short_label(GridCommand::Down),
+                    keys.short_label(GridCommand::Up),
+                    keys.short_label(GridCommand::Right)
+                ),
+                key_style,
+            ),
+            Span::styled(" Move ", desc_style),
+            Span::styled("│", sep_style),
+            Span::styled(
+                format!(" {}", keys.short_label(GridCommand::Attach)),
+                key_style,
+            ),
+            Span::styled(" Attach ", desc_style),
+            Span::styled("│", sep_style),
+            Span::styled(
+                format!(" {}", keys.short_label(GridCommand::InlineInput)),
+                key_style,
+            ),
+            Span::styled(" Type ", desc_style),
+            Span::styled("│", sep_style),
+            Span::styled(
+                format!(" {}", keys.short_label(GridCommand::NextScope)),
+                key_style,
+            ),
+            Span::styled(" Scope ", desc_style),
+            Span::styled("│", sep_style),
+            Span::styled(
+                format!(" {}", keys.short_label(GridCommand::Close)),
+                key_style,
+            ),
+            Span::styled(" Close", desc_style),
+        ]);
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.selection)),
+            area,
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Answering a session from the preview, without attaching: typing into
+//! its pane, or sending a quick answer to a prompt it is waiting on
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::{HomeView, ViewMode};
+use crate::session::Status;
+use crate::tmux::QuickAnswer;
+use crate::tui::app::Action;
+use crate::tui::dialogs::InfoDialog;
+use crate::tui::keybindings::HomeCommand;
+use crate::tui::styles::Theme;
+
+/// The tmux key for a key press, and whether it is literal text rather than
+/// a key name. None for keys tmux has no name for.
+pub(super) fn tmux_key(key: &KeyEvent) -> Option<(String, bool)> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+    let name = match key.code {
+        // Shifted characters already arrive upper-cased
+        KeyCode::Char(c) if !ctrl && !alt => return Some((c.to_string(), true)),
+        KeyCode::Char(c) => c.to_ascii_lowercase().to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Escape".to_string(),
+        KeyCode::Backspace => "BSpace".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "BTab".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PPage".to_string(),
+        KeyCode::PageDown => "NPage".to_string(),
+        KeyCode::Delete => "DC".to_string(),
+        KeyCode::Insert => "IC".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => return None,
+    };
+
+    let mut prefix = String::new();
+    if ctrl {
+        prefix.push_str("C-");
+    }
+    if alt {
+        prefix.push_str("M-");
+    }
+    if shift && !matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+        prefix.push_str("S-");
+    }
+    Some((prefix + &name, false))
+}
+
+impl HomeView {
+    pub fn is_inline_input(&self) -> bool {
+        self.inline_input.is_some()
+    }
+
+    /// Start forwarding keys to a session's agent pane
+    pub(super) fn start_inline_input(&mut self, id: Option<String>) {
+        let Some(inst) = id.as_ref().and_then(|id| self.instance_map.get(id)) else {
+            return;
+        };
+        if matches!(inst.status, Status::Deleting | Status::Starting) {
+            return;
+        }
+        // Without a key to leave, every key would go to the session for good
+        if self
+            .keybindings
+            .home
+            .short_label(HomeCommand::LeaveInlineInput)
+            .is_empty()
+        {
+            self.info_dialog = Some(InfoDialog::new(
+                "No Key to Leave",
+                "Bind a key to leave_inline_input in [keybindings.home] to type into sessions.",
+            ));
+            return;
+        }
+        let running = inst.tmux_session().is_ok_and(|session| session.exists());
+        if !running {
+            self.info_dialog = Some(InfoDialog::new(
+                "Not Running",
+                "This session isn't running. Attach to it with Enter to start it.",
+            ));
+            return;
+        }
+        // Keys always go to the agent, so show the agent's output
+        self.view_mode = ViewMode::Agent;
+        self.inline_input = id;
+    }
+
+    pub(super) fn handle_inline_input_key(&mut self, key: KeyEvent) -> Option<Action> {
+        // Leaving is the only key not sent to the session
+        if self
+            .keybindings
+            .home
+            .is(&key, HomeCommand::LeaveInlineInput)
+        {
+            self.inline_input = None;
+            return None;
+        }
+        let (key, literal) = tmux_key(&key)?;
+        let result = match self
+            .inline_input
+            .as_ref()
+            .and_then(|id| self.instance_map.get(id))
+        {
+            Some(inst) => inst.send_key(&key, literal),
+            None => Err(anyhow::anyhow!("Session no longer exists")),
+        };
+
+        match result {
+            // Show the session's response on the next frame
+            Ok(()) => self.preview_cache.session_id = None,
+            Err(e) => {
+                self.inline_input = None;
+                self.info_dialog = Some(InfoDialog::new(
+                    "Inline Input Stopped",
+                    &format!("Couldn't send keys to the session: {}", e),
+                ));
+            }
+        }
+        None
+    }
+
+    /// Quick answers the session's detected prompt accepts
+    pub(super) fn offered_answers(&self, id: Option<&String>) -> Vec<QuickAnswer> {
+        let Some(reason) = id
+            .and_then(|id| self.instance_map.get(id))
+            .and_then(|inst| inst.waiting_reason.as_ref())
+        else {
+            return Vec::new();
+        };
+        [
+            QuickAnswer::ApproveOnce,
+            QuickAnswer::ApproveAlways,
+            QuickAnswer::Deny,
+        ]
+        .into_iter()
+        .filter(|&answer| reason.answer_keys(answer).is_some())
+        .collect()
+    }
+
+    /// Points at the key for typing into a session, which may be rebound
+    pub(super) fn inline_input_hint(&self) -> String {
+        let label = self.keybindings.home.short_label(HomeCommand::InlineInput);
+        if label.is_empty() {
+            "Bind a key to inline_input in [keybindings.home] to type into the session.".to_string()
+        } else {
+            format!("Use {} to type into the session.", label)
+        }
+    }
+
+    /// Answer the prompt a session is waiting on by sending the keys that
+    /// choose the answer
+    pub(super) fn quick_answer(&mut self, id: Option<String>, answer: QuickAnswer) {
+        let Some(id) = id else {
+            return;
+        };
+        let Some(inst) = self.instance_map.get(&id) else {
+            return;
+        };
+        let Some(keys) = inst
+            .waiting_reason
+            .as_ref()
+            .and_then(|reason| reason.answer_keys(answer))
+        else {
+            let message = match inst.waiting_reason {
+                Some(_) => format!(
+                    "This prompt doesn't offer that answer. {}",
+                    self.inline_input_hint()
+                ),
+                None => "This session isn't waiting on a prompt it can be answered from here."
+                    .to_string(),
+            };
+            self.info_dialog = Some(InfoDialog::new("Nothing to Answer", &message));
+            return;
+        };
+
+        if let Err(e) = keys.iter().try_for_each(|key| inst.send_key(key, false)) {
+            self.info_dialog = Some(InfoDialog::new(
+                "Answer Failed",
+                &format!("Couldn't send the answer to the session: {}", e),
+            ));
+            return;
+        }
+
+        // The prompt is gone; the next status poll tells what comes next
+        if let Some(inst) = self.instance_map.get_mut(&id) {
+            inst.waiting_reason = None;
+        }
+        if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+            inst.waiting_reason = None;
+        }
+        self.preview_cache.session_id = None;
+    }
+
+    /// Status bar hints for the quick answers a session accepts, with `key`
+    /// giving the key label of each answer
+    pub(super) fn quick_answer_hints(
+        &self,
+        id: Option<&String>,
+        key: impl Fn(QuickAnswer) -> String,
+        theme: &Theme,
+    ) -> Vec<Span<'static>> {
+        let answers = self.offered_answers(id);
+        if answers.is_empty() {
+            return Vec::new();
+        }
+        let mut spans = Vec::new();
+        for answer in answers {
+            let label = match answer {
+                QuickAnswer::ApproveOnce => " Approve ",
+                QuickAnswer::ApproveAlways => " Always ",
+                QuickAnswer::Deny => " Deny ",
+            };
+            spans.extend([
+                Span::styled(
+                    format!(" {}", key(answer)),
+                    Style::default().fg(theme.waiting).bold(),
+                ),
+                Span::styled(label, Style::default().fg(theme.dimmed)),
+            ]);
+        }
+        spans.push(Span::styled("│", Style::default().fg(theme.border)));
+        spans
+    }
+
+    pub(super) fn render_inline_input_status_bar(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+    ) {
+        let title = self
+            .inline_input
+            .as_ref()
+            .and_then(|id| self.instance_map.get(id))
+            .map_or("", |inst| inst.title.as_str());
+        let spans = vec![
+            Span::styled(" [Input] ", Style::default().fg(theme.waiting).bold()),
+            Span::styled("│", Style::default().fg(theme.border)),
+            Span::styled(" Keys go to ", Style::default().fg(theme.dimmed)),
+            Span::styled(title, Style::default().fg(theme.accent).bold()),
+            Span::styled(" │", Style::default().fg(theme.border)),
+            Span::styled(
+                format!(
+                    " {}",
+                    self.keybindings
+                        .home
+                        .short_label(HomeCommand::LeaveInlineInput)
+                ),
+                Style::default().fg(theme.accent).bold(),
+            ),
+            Span::styled(" Leave", Style::default().fg(theme.dimmed)),
+        ];
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.selection)),
+            area,
+        );
+    }
+}
@@ -1,21 +1,29 @@
 //! Input handling for HomeView
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use crossterm::event::{KeyCode, KeyEvent};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use super::{HomeView, TerminalMode, ViewMode};
-use crate::session::{flatten_tree, list_profiles, repo_config, Item, Status};
+use crate::session::{list_profiles, repo_config, Instance, Item, Status};
+use crate::tmux::QuickAnswer;
 use crate::tui::app::Action;
 use crate::tui::dialogs::{
-    ConfirmDialog, DeleteDialogConfig, DialogResult, GroupDeleteOptionsDialog, HookTrustAction,
-    InfoDialog, NewSessionData, NewSessionDialog, RenameDialog, UnifiedDeleteDialog,
+    ConfirmDialog, DeleteDialogConfig, DeleteOptions, DialogResult, GroupDeleteOptionsDialog,
+    HookTrustAction, InfoDialog, NewSessionData, NewSessionDialog, PortsAction, PortsDialog,
+    RenameDialog, SnapshotsDialog, UnifiedDeleteDialog,
 };
 use crate::tui::diff::{DiffAction, DiffView};
+use crate::tui::keybindings::{DiffCommand, HomeCommand, SettingsCommand};
 use crate::tui::settings::{SettingsAction, SettingsView};
 
 impl HomeView {
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        // The command palette sits on top of every view
+        if self.palette.is_some() {
+            return self.handle_palette_key(key);
+        }
+
         // Handle unsaved changes confirmation for settings (shown over settings view)
         if self.settings_close_confirm {
             if let Some(dialog) = &mut self.confirm_dialog {
@@ -43,40 +51,31 @@ impl HomeView {
 
         // Handle settings view (full-screen takeover)
         if let Some(ref mut settings) = self.settings_view {
-            match settings.handle_key(key) {
-                SettingsAction::Continue => return None,
-                SettingsAction::Close => {
-                    self.settings_view = None;
-                    // Refresh config-dependent state in case settings changed
-                    self.refresh_from_config();
-                    return None;
-                }
-                SettingsAction::UnsavedChangesWarning => {
-                    // Show confirmation dialog
-                    self.confirm_dialog = Some(ConfirmDialog::new(
-                        "Unsaved Changes",
-                        "You have unsaved changes. Discard them?",
-                        "discard_settings",
-                    ));
-                    self.settings_close_confirm = true;
-                    return None;
-                }
-            }
+            if !settings.is_editing()
+                && self
+                    .keybindings
+                    .settings
+                    .is(&key, SettingsCommand::CommandPalette)
+            {
+                self.open_palette();
+                return None;
+            }
+            let action = settings.handle_key(key);
+            self.finish_settings_action(action);
+            return None;
         }
 
         // Handle diff view (full-screen takeover)
         if let Some(ref mut diff_view) = self.diff_view {
-            match diff_view.handle_key(key) {
-                DiffAction::Continue => return None,
-                DiffAction::Close => {
-                    self.diff_view = None;
-                    return None;
-                }
-                DiffAction::EditFile(path) => {
-                    // Launch external editor (vim or nano)
-                    return Some(Action::EditFile(path));
-                }
-            }
+            if diff_view.branch_select.is_none()
+                && !diff_view.show_help
+                && self.keybindings.diff.is(&key, DiffCommand::CommandPalette)
+            {
+                self.open_palette();
+                return None;
+            }
+            let action = diff_view.handle_key(key);
+            return self.finish_diff_action(action);
         }
 
         // Handle welcome/changelog dialogs first (highest priority)
@@ -110,12 +109,24 @@ impl HomeView {
             return None;
         }
 
+        // Typing into a session from the preview or the grid
+        if self.inline_input.is_some() {
+            return self.handle_inline_input_key(key);
+        }
+
+        // Grid of live previews (replaces the list until closed)
+        if self.grid.is_some() {
+            return self.handle_grid_key(key);
+        }
+
         // Handle other dialog input
         if self.show_help {
-            if matches!(
-                key.code,
-                KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q')
-            ) {
+            if key.code == KeyCode::Esc
+                || matches!(
+                    self.keybindings.home.command(&key),
+                    Some(HomeCommand::Help | HomeCommand::Quit)
+                )
+            {
                 self.show_help = false;
             }
             return None;
@@ -217,18 +228,8 @@ impl HomeView {
         }
 
         if let Some(dialog) = &mut self.unified_delete_dialog {
-            match dialog.handle_key(key) {
-                DialogResult::Continue => {}
-                DialogResult::Cancel => {
-                    self.unified_delete_dialog = None;
-                }
-                DialogResult::Submit(options) => {
-                    self.unified_delete_dialog = None;
-                    if let Err(e) = self.delete_selected(&options) {
-                        tracing::error!("Failed to delete session: {}", e);
-                    }
-                }
-            }
+            let result = dialog.handle_key(key);
+            self.finish_unified_delete(result);
             return None;
         }
 
@@ -252,6 +253,34 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.ports_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.ports_dialog = None;
+                }
+                DialogResult::Submit(action) => {
+                    let id = dialog.session_id().to_string();
+                    self.request_ports(&id, action);
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.snapshots_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.snapshots_dialog = None;
+                }
+                DialogResult::Submit(action) => {
+                    let id = dialog.session_id().to_string();
+                    self.request_snapshots(&id, Some(action));
+                }
+            }
+            return None;
+        }
+
         if let Some(dialog) = &mut self.rename_dialog {
             match dialog.handle_key(key) {
                 DialogResult::Continue => {}
@@ -272,13 +301,51 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.bulk_actions_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.bulk_actions_dialog = None;
+                }
+                DialogResult::Submit(action) => {
+                    self.bulk_actions_dialog = None;
+                    self.apply_bulk_action(action);
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.filters_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.filters_dialog = None;
+                }
+                DialogResult::Submit(selection) => {
+                    self.filters_dialog = None;
+                    return self.apply_filters(selection);
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.bulk_progress_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel | DialogResult::Submit(_) => {
+                    self.bulk_progress_dialog = None;
+                }
+            }
+            return None;
+        }
+
         // Search mode
         if self.search_active {
             match key.code {
                 KeyCode::Esc => {
                     self.search_active = false;
                     self.search_query = Input::default();
-                    self.filtered_items = None;
+                    self.update_filter();
                 }
                 KeyCode::Enter => {
                     self.search_active = false;
@@ -293,23 +360,30 @@ impl HomeView {
         }
 
         // Normal mode keybindings
-        match key.code {
-            KeyCode::Char('q') => return Some(Action::Quit),
-            KeyCode::Char('?') => {
+        let command = self.keybindings.home.command(&key)?;
+        self.run_command(command)
+    }
+
+    /// Carry out a command of the session list, from its key or the command
+    /// palette
+    pub(super) fn run_command(&mut self, command: HomeCommand) -> Option<Action> {
+        match command {
+            HomeCommand::Quit => return Some(Action::Quit),
+            HomeCommand::Help => {
                 self.show_help = true;
             }
-            KeyCode::Char('P') => {
+            HomeCommand::NextProfile => {
                 if let Some(next) = self.get_next_profile() {
                     return Some(Action::SwitchProfile(next));
                 }
             }
-            KeyCode::Char('t') => {
+            HomeCommand::ToggleView => {
                 self.view_mode = match self.view_mode {
                     ViewMode::Agent => ViewMode::Terminal,
                     ViewMode::Terminal => ViewMode::Agent,
                 };
             }
-            KeyCode::Char('c') => {
+            HomeCommand::ToggleTerminalMode => {
                 // Toggle container/host terminal mode (only in Terminal view for sandboxed sessions)
                 if self.view_mode == ViewMode::Terminal {
                     if let Some(id) = &self.selected_session {
@@ -327,11 +401,80 @@ impl HomeView {
                     }
                 }
             }
-            KeyCode::Char('/') => {
+            HomeCommand::Ports => {
+                if let Some(id) = &self.selected_session {
+                    if let Some(inst) = self.instance_map.get(id) {
+                        if inst.is_sandboxed() {
+                            self.ports_dialog = Some(PortsDialog::new(&inst.id, &inst.title));
+                            let id = id.clone();
+                            self.request_ports(&id, PortsAction::Refresh);
+                        } else {
+                            self.info_dialog = Some(InfoDialog::new(
+                                "Not Available",
+                                "Only sandboxed sessions have ports. This session runs directly on the host.",
+                            ));
+                        }
+                    }
+                }
+            }
+            HomeCommand::Snapshots => {
+                if let Some(id) = &self.selected_session {
+                    if let Some(inst) = self.instance_map.get(id) {
+                        if inst.is_sandboxed() {
+                            let mut dialog = SnapshotsDialog::new(&inst.id, &inst.title);
+                            dialog.set_active(
+                                inst.sandbox_info.as_ref().and_then(|s| s.snapshot.clone()),
+                            );
+                            self.snapshots_dialog = Some(dialog);
+                            let id = id.clone();
+                            self.request_snapshots(&id, None);
+                        } else {
+                            self.info_dialog = Some(InfoDialog::new(
+                                "Not Available",
+                                "Only sandboxed sessions have snapshots. This session runs directly on the host.",
+                            ));
+                        }
+                    }
+                }
+            }
+            HomeCommand::Search => {
                 self.search_active = true;
                 self.search_query = Input::default();
             }
-            KeyCode::Char('n') => {
+            HomeCommand::InlineInput => {
+                self.start_inline_input(self.selected_session.clone());
+            }
+            // Handled while typing into a session; nothing to leave here
+            HomeCommand::LeaveInlineInput => {}
+            HomeCommand::ApproveOnce => {
+                self.quick_answer(self.selected_session.clone(), QuickAnswer::ApproveOnce);
+            }
+            HomeCommand::ApproveAlways => {
+                self.quick_answer(self.selected_session.clone(), QuickAnswer::ApproveAlways);
+            }
+            HomeCommand::Deny => {
+                self.quick_answer(self.selected_session.clone(), QuickAnswer::Deny);
+            }
+            HomeCommand::Grid => {
+                self.open_grid();
+            }
+            HomeCommand::UsageSort => {
+                self.cycle_usage_sort();
+            }
+            HomeCommand::Sort => {
+                self.cycle_list_sort();
+            }
+            HomeCommand::FlatList => {
+                self.toggle_flat_list();
+            }
+            HomeCommand::Filters => {
+                self.open_filters();
+            }
+            HomeCommand::ChangesOnly => {
+                self.changes_only = !self.changes_only;
+                self.update_filter();
+            }
+            HomeCommand::NewSession => {
                 let existing_titles: Vec<String> =
                     self.instances.iter().map(|i| i.title.clone()).collect();
                 self.new_dialog = Some(NewSessionDialog::new(
@@ -340,9 +483,9 @@ impl HomeView {
                     self.storage.profile(),
                 ));
             }
-            KeyCode::Char('s') => {
+            HomeCommand::Settings => {
                 // Open settings view
-                match SettingsView::new(self.storage.profile()) {
+                match SettingsView::new(self.storage.profile(), self.keybindings.settings.clone()) {
                     Ok(view) => self.settings_view = Some(view),
                     Err(e) => {
                         tracing::error!("Failed to open settings: {}", e);
@@ -353,7 +496,7 @@ impl HomeView {
                     }
                 }
             }
-            KeyCode::Char('D') => {
+            HomeCommand::Diff => {
                 // Open diff view - requires a selected session
                 let Some(session_id) = &self.selected_session else {
                     self.info_dialog = Some(InfoDialog::new(
@@ -370,7 +513,7 @@ impl HomeView {
                 };
 
                 let repo_path = std::path::PathBuf::from(&inst.project_path);
-                match DiffView::new(repo_path) {
+                match DiffView::new(repo_path, self.keybindings.diff.clone()) {
                     Ok(view) => self.diff_view = Some(view),
                     Err(e) => {
                         tracing::error!("Failed to open diff view: {}", e);
@@ -381,7 +524,7 @@ impl HomeView {
                     }
                 }
             }
-            KeyCode::Char('d') => {
+            HomeCommand::Delete => {
                 // Deletion only allowed in Agent View
                 if self.view_mode == ViewMode::Terminal {
                     self.info_dialog = Some(InfoDialog::new(
@@ -390,7 +533,9 @@ impl HomeView {
                     ));
                     return None;
                 }
-                if let Some(session_id) = &self.selected_session {
+                if self.has_marks() {
+                    self.open_bulk_delete();
+                } else if let Some(session_id) = &self.selected_session {
                     if let Some(inst) = self.instance_map.get(session_id) {
                         if inst.status == Status::Deleting {
                             return None;
@@ -441,7 +586,7 @@ impl HomeView {
                     }
                 }
             }
-            KeyCode::Char('r') if !key.modifiers.contains(KeyModifiers::SHIFT) => {
+            HomeCommand::Rename => {
                 if let Some(id) = &self.selected_session {
                     if let Some(inst) = self.instance_map.get(id) {
                         if inst.status == Status::Deleting {
@@ -459,29 +604,44 @@ impl HomeView {
                     }
                 }
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            HomeCommand::Mark => {
+                self.toggle_mark();
+            }
+            HomeCommand::VisualMark => {
+                self.toggle_mark_range();
+            }
+            HomeCommand::MarkVisible => {
+                self.mark_visible();
+            }
+            HomeCommand::ClearMarks => {
+                self.clear_marks();
+            }
+            HomeCommand::BulkActions => {
+                self.open_bulk_actions();
+            }
+            HomeCommand::MoveUp => {
                 self.move_cursor(-1);
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            HomeCommand::MoveDown => {
                 self.move_cursor(1);
             }
-            KeyCode::PageUp => {
+            HomeCommand::PageUp => {
                 self.move_cursor(-10);
             }
-            KeyCode::PageDown => {
+            HomeCommand::PageDown => {
                 self.move_cursor(10);
             }
-            KeyCode::Home | KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::NONE) => {
+            HomeCommand::Top => {
                 self.cursor = 0;
                 self.update_selected();
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            HomeCommand::Bottom => {
                 if !self.flat_items.is_empty() {
                     self.cursor = self.flat_items.len() - 1;
                     self.update_selected();
                 }
             }
-            KeyCode::Enter => {
+            HomeCommand::Attach => {
                 if let Some(id) = &self.selected_session {
                     if let Some(inst) = self.instance_map.get(id) {
                         if inst.status == Status::Deleting {
@@ -508,13 +668,13 @@ impl HomeView {
                     self.toggle_group_collapsed(&path);
                 }
             }
-            KeyCode::Char('H') => {
+            HomeCommand::ShrinkList => {
                 self.shrink_list();
             }
-            KeyCode::Char('L') => {
+            HomeCommand::GrowList => {
                 self.grow_list();
             }
-            KeyCode::Left | KeyCode::Char('h') => {
+            HomeCommand::Collapse => {
                 if let Some(Item::Group {
                     path, collapsed, ..
                 }) = self.flat_items.get(self.cursor)
@@ -525,7 +685,7 @@ impl HomeView {
                     }
                 }
             }
-            KeyCode::Right | KeyCode::Char('l') => {
+            HomeCommand::Expand => {
                 if let Some(Item::Group {
                     path, collapsed, ..
                 }) = self.flat_items.get(self.cursor)
@@ -536,12 +696,81 @@ impl HomeView {
                     }
                 }
             }
-            _ => {}
+            HomeCommand::CommandPalette => {
+                self.open_palette();
+            }
+            HomeCommand::Pin => {
+                self.toggle_pinned();
+            }
+            HomeCommand::Favorite => {
+                self.toggle_favorite();
+            }
+            HomeCommand::MoveItemUp => {
+                self.move_selected_item(true);
+            }
+            HomeCommand::MoveItemDown => {
+                self.move_selected_item(false);
+            }
         }
 
         None
     }
 
+    /// Act on what the settings view asked for after a key or a command
+    pub(super) fn finish_settings_action(&mut self, action: SettingsAction) {
+        match action {
+            SettingsAction::Continue => {}
+            SettingsAction::Close => {
+                self.settings_view = None;
+                // Refresh config-dependent state in case settings changed
+                self.refresh_from_config();
+            }
+            SettingsAction::UnsavedChangesWarning => {
+                // Show confirmation dialog
+                self.confirm_dialog = Some(ConfirmDialog::new(
+                    "Unsaved Changes",
+                    "You have unsaved changes. Discard them?",
+                    "discard_settings",
+                ));
+                self.settings_close_confirm = true;
+            }
+        }
+    }
+
+    /// Act on what the diff view asked for after a key, a click or a
+    /// command
+    pub(super) fn finish_diff_action(&mut self, action: DiffAction) -> Option<Action> {
+        match action {
+            DiffAction::Continue => None,
+            DiffAction::Close => {
+                self.diff_view = None;
+                None
+            }
+            // Launch external editor (vim or nano)
+            DiffAction::EditFile(path) => Some(Action::EditFile(path)),
+        }
+    }
+
+    /// Act on the answer of the delete dialog, for one session or the
+    /// marked ones
+    pub(super) fn finish_unified_delete(&mut self, result: DialogResult<DeleteOptions>) {
+        match result {
+            DialogResult::Continue => {}
+            DialogResult::Cancel => {
+                self.unified_delete_dialog = None;
+                self.bulk_delete_ids.clear();
+            }
+            DialogResult::Submit(options) => {
+                self.unified_delete_dialog = None;
+                if !self.bulk_delete_ids.is_empty() {
+                    self.delete_marked(&options);
+                } else if let Err(e) = self.delete_selected(&options) {
+                    tracing::error!("Failed to delete session: {}", e);
+                }
+            }
+        }
+    }
+
     pub(super) fn move_cursor(&mut self, delta: i32) {
         let items = if let Some(ref filtered) = self.filtered_items {
             filtered.len()
@@ -574,6 +803,9 @@ impl HomeView {
             if let Some(item) = self.flat_items.get(idx) {
                 match item {
                     Item::Session { id, .. } => {
+                        if self.selected_session.as_ref() != Some(id) {
+                            self.preview_scroll = 0;
+                        }
                         self.selected_session = Some(id.clone());
                         self.selected_group = None;
                     }
@@ -581,6 +813,10 @@ impl HomeView {
                         self.selected_session = None;
                         self.selected_group = Some(path.clone());
                     }
+                    Item::Favorites { .. } => {
+                        self.selected_session = None;
+                        self.selected_group = None;
+                    }
                 }
             }
         }
@@ -588,7 +824,7 @@ impl HomeView {
 
     fn toggle_group_collapsed(&mut self, path: &str) {
         self.group_tree.toggle_collapsed(path);
-        self.flat_items = flatten_tree(&self.group_tree, &self.instances);
+        self.flat_items = self.flatten_items();
         if let Err(e) = self
             .storage
             .save_with_groups(&self.instances, &self.group_tree)
@@ -598,37 +834,106 @@ impl HomeView {
     }
 
     pub(super) fn update_filter(&mut self) {
-        if self.search_query.value().is_empty() {
-            self.filtered_items = None;
-            return;
+        // The range anchor points into the displayed list, which is about to change
+        self.commit_mark_range();
+        self.filtered_items = self.compute_filter();
+        self.cursor = 0;
+        self.update_selected();
+    }
+
+    /// Recompute the active filter while keeping the current selection when it
+    /// still matches (used when the underlying list or git summaries change).
+    /// A favorite listed twice keeps the entry nearest the cursor.
+    pub(super) fn refresh_filter(&mut self) {
+        let selected_session = self.selected_session.clone();
+        let selected_group = self.selected_group.clone();
+
+        self.filtered_items = self.compute_filter();
+
+        let visible_len = self
+            .filtered_items
+            .as_ref()
+            .map_or(self.flat_items.len(), |f| f.len());
+        let position = (0..visible_len)
+            .filter(|&display_idx| {
+                let item_idx = self
+                    .filtered_items
+                    .as_ref()
+                    .map_or(display_idx, |f| f[display_idx]);
+                match self.flat_items.get(item_idx) {
+                    Some(Item::Session { id, .. }) => selected_session.as_ref() == Some(id),
+                    Some(Item::Group { path, .. }) => selected_group.as_ref() == Some(path),
+                    Some(Item::Favorites { .. }) | None => false,
+                }
+            })
+            .min_by_key(|&display_idx| display_idx.abs_diff(self.cursor));
+
+        self.cursor = position.unwrap_or_else(|| self.cursor.min(visible_len.saturating_sub(1)));
+        self.update_selected();
+    }
+
+    pub(super) fn compute_filter(&self) -> Option<Vec<usize>> {
+        let narrowed = self.changes_only || !self.list_filters.is_empty();
+        if self.search_query.value().is_empty() && !narrowed {
+            return None;
         }
 
         let query = self.search_query.value().to_lowercase();
+        let passes = |inst: &Instance| {
+            (!self.changes_only || self.session_has_changes(&inst.id)) && self.matches_filters(inst)
+        };
+        let session_matches = |inst: &Instance| {
+            let query_matches = query.is_empty()
+                || inst.title_lower.contains(&query)
+                || inst.project_path_lower.contains(&query);
+            query_matches && passes(inst)
+        };
+        // Groups stay visible while a session under them passes
+        let kept_groups: Vec<&str> = if narrowed {
+            self.instances
+                .iter()
+                .filter(|inst| passes(inst))
+                .map(|inst| inst.group_path.as_str())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         let mut matches = Vec::new();
 
         for (idx, item) in self.flat_items.iter().enumerate() {
             match item {
                 Item::Session { id, .. } => {
-                    if let Some(inst) = self.instance_map.get(id) {
-                        if inst.title_lower.contains(&query)
-                            || inst.project_path_lower.contains(&query)
-                        {
-                            matches.push(idx);
-                        }
+                    if self.instance_map.get(id).is_some_and(session_matches) {
+                        matches.push(idx);
                     }
                 }
-                Item::Group { name, path, .. } => {
-                    if name.to_lowercase().contains(&query) || path.to_lowercase().contains(&query)
+                // Shown while one of the favorites is
+                Item::Favorites { .. } => {
+                    if self
+                        .instances
+                        .iter()
+                        .any(|inst| inst.favorite && session_matches(inst))
                     {
                         matches.push(idx);
                     }
                 }
+                Item::Group { name, path, .. } => {
+                    let query_matches = query.is_empty()
+                        || name.to_lowercase().contains(&query)
+                        || path.to_lowercase().contains(&query);
+                    let prefix = format!("{}/", path);
+                    let kept = kept_groups
+                        .iter()
+                        .any(|g| *g == path || g.starts_with(&prefix));
+                    if query_matches && (!narrowed || kept) {
+                        matches.push(idx);
+                    }
+                }
             }
         }
 
-        self.filtered_items = Some(matches);
-        self.cursor = 0;
-        self.update_selected();
+        Some(matches)
     }
 
     /// Create a session with optional hooks. Delegates to the background
@@ -662,24 +967,4 @@ impl HomeView {
             }
         }
     }
-
-    /// Handle a mouse event
-    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<Action> {
-        // Pass mouse events to diff view if active
-        if let Some(ref mut diff_view) = self.diff_view {
-            match diff_view.handle_mouse(mouse) {
-                DiffAction::Continue => return None,
-                DiffAction::Close => {
-                    self.diff_view = None;
-                    return None;
-                }
-                DiffAction::EditFile(path) => {
-                    return Some(Action::EditFile(path));
-                }
-            }
-        }
-
-        // No mouse handling for other views currently
-        None
-    }
 }
@@ -1,32 +1,56 @@
 //! Home view - main session list and navigation
 
+mod bulk;
+mod grid;
+mod inline_input;
 mod input;
+mod mouse;
 mod operations;
+mod ordering;
+mod palette;
 mod render;
+mod sorting;
 
 #[cfg(test)]
 mod tests;
 
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::time::Instant;
 
+use ratatui::layout::Rect;
 use tui_input::Input;
 
 use crate::session::{
     config::{load_config, save_config},
-    flatten_tree, resolve_config, DefaultTerminalMode, Group, GroupTree, Instance, Item, Storage,
+    flatten_tree, resolve_config, DefaultTerminalMode, Group, GroupTree, Instance, Item,
+    SessionFilters, SessionSort, Storage,
 };
 use crate::tmux::AvailableTools;
 
+use super::bulk_poller::BulkPoller;
+use super::components::mouse::SplitDrag;
+use super::container_poller::ContainerPoller;
 use super::creation_poller::{CreationPoller, CreationRequest};
 use super::deletion_poller::DeletionPoller;
 use super::dialogs::{
-    ChangelogDialog, ConfirmDialog, GroupDeleteOptionsDialog, HookTrustDialog, InfoDialog,
-    NewSessionData, NewSessionDialog, RenameDialog, UnifiedDeleteDialog, WelcomeDialog,
+    BulkActionsDialog, BulkProgressDialog, ChangelogDialog, CommandPalette, ConfirmDialog,
+    FiltersDialog, GroupDeleteOptionsDialog, HookTrustDialog, InfoDialog, NewSessionData,
+    NewSessionDialog, PortsDialog, RenameDialog, SnapshotsDialog, UnifiedDeleteDialog,
+    WelcomeDialog,
 };
 use super::diff::DiffView;
+use super::git_status_poller::{GitStatusPoller, GitStatusRequest};
+use super::keybindings::Keybindings;
+use super::preview_poller::PreviewPoller;
 use super::settings::SettingsView;
+use super::stats_poller::StatsPoller;
 use super::status_poller::StatusPoller;
+use super::styles::Theme;
+use crate::docker::{ContainerRuntime, ContainerStats};
+use crate::git::diff::ChangeSummary;
+use grid::GridView;
+use palette::PaletteTarget;
 
 /// View mode for the home screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -36,6 +60,13 @@ pub enum ViewMode {
     Terminal,
 }
 
+/// Resource the session list is sorted by (heaviest first within each group)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageSort {
+    Cpu,
+    Memory,
+}
+
 /// Terminal mode for sandboxed sessions (container vs host)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TerminalMode {
@@ -88,6 +119,7 @@ pub(super) const ICON_STARTING: &str = "◌";
 pub(super) const ICON_DELETING: &str = "✗";
 pub(super) const ICON_COLLAPSED: &str = "▶";
 pub(super) const ICON_EXPANDED: &str = "▼";
+pub(super) const ICON_FAVORITES: &str = "★";
 
 pub struct HomeView {
     pub(super) storage: Storage,
@@ -116,11 +148,25 @@ pub struct HomeView {
     pub(super) welcome_dialog: Option<WelcomeDialog>,
     pub(super) changelog_dialog: Option<ChangelogDialog>,
     pub(super) info_dialog: Option<InfoDialog>,
+    pub(super) ports_dialog: Option<PortsDialog>,
+    pub(super) snapshots_dialog: Option<SnapshotsDialog>,
+
+    // Multi-select
+    /// Sessions marked for bulk actions
+    pub(super) marked_sessions: HashSet<String>,
+    /// Cursor position where range marking started
+    pub(super) mark_anchor: Option<usize>,
+    pub(super) bulk_actions_dialog: Option<BulkActionsDialog>,
+    pub(super) bulk_progress_dialog: Option<BulkProgressDialog>,
+    /// Sessions the open delete dialog applies to when deleting marked sessions
+    pub(super) bulk_delete_ids: Vec<String>,
 
     // Search
     pub(super) search_active: bool,
     pub(super) search_query: Input,
     pub(super) filtered_items: Option<Vec<usize>>,
+    /// Only show sessions whose worktree has changes against the base branch
+    pub(super) changes_only: bool,
 
     // Tool availability
     pub(super) available_tools: AvailableTools,
@@ -129,9 +175,37 @@ pub struct HomeView {
     pub(super) status_poller: StatusPoller,
     pub(super) pending_status_refresh: bool,
 
+    // Performance: background git status summaries
+    pub(super) git_status_poller: GitStatusPoller,
+    pub(super) pending_git_status_refresh: bool,
+    pub(super) git_summaries: HashMap<String, ChangeSummary>,
+    /// Base branch from config; None auto-detects per repository
+    pub(super) diff_base_branch: Option<String>,
+
+    // Performance: background container resource usage
+    pub(super) stats_poller: StatsPoller,
+    pub(super) pending_stats_refresh: bool,
+    /// Latest usage of each running sandbox, keyed by session ID
+    pub(super) container_stats: HashMap<String, ContainerStats>,
+    pub(super) usage_sort: Option<UsageSort>,
+
+    // Sort and filter chips of the list, persisted in the app state
+    /// Order of the list; None is group tree order
+    pub(super) list_sort: Option<SessionSort>,
+    /// Show sessions without their groups, so the sort applies across groups
+    pub(super) flat_list: bool,
+    pub(super) list_filters: SessionFilters,
+    pub(super) filters_dialog: Option<FiltersDialog>,
+
     // Performance: background deletion
     pub(super) deletion_poller: DeletionPoller,
 
+    // Stop, restart, prompt and land actions on marked sessions
+    pub(super) bulk_poller: BulkPoller,
+
+    // Snapshot and port work on a session's container
+    pub(super) container_poller: ContainerPoller,
+
     // Performance: background session creation (for sandbox)
     pub(super) creation_poller: CreationPoller,
     /// Set to true if user cancelled while creation was pending
@@ -157,8 +231,31 @@ pub struct HomeView {
     // Diff view
     pub(super) diff_view: Option<DiffView>,
 
+    /// Session whose pane receives typed keys, while typing into it from the
+    /// preview or the grid
+    pub(super) inline_input: Option<String>,
+
+    // Grid of live previews, captured in the background
+    pub(super) grid: Option<GridView>,
+
+    /// Command palette, over whichever view is showing
+    pub(super) palette: Option<CommandPalette<PaletteTarget>>,
+    pub(super) preview_poller: PreviewPoller,
+
     // Resizable list column width (percentage-like units)
     pub(super) list_width: u16,
+
+    // Screen, list and preview areas for mouse input (set during render)
+    pub(super) screen_area: Rect,
+    pub(super) list_area: Rect,
+    pub(super) preview_area: Rect,
+    /// Drag of the border between the list and the preview
+    pub(super) split_drag: SplitDrag,
+    /// Lines the preview is scrolled back from the bottom with the wheel
+    pub(super) preview_scroll: u16,
+
+    // Active key bindings of every view
+    pub(super) keybindings: Keybindings,
 }
 
 impl HomeView {
@@ -176,13 +273,27 @@ impl HomeView {
         let group_tree = GroupTree::new_with_groups(&instances, &groups);
         let flat_items = flatten_tree(&group_tree, &instances);
 
-        // Load the resolved config to get the default terminal mode
-        let default_terminal_mode = resolve_config(storage.profile())
+        // Load the resolved config to get the default terminal mode and diff base
+        let resolved_config = resolve_config(storage.profile()).ok();
+        let default_terminal_mode = resolved_config
+            .as_ref()
             .map(|config| match config.sandbox.default_terminal_mode {
                 DefaultTerminalMode::Host => TerminalMode::Host,
                 DefaultTerminalMode::Container => TerminalMode::Container,
             })
             .unwrap_or_default();
+        let diff_base_branch = resolved_config
+            .as_ref()
+            .and_then(|config| config.diff.default_branch.clone());
+        let (keybindings, keybinding_errors) = resolved_config
+            .map(|config| Keybindings::load(&config.keybindings))
+            .unwrap_or_default();
+
+        let app_state = load_config()
+            .ok()
+            .flatten()
+            .map(|c| c.app_state)
+            .unwrap_or_default();
 
         let mut view = Self {
             storage,
@@ -206,13 +317,35 @@ impl HomeView {
             welcome_dialog: None,
             changelog_dialog: None,
             info_dialog: None,
+            ports_dialog: None,
+            snapshots_dialog: None,
+            marked_sessions: HashSet::new(),
+            mark_anchor: None,
+            bulk_actions_dialog: None,
+            bulk_progress_dialog: None,
+            bulk_delete_ids: Vec::new(),
             search_active: false,
             search_query: Input::default(),
             filtered_items: None,
+            changes_only: false,
             available_tools,
             status_poller: StatusPoller::new(),
             pending_status_refresh: false,
+            git_status_poller: GitStatusPoller::new(),
+            pending_git_status_refresh: false,
+            git_summaries: HashMap::new(),
+            diff_base_branch,
+            stats_poller: StatsPoller::new(),
+            pending_stats_refresh: false,
+            container_stats: HashMap::new(),
+            usage_sort: None,
+            list_sort: app_state.home_sort,
+            flat_list: app_state.home_flat,
+            list_filters: app_state.home_filters,
+            filters_dialog: None,
             deletion_poller: DeletionPoller::new(),
+            bulk_poller: BulkPoller::new(),
+            container_poller: ContainerPoller::new(),
             creation_poller: CreationPoller::new(),
             creation_cancelled: false,
             on_launch_hooks_ran: HashSet::new(),
@@ -224,13 +357,36 @@ impl HomeView {
             settings_view: None,
             settings_close_confirm: false,
             diff_view: None,
-            list_width: load_config()
-                .ok()
-                .flatten()
-                .and_then(|c| c.app_state.home_list_width)
-                .unwrap_or(35),
+            inline_input: None,
+            grid: None,
+            palette: None,
+            preview_poller: PreviewPoller::new(),
+            list_width: app_state.home_list_width.unwrap_or(35),
+            screen_area: Rect::default(),
+            list_area: Rect::default(),
+            preview_area: Rect::default(),
+            split_drag: SplitDrag::default(),
+            preview_scroll: 0,
+            keybindings,
         };
 
+        if !keybinding_errors.is_empty() {
+            for error in &keybinding_errors {
+                tracing::warn!("Ignoring key bindings: {}", error);
+            }
+            view.info_dialog = Some(InfoDialog::new(
+                "Key Binding Problems",
+                &format!(
+                    "These views keep their default keys:\n\n{}",
+                    keybinding_errors.join("\n")
+                ),
+            ));
+        }
+
+        if view.list_sort.is_some() || view.flat_list || !view.list_filters.is_empty() {
+            view.flat_items = view.flatten_items();
+            view.filtered_items = view.compute_filter();
+        }
         view.update_selected();
         Ok(view)
     }
@@ -242,6 +398,7 @@ impl HomeView {
             if let Some(prev) = self.instance_map.get(&inst.id) {
                 inst.status = prev.status;
                 inst.last_error = prev.last_error.clone();
+                inst.waiting_reason = prev.waiting_reason.clone();
                 inst.last_error_check = prev.last_error_check;
                 inst.last_start_time = prev.last_start_time;
             }
@@ -256,13 +413,19 @@ impl HomeView {
             .collect();
         self.groups = groups;
         self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
-        self.flat_items = flatten_tree(&self.group_tree, &self.instances);
+        self.flat_items = self.flatten_items();
+        self.marked_sessions
+            .retain(|id| self.instance_map.contains_key(id));
 
         if self.cursor >= self.flat_items.len() && !self.flat_items.is_empty() {
             self.cursor = self.flat_items.len() - 1;
         }
 
-        self.update_selected();
+        if self.filtered_items.is_some() {
+            self.refresh_filter();
+        } else {
+            self.update_selected();
+        }
         Ok(())
     }
 
@@ -287,25 +450,156 @@ impl HomeView {
                     if inst.status != Status::Deleting {
                         inst.status = update.status;
                         inst.last_error = update.last_error.clone();
+                        inst.waiting_reason = update.waiting_reason.clone();
                     }
                 }
                 if let Some(inst) = self.instance_map.get_mut(&update.id) {
                     if inst.status != Status::Deleting {
                         inst.status = update.status;
                         inst.last_error = update.last_error;
+                        inst.waiting_reason = update.waiting_reason;
                     }
                 }
             }
             self.pending_status_refresh = false;
+            if self.list_follows_status() {
+                self.resort();
+            }
             return true;
         }
         false
     }
 
+    /// Request git change summaries in the background (non-blocking).
+    /// Call `apply_git_status_updates` to check for and apply results.
+    pub fn request_git_status_refresh(&mut self) {
+        use crate::session::Status;
+
+        if self.pending_git_status_refresh {
+            return;
+        }
+        let requests: Vec<GitStatusRequest> = self
+            .instances
+            .iter()
+            .filter(|inst| inst.status != Status::Deleting)
+            .map(|inst| GitStatusRequest {
+                id: inst.id.clone(),
+                project_path: inst.project_path.clone().into(),
+                base_branch: self.diff_base_branch.clone(),
+            })
+            .collect();
+        if requests.is_empty() {
+            return;
+        }
+        self.git_status_poller.request_refresh(requests);
+        self.pending_git_status_refresh = true;
+    }
+
+    /// Apply any pending git change summaries from the background poller.
+    /// Returns true if updates were applied.
+    pub fn apply_git_status_updates(&mut self) -> bool {
+        let Some(updates) = self.git_status_poller.try_recv_updates() else {
+            return false;
+        };
+        for update in updates {
+            match update.summary {
+                Some(summary) => {
+                    self.git_summaries.insert(update.id, summary);
+                }
+                None => {
+                    self.git_summaries.remove(&update.id);
+                }
+            }
+        }
+        self.pending_git_status_refresh = false;
+        if self.list_sort == Some(SessionSort::Changes) {
+            self.resort();
+        } else if self.changes_only {
+            self.refresh_filter();
+        }
+        true
+    }
+
+    /// Request container resource usage in the background (non-blocking).
+    /// Call `apply_stats_updates` to check for and apply results.
+    pub fn request_stats_refresh(&mut self) {
+        if self.pending_stats_refresh {
+            return;
+        }
+        let mut runtimes: Vec<ContainerRuntime> = Vec::new();
+        for sandbox in self
+            .instances
+            .iter()
+            .filter_map(|inst| inst.sandbox_info.as_ref().filter(|s| s.enabled))
+        {
+            if !runtimes.contains(&sandbox.runtime) {
+                runtimes.push(sandbox.runtime);
+            }
+        }
+        if runtimes.is_empty() {
+            self.container_stats.clear();
+            return;
+        }
+        self.stats_poller.request_refresh(runtimes);
+        self.pending_stats_refresh = true;
+    }
+
+    /// Apply any pending container stats from the background poller.
+    /// Returns true if updates were applied.
+    pub fn apply_stats_updates(&mut self) -> bool {
+        let Some(stats) = self.stats_poller.try_recv_updates() else {
+            return false;
+        };
+        let mut by_container: HashMap<String, ContainerStats> = stats
+            .into_iter()
+            .map(|stats| (stats.name.clone(), stats))
+            .collect();
+        self.container_stats = self
+            .instances
+            .iter()
+            .filter_map(|inst| {
+                let sandbox = inst.sandbox_info.as_ref()?;
+                let stats = by_container.remove(&sandbox.container_name)?;
+                if stats.near_memory_limit()
+                    && !self
+                        .container_stats
+                        .get(&inst.id)
+                        .is_some_and(|s| s.near_memory_limit())
+                {
+                    tracing::warn!(
+                        "Session '{}' is using {:.0}% of its memory limit",
+                        inst.title,
+                        stats.memory_percent()
+                    );
+                }
+                Some((inst.id.clone(), stats))
+            })
+            .collect();
+        self.pending_stats_refresh = false;
+        if self.usage_sort.is_some() {
+            self.resort();
+        }
+        true
+    }
+
+    /// Whether the session's worktree has changes against its base branch
+    pub(super) fn session_has_changes(&self, id: &str) -> bool {
+        self.git_summaries
+            .get(id)
+            .is_some_and(|summary| summary.has_changes())
+    }
+
     pub fn apply_deletion_results(&mut self) -> bool {
         use crate::session::Status;
 
         if let Some(result) = self.deletion_poller.try_recv_result() {
+            if let Some(dialog) = &mut self.bulk_progress_dialog {
+                let outcome = match &result.error {
+                    None => Ok("deleted".to_string()),
+                    Some(error) => Err(error.clone()),
+                };
+                dialog.set_result(&result.session_id, outcome);
+            }
             if result.success {
                 self.instances.retain(|i| i.id != result.session_id);
                 self.instance_map.remove(&result.session_id);
@@ -346,13 +640,48 @@ impl HomeView {
         let has_hooks = hooks
             .as_ref()
             .is_some_and(|h| !h.on_create.is_empty() || !h.on_launch.is_empty());
+        let build = if data.sandbox {
+            match crate::session::builder::resolve_image_build(
+                self.storage.profile(),
+                Path::new(&data.path),
+            ) {
+                Ok(build) => build,
+                Err(e) => {
+                    if let Some(dialog) = &mut self.new_dialog {
+                        dialog.set_error(format!("{:#}", e));
+                    }
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        // A configured Dockerfile only builds when the image is left at the
+        // default; say so instead of quietly using one or the other
+        let default_image = crate::docker::effective_default_image();
+        if let Some(build) = build
+            .as_ref()
+            .filter(|_| data.sandbox_image != default_image)
+        {
+            if let Some(dialog) = &mut self.new_dialog {
+                dialog.set_error(format!(
+                    "This project builds its image from {}. Set Image back to {} to use it",
+                    build.dockerfile.display(),
+                    default_image
+                ));
+            }
+            return;
+        }
+        let builds_image = build.is_some();
         if let Some(dialog) = &mut self.new_dialog {
             dialog.set_loading(true);
             dialog.set_has_hooks(has_hooks);
+            dialog.set_builds_image(builds_image);
         }
 
         self.creation_cancelled = false;
         let request = CreationRequest {
+            profile: self.storage.profile().to_string(),
             data,
             existing_instances: self.instances.clone(),
             hooks,
@@ -469,8 +798,16 @@ impl HomeView {
             || self.welcome_dialog.is_some()
             || self.changelog_dialog.is_some()
             || self.info_dialog.is_some()
+            || self.ports_dialog.is_some()
+            || self.snapshots_dialog.is_some()
+            || self.bulk_actions_dialog.is_some()
+            || self.filters_dialog.is_some()
+            || self.bulk_progress_dialog.is_some()
             || self.settings_view.is_some()
             || self.diff_view.is_some()
+            || self.grid.is_some()
+            || self.inline_input.is_some()
+            || self.palette.is_some()
     }
 
     pub fn shrink_list(&mut self) {
@@ -483,7 +820,7 @@ impl HomeView {
         self.save_list_width();
     }
 
-    fn save_list_width(&self) {
+    pub(super) fn save_list_width(&self) {
         if let Ok(mut config) = load_config().map(|c| c.unwrap_or_default()) {
             config.app_state.home_list_width = Some(self.list_width);
             let _ = save_config(&config);
@@ -566,6 +903,21 @@ impl HomeView {
 
     /// Refresh all config-dependent state from the current profile's config.
     /// Call this after settings are saved to pick up any changes.
+    pub fn profile(&self) -> &str {
+        self.storage.profile()
+    }
+
+    pub fn is_settings_open(&self) -> bool {
+        self.settings_view.is_some()
+    }
+
+    /// Theme being previewed in the settings view, if any
+    pub fn preview_theme(&self) -> Option<&Theme> {
+        self.settings_view
+            .as_ref()
+            .and_then(|settings| settings.preview_theme())
+    }
+
     pub fn refresh_from_config(&mut self) {
         if let Ok(config) = resolve_config(self.storage.profile()) {
             // Refresh default terminal mode for sandboxed sessions
@@ -574,6 +926,8 @@ impl HomeView {
                 DefaultTerminalMode::Container => TerminalMode::Container,
             };
 
+            self.diff_base_branch = config.diff.default_branch;
+
             // Add other config-dependent state refreshes here as needed
         }
     }
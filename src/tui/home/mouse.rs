@@ -0,0 +1,139 @@
+//! Mouse input on the home screen: the list, the preview, the border
+//! between them, and the dialogs that take clicks
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::widgets::Block;
+
+use super::HomeView;
+use crate::tui::app::Action;
+use crate::tui::components::mouse::{hit, SplitEvent};
+
+/// How far back the preview scrolls, in lines
+const MAX_PREVIEW_SCROLLBACK: u16 = 1000;
+
+/// Lines the wheel scrolls the preview by
+const PREVIEW_SCROLL_STEP: u16 = 3;
+
+impl HomeView {
+    /// Handle a mouse event
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<Action> {
+        if self.palette.is_some() {
+            return None;
+        }
+
+        // Settings take clicks, but not while confirming to discard changes
+        if let Some(ref mut settings) = self.settings_view {
+            if !self.settings_close_confirm {
+                settings.handle_mouse(mouse);
+            }
+            return None;
+        }
+
+        if let Some(ref mut diff_view) = self.diff_view {
+            let action = diff_view.handle_mouse(mouse);
+            return self.finish_diff_action(action);
+        }
+
+        // Dialogs on top of the list, in the order they take keys. Those
+        // without mouse support swallow the event.
+        if self.welcome_dialog.is_some()
+            || self.changelog_dialog.is_some()
+            || self.info_dialog.is_some()
+            || self.inline_input.is_some()
+            || self.grid.is_some()
+            || self.show_help
+            || self.hook_trust_dialog.is_some()
+        {
+            return None;
+        }
+        if let Some(dialog) = &mut self.new_dialog {
+            dialog.handle_mouse(mouse, self.screen_area);
+            return None;
+        }
+        if self.confirm_dialog.is_some() {
+            return None;
+        }
+        if let Some(dialog) = &mut self.unified_delete_dialog {
+            let result = dialog.handle_mouse(mouse, self.screen_area);
+            self.finish_unified_delete(result);
+            return None;
+        }
+        if self.group_delete_options_dialog.is_some()
+            || self.ports_dialog.is_some()
+            || self.snapshots_dialog.is_some()
+        {
+            return None;
+        }
+        if let Some(dialog) = &mut self.rename_dialog {
+            dialog.handle_mouse(mouse, self.screen_area);
+            return None;
+        }
+        if self.has_dialog() {
+            return None;
+        }
+
+        self.handle_panels_mouse(mouse);
+        None
+    }
+
+    /// Clicks and the wheel on the list and the preview, and dragging the
+    /// border between them
+    fn handle_panels_mouse(&mut self, mouse: MouseEvent) {
+        if let Some(event) = self.split_drag.handle(&mouse, self.list_area) {
+            match event {
+                SplitEvent::Resize(width) => self.list_width = width.clamp(10, 80),
+                SplitEvent::Done => self.save_list_width(),
+            }
+            return;
+        }
+
+        let over_list = hit(self.list_area, &mouse);
+        let over_preview = hit(self.preview_area, &mouse);
+        match mouse.kind {
+            MouseEventKind::ScrollUp if over_list => self.move_cursor(-1),
+            MouseEventKind::ScrollDown if over_list => self.move_cursor(1),
+            MouseEventKind::ScrollUp if over_preview => self.scroll_preview(true),
+            MouseEventKind::ScrollDown if over_preview => self.scroll_preview(false),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let mut rows = Block::bordered().inner(self.list_area);
+                // The search bar takes the last row
+                if self.search_active {
+                    rows.height = rows.height.saturating_sub(1);
+                }
+                if hit(rows, &mouse) {
+                    self.select_row((mouse.row - rows.y) as usize);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Select the item shown at a row of the list
+    fn select_row(&mut self, row: usize) {
+        let items = self
+            .filtered_items
+            .as_ref()
+            .map_or(self.flat_items.len(), |filtered| filtered.len());
+        if row < items {
+            self.cursor = row;
+            self.update_selected();
+        }
+    }
+
+    /// Scroll the preview back through the pane's history, or toward its
+    /// bottom
+    fn scroll_preview(&mut self, back: bool) {
+        if self.selected_session.is_none() {
+            return;
+        }
+        self.preview_scroll = if back {
+            (self.preview_scroll + PREVIEW_SCROLL_STEP).min(MAX_PREVIEW_SCROLLBACK)
+        } else {
+            self.preview_scroll.saturating_sub(PREVIEW_SCROLL_STEP)
+        };
+        // Capture the history on the next frame
+        self.preview_cache.session_id = None;
+        self.terminal_preview_cache.session_id = None;
+        self.container_terminal_preview_cache.session_id = None;
+    }
+}
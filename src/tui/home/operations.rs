@@ -1,9 +1,12 @@
-//! Session operations for HomeView (create, delete, rename)
+//! Session operations for HomeView (create, delete, rename, ports, snapshots)
 
 use crate::session::builder::{self, InstanceParams};
-use crate::session::{flatten_tree, list_profiles, GroupTree, Status, Storage};
+use crate::session::{list_profiles, GroupTree, Status, Storage};
+use crate::tui::container_poller::{ContainerListing, ContainerOperation, ContainerRequest};
 use crate::tui::deletion_poller::DeletionRequest;
-use crate::tui::dialogs::{DeleteOptions, GroupDeleteOptions, NewSessionData};
+use crate::tui::dialogs::{
+    DeleteOptions, GroupDeleteOptions, NewSessionData, PortsAction, SnapshotAction,
+};
 
 use super::HomeView;
 
@@ -12,14 +15,19 @@ impl HomeView {
         let existing_titles: Vec<&str> = self.instances.iter().map(|i| i.title.as_str()).collect();
 
         let params = InstanceParams {
+            profile: self.storage.profile().to_string(),
             title: data.title,
             path: data.path,
             group: data.group,
             tool: data.tool,
             worktree_branch: data.worktree_branch,
             create_new_branch: data.create_new_branch,
+            base_ref: data.base_ref,
             sandbox: data.sandbox,
             sandbox_image: data.sandbox_image,
+            cpu_limit: data.cpu_limit,
+            memory_limit: data.memory_limit,
+            isolated: data.isolated,
             yolo_mode: data.yolo_mode,
             extra_env_keys: data.extra_env_keys,
             extra_env_values: data.extra_env_values,
@@ -139,7 +147,7 @@ impl HomeView {
             self.groups = self.group_tree.get_all_groups();
             self.storage
                 .save_with_groups(&self.instances, &self.group_tree)?;
-            self.flat_items = flatten_tree(&self.group_tree, &self.instances);
+            self.flat_items = self.flatten_items();
         }
         Ok(())
     }
@@ -285,4 +293,103 @@ impl HomeView {
         }
         Ok(())
     }
+
+    /// Hand a ports dialog action to the container worker and show what it
+    /// is doing in the dialog. Forwarded ports are saved with the session
+    /// once the worker is done.
+    pub(super) fn request_ports(&mut self, id: &str, action: PortsAction) {
+        let Some(inst) = self.instance_map.get(id) else {
+            return;
+        };
+        let busy = match &action {
+            PortsAction::Refresh => "Checking ports...".to_string(),
+            PortsAction::Forward(port) => format!("Forwarding port {}...", port),
+            PortsAction::Unforward(port) => format!("Removing port {}...", port),
+        };
+        if let Some(dialog) = &mut self.ports_dialog {
+            dialog.set_busy(Some(busy));
+        }
+        self.container_poller.request(ContainerRequest {
+            instance: inst.clone(),
+            operation: ContainerOperation::Ports(action),
+        });
+    }
+
+    /// Hand a snapshot action, or listing the snapshots, to the container
+    /// worker and show what it is doing in the dialog. Rolling back
+    /// recreates the container and restarts a running agent.
+    pub(super) fn request_snapshots(&mut self, id: &str, action: Option<SnapshotAction>) {
+        let Some(inst) = self.instance_map.get(id) else {
+            return;
+        };
+        let busy = match &action {
+            None => "Loading snapshots...".to_string(),
+            Some(SnapshotAction::Create(_)) => "Creating snapshot...".to_string(),
+            Some(SnapshotAction::Rollback(name)) => format!("Rolling back to {}...", name),
+            Some(SnapshotAction::Delete(name)) => format!("Deleting {}...", name),
+        };
+        if let Some(dialog) = &mut self.snapshots_dialog {
+            dialog.set_busy(Some(busy));
+        }
+        self.container_poller.request(ContainerRequest {
+            instance: inst.clone(),
+            operation: action.map_or(
+                ContainerOperation::ListSnapshots,
+                ContainerOperation::Snapshot,
+            ),
+        });
+    }
+
+    /// Apply finished container work (non-blocking)
+    pub fn apply_container_results(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(result) = self.container_poller.try_recv_result() {
+            if let Some(updated) = result.instance {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == updated.id) {
+                    *inst = updated.clone();
+                }
+                self.instance_map.insert(updated.id.clone(), updated);
+                if let Err(e) = self
+                    .storage
+                    .save_with_groups(&self.instances, &self.group_tree)
+                {
+                    tracing::error!("Failed to save container changes: {}", e);
+                }
+            }
+            if let Some(error) = &result.error {
+                tracing::error!("Container action failed: {}", error);
+            }
+            match result.listing {
+                ContainerListing::Ports(ports) => {
+                    if let Some(dialog) = self
+                        .ports_dialog
+                        .as_mut()
+                        .filter(|d| d.session_id() == result.session_id)
+                    {
+                        dialog.set_busy(None);
+                        dialog.set_ports(ports.map_err(anyhow::Error::msg));
+                        if let Some(error) = result.error {
+                            dialog.set_error(error);
+                        }
+                    }
+                }
+                ContainerListing::Snapshots { snapshots, active } => {
+                    if let Some(dialog) = self
+                        .snapshots_dialog
+                        .as_mut()
+                        .filter(|d| d.session_id() == result.session_id)
+                    {
+                        dialog.set_busy(None);
+                        dialog.set_snapshots(snapshots.map_err(anyhow::Error::msg));
+                        dialog.set_active(active);
+                        if let Some(error) = result.error {
+                            dialog.set_error(error);
+                        }
+                    }
+                }
+            }
+            changed = true;
+        }
+        changed
+    }
 }
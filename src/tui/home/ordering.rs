@@ -0,0 +1,112 @@
+//! Pinning, favorites and moving sessions and groups by hand. The order of
+//! sessions is their order in sessions.json; groups keep theirs in
+//! groups.json.
+
+use super::HomeView;
+use crate::session::{Instance, Item};
+use crate::tui::dialogs::InfoDialog;
+use crate::tui::keybindings::HomeCommand;
+
+impl HomeView {
+    pub(super) fn toggle_pinned(&mut self) {
+        self.change_selected_session(|inst| inst.pinned = !inst.pinned);
+    }
+
+    pub(super) fn toggle_favorite(&mut self) {
+        self.change_selected_session(|inst| inst.favorite = !inst.favorite);
+    }
+
+    fn change_selected_session(&mut self, change: impl Fn(&mut Instance)) {
+        let Some(id) = self.selected_session.clone() else {
+            return;
+        };
+        if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+            change(inst);
+        }
+        if let Some(inst) = self.instance_map.get_mut(&id) {
+            change(inst);
+        }
+        self.save_order();
+        self.resort();
+    }
+
+    /// Move the selected session above or below its neighbour in the same
+    /// group, or the selected group among its sibling groups. Pinned and
+    /// unpinned sessions are moved among their own kind.
+    pub(super) fn move_selected_item(&mut self, up: bool) {
+        if self.list_sort.is_some() || self.usage_sort.is_some() {
+            self.info_dialog = Some(InfoDialog::new(
+                "List Is Sorted",
+                &format!(
+                    "Sessions and groups can only be moved in tree order. Press {} to cycle the sort back to it.",
+                    self.keybindings.home.short_label(HomeCommand::Sort)
+                ),
+            ));
+            return;
+        }
+        let index = match &self.filtered_items {
+            Some(filtered) => filtered.get(self.cursor).copied(),
+            None => Some(self.cursor),
+        };
+        let Some(index) = index else {
+            return;
+        };
+        let moved = match self.flat_items.get(index) {
+            Some(Item::Group { path, .. }) => {
+                let path = path.clone();
+                let moved = self.group_tree.move_group(&path, up);
+                if moved {
+                    self.groups = self.group_tree.get_all_groups();
+                }
+                moved
+            }
+            Some(Item::Session { id, depth }) => {
+                let (id, depth) = (id.clone(), *depth);
+                self.swap_with_neighbour(index, &id, depth, up)
+            }
+            Some(Item::Favorites { .. }) | None => false,
+        };
+        if moved {
+            self.save_order();
+            self.resort();
+        }
+    }
+
+    /// Swap a session with the next session above or below it at the same
+    /// level of the tree, if there is one before the group ends
+    fn swap_with_neighbour(&mut self, index: usize, id: &str, depth: usize, up: bool) -> bool {
+        let mut neighbours: Box<dyn Iterator<Item = &Item>> = if up {
+            Box::new(self.flat_items[..index].iter().rev())
+        } else {
+            Box::new(self.flat_items[index + 1..].iter())
+        };
+        let Some(Item::Session {
+            id: neighbour,
+            depth: neighbour_depth,
+        }) = neighbours.find(|item| item.depth() <= depth)
+        else {
+            return false;
+        };
+        if *neighbour_depth != depth {
+            return false;
+        }
+        let position = |id: &str| self.instances.iter().position(|i| i.id == id);
+        let (Some(a), Some(b)) = (position(id), position(neighbour)) else {
+            return false;
+        };
+        if self.instances[a].pinned != self.instances[b].pinned {
+            return false;
+        }
+        self.instances.swap(a, b);
+        true
+    }
+
+    fn save_order(&self) {
+        if let Err(e) = self
+            .storage
+            .save_with_groups(&self.instances, &self.group_tree)
+        {
+            tracing::error!("Failed to save session order: {}", e);
+        }
+    }
+}
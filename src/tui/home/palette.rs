@@ -0,0 +1,231 @@
+//! Command palette of the home screen: the actions of whichever view is
+//! showing, plus jumping to a session, group or profile by name
+
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use tui_input::Input;
+
+use super::HomeView;
+use crate::session::{list_profiles, Item};
+use crate::tui::app::Action;
+use crate::tui::dialogs::{CommandPalette, DialogResult, InfoDialog, PaletteItem};
+use crate::tui::keybindings::{
+    Command, DiffCommand, GridCommand, HomeCommand, Keymap, SettingsCommand,
+};
+use crate::tui::styles::Theme;
+
+/// What an entry of the palette runs or jumps to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteTarget {
+    Home(HomeCommand),
+    Grid(GridCommand),
+    Diff(DiffCommand),
+    Settings(SettingsCommand),
+    Session(String),
+    Group(String),
+    Profile(String),
+}
+
+/// Entries for the commands of a view, labelled with their keys. Commands
+/// without keys are offered too; the palette is the only way to run them.
+fn command_items<C: Command>(
+    keymap: &Keymap<C>,
+    target: impl Fn(C) -> PaletteTarget,
+) -> Vec<PaletteItem<PaletteTarget>> {
+    C::specs()
+        .iter()
+        .filter(|spec| !matches!(spec.name, "command_palette" | "leave_inline_input"))
+        .map(|spec| PaletteItem {
+            target: target(spec.command),
+            title: spec.description.to_string(),
+            detail: keymap.label(spec.command),
+        })
+        .collect()
+}
+
+impl HomeView {
+    /// Open the palette on the commands of the view that is showing. Over
+    /// the list and the grid, sessions, groups and profiles can be jumped
+    /// to as well.
+    pub(super) fn open_palette(&mut self) {
+        let keybindings = &self.keybindings;
+        let mut items = if self.settings_view.is_some() {
+            command_items(&keybindings.settings, PaletteTarget::Settings)
+        } else if self.diff_view.is_some() {
+            command_items(&keybindings.diff, PaletteTarget::Diff)
+        } else if self.grid.is_some() {
+            command_items(&keybindings.grid, PaletteTarget::Grid)
+        } else {
+            command_items(&keybindings.home, PaletteTarget::Home)
+        };
+        if self.settings_view.is_none() && self.diff_view.is_none() {
+            items.extend(self.jump_items());
+        }
+        self.palette = Some(CommandPalette::new(items));
+    }
+
+    fn jump_items(&self) -> Vec<PaletteItem<PaletteTarget>> {
+        let mut items: Vec<_> = self
+            .instances
+            .iter()
+            .map(|inst| PaletteItem {
+                target: PaletteTarget::Session(inst.id.clone()),
+                title: inst.title.clone(),
+                detail: if inst.group_path.is_empty() {
+                    "session".to_string()
+                } else {
+                    format!("session in {}", inst.group_path)
+                },
+            })
+            .collect();
+        // The flat list has no groups to jump to
+        if !self.flat_list {
+            items.extend(
+                self.group_tree
+                    .get_all_groups()
+                    .into_iter()
+                    .map(|group| PaletteItem {
+                        target: PaletteTarget::Group(group.path.clone()),
+                        title: group.path,
+                        detail: "group".to_string(),
+                    }),
+            );
+        }
+        let current = self.storage.profile();
+        items.extend(
+            list_profiles()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|profile| profile != current)
+                .map(|profile| PaletteItem {
+                    target: PaletteTarget::Profile(profile.clone()),
+                    title: profile,
+                    detail: "profile".to_string(),
+                }),
+        );
+        items
+    }
+
+    pub(super) fn handle_palette_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let palette = self.palette.as_mut()?;
+        match palette.handle_key(key) {
+            DialogResult::Continue => None,
+            DialogResult::Cancel => {
+                self.palette = None;
+                None
+            }
+            DialogResult::Submit(target) => {
+                self.palette = None;
+                self.run_palette_target(target)
+            }
+        }
+    }
+
+    fn run_palette_target(&mut self, target: PaletteTarget) -> Option<Action> {
+        match target {
+            PaletteTarget::Home(command) => self.run_command(command),
+            PaletteTarget::Grid(command) => self.run_grid_command(command),
+            PaletteTarget::Diff(command) => {
+                let action = self.diff_view.as_mut()?.run_command(command);
+                self.finish_diff_action(action)
+            }
+            PaletteTarget::Settings(command) => {
+                let action = self.settings_view.as_mut()?.run_command(command);
+                self.finish_settings_action(action);
+                None
+            }
+            PaletteTarget::Session(id) => {
+                let inst = self.instance_map.get(&id)?;
+                let (title, group_path) = (inst.title.clone(), inst.group_path.clone());
+                self.grid = None;
+                self.expand_groups_to(&group_path, true);
+                self.reveal_item(
+                    |item| matches!(item, Item::Session { id: item_id, .. } if *item_id == id),
+                    &title,
+                );
+                None
+            }
+            PaletteTarget::Group(path) => {
+                self.grid = None;
+                self.expand_groups_to(&path, false);
+                self.reveal_item(
+                    |item| matches!(item, Item::Group { path: item_path, .. } if *item_path == path),
+                    &path,
+                );
+                None
+            }
+            PaletteTarget::Profile(profile) => Some(Action::SwitchProfile(profile)),
+        }
+    }
+
+    /// Expand the collapsed groups above `path`, and the group itself when
+    /// `inclusive`
+    fn expand_groups_to(&mut self, path: &str, inclusive: bool) {
+        let collapsed: Vec<String> = self
+            .group_tree
+            .get_all_groups()
+            .into_iter()
+            .filter(|group| {
+                group.collapsed
+                    && ((inclusive && group.path == path)
+                        || path.starts_with(&format!("{}/", group.path)))
+            })
+            .map(|group| group.path)
+            .collect();
+        if collapsed.is_empty() {
+            return;
+        }
+        for group in &collapsed {
+            self.group_tree.toggle_collapsed(group);
+        }
+        self.flat_items = self.flatten_items();
+        if let Err(e) = self
+            .storage
+            .save_with_groups(&self.instances, &self.group_tree)
+        {
+            tracing::error!("Failed to save group state: {}", e);
+        }
+    }
+
+    /// Move the cursor to an item of the list, clearing the search if it
+    /// hides the item. Filter chips are left alone; an item they hide is
+    /// reported instead.
+    fn reveal_item(&mut self, is_target: impl Fn(&Item) -> bool, title: &str) {
+        self.refresh_filter();
+        let Some(index) = self.flat_items.iter().position(is_target) else {
+            return;
+        };
+        let shown = |filtered: &Option<Vec<usize>>| {
+            filtered
+                .as_ref()
+                .map_or(Some(index), |f| f.iter().position(|&i| i == index))
+        };
+        if shown(&self.filtered_items).is_none() && !self.search_query.value().is_empty() {
+            self.commit_mark_range();
+            self.search_active = false;
+            self.search_query = Input::default();
+            self.filtered_items = self.compute_filter();
+        }
+        match shown(&self.filtered_items) {
+            Some(position) => {
+                self.cursor = position;
+                self.update_selected();
+            }
+            None => {
+                self.info_dialog = Some(InfoDialog::new(
+                    "Hidden by Filters",
+                    &format!(
+                        "'{}' is hidden by the list filters. Clear them to see it.",
+                        title
+                    ),
+                ));
+            }
+        }
+    }
+
+    pub(super) fn render_palette(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if let Some(palette) = &mut self.palette {
+            palette.render(frame, area, theme);
+        }
+    }
+}
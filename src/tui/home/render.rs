@@ -4,12 +4,18 @@ use ratatui::prelude::*;
 use ratatui::widgets::*;
 use std::time::Instant;
 
+use super::sorting::sort_label;
 use super::{
-    get_indent, HomeView, TerminalMode, ViewMode, ICON_COLLAPSED, ICON_DELETING, ICON_ERROR,
-    ICON_EXPANDED, ICON_IDLE, ICON_RUNNING, ICON_STARTING, ICON_WAITING,
+    get_indent, HomeView, TerminalMode, UsageSort, ViewMode, ICON_COLLAPSED, ICON_DELETING,
+    ICON_ERROR, ICON_EXPANDED, ICON_FAVORITES, ICON_IDLE, ICON_RUNNING, ICON_STARTING,
+    ICON_WAITING,
 };
+use crate::docker::{stats::format_size, ContainerStats};
+use crate::git::diff::ChangeSummary;
 use crate::session::{Item, Status};
+use crate::tmux::QuickAnswer;
 use crate::tui::components::{HelpOverlay, Preview};
+use crate::tui::keybindings::HomeCommand;
 use crate::tui::styles::Theme;
 use crate::update::UpdateInfo;
 
@@ -21,6 +27,8 @@ impl HomeView {
         theme: &Theme,
         update_info: Option<&UpdateInfo>,
     ) {
+        self.screen_area = area;
+
         // Settings view takes over the whole screen
         if let Some(ref mut settings) = self.settings_view {
             settings.render(frame, area, theme);
@@ -30,6 +38,7 @@ impl HomeView {
                     dialog.render(frame, area, theme);
                 }
             }
+            self.render_palette(frame, area, theme);
             return;
         }
 
@@ -39,6 +48,7 @@ impl HomeView {
             let _ = diff.get_current_diff();
 
             diff.render(frame, area, theme);
+            self.render_palette(frame, area, theme);
             return;
         }
 
@@ -57,15 +67,29 @@ impl HomeView {
             .constraints(constraints)
             .split(area);
 
-        // Layout: left panel (list) and right panel (preview)
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(self.list_width), Constraint::Min(40)])
-            .split(main_chunks[0]);
+        if self.grid.is_some() {
+            // The grid of live previews replaces both panels
+            self.render_grid(frame, main_chunks[0], theme);
+        } else {
+            // Layout: left panel (list) and right panel (preview)
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(self.list_width), Constraint::Min(40)])
+                .split(main_chunks[0]);
+
+            self.list_area = chunks[0];
+            self.preview_area = chunks[1];
+            self.render_list(frame, chunks[0], theme);
+            self.render_preview(frame, chunks[1], theme);
+        }
 
-        self.render_list(frame, chunks[0], theme);
-        self.render_preview(frame, chunks[1], theme);
-        self.render_status_bar(frame, main_chunks[1], theme);
+        if self.inline_input.is_some() {
+            self.render_inline_input_status_bar(frame, main_chunks[1], theme);
+        } else if self.grid.is_some() {
+            self.render_grid_status_bar(frame, main_chunks[1], theme);
+        } else {
+            self.render_status_bar(frame, main_chunks[1], theme);
+        }
 
         if let Some(info) = update_info {
             self.render_update_bar(frame, main_chunks[2], theme, info);
@@ -73,7 +97,13 @@ impl HomeView {
 
         // Render dialogs on top
         if self.show_help {
-            HelpOverlay::render(frame, area, theme);
+            HelpOverlay::render(
+                frame,
+                area,
+                theme,
+                "Keyboard Shortcuts",
+                &self.keybindings.home.help_sections(),
+            );
         }
 
         if let Some(dialog) = &self.new_dialog {
@@ -108,16 +138,55 @@ impl HomeView {
             dialog.render(frame, area, theme);
         }
 
+        if let Some(dialog) = &self.ports_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.snapshots_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.bulk_actions_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.filters_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        if let Some(dialog) = &self.bulk_progress_dialog {
+            dialog.render(frame, area, theme);
+        }
+
+        self.render_palette(frame, area, theme);
+
         if let Some(dialog) = &self.info_dialog {
             dialog.render(frame, area, theme);
         }
     }
 
     fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let title = match self.view_mode {
+        let mut title = match self.view_mode {
             ViewMode::Agent => format!(" Agent of Empires [{}] ", self.storage.profile()),
             ViewMode::Terminal => format!(" Terminals [{}] ", self.storage.profile()),
         };
+        if self.changes_only {
+            title.push_str("(changed) ");
+        }
+        if !self.list_filters.is_empty() {
+            title.push_str("(filtered) ");
+        }
+        match self.usage_sort {
+            Some(UsageSort::Cpu) => title.push_str("(by CPU) "),
+            Some(UsageSort::Memory) => title.push_str("(by memory) "),
+            None => {}
+        }
+        if let Some(sort) = self.list_sort {
+            title.push_str(&format!("(by {}) ", sort_label(sort)));
+        }
+        if self.flat_list {
+            title.push_str("(flat) ");
+        }
         let (border_color, title_color) = match self.view_mode {
             ViewMode::Agent => (theme.border, theme.title),
             ViewMode::Terminal => (theme.terminal_border, theme.terminal_border),
@@ -208,6 +277,11 @@ impl HomeView {
         use std::borrow::Cow;
 
         let (icon, text, style): (&str, Cow<str>, Style) = match item {
+            Item::Favorites { session_count } => {
+                let text = Cow::Owned(format!("Favorites ({})", session_count));
+                let style = Style::default().fg(theme.group).bold();
+                (ICON_FAVORITES, text, style)
+            }
             Item::Group {
                 name,
                 collapsed,
@@ -227,22 +301,7 @@ impl HomeView {
                 if let Some(inst) = self.instance_map.get(id) {
                     match self.view_mode {
                         ViewMode::Agent => {
-                            let icon = match inst.status {
-                                Status::Running => ICON_RUNNING,
-                                Status::Waiting => ICON_WAITING,
-                                Status::Idle => ICON_IDLE,
-                                Status::Error => ICON_ERROR,
-                                Status::Starting => ICON_STARTING,
-                                Status::Deleting => ICON_DELETING,
-                            };
-                            let color = match inst.status {
-                                Status::Running => theme.running,
-                                Status::Waiting => theme.waiting,
-                                Status::Idle => theme.idle,
-                                Status::Error => theme.error,
-                                Status::Starting => theme.dimmed,
-                                Status::Deleting => theme.waiting,
-                            };
+                            let (icon, color) = status_icon(inst.status, theme);
                             let style = Style::default().fg(color);
                             (icon, Cow::Borrowed(&inst.title), style)
                         }
@@ -282,7 +341,15 @@ impl HomeView {
             }
         };
 
-        let mut line_spans = Vec::with_capacity(5);
+        let mut line_spans = Vec::with_capacity(6);
+        // Reserve a mark column while marking so rows don't shift
+        if self.has_marks() {
+            let marked = matches!(item, Item::Session { id, .. } if self.is_marked(id));
+            line_spans.push(Span::styled(
+                if marked { "✓ " } else { "  " },
+                Style::default().fg(theme.accent).bold(),
+            ));
+        }
         line_spans.push(Span::raw(indent));
         line_spans.push(Span::styled(format!("{} ", icon), style));
         line_spans.push(Span::styled(
@@ -292,11 +359,29 @@ impl HomeView {
 
         if let Item::Session { id, .. } = item {
             if let Some(inst) = self.instance_map.get(id) {
+                if inst.favorite {
+                    line_spans.push(Span::styled(
+                        format!(" {}", ICON_FAVORITES),
+                        Style::default().fg(theme.accent),
+                    ));
+                }
+                if inst.pinned {
+                    line_spans.push(Span::styled(" [pinned]", Style::default().fg(theme.dimmed)));
+                }
                 if let Some(wt_info) = &inst.worktree_info {
                     line_spans.push(Span::styled(
                         format!("  {}", wt_info.branch),
                         Style::default().fg(Color::Cyan),
                     ));
+                    if !wt_info.sync_conflicts.is_empty() {
+                        line_spans.push(Span::styled(
+                            " ⚠ conflict",
+                            Style::default().fg(theme.error),
+                        ));
+                    }
+                }
+                if let Some(summary) = self.git_summaries.get(id) {
+                    line_spans.extend(git_summary_spans(summary, theme));
                 }
                 if inst.is_sandboxed() {
                     match self.view_mode {
@@ -305,6 +390,9 @@ impl HomeView {
                                 " [sandbox]",
                                 Style::default().fg(Color::Magenta),
                             ));
+                            if let Some(stats) = self.container_stats.get(id) {
+                                line_spans.extend(usage_spans(stats, self.usage_sort, theme));
+                            }
                         }
                         ViewMode::Terminal => {
                             let mode = self.get_terminal_mode(id);
@@ -332,12 +420,18 @@ impl HomeView {
     /// Refresh preview cache if needed (session changed, dimensions changed, or timer expired)
     fn refresh_preview_cache_if_needed(&mut self, width: u16, height: u16) {
         const PREVIEW_REFRESH_MS: u128 = 250; // Refresh preview 4x/second max
+        const INLINE_INPUT_REFRESH_MS: u128 = 100; // Faster while typing into the session
 
+        let refresh_ms = if self.inline_input.is_some() {
+            INLINE_INPUT_REFRESH_MS
+        } else {
+            PREVIEW_REFRESH_MS
+        };
         let needs_refresh = match &self.selected_session {
             Some(id) => {
                 self.preview_cache.session_id.as_ref() != Some(id)
                     || self.preview_cache.dimensions != (width, height)
-                    || self.preview_cache.last_refresh.elapsed().as_millis() > PREVIEW_REFRESH_MS
+                    || self.preview_cache.last_refresh.elapsed().as_millis() > refresh_ms
             }
             None => false,
         };
@@ -346,7 +440,11 @@ impl HomeView {
             if let Some(id) = &self.selected_session {
                 if let Some(inst) = self.instance_map.get(id) {
                     self.preview_cache.content = inst
-                        .capture_output_with_size(height as usize, width, height)
+                        .capture_output_with_size(
+                            (height + self.preview_scroll) as usize,
+                            width,
+                            height,
+                        )
                         .unwrap_or_default();
                     self.preview_cache.session_id = Some(id.clone());
                     self.preview_cache.dimensions = (width, height);
@@ -379,7 +477,7 @@ impl HomeView {
                 if let Some(inst) = self.instance_map.get(id) {
                     self.terminal_preview_cache.content = inst
                         .terminal_tmux_session()
-                        .and_then(|s| s.capture_pane(height as usize))
+                        .and_then(|s| s.capture_pane((height + self.preview_scroll) as usize))
                         .unwrap_or_default();
                     self.terminal_preview_cache.session_id = Some(id.clone());
                     self.terminal_preview_cache.dimensions = (width, height);
@@ -412,7 +510,7 @@ impl HomeView {
                 if let Some(inst) = self.instance_map.get(id) {
                     self.container_terminal_preview_cache.content = inst
                         .container_terminal_tmux_session()
-                        .and_then(|s| s.capture_pane(height as usize))
+                        .and_then(|s| s.capture_pane((height + self.preview_scroll) as usize))
                         .unwrap_or_default();
                     self.container_terminal_preview_cache.session_id = Some(id.clone());
                     self.container_terminal_preview_cache.dimensions = (width, height);
@@ -431,6 +529,19 @@ impl HomeView {
             ViewMode::Agent => (theme.border, theme.title),
             ViewMode::Terminal => (theme.terminal_border, theme.terminal_border),
         };
+        // Keys typed into the session go to the agent, shown in Agent view
+        let (title, border_color, title_color) = if self.inline_input.is_some() {
+            (
+                " Preview · typing into session ".to_string(),
+                theme.waiting,
+                theme.waiting,
+            )
+        } else if self.preview_scroll > 0 {
+            let title = format!("{}· scrolled back {} lines ", title, self.preview_scroll);
+            (title, border_color, title_color)
+        } else {
+            (title.to_string(), border_color, title_color)
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color))
@@ -451,7 +562,9 @@ impl HomeView {
                             frame,
                             inner,
                             inst,
+                            self.container_stats.get(id),
                             &self.preview_cache.content,
+                            self.preview_scroll,
                             theme,
                         );
                     }
@@ -519,6 +632,7 @@ impl HomeView {
                             inst,
                             terminal_running,
                             preview_content,
+                            self.preview_scroll,
                             theme,
                         );
                     }
@@ -536,6 +650,7 @@ impl HomeView {
         let key_style = Style::default().fg(theme.accent).bold();
         let desc_style = Style::default().fg(theme.dimmed);
         let sep_style = Style::default().fg(theme.border);
+        let keys = &self.keybindings.home;
 
         let (mode_indicator, mode_color) = match self.view_mode {
             ViewMode::Agent => ("[Agent]", theme.waiting),
@@ -546,9 +661,42 @@ impl HomeView {
         let mut spans = vec![
             Span::styled(format!(" {} ", mode_indicator), mode_style),
             Span::styled("│", sep_style),
-            Span::styled(" j/k", key_style),
-            Span::styled(" Nav ", desc_style),
         ];
+        if self.has_marks() {
+            spans.extend([
+                Span::styled(
+                    format!(" {} marked ", self.marked_ids().len()),
+                    Style::default().fg(theme.accent).bold(),
+                ),
+                Span::styled(keys.short_label(HomeCommand::BulkActions), key_style),
+                Span::styled(" Bulk ", desc_style),
+                Span::styled(keys.short_label(HomeCommand::ClearMarks), key_style),
+                Span::styled(" Clear ", desc_style),
+                Span::styled("│", sep_style),
+            ]);
+        }
+        spans.extend(self.quick_answer_hints(
+            self.selected_session.as_ref(),
+            |answer| {
+                keys.short_label(match answer {
+                    QuickAnswer::ApproveOnce => HomeCommand::ApproveOnce,
+                    QuickAnswer::ApproveAlways => HomeCommand::ApproveAlways,
+                    QuickAnswer::Deny => HomeCommand::Deny,
+                })
+            },
+            theme,
+        ));
+        spans.extend([
+            Span::styled(
+                format!(
+                    " {}/{}",
+                    keys.short_label(HomeCommand::MoveDown),
+                    keys.short_label(HomeCommand::MoveUp)
+                ),
+                key_style,
+            ),
+            Span::styled(" Nav ", desc_style),
+        ]);
         if let Some(enter_action_text) = match self.flat_items.get(self.cursor) {
             Some(Item::Group {
                 collapsed: true, ..
@@ -557,17 +705,23 @@ impl HomeView {
                 collapsed: false, ..
             }) => Some(" Collapse "),
             Some(Item::Session { .. }) => Some(" Attach "),
-            None => None,
+            Some(Item::Favorites { .. }) | None => None,
         } {
             spans.extend([
                 Span::styled("│", sep_style),
-                Span::styled(" Enter", key_style),
+                Span::styled(
+                    format!(" {}", keys.short_label(HomeCommand::Attach)),
+                    key_style,
+                ),
                 Span::styled(enter_action_text, desc_style),
             ])
         }
         spans.extend([
             Span::styled("│", sep_style),
-            Span::styled(" t", key_style),
+            Span::styled(
+                format!(" {}", keys.short_label(HomeCommand::ToggleView)),
+                key_style,
+            ),
             Span::styled(" View ", desc_style),
         ]);
 
@@ -578,7 +732,10 @@ impl HomeView {
                     if inst.is_sandboxed() {
                         spans.extend([
                             Span::styled("│", sep_style),
-                            Span::styled(" c", key_style),
+                            Span::styled(
+                                format!(" {}", keys.short_label(HomeCommand::ToggleTerminalMode)),
+                                key_style,
+                            ),
                             Span::styled(" Mode ", desc_style),
                         ]);
                     }
@@ -588,30 +745,48 @@ impl HomeView {
 
         spans.extend([
             Span::styled("│", sep_style),
-            Span::styled(" n", key_style),
+            Span::styled(
+                format!(" {}", keys.short_label(HomeCommand::NewSession)),
+                key_style,
+            ),
             Span::styled(" New ", desc_style),
         ]);
 
         if !self.flat_items.is_empty() {
             spans.extend([
                 Span::styled("│", sep_style),
-                Span::styled(" d", key_style),
+                Span::styled(
+                    format!(" {}", keys.short_label(HomeCommand::Delete)),
+                    key_style,
+                ),
                 Span::styled(" Del ", desc_style),
             ]);
         }
 
         spans.extend([
             Span::styled("│", sep_style),
-            Span::styled(" /", key_style),
+            Span::styled(
+                format!(" {}", keys.short_label(HomeCommand::Search)),
+                key_style,
+            ),
             Span::styled(" Search ", desc_style),
             Span::styled("│", sep_style),
-            Span::styled(" D", key_style),
+            Span::styled(
+                format!(" {}", keys.short_label(HomeCommand::Diff)),
+                key_style,
+            ),
             Span::styled(" Diff ", desc_style),
             Span::styled("│", sep_style),
-            Span::styled(" ?", key_style),
+            Span::styled(
+                format!(" {}", keys.short_label(HomeCommand::Help)),
+                key_style,
+            ),
             Span::styled(" Help ", desc_style),
             Span::styled("│", sep_style),
-            Span::styled(" q", key_style),
+            Span::styled(
+                format!(" {}", keys.short_label(HomeCommand::Quit)),
+                key_style,
+            ),
             Span::styled(" Quit", desc_style),
         ]);
 
@@ -630,3 +805,84 @@ impl HomeView {
         frame.render_widget(bar, area);
     }
 }
+
+/// Icon and color for a session's status in the agent view
+pub(super) fn status_icon(status: Status, theme: &Theme) -> (&'static str, Color) {
+    match status {
+        Status::Running => (ICON_RUNNING, theme.running),
+        Status::Waiting => (ICON_WAITING, theme.waiting),
+        Status::Idle => (ICON_IDLE, theme.idle),
+        Status::Error => (ICON_ERROR, theme.error),
+        Status::Starting => (ICON_STARTING, theme.dimmed),
+        Status::Deleting => (ICON_DELETING, theme.waiting),
+    }
+}
+
+/// CPU and memory of a running sandbox for its session row. The active sort
+/// key is highlighted, and memory turns red near the container's limit.
+fn usage_spans(
+    stats: &ContainerStats,
+    sort: Option<UsageSort>,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let style_for = |key: UsageSort| {
+        if sort == Some(key) {
+            Style::default().fg(theme.text)
+        } else {
+            Style::default().fg(theme.dimmed)
+        }
+    };
+    let memory_style = if stats.near_memory_limit() {
+        Style::default().fg(theme.error)
+    } else {
+        style_for(UsageSort::Memory)
+    };
+
+    let mut spans = vec![
+        Span::styled(
+            format!(" {:.0}%", stats.cpu_percent),
+            style_for(UsageSort::Cpu),
+        ),
+        Span::styled(format!(" {}", format_size(stats.memory_used)), memory_style),
+    ];
+    if stats.near_memory_limit() {
+        spans.push(Span::styled(" ⚠", Style::default().fg(theme.error)));
+    }
+    spans
+}
+
+/// Compact ahead/behind, dirty and +/- indicators for a session row.
+/// Zero counts are omitted so untouched worktrees stay uncluttered.
+fn git_summary_spans(summary: &ChangeSummary, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let dimmed = Style::default().fg(theme.dimmed);
+
+    if summary.ahead > 0 || summary.behind > 0 {
+        let mut text = String::from(" ");
+        if summary.ahead > 0 {
+            text.push_str(&format!("↑{}", summary.ahead));
+        }
+        if summary.behind > 0 {
+            text.push_str(&format!("↓{}", summary.behind));
+        }
+        spans.push(Span::styled(text, dimmed));
+    }
+    if summary.dirty_files > 0 {
+        spans.push(Span::styled(
+            format!(" ●{}", summary.dirty_files),
+            Style::default().fg(theme.waiting),
+        ));
+    }
+    if summary.additions > 0 || summary.deletions > 0 {
+        spans.push(Span::styled(
+            format!(" +{}", summary.additions),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Span::styled(
+            format!("-{}", summary.deletions),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    spans
+}
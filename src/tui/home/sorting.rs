@@ -0,0 +1,199 @@
+//! Sort modes and filter chips of the session list
+
+use std::cmp::Ordering;
+
+use super::{HomeView, UsageSort};
+use crate::session::{
+    config::{load_config, save_config},
+    flatten_tree, list_profiles, Instance, Item, SessionSort, Status,
+};
+use crate::tui::app::Action;
+use crate::tui::dialogs::{FilterSelection, FiltersDialog};
+
+/// Sort modes in the order the sort key cycles through them
+const SORTS: [SessionSort; 6] = [
+    SessionSort::Status,
+    SessionSort::LastAccessed,
+    SessionSort::Created,
+    SessionSort::Title,
+    SessionSort::Tool,
+    SessionSort::Changes,
+];
+
+pub(super) fn sort_label(sort: SessionSort) -> &'static str {
+    match sort {
+        SessionSort::Status => "status",
+        SessionSort::LastAccessed => "last used",
+        SessionSort::Created => "newest",
+        SessionSort::Title => "title",
+        SessionSort::Tool => "tool",
+        SessionSort::Changes => "changes",
+    }
+}
+
+fn status_rank(status: Status) -> u8 {
+    match status {
+        Status::Waiting => 0,
+        Status::Error => 1,
+        Status::Running => 2,
+        Status::Starting => 3,
+        Status::Idle => 4,
+        Status::Deleting => 5,
+    }
+}
+
+impl HomeView {
+    fn compare_sessions(&self, sort: SessionSort, a: &Instance, b: &Instance) -> Ordering {
+        let by_title = || a.title.to_lowercase().cmp(&b.title.to_lowercase());
+        match sort {
+            SessionSort::Status => status_rank(a.status).cmp(&status_rank(b.status)),
+            // Never attached sessions (None) go last
+            SessionSort::LastAccessed => b.last_accessed_at.cmp(&a.last_accessed_at),
+            SessionSort::Created => b.created_at.cmp(&a.created_at),
+            SessionSort::Title => by_title(),
+            SessionSort::Tool => a.tool.cmp(&b.tool).then_with(by_title),
+            // Sessions without changes (None) go last
+            SessionSort::Changes => {
+                let changes = |inst: &Instance| {
+                    self.git_summaries
+                        .get(&inst.id)
+                        .filter(|summary| summary.has_changes())
+                        .map(|summary| {
+                            (
+                                summary.additions + summary.deletions,
+                                summary.changed_files + summary.dirty_files,
+                            )
+                        })
+                };
+                changes(b).cmp(&changes(a)).then_with(by_title)
+            }
+        }
+    }
+
+    /// Flatten the group tree, ordering sessions within each group by the
+    /// list sort, or across all sessions in the flat list. The usage sort
+    /// takes precedence while active; sessions without stats keep their
+    /// order at the end.
+    pub(super) fn flatten_items(&self) -> Vec<Item> {
+        if self.list_sort.is_none() && self.usage_sort.is_none() && !self.flat_list {
+            return flatten_tree(&self.group_tree, &self.instances);
+        }
+        let mut sorted = self.instances.clone();
+        if let Some(sort) = self.list_sort {
+            sorted.sort_by(|a, b| self.compare_sessions(sort, a, b));
+        }
+        if let Some(sort) = self.usage_sort {
+            let usage = |inst: &Instance| {
+                self.container_stats.get(&inst.id).map(|stats| match sort {
+                    UsageSort::Cpu => stats.cpu_percent,
+                    UsageSort::Memory => stats.memory_used as f64,
+                })
+            };
+            sorted.sort_by(|a, b| usage(b).partial_cmp(&usage(a)).unwrap_or(Ordering::Equal));
+        }
+        if self.flat_list {
+            // Pins stay on top of the flat list too
+            sorted.sort_by_key(|inst| !inst.pinned);
+            return sorted
+                .into_iter()
+                .map(|inst| Item::Session {
+                    id: inst.id,
+                    depth: 0,
+                })
+                .collect();
+        }
+        flatten_tree(&self.group_tree, &sorted)
+    }
+
+    /// Rebuild the list after its order changed, keeping the selection
+    pub(super) fn resort(&mut self) {
+        self.flat_items = self.flatten_items();
+        self.refresh_filter();
+    }
+
+    /// Cycle the usage sort: tree order, CPU, memory.
+    pub(super) fn cycle_usage_sort(&mut self) {
+        self.usage_sort = match self.usage_sort {
+            None => Some(UsageSort::Cpu),
+            Some(UsageSort::Cpu) => Some(UsageSort::Memory),
+            Some(UsageSort::Memory) => None,
+        };
+        self.resort();
+    }
+
+    /// Cycle the list sort through every mode and back to tree order
+    pub(super) fn cycle_list_sort(&mut self) {
+        self.list_sort = match self.list_sort {
+            None => Some(SORTS[0]),
+            Some(sort) => SORTS
+                .iter()
+                .position(|s| *s == sort)
+                .and_then(|i| SORTS.get(i + 1))
+                .copied(),
+        };
+        self.resort();
+        self.save_list_state();
+    }
+
+    pub(super) fn toggle_flat_list(&mut self) {
+        self.flat_list = !self.flat_list;
+        self.resort();
+        self.save_list_state();
+    }
+
+    /// Whether the session passes the filter chips
+    pub(super) fn matches_filters(&self, inst: &Instance) -> bool {
+        let filters = &self.list_filters;
+        (filters.statuses.is_empty() || filters.statuses.contains(&inst.status))
+            && (filters.tools.is_empty() || filters.tools.contains(&inst.tool))
+            && filters
+                .sandboxed
+                .map_or(true, |sandboxed| inst.is_sandboxed() == sandboxed)
+            && filters
+                .worktree
+                .map_or(true, |worktree| inst.worktree_info.is_some() == worktree)
+    }
+
+    /// Whether the list depends on session statuses, so status updates
+    /// need to rebuild it
+    pub(super) fn list_follows_status(&self) -> bool {
+        self.list_sort == Some(SessionSort::Status) || !self.list_filters.statuses.is_empty()
+    }
+
+    pub(super) fn open_filters(&mut self) {
+        let tools = self
+            .instances
+            .iter()
+            .map(|inst| inst.tool.clone())
+            .collect();
+        let current_profile = self.storage.profile().to_string();
+        let profiles = list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
+        self.filters_dialog = Some(FiltersDialog::new(
+            self.list_filters.clone(),
+            tools,
+            &current_profile,
+            profiles,
+        ));
+    }
+
+    /// Apply the filters picked in the dialog, switching to the picked
+    /// profile if it isn't the current one
+    pub(super) fn apply_filters(&mut self, selection: FilterSelection) -> Option<Action> {
+        if selection.filters != self.list_filters {
+            self.list_filters = selection.filters;
+            self.save_list_state();
+            self.update_filter();
+        }
+        (selection.profile != self.storage.profile())
+            .then_some(Action::SwitchProfile(selection.profile))
+    }
+
+    fn save_list_state(&self) {
+        if let Ok(mut config) = load_config().map(|c| c.unwrap_or_default()) {
+            config.app_state.home_sort = self.list_sort;
+            config.app_state.home_flat = self.flat_list;
+            config.app_state.home_filters = self.list_filters.clone();
+            let _ = save_config(&config);
+        }
+    }
+}
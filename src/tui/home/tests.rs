@@ -1,11 +1,13 @@
 //! Tests for HomeView
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
 use serial_test::serial;
 use tempfile::TempDir;
 use tui_input::Input;
 
 use super::{HomeView, ViewMode};
+use crate::git::diff::ChangeSummary;
 use crate::session::{Instance, Item, Storage};
 use crate::tmux::AvailableTools;
 use crate::tui::app::Action;
@@ -524,6 +526,147 @@ fn test_cursor_moves_within_filtered_list() {
     assert_eq!(env.view.cursor, filtered_count - 1);
 }
 
+fn changed_summary() -> ChangeSummary {
+    ChangeSummary {
+        ahead: 1,
+        changed_files: 2,
+        additions: 10,
+        deletions: 3,
+        ..Default::default()
+    }
+}
+
+#[test]
+#[serial]
+fn test_changes_filter_shows_only_changed_sessions() {
+    let mut env = create_test_env_with_sessions(4);
+    let changed_id = env.view.instances[2].id.clone();
+    env.view
+        .git_summaries
+        .insert(changed_id.clone(), changed_summary());
+    env.view
+        .git_summaries
+        .insert(env.view.instances[0].id.clone(), ChangeSummary::default());
+
+    env.view.handle_key(key(KeyCode::Char('C')));
+    assert!(env.view.changes_only);
+    let filtered = env.view.filtered_items.as_ref().unwrap();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(
+        env.view.selected_session.as_deref(),
+        Some(changed_id.as_str())
+    );
+
+    env.view.handle_key(key(KeyCode::Char('C')));
+    assert!(!env.view.changes_only);
+    assert!(env.view.filtered_items.is_none());
+}
+
+#[test]
+#[serial]
+fn test_changes_sort_puts_most_changed_first() {
+    use crate::session::SessionSort;
+
+    let mut env = create_test_env_with_sessions(4);
+    let small = env.view.instances[1].id.clone();
+    let large = env.view.instances[3].id.clone();
+    env.view
+        .git_summaries
+        .insert(small.clone(), changed_summary());
+    env.view.git_summaries.insert(
+        large.clone(),
+        ChangeSummary {
+            additions: 100,
+            ..changed_summary()
+        },
+    );
+    env.view
+        .git_summaries
+        .insert(env.view.instances[0].id.clone(), ChangeSummary::default());
+
+    env.view.list_sort = Some(SessionSort::Changes);
+    env.view.flat_list = true;
+    env.view.resort();
+    let ids: Vec<&str> = env
+        .view
+        .flat_items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Session { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(&ids[..2], &[large.as_str(), small.as_str()]);
+}
+
+#[test]
+#[serial]
+fn test_changes_filter_keeps_groups_of_changed_sessions() {
+    let mut env = create_test_env_with_groups();
+    let work_id = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.group_path == "work")
+        .unwrap()
+        .id
+        .clone();
+    env.view.git_summaries.insert(work_id, changed_summary());
+
+    env.view.handle_key(key(KeyCode::Char('C')));
+    let filtered = env.view.filtered_items.as_ref().unwrap();
+    let groups: Vec<&str> = filtered
+        .iter()
+        .filter_map(|&idx| match &env.view.flat_items[idx] {
+            Item::Group { path, .. } => Some(path.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(groups, vec!["work"]);
+}
+
+#[test]
+#[serial]
+fn test_changes_filter_combines_with_search() {
+    let mut env = create_test_env_with_sessions(4);
+    for inst in &env.view.instances {
+        env.view
+            .git_summaries
+            .insert(inst.id.clone(), changed_summary());
+    }
+    env.view.changes_only = true;
+    env.view.search_query = Input::new("session3".to_string());
+    env.view.update_filter();
+    assert_eq!(env.view.filtered_items.as_ref().unwrap().len(), 1);
+
+    // Clearing the search keeps the changes filter active
+    env.view.search_active = true;
+    env.view.handle_key(key(KeyCode::Esc));
+    assert_eq!(env.view.filtered_items.as_ref().unwrap().len(), 4);
+}
+
+#[test]
+#[serial]
+fn test_refresh_filter_preserves_selection() {
+    let mut env = create_test_env_with_sessions(4);
+    for inst in &env.view.instances {
+        env.view
+            .git_summaries
+            .insert(inst.id.clone(), changed_summary());
+    }
+    env.view.handle_key(key(KeyCode::Char('C')));
+    env.view.handle_key(key(KeyCode::Down));
+    env.view.handle_key(key(KeyCode::Down));
+    let selected = env.view.selected_session.clone();
+
+    let first_id = env.view.instances[0].id.clone();
+    env.view.git_summaries.remove(&first_id);
+    env.view.refresh_filter();
+
+    assert_eq!(env.view.filtered_items.as_ref().unwrap().len(), 3);
+    assert_eq!(env.view.selected_session, selected);
+}
+
 #[test]
 #[serial]
 fn test_r_opens_rename_dialog() {
@@ -719,6 +862,203 @@ fn test_d_shows_info_dialog_in_terminal_view() {
     assert!(view.unified_delete_dialog.is_none());
 }
 
+#[test]
+#[serial]
+fn test_p_on_host_session_shows_info_dialog() {
+    let env = create_test_env_with_sessions(1);
+    let mut view = env.view;
+
+    view.handle_key(key(KeyCode::Char('p')));
+    assert!(view.ports_dialog.is_none());
+    assert!(view.info_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_usage_sort_orders_sessions_by_stats() {
+    use crate::docker::ContainerStats;
+
+    let env = create_test_env_with_sessions(3);
+    let mut view = env.view;
+    let ids: Vec<String> = view.instances.iter().map(|i| i.id.clone()).collect();
+    let stats = |cpu: f64, memory_used: u64| ContainerStats {
+        name: String::new(),
+        cpu_percent: cpu,
+        memory_used,
+        memory_limit: 1 << 30,
+        pids: 1,
+    };
+    view.container_stats
+        .insert(ids[1].clone(), stats(80.0, 100 << 20));
+    view.container_stats
+        .insert(ids[2].clone(), stats(5.0, 900 << 20));
+
+    let session_order = |view: &HomeView| -> Vec<String> {
+        view.flat_items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Session { id, .. } => Some(id.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    view.handle_key(key(KeyCode::Char('u')));
+    assert_eq!(
+        session_order(&view),
+        vec![ids[1].clone(), ids[2].clone(), ids[0].clone()]
+    );
+
+    view.handle_key(key(KeyCode::Char('u')));
+    assert_eq!(
+        session_order(&view),
+        vec![ids[2].clone(), ids[1].clone(), ids[0].clone()]
+    );
+
+    view.handle_key(key(KeyCode::Char('u')));
+    assert!(view.usage_sort.is_none());
+    assert_eq!(session_order(&view), ids);
+}
+
+fn set_status(view: &mut HomeView, id: &str, status: crate::session::Status) {
+    view.instances
+        .iter_mut()
+        .find(|i| i.id == id)
+        .unwrap()
+        .status = status;
+    view.instance_map.get_mut(id).unwrap().status = status;
+}
+
+#[test]
+#[serial]
+fn test_status_sort_across_groups_persists() {
+    use crate::session::{SessionSort, Status};
+
+    let mut env = create_test_env_with_groups();
+    let id_of = |view: &HomeView, title: &str| {
+        view.instances
+            .iter()
+            .find(|i| i.title == title)
+            .unwrap()
+            .id
+            .clone()
+    };
+    let personal = id_of(&env.view, "personal-project");
+    let work = id_of(&env.view, "work-project");
+    let ungrouped = id_of(&env.view, "ungrouped");
+    set_status(&mut env.view, &personal, Status::Waiting);
+    set_status(&mut env.view, &work, Status::Error);
+
+    env.view.handle_key(key(KeyCode::Char('o')));
+    assert_eq!(env.view.list_sort, Some(SessionSort::Status));
+    // Within groups the tree order of the groups is kept
+    assert!(matches!(&env.view.flat_items[0], Item::Session { id, .. } if *id == ungrouped));
+
+    env.view.handle_key(key(KeyCode::Char('F')));
+    let flat: Vec<(&str, usize)> = env
+        .view
+        .flat_items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Session { id, depth } => Some((id.as_str(), *depth)),
+            Item::Group { .. } | Item::Favorites { .. } => None,
+        })
+        .collect();
+    assert_eq!(env.view.flat_items.len(), 3);
+    assert_eq!(
+        flat,
+        vec![
+            (personal.as_str(), 0),
+            (work.as_str(), 0),
+            (ungrouped.as_str(), 0)
+        ]
+    );
+
+    // A new view picks up the sort and the flat list
+    let view = HomeView::new(Storage::new("test").unwrap(), env.view.available_tools()).unwrap();
+    assert_eq!(view.list_sort, Some(SessionSort::Status));
+    assert!(view.flat_list);
+    assert_eq!(view.flat_items.len(), 3);
+}
+
+#[test]
+#[serial]
+fn test_sort_cycles_back_to_tree_order() {
+    let mut env = create_test_env_with_groups();
+    let order = |view: &HomeView| -> Vec<String> {
+        view.flat_items
+            .iter()
+            .map(|item| match item {
+                Item::Session { id, .. } => id.clone(),
+                Item::Group { path, .. } => path.clone(),
+                Item::Favorites { .. } => "favorites".to_string(),
+            })
+            .collect()
+    };
+    let tree = order(&env.view);
+    for _ in 0..6 {
+        env.view.handle_key(key(KeyCode::Char('o')));
+        assert!(env.view.list_sort.is_some());
+    }
+    env.view.handle_key(key(KeyCode::Char('o')));
+    assert!(env.view.list_sort.is_none());
+    assert_eq!(order(&env.view), tree);
+}
+
+#[test]
+#[serial]
+fn test_filter_chips_hide_sessions_and_their_groups() {
+    use crate::session::{SessionFilters, Status};
+    use crate::tui::dialogs::FilterSelection;
+
+    let mut env = create_test_env_with_groups();
+    let work = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.group_path == "work")
+        .unwrap()
+        .id
+        .clone();
+    set_status(&mut env.view, &work, Status::Waiting);
+
+    env.view.handle_key(key(KeyCode::Char('f')));
+    assert!(env.view.filters_dialog.is_some());
+
+    let action = env.view.apply_filters(FilterSelection {
+        filters: SessionFilters {
+            statuses: vec![Status::Waiting],
+            ..Default::default()
+        },
+        profile: "test".to_string(),
+    });
+    assert_eq!(action, None);
+    let shown: Vec<&Item> = env
+        .view
+        .filtered_items
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|&idx| &env.view.flat_items[idx])
+        .collect();
+    assert_eq!(shown.len(), 2);
+    assert!(matches!(shown[0], Item::Group { path, .. } if path == "work"));
+    assert!(matches!(shown[1], Item::Session { id, .. } if *id == work));
+
+    // The list follows status changes
+    set_status(&mut env.view, &work, Status::Idle);
+    env.view.resort();
+    assert_eq!(env.view.filtered_items.as_ref().unwrap().len(), 0);
+
+    // Picking another profile switches to it
+    let action = env.view.apply_filters(FilterSelection {
+        filters: SessionFilters::default(),
+        profile: "other".to_string(),
+    });
+    assert_eq!(action, Some(Action::SwitchProfile("other".to_string())));
+    assert!(env.view.filtered_items.is_none());
+}
+
 #[test]
 #[serial]
 fn test_has_dialog_includes_info_dialog() {
@@ -741,7 +1081,7 @@ fn test_has_dialog_includes_settings_view() {
 
     assert!(!view.has_dialog());
 
-    view.settings_view = Some(SettingsView::new("test").unwrap());
+    view.settings_view = Some(SettingsView::new("test", Default::default()).unwrap());
     assert!(view.has_dialog());
 }
 
@@ -784,6 +1124,14 @@ fn create_test_env_with_group_sessions() -> TestEnv {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     });
     instances.push(inst3);
 
@@ -825,6 +1173,7 @@ fn test_group_has_managed_worktrees() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        sync_conflicts: Vec::new(),
     });
 
     let mut inst2 = Instance::new("other-session", "/tmp/other");
@@ -865,6 +1214,14 @@ fn test_group_has_containers() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     });
 
     let mut inst2 = Instance::new("other-session", "/tmp/other");
@@ -989,6 +1346,7 @@ fn test_delete_group_with_sessions_respects_worktree_option() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        sync_conflicts: Vec::new(),
     });
 
     storage.save(&[inst1]).unwrap();
@@ -1042,6 +1400,14 @@ fn test_delete_group_with_sessions_respects_container_option() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     });
 
     storage.save(&[inst1]).unwrap();
@@ -1300,3 +1666,706 @@ fn test_uppercase_l_grows_list() {
     env.view.handle_key(key(KeyCode::Char('L')));
     assert_eq!(env.view.list_width, 40);
 }
+
+fn create_test_env_with_keybindings(home: &[(&str, &str)]) -> TestEnv {
+    let temp = TempDir::new().unwrap();
+    setup_test_home(&temp);
+    let mut config = crate::session::Config::default();
+    config.keybindings.home = home
+        .iter()
+        .map(|(name, key)| {
+            (
+                name.to_string(),
+                crate::session::KeyList::One(key.to_string()),
+            )
+        })
+        .collect();
+    crate::session::save_config(&config).unwrap();
+
+    let storage = Storage::new("test").unwrap();
+    let tools = AvailableTools {
+        claude: true,
+        opencode: false,
+        vibe: false,
+        codex: false,
+        gemini: false,
+    };
+    let view = HomeView::new(storage, tools).unwrap();
+    TestEnv { _temp: temp, view }
+}
+
+#[test]
+#[serial]
+fn test_configured_keybinding_replaces_default() {
+    let mut env = create_test_env_with_keybindings(&[("new_session", "a")]);
+    assert!(env.view.info_dialog.is_none());
+
+    env.view.handle_key(key(KeyCode::Char('n')));
+    assert!(env.view.new_dialog.is_none());
+
+    env.view.handle_key(key(KeyCode::Char('a')));
+    assert!(env.view.new_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_rebound_leave_key_ends_inline_input() {
+    let mut env = create_test_env_with_keybindings(&[("leave_inline_input", "ctrl+l")]);
+    assert!(env.view.info_dialog.is_none());
+
+    env.view.inline_input = Some("session".to_string());
+    env.view
+        .handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+    assert!(!env.view.is_inline_input());
+    assert!(env.view.info_dialog.is_none());
+}
+
+#[test]
+#[serial]
+fn test_conflicting_keybindings_keep_defaults_and_warn() {
+    let mut env = create_test_env_with_keybindings(&[("new_session", "d")]);
+    assert!(env.view.info_dialog.is_some());
+    env.view.info_dialog = None;
+
+    env.view.handle_key(key(KeyCode::Char('n')));
+    assert!(env.view.new_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_space_toggles_mark_on_session() {
+    let mut env = create_test_env_with_sessions(3);
+    let id = env.view.selected_session.clone().unwrap();
+
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    assert!(env.view.is_marked(&id));
+    assert_eq!(env.view.marked_ids(), vec![id.clone()]);
+
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    assert!(!env.view.is_marked(&id));
+    assert!(!env.view.has_marks());
+}
+
+#[test]
+#[serial]
+fn test_space_on_group_marks_its_sessions() {
+    let mut env = create_test_env_with_groups();
+    let group_idx = env
+        .view
+        .flat_items
+        .iter()
+        .position(|item| matches!(item, Item::Group { path, .. } if path == "work"))
+        .unwrap();
+    env.view.cursor = group_idx;
+    env.view.update_selected();
+
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    let marked = env.view.marked_ids();
+    assert_eq!(marked.len(), 1);
+    assert_eq!(env.view.instance_map[&marked[0]].title, "work-project");
+
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    assert!(env.view.marked_ids().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_range_marking_follows_cursor() {
+    let mut env = create_test_env_with_sessions(5);
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char('v')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(env.view.marked_ids().len(), 3);
+
+    // Finishing the range keeps it marked while the cursor moves on
+    env.view.handle_key(key(KeyCode::Char('v')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(env.view.mark_anchor, None);
+    assert_eq!(env.view.marked_ids().len(), 3);
+}
+
+#[test]
+#[serial]
+fn test_mark_visible_uses_search_filter() {
+    let mut env = create_test_env_with_sessions(5);
+    env.view.search_query = Input::new("session3".to_string());
+    env.view.update_filter();
+
+    env.view.handle_key(key(KeyCode::Char('*')));
+    let marked = env.view.marked_ids();
+    assert_eq!(marked.len(), 1);
+    assert_eq!(env.view.instance_map[&marked[0]].title, "session3");
+}
+
+#[test]
+#[serial]
+fn test_esc_clears_marks() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('*')));
+    assert_eq!(env.view.marked_ids().len(), 3);
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(!env.view.has_marks());
+}
+
+#[test]
+#[serial]
+fn test_bulk_actions_need_marks() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('b')));
+    assert!(env.view.bulk_actions_dialog.is_none());
+    assert!(env.view.info_dialog.is_some());
+    env.view.info_dialog = None;
+
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    env.view.handle_key(key(KeyCode::Char('b')));
+    assert!(env.view.bulk_actions_dialog.is_some());
+    assert!(env.view.has_dialog());
+}
+
+#[test]
+#[serial]
+fn test_bulk_move_to_group_reports_each_session() {
+    use crate::tui::dialogs::BulkAction;
+
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char(' ')));
+
+    env.view
+        .apply_bulk_action(BulkAction::MoveToGroup("team".to_string()));
+
+    let grouped: Vec<&str> = env
+        .view
+        .instances
+        .iter()
+        .filter(|i| i.group_path == "team")
+        .map(|i| i.title.as_str())
+        .collect();
+    assert_eq!(grouped.len(), 2);
+    assert!(!env.view.has_marks());
+    assert!(env
+        .view
+        .bulk_progress_dialog
+        .as_ref()
+        .is_some_and(|dialog| dialog.is_done()));
+}
+
+#[test]
+#[serial]
+fn test_delete_with_marks_deletes_marked_sessions() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('*')));
+    env.view.handle_key(key(KeyCode::Char('d')));
+    assert!(env.view.unified_delete_dialog.is_some());
+    assert_eq!(env.view.bulk_delete_ids.len(), 3);
+
+    env.view.handle_key(key(KeyCode::Char('y')));
+    assert!(env.view.unified_delete_dialog.is_none());
+    assert!(env
+        .view
+        .instances
+        .iter()
+        .all(|i| i.status == crate::session::Status::Deleting));
+    let dialog = env.view.bulk_progress_dialog.as_ref().unwrap();
+    assert!(!dialog.is_done());
+}
+
+#[test]
+#[serial]
+fn test_grid_opens_on_marked_sessions() {
+    use super::grid::GridScope;
+
+    let mut env = create_test_env_with_sessions(4);
+    env.view.handle_key(key(KeyCode::Char(' ')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char(' ')));
+
+    env.view.handle_key(key(KeyCode::Char('w')));
+    assert!(env.view.has_dialog());
+    assert_eq!(env.view.grid.as_ref().unwrap().scope, GridScope::Marked);
+    assert_eq!(env.view.grid_tiles(), env.view.marked_ids());
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.grid.is_none());
+    assert!(env.view.has_marks());
+}
+
+#[test]
+#[serial]
+fn test_grid_scope_cycles_from_selected_group() {
+    use super::grid::GridScope;
+
+    let mut env = create_test_env_with_groups();
+    let work_id = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.group_path == "work")
+        .map(|i| i.id.clone())
+        .unwrap();
+    let idx = env
+        .view
+        .flat_items
+        .iter()
+        .position(|item| matches!(item, Item::Session { id, .. } if *id == work_id))
+        .unwrap();
+    env.view.cursor = idx;
+    env.view.update_selected();
+
+    env.view.handle_key(key(KeyCode::Char('w')));
+    let scope = |env: &TestEnv| env.view.grid.as_ref().unwrap().scope.clone();
+    assert_eq!(scope(&env), GridScope::Group("work".to_string()));
+    assert_eq!(env.view.grid_tiles().len(), 1);
+
+    env.view.handle_key(key(KeyCode::Char('f')));
+    assert_eq!(scope(&env), GridScope::Waiting);
+    assert!(env.view.grid_tiles().is_empty());
+
+    env.view.handle_key(key(KeyCode::Char('f')));
+    assert_eq!(scope(&env), GridScope::Shown);
+    assert_eq!(env.view.grid_tiles().len(), 3);
+
+    env.view.handle_key(key(KeyCode::Char('f')));
+    assert_eq!(scope(&env), GridScope::Group("work".to_string()));
+}
+
+#[test]
+#[serial]
+fn test_grid_focus_moves_between_tiles_and_attaches() {
+    let mut env = create_test_env_with_sessions(4);
+    env.view.handle_key(key(KeyCode::Char('w')));
+    env.view.grid.as_mut().unwrap().columns = 2;
+    let tiles = env.view.grid_tiles();
+    assert_eq!(tiles.len(), 4);
+
+    // Moving off the edge keeps the focus where it is
+    env.view.handle_key(key(KeyCode::Char('k')));
+    env.view.handle_key(key(KeyCode::Char('h')));
+    env.view.handle_key(key(KeyCode::Char('l')));
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char('l')));
+
+    let action = env.view.handle_key(key(KeyCode::Enter));
+    assert_eq!(action, Some(Action::AttachSession(tiles[3].clone())));
+}
+
+#[test]
+fn test_grid_layout_fits_tiles_in_area() {
+    use super::grid::{grid_layout, MAX_TILES};
+    use ratatui::layout::Rect;
+
+    let wide = Rect::new(0, 0, 200, 50);
+    assert_eq!(grid_layout(1, wide), (1, 1, 1));
+    assert_eq!(grid_layout(4, wide), (2, 2, 4));
+    assert_eq!(grid_layout(5, wide), (3, 2, 5));
+    assert_eq!(grid_layout(40, wide).2, MAX_TILES);
+
+    // A short terminal gets more columns and fewer tiles
+    let short = Rect::new(0, 0, 100, 12);
+    assert_eq!(grid_layout(9, short), (4, 2, 8));
+}
+
+#[test]
+fn test_inline_input_translates_keys_for_tmux() {
+    use super::inline_input::tmux_key;
+
+    let with = |code, modifiers| tmux_key(&KeyEvent::new(code, modifiers));
+    assert_eq!(
+        with(KeyCode::Char('1'), KeyModifiers::NONE),
+        Some(("1".to_string(), true))
+    );
+    assert_eq!(
+        with(KeyCode::Char('Y'), KeyModifiers::SHIFT),
+        Some(("Y".to_string(), true))
+    );
+    assert_eq!(
+        with(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        Some(("C-c".to_string(), false))
+    );
+    assert_eq!(
+        with(KeyCode::Char('b'), KeyModifiers::ALT),
+        Some(("M-b".to_string(), false))
+    );
+    assert_eq!(
+        with(KeyCode::Enter, KeyModifiers::NONE),
+        Some(("Enter".to_string(), false))
+    );
+    assert_eq!(
+        with(KeyCode::Esc, KeyModifiers::NONE),
+        Some(("Escape".to_string(), false))
+    );
+    assert_eq!(
+        with(KeyCode::Up, KeyModifiers::SHIFT),
+        Some(("S-Up".to_string(), false))
+    );
+    assert_eq!(
+        with(KeyCode::BackTab, KeyModifiers::SHIFT),
+        Some(("BTab".to_string(), false))
+    );
+    assert_eq!(with(KeyCode::Null, KeyModifiers::NONE), None);
+}
+
+#[test]
+#[serial]
+fn test_inline_input_needs_running_session() {
+    let mut env = create_test_env_with_sessions(2);
+    env.view.handle_key(key(KeyCode::Char('i')));
+    assert!(!env.view.is_inline_input());
+    assert!(env.view.info_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_inline_input_takes_every_key_until_left() {
+    let mut env = create_test_env_with_sessions(2);
+    let id = env.view.selected_session.clone().unwrap();
+    env.view.inline_input = Some(id.clone());
+    assert!(env.view.has_dialog());
+
+    // Leaving is the only key handled by aoe itself
+    env.view
+        .handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL));
+    assert!(!env.view.is_inline_input());
+    assert_eq!(env.view.selected_session, Some(id.clone()));
+
+    // Keys that can't reach the session end inline input
+    env.view.inline_input = Some(id.clone());
+    let action = env.view.handle_key(key(KeyCode::Char('q')));
+    assert_eq!(action, None);
+    assert!(!env.view.is_inline_input());
+    assert!(env.view.info_dialog.is_some());
+    assert_eq!(env.view.selected_session, Some(id));
+}
+
+#[test]
+#[serial]
+fn test_quick_answer_needs_detected_prompt() {
+    let mut env = create_test_env_with_sessions(2);
+    env.view.handle_key(key(KeyCode::Char('y')));
+    assert!(env.view.info_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_quick_answers_offered_by_prompt() {
+    use crate::tmux::{QuickAnswer, WaitingReason};
+
+    let mut env = create_test_env_with_sessions(2);
+    let id = env.view.selected_session.clone().unwrap();
+    assert!(env.view.offered_answers(Some(&id)).is_empty());
+
+    env.view.instance_map.get_mut(&id).unwrap().waiting_reason = Some(WaitingReason::YesNo);
+    assert_eq!(
+        env.view.offered_answers(Some(&id)),
+        vec![QuickAnswer::ApproveOnce, QuickAnswer::Deny]
+    );
+
+    // A session that isn't running can't take the answer, so the prompt stays
+    env.view.handle_key(key(KeyCode::Char('y')));
+    assert!(env.view.info_dialog.is_some());
+    assert_eq!(
+        env.view.instance_map[&id].waiting_reason,
+        Some(WaitingReason::YesNo)
+    );
+}
+
+#[test]
+#[serial]
+fn test_inline_input_hint_names_the_bound_key() {
+    let env = create_test_env_with_sessions(1);
+    assert_eq!(
+        env.view.inline_input_hint(),
+        "Use i to type into the session."
+    );
+
+    let env = create_test_env_with_keybindings(&[("inline_input", "e")]);
+    assert_eq!(
+        env.view.inline_input_hint(),
+        "Use e to type into the session."
+    );
+}
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+/// Lay the panels out the way a 120x30 frame with the default width does
+fn lay_out_panels(view: &mut HomeView) {
+    view.screen_area = Rect::new(0, 0, 120, 30);
+    view.list_area = Rect::new(0, 0, view.list_width, 29);
+    view.preview_area = Rect::new(view.list_width, 0, 120 - view.list_width, 29);
+}
+
+#[test]
+#[serial]
+fn test_click_and_wheel_select_list_rows() {
+    let mut env = create_test_env_with_sessions(4);
+    lay_out_panels(&mut env.view);
+
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3));
+    assert_eq!(env.view.cursor, 2);
+    // Rows past the last session select nothing
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 20));
+    assert_eq!(env.view.cursor, 2);
+
+    env.view
+        .handle_mouse(mouse(MouseEventKind::ScrollDown, 5, 10));
+    assert_eq!(env.view.cursor, 3);
+    env.view
+        .handle_mouse(mouse(MouseEventKind::ScrollUp, 5, 10));
+    env.view
+        .handle_mouse(mouse(MouseEventKind::ScrollUp, 5, 10));
+    assert_eq!(env.view.cursor, 1);
+}
+
+#[test]
+#[serial]
+fn test_drag_resizes_list_and_persists_width() {
+    let mut env = create_test_env_with_sessions(2);
+    lay_out_panels(&mut env.view);
+
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 34, 5));
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 49, 5));
+    assert_eq!(env.view.list_width, 50);
+    // The drag never gets narrower than the keyboard allows
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 2, 5));
+    assert_eq!(env.view.list_width, 10);
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 59, 5));
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Up(MouseButton::Left), 59, 5));
+
+    let config = crate::session::config::load_config().unwrap().unwrap();
+    assert_eq!(config.app_state.home_list_width, Some(60));
+}
+
+#[test]
+#[serial]
+fn test_wheel_scrolls_preview_until_selection_changes() {
+    let mut env = create_test_env_with_sessions(2);
+    lay_out_panels(&mut env.view);
+
+    env.view
+        .handle_mouse(mouse(MouseEventKind::ScrollUp, 60, 10));
+    env.view
+        .handle_mouse(mouse(MouseEventKind::ScrollUp, 60, 10));
+    assert_eq!(env.view.preview_scroll, 6);
+    env.view
+        .handle_mouse(mouse(MouseEventKind::ScrollDown, 60, 10));
+    assert_eq!(env.view.preview_scroll, 3);
+
+    env.view.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(env.view.preview_scroll, 0);
+}
+
+#[test]
+#[serial]
+fn test_dialogs_take_mouse_before_the_list() {
+    let mut env = create_test_env_with_sessions(3);
+    lay_out_panels(&mut env.view);
+    env.view.show_help = true;
+
+    env.view
+        .handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3));
+    assert_eq!(env.view.cursor, 0);
+}
+
+fn open_palette(view: &mut HomeView, query: &str) {
+    view.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+    for c in query.chars() {
+        view.handle_key(key(KeyCode::Char(c)));
+    }
+}
+
+#[test]
+#[serial]
+fn test_palette_runs_list_commands() {
+    let mut env = create_test_env_with_sessions(2);
+    open_palette(&mut env.view, "new session");
+    assert!(env.view.palette.is_some());
+    assert!(env.view.has_dialog());
+
+    // Keys go to the palette, not the list
+    env.view.handle_key(key(KeyCode::Char('q')));
+    env.view.handle_key(key(KeyCode::Backspace));
+    let action = env.view.handle_key(key(KeyCode::Enter));
+    assert_eq!(action, None);
+    assert!(env.view.palette.is_none());
+    assert!(env.view.new_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_palette_jumps_to_hidden_session() {
+    let mut env = create_test_env_with_groups();
+    env.view.group_tree.toggle_collapsed("work");
+    env.view.flat_items = env.view.flatten_items();
+    env.view.search_query = Input::new("personal".to_string());
+    env.view.update_filter();
+
+    open_palette(&mut env.view, "work-project");
+    env.view.handle_key(key(KeyCode::Enter));
+
+    let work = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.group_path == "work")
+        .unwrap();
+    assert_eq!(env.view.selected_session.as_ref(), Some(&work.id));
+    assert!(env.view.search_query.value().is_empty());
+    assert!(env
+        .view
+        .group_tree
+        .get_all_groups()
+        .iter()
+        .all(|group| !group.collapsed));
+}
+
+#[test]
+#[serial]
+fn test_palette_jumps_to_group() {
+    let mut env = create_test_env_with_groups();
+    open_palette(&mut env.view, "personal");
+    // The session under the group ranks first
+    env.view.handle_key(key(KeyCode::Down));
+    env.view.handle_key(key(KeyCode::Enter));
+    assert_eq!(env.view.selected_group.as_deref(), Some("personal"));
+    assert!(env.view.selected_session.is_none());
+}
+
+#[test]
+#[serial]
+fn test_palette_runs_grid_commands_over_the_grid() {
+    let mut env = create_test_env_with_sessions(2);
+    env.view.handle_key(key(KeyCode::Char('w')));
+    open_palette(&mut env.view, "close grid");
+    env.view.handle_key(key(KeyCode::Enter));
+    assert!(env.view.grid.is_none());
+    assert!(env.view.palette.is_none());
+}
+
+fn session_ids(view: &HomeView) -> Vec<String> {
+    view.flat_items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Session { id, .. } => Some(id.clone()),
+            Item::Group { .. } | Item::Favorites { .. } => None,
+        })
+        .collect()
+}
+
+#[test]
+#[serial]
+fn test_move_keys_reorder_sessions_and_persist() {
+    let mut env = create_test_env_with_sessions(3);
+    let ids = session_ids(&env.view);
+
+    env.view.handle_key(key(KeyCode::Char('J')));
+    assert_eq!(session_ids(&env.view), [1, 0, 2].map(|i| ids[i].clone()));
+    // The moved session stays selected
+    assert_eq!(env.view.selected_session.as_ref(), Some(&ids[0]));
+    assert_eq!(env.view.cursor, 1);
+
+    env.view.handle_key(key(KeyCode::Char('J')));
+    env.view.handle_key(key(KeyCode::Char('J')));
+    assert_eq!(session_ids(&env.view), [1, 2, 0].map(|i| ids[i].clone()));
+
+    let view = HomeView::new(Storage::new("test").unwrap(), env.view.available_tools()).unwrap();
+    assert_eq!(session_ids(&view), [1, 2, 0].map(|i| ids[i].clone()));
+}
+
+#[test]
+#[serial]
+fn test_pinned_session_moves_to_top_of_its_group() {
+    let mut env = create_test_env_with_sessions(3);
+    let ids = session_ids(&env.view);
+    env.view.cursor = 2;
+    env.view.update_selected();
+
+    env.view.handle_key(key(KeyCode::Char('T')));
+    assert_eq!(session_ids(&env.view), [2, 0, 1].map(|i| ids[i].clone()));
+    assert_eq!(env.view.cursor, 0);
+    assert!(env.view.instance_map[&ids[2]].pinned);
+
+    // Unpinned sessions can't be moved above a pinned one
+    env.view.handle_key(key(KeyCode::Char('j')));
+    env.view.handle_key(key(KeyCode::Char('K')));
+    assert_eq!(session_ids(&env.view), [2, 0, 1].map(|i| ids[i].clone()));
+
+    env.view.handle_key(key(KeyCode::Char('k')));
+    env.view.handle_key(key(KeyCode::Char('T')));
+    assert_eq!(session_ids(&env.view), ids);
+}
+
+#[test]
+#[serial]
+fn test_favorites_group_lists_favorites_on_top() {
+    let mut env = create_test_env_with_groups();
+    let work = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.group_path == "work")
+        .map(|i| i.id.clone())
+        .unwrap();
+    env.view.select_session_by_id(&work);
+    let cursor = env.view.cursor;
+
+    env.view.handle_key(key(KeyCode::Char('B')));
+    assert!(matches!(
+        env.view.flat_items[0],
+        Item::Favorites { session_count: 1 }
+    ));
+    assert!(matches!(&env.view.flat_items[1], Item::Session { id, .. } if *id == work));
+    // The selection stays on the session's entry in its own group
+    assert_eq!(env.view.cursor, cursor + 2);
+
+    // The grid counts a favorite once
+    env.view.search_query = Input::new("project".to_string());
+    env.view.update_filter();
+    assert_eq!(env.view.visible_session_ids().len(), 2);
+
+    env.view.search_query = Input::default();
+    env.view.update_filter();
+    env.view.select_session_by_id(&work);
+    env.view.handle_key(key(KeyCode::Char('B')));
+    assert!(!matches!(env.view.flat_items[0], Item::Favorites { .. }));
+}
+
+#[test]
+#[serial]
+fn test_move_keys_reorder_groups() {
+    let mut env = create_test_env_with_groups();
+    let group_index = |view: &HomeView, name: &str| {
+        view.flat_items
+            .iter()
+            .position(|item| matches!(item, Item::Group { path, .. } if path == name))
+            .unwrap()
+    };
+    assert!(group_index(&env.view, "personal") < group_index(&env.view, "work"));
+
+    env.view.cursor = group_index(&env.view, "work");
+    env.view.update_selected();
+    env.view.handle_key(key(KeyCode::Char('K')));
+    assert!(group_index(&env.view, "work") < group_index(&env.view, "personal"));
+    assert_eq!(env.view.selected_group.as_deref(), Some("work"));
+
+    // Moving is refused while the list is sorted
+    env.view.handle_key(key(KeyCode::Char('o')));
+    env.view.handle_key(key(KeyCode::Char('J')));
+    assert!(env.view.info_dialog.is_some());
+}
@@ -0,0 +1,1175 @@
+//! Configurable key bindings
+//!
+//! Each view has a command enum with a table giving every command its config
+//! name, help text and default keys. The `[keybindings.<view>]` config tables
+//! replace a command's default keys:
+//!
+//! ```toml
+//! [keybindings.home]
+//! new_session = "a"
+//! delete = ["d", "delete"]
+//! quit = []
+//! ```
+//!
+//! Keys are written as a character (`n`, `G`, `?`) or a named key (`enter`,
+//! `esc`, `tab`, `space`, `up`, `pgdn`, `f1`, ...), optionally prefixed with
+//! `ctrl+`, `alt+` or `shift+`. Bindings that name unknown commands or keys,
+//! or bind one key to two commands of a view, are reported when loading and
+//! that view keeps its defaults.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::session::{KeyList, KeybindingsConfig};
+
+/// A key with its modifiers. Shift is folded into characters, so `G` and
+/// `shift+g` are the same chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (mods, key) = match spec.strip_suffix("++") {
+            Some(mods) => (mods, "+"),
+            None => match spec.rsplit_once('+') {
+                Some((mods, key)) if !key.is_empty() => (mods, key),
+                _ => ("", spec),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{}' in '{}'", m, spec)),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", spec)),
+                },
+            },
+        };
+
+        Ok(Self::normalized(code, modifiers))
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::normalized(key.code, key.modifiers)
+    }
+
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "S-Tab"),
+            KeyCode::Backspace => write!(f, "Bksp"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Static description of a command
+pub struct CommandSpec<C> {
+    pub command: C,
+    /// Name used in `[keybindings]`
+    pub name: &'static str,
+    /// Help overlay section
+    pub section: &'static str,
+    pub description: &'static str,
+    pub default_keys: &'static [&'static str],
+}
+
+pub trait Command: Copy + Eq + fmt::Debug + 'static {
+    /// Every command of the view, in help overlay order
+    fn specs() -> &'static [CommandSpec<Self>];
+
+    fn spec(self) -> &'static CommandSpec<Self> {
+        Self::specs()
+            .iter()
+            .find(|spec| spec.command == self)
+            .expect("every command has a spec")
+    }
+}
+
+/// Active bindings of one view
+#[derive(Debug, Clone)]
+pub struct Keymap<C> {
+    bindings: Vec<(KeyChord, C)>,
+}
+
+impl<C: Command> Default for Keymap<C> {
+    fn default() -> Self {
+        Self::with_overrides(&BTreeMap::new()).expect("default key bindings are valid")
+    }
+}
+
+impl<C: Command> Keymap<C> {
+    /// Defaults with the keys of the commands named in `overrides` replaced.
+    pub fn with_overrides(overrides: &BTreeMap<String, KeyList>) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+
+        for name in overrides.keys() {
+            if !C::specs().iter().any(|spec| spec.name == name) {
+                errors.push(format!("unknown command '{}'", name));
+            }
+        }
+
+        let mut bindings: Vec<(KeyChord, C)> = Vec::new();
+        for spec in C::specs() {
+            let keys: Vec<&str> = match overrides.get(spec.name) {
+                Some(list) => list.keys(),
+                None => spec.default_keys.to_vec(),
+            };
+            for key in keys {
+                let chord = match KeyChord::parse(key) {
+                    Ok(chord) => chord,
+                    Err(e) => {
+                        errors.push(format!("{}: {}", spec.name, e));
+                        continue;
+                    }
+                };
+                match bindings.iter().find(|(bound, _)| *bound == chord) {
+                    Some((_, other)) if *other != spec.command => errors.push(format!(
+                        "'{}' is bound to both {} and {}",
+                        key,
+                        other.spec().name,
+                        spec.name
+                    )),
+                    Some(_) => {}
+                    None => bindings.push((chord, spec.command)),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { bindings })
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn command(&self, key: &KeyEvent) -> Option<C> {
+        let chord = KeyChord::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == chord)
+            .map(|(_, command)| *command)
+    }
+
+    pub fn is(&self, key: &KeyEvent, command: C) -> bool {
+        self.command(key) == Some(command)
+    }
+
+    /// All keys of a command joined with `/`, e.g. `j/↓`
+    pub fn label(&self, command: C) -> String {
+        self.bindings
+            .iter()
+            .filter(|(_, c)| *c == command)
+            .map(|(chord, _)| chord.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// The first key of a command, for compact hints
+    pub fn short_label(&self, command: C) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, c)| *c == command)
+            .map(|(chord, _)| chord.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Bound commands grouped by section, as (keys, description) pairs.
+    pub fn help_sections(&self) -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+        let mut sections: Vec<(&'static str, Vec<(String, &'static str)>)> = Vec::new();
+        for spec in C::specs() {
+            let label = self.label(spec.command);
+            if label.is_empty() {
+                continue;
+            }
+            match sections.iter_mut().find(|(name, _)| *name == spec.section) {
+                Some((_, entries)) => entries.push((label, spec.description)),
+                None => sections.push((spec.section, vec![(label, spec.description)])),
+            }
+        }
+        sections
+    }
+}
+
+/// Commands of the session list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomeCommand {
+    MoveDown,
+    MoveUp,
+    Collapse,
+    Expand,
+    Top,
+    Bottom,
+    PageUp,
+    PageDown,
+    Attach,
+    NewSession,
+    Delete,
+    Pin,
+    Favorite,
+    MoveItemUp,
+    MoveItemDown,
+    Mark,
+    VisualMark,
+    MarkVisible,
+    ClearMarks,
+    BulkActions,
+    InlineInput,
+    ApproveOnce,
+    ApproveAlways,
+    Deny,
+    Rename,
+    Ports,
+    Snapshots,
+    ToggleView,
+    ToggleTerminalMode,
+    Diff,
+    Grid,
+    UsageSort,
+    Sort,
+    FlatList,
+    ShrinkList,
+    GrowList,
+    Search,
+    ChangesOnly,
+    Filters,
+    Settings,
+    NextProfile,
+    CommandPalette,
+    LeaveInlineInput,
+    Help,
+    Quit,
+}
+
+impl Command for HomeCommand {
+    fn specs() -> &'static [CommandSpec<Self>] {
+        use HomeCommand::*;
+        const NAV: &str = "Navigation";
+        const ACTIONS: &str = "Actions";
+        const SELECTION: &str = "Selection";
+        const ORDER: &str = "Order";
+        const VIEWS: &str = "Views";
+        const OTHER: &str = "Other";
+        &[
+            CommandSpec {
+                command: MoveDown,
+                name: "move_down",
+                section: NAV,
+                description: "Move down",
+                default_keys: &["j", "down"],
+            },
+            CommandSpec {
+                command: MoveUp,
+                name: "move_up",
+                section: NAV,
+                description: "Move up",
+                default_keys: &["k", "up"],
+            },
+            CommandSpec {
+                command: Collapse,
+                name: "collapse",
+                section: NAV,
+                description: "Collapse group",
+                default_keys: &["h", "left"],
+            },
+            CommandSpec {
+                command: Expand,
+                name: "expand",
+                section: NAV,
+                description: "Expand group",
+                default_keys: &["l", "right"],
+            },
+            CommandSpec {
+                command: Top,
+                name: "top",
+                section: NAV,
+                description: "Go to top",
+                default_keys: &["g", "home"],
+            },
+            CommandSpec {
+                command: Bottom,
+                name: "bottom",
+                section: NAV,
+                description: "Go to bottom",
+                default_keys: &["G", "end"],
+            },
+            CommandSpec {
+                command: PageUp,
+                name: "page_up",
+                section: NAV,
+                description: "Move 10 items up",
+                default_keys: &["pgup"],
+            },
+            CommandSpec {
+                command: PageDown,
+                name: "page_down",
+                section: NAV,
+                description: "Move 10 items down",
+                default_keys: &["pgdn"],
+            },
+            CommandSpec {
+                command: Attach,
+                name: "attach",
+                section: ACTIONS,
+                description: "Attach to session",
+                default_keys: &["enter"],
+            },
+            CommandSpec {
+                command: NewSession,
+                name: "new_session",
+                section: ACTIONS,
+                description: "New session",
+                default_keys: &["n"],
+            },
+            CommandSpec {
+                command: Delete,
+                name: "delete",
+                section: ACTIONS,
+                description: "Delete session/group",
+                default_keys: &["d"],
+            },
+            CommandSpec {
+                command: Rename,
+                name: "rename",
+                section: ACTIONS,
+                description: "Rename session",
+                default_keys: &["r"],
+            },
+            CommandSpec {
+                command: Pin,
+                name: "pin",
+                section: ORDER,
+                description: "Pin to top of group",
+                default_keys: &["T"],
+            },
+            CommandSpec {
+                command: Favorite,
+                name: "favorite",
+                section: ORDER,
+                description: "Toggle favorite",
+                default_keys: &["B"],
+            },
+            CommandSpec {
+                command: MoveItemUp,
+                name: "move_item_up",
+                section: ORDER,
+                description: "Move item up",
+                default_keys: &["K"],
+            },
+            CommandSpec {
+                command: MoveItemDown,
+                name: "move_item_down",
+                section: ORDER,
+                description: "Move item down",
+                default_keys: &["J"],
+            },
+            CommandSpec {
+                command: Mark,
+                name: "mark",
+                section: SELECTION,
+                description: "Mark/unmark session",
+                default_keys: &["space"],
+            },
+            CommandSpec {
+                command: VisualMark,
+                name: "visual_mark",
+                section: SELECTION,
+                description: "Start/finish marking a range",
+                default_keys: &["v"],
+            },
+            CommandSpec {
+                command: MarkVisible,
+                name: "mark_visible",
+                section: SELECTION,
+                description: "Mark all shown (search/filter)",
+                default_keys: &["*"],
+            },
+            CommandSpec {
+                command: ClearMarks,
+                name: "clear_marks",
+                section: SELECTION,
+                description: "Clear marks",
+                default_keys: &["esc"],
+            },
+            CommandSpec {
+                command: BulkActions,
+                name: "bulk_actions",
+                section: SELECTION,
+                description: "Actions on marked sessions",
+                default_keys: &["b"],
+            },
+            CommandSpec {
+                command: InlineInput,
+                name: "inline_input",
+                section: ACTIONS,
+                description: "Type into session from preview",
+                default_keys: &["i"],
+            },
+            CommandSpec {
+                command: ApproveOnce,
+                name: "approve_once",
+                section: ACTIONS,
+                description: "Approve waiting prompt once",
+                default_keys: &["y"],
+            },
+            CommandSpec {
+                command: ApproveAlways,
+                name: "approve_always",
+                section: ACTIONS,
+                description: "Approve waiting prompt always",
+                default_keys: &["Y"],
+            },
+            CommandSpec {
+                command: Deny,
+                name: "deny",
+                section: ACTIONS,
+                description: "Deny waiting prompt",
+                default_keys: &["N"],
+            },
+            CommandSpec {
+                command: Ports,
+                name: "ports",
+                section: ACTIONS,
+                description: "Ports (sandbox)",
+                default_keys: &["p"],
+            },
+            CommandSpec {
+                command: Snapshots,
+                name: "snapshots",
+                section: ACTIONS,
+                description: "Snapshots (sandbox)",
+                default_keys: &["S"],
+            },
+            CommandSpec {
+                command: ToggleView,
+                name: "toggle_view",
+                section: VIEWS,
+                description: "Toggle Agent/Terminal view",
+                default_keys: &["t"],
+            },
+            CommandSpec {
+                command: ToggleTerminalMode,
+                name: "toggle_terminal_mode",
+                section: VIEWS,
+                description: "Toggle container/host (sandbox)",
+                default_keys: &["c"],
+            },
+            CommandSpec {
+                command: Diff,
+                name: "diff",
+                section: VIEWS,
+                description: "Diff view (git changes)",
+                default_keys: &["D"],
+            },
+            CommandSpec {
+                command: Grid,
+                name: "grid",
+                section: VIEWS,
+                description: "Grid of live previews",
+                default_keys: &["w"],
+            },
+            CommandSpec {
+                command: UsageSort,
+                name: "usage_sort",
+                section: VIEWS,
+                description: "Sort by CPU/memory (sandbox)",
+                default_keys: &["u"],
+            },
+            CommandSpec {
+                command: Sort,
+                name: "sort",
+                section: VIEWS,
+                description: "Cycle list sort",
+                default_keys: &["o"],
+            },
+            CommandSpec {
+                command: FlatList,
+                name: "flat_list",
+                section: VIEWS,
+                description: "Show list without groups",
+                default_keys: &["F"],
+            },
+            CommandSpec {
+                command: ShrinkList,
+                name: "shrink_list",
+                section: VIEWS,
+                description: "Shrink list panel",
+                default_keys: &["H"],
+            },
+            CommandSpec {
+                command: GrowList,
+                name: "grow_list",
+                section: VIEWS,
+                description: "Grow list panel",
+                default_keys: &["L"],
+            },
+            CommandSpec {
+                command: Search,
+                name: "search",
+                section: OTHER,
+                description: "Search",
+                default_keys: &["/"],
+            },
+            CommandSpec {
+                command: ChangesOnly,
+                name: "changes_only",
+                section: OTHER,
+                description: "Only sessions with git changes",
+                default_keys: &["C"],
+            },
+            CommandSpec {
+                command: Filters,
+                name: "filters",
+                section: OTHER,
+                description: "Filter chips",
+                default_keys: &["f"],
+            },
+            CommandSpec {
+                command: Settings,
+                name: "settings",
+                section: OTHER,
+                description: "Settings",
+                default_keys: &["s"],
+            },
+            CommandSpec {
+                command: NextProfile,
+                name: "next_profile",
+                section: OTHER,
+                description: "Next profile",
+                default_keys: &["P"],
+            },
+            CommandSpec {
+                command: CommandPalette,
+                name: "command_palette",
+                section: OTHER,
+                description: "Command palette",
+                default_keys: &["ctrl+p"],
+            },
+            // The only key aoe handles while typing into a session
+            CommandSpec {
+                command: LeaveInlineInput,
+                name: "leave_inline_input",
+                section: OTHER,
+                description: "Stop typing into session",
+                default_keys: &["ctrl+q"],
+            },
+            CommandSpec {
+                command: Help,
+                name: "help",
+                section: OTHER,
+                description: "Toggle help",
+                default_keys: &["?"],
+            },
+            CommandSpec {
+                command: Quit,
+                name: "quit",
+                section: OTHER,
+                description: "Quit",
+                default_keys: &["q"],
+            },
+        ]
+    }
+}
+
+/// Commands of the diff view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffCommand {
+    NextFile,
+    PrevFile,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    Top,
+    Bottom,
+    ShrinkFileList,
+    GrowFileList,
+    Edit,
+    SelectBranch,
+    Refresh,
+    CommandPalette,
+    Help,
+    Close,
+}
+
+impl Command for DiffCommand {
+    fn specs() -> &'static [CommandSpec<Self>] {
+        use DiffCommand::*;
+        const NAV: &str = "Navigation";
+        const ACTIONS: &str = "Actions";
+        const OTHER: &str = "Other";
+        &[
+            CommandSpec {
+                command: NextFile,
+                name: "next_file",
+                section: NAV,
+                description: "Next file",
+                default_keys: &["j", "down"],
+            },
+            CommandSpec {
+                command: PrevFile,
+                name: "prev_file",
+                section: NAV,
+                description: "Previous file",
+                default_keys: &["k", "up"],
+            },
+            CommandSpec {
+                command: PageDown,
+                name: "page_down",
+                section: NAV,
+                description: "Page down in diff",
+                default_keys: &["pgdn"],
+            },
+            CommandSpec {
+                command: PageUp,
+                name: "page_up",
+                section: NAV,
+                description: "Page up in diff",
+                default_keys: &["pgup"],
+            },
+            CommandSpec {
+                command: HalfPageDown,
+                name: "half_page_down",
+                section: NAV,
+                description: "Half-page down",
+                default_keys: &["ctrl+d"],
+            },
+            CommandSpec {
+                command: HalfPageUp,
+                name: "half_page_up",
+                section: NAV,
+                description: "Half-page up",
+                default_keys: &["ctrl+u"],
+            },
+            CommandSpec {
+                command: Top,
+                name: "top",
+                section: NAV,
+                description: "Go to top of diff",
+                default_keys: &["g", "home"],
+            },
+            CommandSpec {
+                command: Bottom,
+                name: "bottom",
+                section: NAV,
+                description: "Go to bottom of diff",
+                default_keys: &["G", "end"],
+            },
+            CommandSpec {
+                command: ShrinkFileList,
+                name: "shrink_file_list",
+                section: NAV,
+                description: "Shrink file list",
+                default_keys: &["h", "left"],
+            },
+            CommandSpec {
+                command: GrowFileList,
+                name: "grow_file_list",
+                section: NAV,
+                description: "Grow file list",
+                default_keys: &["l", "right"],
+            },
+            CommandSpec {
+                command: Edit,
+                name: "edit",
+                section: ACTIONS,
+                description: "Edit file in external editor",
+                default_keys: &["e", "enter"],
+            },
+            CommandSpec {
+                command: SelectBranch,
+                name: "select_branch",
+                section: ACTIONS,
+                description: "Select base branch",
+                default_keys: &["b"],
+            },
+            CommandSpec {
+                command: Refresh,
+                name: "refresh",
+                section: ACTIONS,
+                description: "Refresh diff",
+                default_keys: &["r"],
+            },
+            CommandSpec {
+                command: CommandPalette,
+                name: "command_palette",
+                section: OTHER,
+                description: "Command palette",
+                default_keys: &["ctrl+p"],
+            },
+            CommandSpec {
+                command: Help,
+                name: "help",
+                section: OTHER,
+                description: "Toggle this help",
+                default_keys: &["?"],
+            },
+            CommandSpec {
+                command: Close,
+                name: "close",
+                section: OTHER,
+                description: "Close diff view",
+                default_keys: &["q", "esc"],
+            },
+        ]
+    }
+}
+
+/// Commands of the grid of live previews
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridCommand {
+    Left,
+    Right,
+    Up,
+    Down,
+    Attach,
+    InlineInput,
+    ApproveOnce,
+    ApproveAlways,
+    Deny,
+    NextScope,
+    CommandPalette,
+    Close,
+}
+
+impl Command for GridCommand {
+    fn specs() -> &'static [CommandSpec<Self>] {
+        use GridCommand::*;
+        const GRID: &str = "Grid";
+        &[
+            CommandSpec {
+                command: Left,
+                name: "left",
+                section: GRID,
+                description: "Tile to the left",
+                default_keys: &["h", "left"],
+            },
+            CommandSpec {
+                command: Right,
+                name: "right",
+                section: GRID,
+                description: "Tile to the right",
+                default_keys: &["l", "right"],
+            },
+            CommandSpec {
+                command: Up,
+                name: "up",
+                section: GRID,
+                description: "Tile above",
+                default_keys: &["k", "up"],
+            },
+            CommandSpec {
+                command: Down,
+                name: "down",
+                section: GRID,
+                description: "Tile below",
+                default_keys: &["j", "down"],
+            },
+            CommandSpec {
+                command: Attach,
+                name: "attach",
+                section: GRID,
+                description: "Attach to session",
+                default_keys: &["enter"],
+            },
+            CommandSpec {
+                command: InlineInput,
+                name: "inline_input",
+                section: GRID,
+                description: "Type into focused session",
+                default_keys: &["i"],
+            },
+            CommandSpec {
+                command: ApproveOnce,
+                name: "approve_once",
+                section: GRID,
+                description: "Approve focused prompt once",
+                default_keys: &["y"],
+            },
+            CommandSpec {
+                command: ApproveAlways,
+                name: "approve_always",
+                section: GRID,
+                description: "Approve focused prompt always",
+                default_keys: &["Y"],
+            },
+            CommandSpec {
+                command: Deny,
+                name: "deny",
+                section: GRID,
+                description: "Deny focused prompt",
+                default_keys: &["N"],
+            },
+            CommandSpec {
+                command: NextScope,
+                name: "next_scope",
+                section: GRID,
+                description: "Show marked/group/waiting/all sessions",
+                default_keys: &["f"],
+            },
+            CommandSpec {
+                command: CommandPalette,
+                name: "command_palette",
+                section: GRID,
+                description: "Command palette",
+                default_keys: &["ctrl+p"],
+            },
+            CommandSpec {
+                command: Close,
+                name: "close",
+                section: GRID,
+                description: "Close grid",
+                default_keys: &["q", "esc", "w"],
+            },
+        ]
+    }
+}
+
+/// Commands of the settings view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsCommand {
+    Save,
+    Close,
+    SwitchScope,
+    FocusCategories,
+    FocusFields,
+    Up,
+    Down,
+    Toggle,
+    Edit,
+    Reset,
+    CommandPalette,
+}
+
+impl Command for SettingsCommand {
+    fn specs() -> &'static [CommandSpec<Self>] {
+        use SettingsCommand::*;
+        const SETTINGS: &str = "Settings";
+        &[
+            CommandSpec {
+                command: Save,
+                name: "save",
+                section: SETTINGS,
+                description: "Save settings",
+                default_keys: &["ctrl+s"],
+            },
+            CommandSpec {
+                command: Close,
+                name: "close",
+                section: SETTINGS,
+                description: "Close settings",
+                default_keys: &["esc", "q"],
+            },
+            CommandSpec {
+                command: SwitchScope,
+                name: "switch_scope",
+                section: SETTINGS,
+                description: "Switch global/profile scope",
+                default_keys: &["tab", "backtab"],
+            },
+            CommandSpec {
+                command: FocusCategories,
+                name: "focus_categories",
+                section: SETTINGS,
+                description: "Focus categories",
+                default_keys: &["h", "left"],
+            },
+            CommandSpec {
+                command: FocusFields,
+                name: "focus_fields",
+                section: SETTINGS,
+                description: "Focus fields",
+                default_keys: &["l", "right"],
+            },
+            CommandSpec {
+                command: Up,
+                name: "up",
+                section: SETTINGS,
+                description: "Previous item",
+                default_keys: &["k", "up"],
+            },
+            CommandSpec {
+                command: Down,
+                name: "down",
+                section: SETTINGS,
+                description: "Next item",
+                default_keys: &["j", "down"],
+            },
+            CommandSpec {
+                command: Toggle,
+                name: "toggle",
+                section: SETTINGS,
+                description: "Toggle option",
+                default_keys: &["space"],
+            },
+            CommandSpec {
+                command: Edit,
+                name: "edit",
+                section: SETTINGS,
+                description: "Edit field",
+                default_keys: &["enter"],
+            },
+            CommandSpec {
+                command: Reset,
+                name: "reset",
+                section: SETTINGS,
+                description: "Reset profile override",
+                default_keys: &["r"],
+            },
+            CommandSpec {
+                command: CommandPalette,
+                name: "command_palette",
+                section: SETTINGS,
+                description: "Command palette",
+                default_keys: &["ctrl+p"],
+            },
+        ]
+    }
+}
+
+/// Bindings of every view
+#[derive(Debug, Clone, Default)]
+pub struct Keybindings {
+    pub home: Keymap<HomeCommand>,
+    pub diff: Keymap<DiffCommand>,
+    pub grid: Keymap<GridCommand>,
+    pub settings: Keymap<SettingsCommand>,
+}
+
+impl Keybindings {
+    /// Apply the configured bindings. A view whose bindings have problems keeps
+    /// its defaults; the problems are returned for display.
+    pub fn load(config: &KeybindingsConfig) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let bindings = Self {
+            home: keymap_or_default(&config.home, "home", &mut errors),
+            diff: keymap_or_default(&config.diff, "diff", &mut errors),
+            grid: keymap_or_default(&config.grid, "grid", &mut errors),
+            settings: keymap_or_default(&config.settings, "settings", &mut errors),
+        };
+        (bindings, errors)
+    }
+}
+
+fn keymap_or_default<C: Command>(
+    overrides: &BTreeMap<String, KeyList>,
+    view: &str,
+    errors: &mut Vec<String>,
+) -> Keymap<C> {
+    Keymap::with_overrides(overrides).unwrap_or_else(|view_errors| {
+        errors.extend(
+            view_errors
+                .into_iter()
+                .map(|e| format!("[keybindings.{}] {}", view, e)),
+        );
+        Keymap::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn overrides(entries: &[(&str, &[&str])]) -> BTreeMap<String, KeyList> {
+        entries
+            .iter()
+            .map(|(name, keys)| {
+                let keys = keys.iter().map(|k| k.to_string()).collect();
+                (name.to_string(), KeyList::Many(keys))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_chords() {
+        assert_eq!(
+            KeyChord::parse("ctrl+d").unwrap(),
+            KeyChord::from_event(&press(KeyCode::Char('d'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            KeyChord::parse("shift+g").unwrap(),
+            KeyChord::parse("G").unwrap()
+        );
+        assert_eq!(
+            KeyChord::parse("shift+tab").unwrap(),
+            KeyChord::parse("backtab").unwrap()
+        );
+        assert_eq!(KeyChord::parse("ctrl++").unwrap().to_string(), "Ctrl++");
+        assert_eq!(KeyChord::parse("+").unwrap().to_string(), "+");
+        assert_eq!(KeyChord::parse("PgDn").unwrap().to_string(), "PgDn");
+        assert_eq!(KeyChord::parse("f5").unwrap().to_string(), "F5");
+        assert!(KeyChord::parse("hyper+x").is_err());
+        assert!(KeyChord::parse("banana").is_err());
+    }
+
+    #[test]
+    fn test_uppercase_events_match_with_shift() {
+        let keymap = Keymap::<HomeCommand>::default();
+        assert_eq!(
+            keymap.command(&press(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            Some(HomeCommand::Bottom)
+        );
+        assert_eq!(
+            keymap.command(&press(KeyCode::Char('g'), KeyModifiers::NONE)),
+            Some(HomeCommand::Top)
+        );
+        assert_eq!(
+            keymap.command(&press(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        assert!(Keymap::<HomeCommand>::with_overrides(&BTreeMap::new()).is_ok());
+        assert!(Keymap::<DiffCommand>::with_overrides(&BTreeMap::new()).is_ok());
+        assert!(Keymap::<GridCommand>::with_overrides(&BTreeMap::new()).is_ok());
+        assert!(Keymap::<SettingsCommand>::with_overrides(&BTreeMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_override_replaces_default_keys() {
+        let keymap =
+            Keymap::<HomeCommand>::with_overrides(&overrides(&[("new_session", &["a"])])).unwrap();
+        assert_eq!(
+            keymap.command(&press(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Some(HomeCommand::NewSession)
+        );
+        assert_eq!(
+            keymap.command(&press(KeyCode::Char('n'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(keymap.label(HomeCommand::MoveDown), "j/↓");
+
+        let unbound = Keymap::<HomeCommand>::with_overrides(&overrides(&[("quit", &[])])).unwrap();
+        assert!(unbound
+            .help_sections()
+            .iter()
+            .all(|(_, entries)| entries.iter().all(|(_, desc)| *desc != "Quit")));
+    }
+
+    #[test]
+    fn test_conflicts_and_unknown_names_are_reported() {
+        let errors = Keymap::<HomeCommand>::with_overrides(&overrides(&[("new_session", &["d"])]))
+            .unwrap_err();
+        assert_eq!(errors, vec!["'d' is bound to both new_session and delete"]);
+
+        let errors = Keymap::<HomeCommand>::with_overrides(&overrides(&[
+            ("launch", &["x"]),
+            ("search", &["ctrl+banana"]),
+        ]))
+        .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("unknown command 'launch'"));
+        assert!(errors[1].starts_with("search:"));
+    }
+
+    #[test]
+    fn test_load_falls_back_per_view() {
+        let config = KeybindingsConfig {
+            home: overrides(&[("new_session", &["d"])]),
+            diff: overrides(&[("refresh", &["R"])]),
+            ..Default::default()
+        };
+        let (bindings, errors) = Keybindings::load(&config);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("[keybindings.home]"));
+        assert_eq!(
+            bindings
+                .home
+                .command(&press(KeyCode::Char('n'), KeyModifiers::NONE)),
+            Some(HomeCommand::NewSession)
+        );
+        assert_eq!(
+            bindings
+                .diff
+                .command(&press(KeyCode::Char('R'), KeyModifiers::SHIFT)),
+            Some(DiffCommand::Refresh)
+        );
+    }
+}
@@ -1,13 +1,19 @@
 //! Terminal User Interface module
 
 mod app;
+mod bulk_poller;
 mod components;
+mod container_poller;
 mod creation_poller;
 mod deletion_poller;
 pub mod dialogs;
 pub mod diff;
+mod git_status_poller;
 mod home;
+pub mod keybindings;
+mod preview_poller;
 pub mod settings;
+mod stats_poller;
 mod status_poller;
 mod styles;
 
@@ -0,0 +1,121 @@
+//! Background capture of session output for the grid view
+//!
+//! The grid shows many sessions at once, and capturing each pane takes a
+//! tmux round trip, so captures run on their own thread instead of during
+//! rendering like the single preview.
+
+use std::sync::mpsc;
+use std::thread;
+
+use crate::session::Instance;
+
+/// A session to capture, sized to the tile it is shown in
+pub struct PreviewRequest {
+    pub instance: Instance,
+    pub width: u16,
+    pub height: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewUpdate {
+    pub session_id: String,
+    pub content: String,
+    pub dimensions: (u16, u16),
+}
+
+/// Background thread that captures pane output without blocking the UI
+pub struct PreviewPoller {
+    request_tx: mpsc::Sender<Vec<PreviewRequest>>,
+    result_rx: mpsc::Receiver<Vec<PreviewUpdate>>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl PreviewPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<Vec<PreviewRequest>>();
+        let (result_tx, result_rx) = mpsc::channel::<Vec<PreviewUpdate>>();
+
+        let handle = thread::spawn(move || {
+            Self::polling_loop(request_rx, result_tx);
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn polling_loop(
+        request_rx: mpsc::Receiver<Vec<PreviewRequest>>,
+        result_tx: mpsc::Sender<Vec<PreviewUpdate>>,
+    ) {
+        while let Ok(requests) = request_rx.recv() {
+            let updates = requests.iter().map(Self::capture).collect();
+            if result_tx.send(updates).is_err() {
+                // Receiver dropped, exit the loop
+                break;
+            }
+        }
+    }
+
+    fn capture(request: &PreviewRequest) -> PreviewUpdate {
+        let content = request
+            .instance
+            .capture_output_with_size(request.height as usize, request.width, request.height)
+            .unwrap_or_default();
+        PreviewUpdate {
+            session_id: request.instance.id.clone(),
+            content,
+            dimensions: (request.width, request.height),
+        }
+    }
+
+    /// Request captures of the given sessions (non-blocking).
+    pub fn request_refresh(&self, requests: Vec<PreviewRequest>) {
+        let _ = self.request_tx.send(requests);
+    }
+
+    /// Try to receive captured output without blocking.
+    /// Returns None if no results are available yet.
+    pub fn try_recv_updates(&self) -> Option<Vec<PreviewUpdate>> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for PreviewPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sessions_without_pane_capture_empty() {
+        let poller = PreviewPoller::new();
+        let instance = Instance::new("Not Running", "/tmp/test-project");
+        let session_id = instance.id.clone();
+        poller.request_refresh(vec![PreviewRequest {
+            instance,
+            width: 40,
+            height: 10,
+        }]);
+
+        let updates = poller
+            .result_rx
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(
+            updates,
+            vec![PreviewUpdate {
+                session_id,
+                content: String::new(),
+                dimensions: (40, 10),
+            }]
+        );
+    }
+}
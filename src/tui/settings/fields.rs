@@ -2,12 +2,14 @@
 
 use std::collections::HashMap;
 
+use crate::docker::ContainerRuntime;
 use crate::session::{
-    validate_check_interval, Config, DefaultTerminalMode, ProfileConfig, TmuxMouseMode,
-    TmuxStatusBarMode,
+    validate_check_interval, Config, DefaultTerminalMode, NetworkMode, ProfileConfig,
+    TmuxMouseMode, TmuxStatusBarMode,
 };
 
 use super::SettingsScope;
+use crate::tui::styles::{available_themes, BUILTIN_THEMES};
 
 /// Categories of settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +19,7 @@ pub enum SettingsCategory {
     Sandbox,
     Tmux,
     Session,
+    Theme,
 }
 
 impl SettingsCategory {
@@ -27,6 +30,7 @@ impl SettingsCategory {
             Self::Sandbox => "Sandbox",
             Self::Tmux => "Tmux",
             Self::Session => "Session",
+            Self::Theme => "Theme",
         }
     }
 }
@@ -43,20 +47,27 @@ pub enum FieldKey {
     BareRepoPathTemplate,
     WorktreeAutoCleanup,
     DeleteBranchOnCleanup,
+    PullRequestRef,
     // Sandbox
     SandboxEnabledByDefault,
     YoloModeDefault,
     DefaultImage,
+    Runtime,
     Environment,
     EnvironmentValues,
     SandboxAutoCleanup,
     DefaultTerminalMode,
     VolumeIgnores,
+    NetworkMode,
+    NetworkAllowlist,
+    Ports,
     // Tmux
     StatusBar,
     Mouse,
     // Session
     DefaultTool,
+    // Theme
+    Theme,
 }
 
 /// Resolve a field value from global config and optional profile override.
@@ -110,6 +121,21 @@ fn set_or_clear_override<T, S, F>(
     }
 }
 
+fn runtime_from_index(selected: usize) -> ContainerRuntime {
+    match selected {
+        0 => ContainerRuntime::Docker,
+        _ => ContainerRuntime::Podman,
+    }
+}
+
+fn network_mode_from_index(selected: usize) -> NetworkMode {
+    match selected {
+        0 => NetworkMode::Full,
+        1 => NetworkMode::None,
+        _ => NetworkMode::Allowlist,
+    }
+}
+
 /// Value types for settings fields
 #[derive(Debug, Clone)]
 pub enum FieldValue {
@@ -161,6 +187,7 @@ pub fn build_fields_for_category(
         SettingsCategory::Sandbox => build_sandbox_fields(scope, global, profile),
         SettingsCategory::Tmux => build_tmux_fields(scope, global, profile),
         SettingsCategory::Session => build_session_fields(scope, global, profile),
+        SettingsCategory::Theme => build_theme_fields(scope, global, profile),
     }
 }
 
@@ -242,6 +269,11 @@ fn build_worktree_fields(
         global.worktree.delete_branch_on_cleanup,
         wt.and_then(|w| w.delete_branch_on_cleanup),
     );
+    let (pull_request_ref, o5) = resolve_value(
+        scope,
+        global.worktree.pull_request_ref.clone(),
+        wt.and_then(|w| w.pull_request_ref.clone()),
+    );
 
     vec![
         SettingField {
@@ -276,6 +308,14 @@ fn build_worktree_fields(
             category: SettingsCategory::Worktree,
             has_override: o4,
         },
+        SettingField {
+            key: FieldKey::PullRequestRef,
+            label: "Pull Request Ref",
+            description: "Remote ref fetched for pull request worktrees ({number})",
+            value: FieldValue::Text(pull_request_ref),
+            category: SettingsCategory::Worktree,
+            has_override: o5,
+        },
     ]
 }
 
@@ -335,10 +375,35 @@ fn build_sandbox_fields(
         sb.and_then(|s| s.volume_ignores.clone()),
     );
 
+    let (runtime, o_runtime) =
+        resolve_value(scope, global.sandbox.runtime, sb.and_then(|s| s.runtime));
+    let sb_network = sb.and_then(|s| s.network.as_ref());
+    let (network_mode, o8) = resolve_value(
+        scope,
+        global.sandbox.network.mode,
+        sb_network.and_then(|n| n.mode),
+    );
+    let (network_allowlist, o9) = resolve_value(
+        scope,
+        global.sandbox.network.allowlist.clone(),
+        sb_network.and_then(|n| n.allowlist.clone()),
+    );
+
+    let (ports, o10) = resolve_value(
+        scope,
+        global.sandbox.ports.clone(),
+        sb.and_then(|s| s.ports.clone()),
+    );
+
     let terminal_mode_selected = match default_terminal_mode {
         DefaultTerminalMode::Host => 0,
         DefaultTerminalMode::Container => 1,
     };
+    let network_mode_selected = match network_mode {
+        NetworkMode::Full => 0,
+        NetworkMode::None => 1,
+        NetworkMode::Allowlist => 2,
+    };
 
     vec![
         SettingField {
@@ -365,6 +430,20 @@ fn build_sandbox_fields(
             category: SettingsCategory::Sandbox,
             has_override: o3,
         },
+        SettingField {
+            key: FieldKey::Runtime,
+            label: "Container Runtime",
+            description: "Runtime for new sandboxes (existing ones keep theirs)",
+            value: FieldValue::Select {
+                selected: match runtime {
+                    ContainerRuntime::Docker => 0,
+                    ContainerRuntime::Podman => 1,
+                },
+                options: vec!["Docker".into(), "Podman".into()],
+            },
+            category: SettingsCategory::Sandbox,
+            has_override: o_runtime,
+        },
         SettingField {
             key: FieldKey::Environment,
             label: "Environment Variables",
@@ -376,7 +455,7 @@ fn build_sandbox_fields(
         SettingField {
             key: FieldKey::EnvironmentValues,
             label: "Environment Values",
-            description: "Custom KEY=VALUE env vars for sandbox. Use $VAR to reference host vars",
+            description: "Custom KEY=VALUE env vars for sandbox. Values may be $VAR, file:PATH, cmd:COMMAND or keyring:NAME",
             value: FieldValue::List(env_values_list),
             category: SettingsCategory::Sandbox,
             has_override: o_env_vals,
@@ -408,6 +487,33 @@ fn build_sandbox_fields(
             category: SettingsCategory::Sandbox,
             has_override: o7,
         },
+        SettingField {
+            key: FieldKey::NetworkMode,
+            label: "Network Mode",
+            description: "Network access for new containers: full, none, or allowlist only",
+            value: FieldValue::Select {
+                selected: network_mode_selected,
+                options: vec!["Full".into(), "None".into(), "Allowlist".into()],
+            },
+            category: SettingsCategory::Sandbox,
+            has_override: o8,
+        },
+        SettingField {
+            key: FieldKey::NetworkAllowlist,
+            label: "Network Allowlist",
+            description: "Hosts and CIDRs reachable in allowlist mode (e.g. *.npmjs.org)",
+            value: FieldValue::List(network_allowlist),
+            category: SettingsCategory::Sandbox,
+            has_override: o9,
+        },
+        SettingField {
+            key: FieldKey::Ports,
+            label: "Published Ports",
+            description: "Container ports to publish on 127.0.0.1 (e.g. 3000 or 8080:3000)",
+            value: FieldValue::List(ports),
+            category: SettingsCategory::Sandbox,
+            has_override: o10,
+        },
     ]
 }
 
@@ -509,6 +615,47 @@ fn build_session_fields(
     }]
 }
 
+/// Theme name as displayed, with the unset default spelled out
+fn theme_name(name: &str) -> String {
+    if name.is_empty() {
+        BUILTIN_THEMES[0].to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn build_theme_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let (name, has_override) = resolve_value(
+        scope,
+        global.theme.name.clone(),
+        profile.theme.as_ref().and_then(|t| t.name.clone()),
+    );
+    let name = theme_name(&name);
+
+    // Keep a configured theme selectable even if its file has gone missing
+    let mut options = available_themes();
+    let selected = match options.iter().position(|o| *o == name) {
+        Some(i) => i,
+        None => {
+            options.push(name);
+            options.len() - 1
+        }
+    };
+
+    vec![SettingField {
+        key: FieldKey::Theme,
+        label: "Theme",
+        description: "TUI colors. Enter opens a live preview; add your own in themes/<name>.toml",
+        value: FieldValue::Select { selected, options },
+        category: SettingsCategory::Theme,
+        has_override,
+    }]
+}
+
 /// Apply a field's value back to the appropriate config.
 /// For profile scope, if the value matches global, the override is removed.
 pub fn apply_field_to_config(
@@ -540,17 +687,30 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::DeleteBranchOnCleanup, FieldValue::Bool(v)) => {
             config.worktree.delete_branch_on_cleanup = *v
         }
+        (FieldKey::PullRequestRef, FieldValue::Text(v)) => {
+            config.worktree.pull_request_ref = v.clone()
+        }
         // Sandbox
         (FieldKey::SandboxEnabledByDefault, FieldValue::Bool(v)) => {
             config.sandbox.enabled_by_default = *v
         }
         (FieldKey::YoloModeDefault, FieldValue::Bool(v)) => config.sandbox.yolo_mode_default = *v,
         (FieldKey::DefaultImage, FieldValue::Text(v)) => config.sandbox.default_image = v.clone(),
+        (FieldKey::Runtime, FieldValue::Select { selected, .. }) => {
+            config.sandbox.runtime = runtime_from_index(*selected);
+        }
         (FieldKey::Environment, FieldValue::List(v)) => config.sandbox.environment = v.clone(),
         (FieldKey::EnvironmentValues, FieldValue::List(v)) => {
             config.sandbox.environment_values = parse_env_values_list(v);
         }
         (FieldKey::VolumeIgnores, FieldValue::List(v)) => config.sandbox.volume_ignores = v.clone(),
+        (FieldKey::NetworkMode, FieldValue::Select { selected, .. }) => {
+            config.sandbox.network.mode = network_mode_from_index(*selected);
+        }
+        (FieldKey::NetworkAllowlist, FieldValue::List(v)) => {
+            config.sandbox.network.allowlist = v.clone()
+        }
+        (FieldKey::Ports, FieldValue::List(v)) => config.sandbox.ports = v.clone(),
         (FieldKey::SandboxAutoCleanup, FieldValue::Bool(v)) => config.sandbox.auto_cleanup = *v,
         (FieldKey::DefaultTerminalMode, FieldValue::Select { selected, .. }) => {
             config.sandbox.default_terminal_mode = match selected {
@@ -584,6 +744,12 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
                 _ => None, // Auto
             };
         }
+        // Theme
+        (FieldKey::Theme, FieldValue::Select { selected, options }) => {
+            if let Some(name) = options.get(*selected) {
+                config.theme.name = name.clone();
+            }
+        }
         _ => {}
     }
 }
@@ -650,6 +816,14 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 |s, val| s.delete_branch_on_cleanup = val,
             );
         }
+        (FieldKey::PullRequestRef, FieldValue::Text(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.worktree.pull_request_ref,
+                &mut config.worktree,
+                |s, val| s.pull_request_ref = val,
+            );
+        }
         // Sandbox
         (FieldKey::SandboxEnabledByDefault, FieldValue::Bool(v)) => {
             set_or_clear_override(
@@ -700,6 +874,38 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 |s, val| s.volume_ignores = val,
             );
         }
+        (FieldKey::Runtime, FieldValue::Select { selected, .. }) => {
+            set_or_clear_override(
+                runtime_from_index(*selected),
+                &global.sandbox.runtime,
+                &mut config.sandbox,
+                |s, val| s.runtime = val,
+            );
+        }
+        (FieldKey::NetworkMode, FieldValue::Select { selected, .. }) => {
+            set_or_clear_override(
+                network_mode_from_index(*selected),
+                &global.sandbox.network.mode,
+                &mut config.sandbox,
+                |s, val| s.network.get_or_insert_with(Default::default).mode = val,
+            );
+        }
+        (FieldKey::NetworkAllowlist, FieldValue::List(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.sandbox.network.allowlist,
+                &mut config.sandbox,
+                |s, val| s.network.get_or_insert_with(Default::default).allowlist = val,
+            );
+        }
+        (FieldKey::Ports, FieldValue::List(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.sandbox.ports,
+                &mut config.sandbox,
+                |s, val| s.ports = val,
+            );
+        }
         (FieldKey::SandboxAutoCleanup, FieldValue::Bool(v)) => {
             set_or_clear_override(
                 *v,
@@ -764,6 +970,17 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 session.default_tool = tool;
             }
         }
+        // Theme
+        (FieldKey::Theme, FieldValue::Select { selected, options }) => {
+            if let Some(name) = options.get(*selected) {
+                set_or_clear_override(
+                    name.clone(),
+                    &theme_name(&global.theme.name),
+                    &mut config.theme,
+                    |t, val| t.name = val,
+                );
+            }
+        }
         _ => {}
     }
 }
@@ -925,4 +1142,38 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_theme_field_round_trips_through_profile() {
+        let global = Config::default();
+        let mut profile = ProfileConfig::default();
+
+        let mut fields = build_fields_for_category(
+            SettingsCategory::Theme,
+            SettingsScope::Profile,
+            &global,
+            &profile,
+        );
+        let field = &mut fields[0];
+        let FieldValue::Select { selected, options } = &mut field.value else {
+            panic!("Theme should be a Select field");
+        };
+        assert_eq!(options[*selected], "phosphor");
+        assert!(!field.has_override);
+
+        *selected = options.iter().position(|o| o == "light").unwrap();
+        apply_field_to_profile(field, &global, &mut profile);
+        assert_eq!(
+            profile.theme.as_ref().and_then(|t| t.name.as_deref()),
+            Some("light")
+        );
+
+        // Selecting the global theme again clears the override
+        let FieldValue::Select { selected, .. } = &mut field.value else {
+            unreachable!();
+        };
+        *selected = 0;
+        apply_field_to_profile(field, &global, &mut profile);
+        assert_eq!(profile.theme.as_ref().and_then(|t| t.name.as_deref()), None);
+    }
 }
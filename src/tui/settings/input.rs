@@ -1,9 +1,12 @@
 //! Input handling for the settings view
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
+use crate::tui::components::mouse::hit;
+use crate::tui::keybindings::SettingsCommand;
+
 use super::{FieldKey, FieldValue, ListEditState, SettingsFocus, SettingsScope, SettingsView};
 
 /// Result of handling a key event in the settings view
@@ -31,10 +34,23 @@ impl SettingsView {
             return self.handle_list_edit_key(key);
         }
 
+        // Handle theme picker
+        if self.theme_picker.is_some() {
+            return self.handle_theme_picker_key(key);
+        }
+
         // Normal mode
-        match (key.code, key.modifiers) {
+        let Some(command) = self.keymap.command(&key) else {
+            return SettingsAction::Continue;
+        };
+        self.run_command(command)
+    }
+
+    /// Carry out a command, from its key or the command palette
+    pub fn run_command(&mut self, command: SettingsCommand) -> SettingsAction {
+        match command {
             // Save
-            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+            SettingsCommand::Save => {
                 if let Err(e) = self.save() {
                     self.error_message = Some(format!("Failed to save: {}", e));
                 }
@@ -42,7 +58,7 @@ impl SettingsView {
             }
 
             // Close
-            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => {
+            SettingsCommand::Close => {
                 if self.has_changes {
                     SettingsAction::UnsavedChangesWarning
                 } else {
@@ -51,7 +67,7 @@ impl SettingsView {
             }
 
             // Switch scope tabs
-            (KeyCode::Tab, _) | (KeyCode::BackTab, _) => {
+            SettingsCommand::SwitchScope => {
                 self.scope = match self.scope {
                     SettingsScope::Global => SettingsScope::Profile,
                     SettingsScope::Profile => SettingsScope::Global,
@@ -61,17 +77,17 @@ impl SettingsView {
             }
 
             // Switch focus between categories and fields
-            (KeyCode::Left, _) | (KeyCode::Char('h'), _) => {
+            SettingsCommand::FocusCategories => {
                 self.focus = SettingsFocus::Categories;
                 SettingsAction::Continue
             }
-            (KeyCode::Right, _) | (KeyCode::Char('l'), _) => {
+            SettingsCommand::FocusFields => {
                 self.focus = SettingsFocus::Fields;
                 SettingsAction::Continue
             }
 
             // Navigate up/down
-            (KeyCode::Up, _) | (KeyCode::Char('k'), _) => {
+            SettingsCommand::Up => {
                 match self.focus {
                     SettingsFocus::Categories => {
                         if self.selected_category > 0 {
@@ -88,7 +104,7 @@ impl SettingsView {
                 }
                 SettingsAction::Continue
             }
-            (KeyCode::Down, _) | (KeyCode::Char('j'), _) => {
+            SettingsCommand::Down => {
                 match self.focus {
                     SettingsFocus::Categories => {
                         if self.selected_category < self.categories.len().saturating_sub(1) {
@@ -107,7 +123,7 @@ impl SettingsView {
             }
 
             // Toggle boolean / edit field
-            (KeyCode::Char(' '), _) => {
+            SettingsCommand::Toggle => {
                 if self.focus == SettingsFocus::Fields && !self.fields.is_empty() {
                     let field = &mut self.fields[self.selected_field];
                     if let FieldValue::Bool(ref mut value) = field.value {
@@ -119,40 +135,9 @@ impl SettingsView {
             }
 
             // Enter - edit field or expand list
-            (KeyCode::Enter, _) => {
+            SettingsCommand::Edit => {
                 if self.focus == SettingsFocus::Fields && !self.fields.is_empty() {
-                    let field = &self.fields[self.selected_field];
-                    match &field.value {
-                        FieldValue::Bool(value) => {
-                            // Toggle boolean on Enter too
-                            let new_value = !value;
-                            self.fields[self.selected_field].value = FieldValue::Bool(new_value);
-                            self.apply_field_to_config(self.selected_field);
-                        }
-                        FieldValue::Text(value) => {
-                            self.editing_input = Some(Input::new(value.clone()));
-                        }
-                        FieldValue::OptionalText(value) => {
-                            self.editing_input =
-                                Some(Input::new(value.clone().unwrap_or_default()));
-                        }
-                        FieldValue::Number(value) => {
-                            self.editing_input = Some(Input::new(value.to_string()));
-                        }
-                        FieldValue::Select { selected, options } => {
-                            // Cycle through options
-                            let new_selected = (*selected + 1) % options.len();
-                            self.fields[self.selected_field].value = FieldValue::Select {
-                                selected: new_selected,
-                                options: options.clone(),
-                            };
-                            self.apply_field_to_config(self.selected_field);
-                        }
-                        FieldValue::List(_) => {
-                            // Expand list for editing
-                            self.list_edit_state = Some(ListEditState::default());
-                        }
-                    }
+                    self.activate_field();
                 } else if self.focus == SettingsFocus::Categories {
                     // Move to fields when pressing Enter on a category
                     self.focus = SettingsFocus::Fields;
@@ -161,19 +146,182 @@ impl SettingsView {
             }
 
             // Reset field to default (clear profile override)
-            (KeyCode::Char('r'), _) => {
+            SettingsCommand::Reset => {
                 if self.scope == SettingsScope::Profile
                     && self.focus == SettingsFocus::Fields
                     && !self.fields.is_empty()
                 {
                     self.clear_profile_override(self.selected_field);
                     self.rebuild_fields();
+                    if let Some(FieldValue::Select { selected, options }) = self
+                        .fields
+                        .get(self.selected_field)
+                        .filter(|f| f.key == FieldKey::Theme)
+                        .map(|f| f.value.clone())
+                    {
+                        self.preview_theme_named(&options[selected]);
+                    }
                 }
                 SettingsAction::Continue
             }
 
-            _ => SettingsAction::Continue,
+            // The home screen opens the palette over the settings
+            SettingsCommand::CommandPalette => SettingsAction::Continue,
+        }
+    }
+
+    /// Handle a mouse event: a click selects a category or a field, and
+    /// edits a field that was already selected (checkboxes toggle on the
+    /// first click); the wheel scrolls the fields or moves between
+    /// categories
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.is_editing() {
+            return;
+        }
+        let over_categories = hit(self.categories_area, &mouse);
+        let over_fields = hit(self.fields_area, &mouse);
+        match mouse.kind {
+            MouseEventKind::ScrollUp if over_categories => {
+                self.select_category(self.selected_category.saturating_sub(1));
+            }
+            MouseEventKind::ScrollDown if over_categories => {
+                self.select_category(self.selected_category + 1);
+            }
+            MouseEventKind::ScrollUp if over_fields => {
+                self.fields_scroll_offset = self.fields_scroll_offset.saturating_sub(3);
+            }
+            MouseEventKind::ScrollDown if over_fields => {
+                let max_scroll = self
+                    .fields_content_height()
+                    .saturating_sub(self.fields_viewport_height);
+                self.fields_scroll_offset = (self.fields_scroll_offset + 3).min(max_scroll);
+            }
+            MouseEventKind::Down(MouseButton::Left) if over_categories => {
+                self.success_message = None;
+                self.focus = SettingsFocus::Categories;
+                self.select_category((mouse.row - self.categories_area.y) as usize);
+            }
+            MouseEventKind::Down(MouseButton::Left) if over_fields => {
+                self.success_message = None;
+                let Some(index) = self.field_at(mouse.row - self.fields_area.y) else {
+                    return;
+                };
+                let reselected =
+                    self.focus == SettingsFocus::Fields && index == self.selected_field;
+                self.focus = SettingsFocus::Fields;
+                self.selected_field = index;
+                self.ensure_field_visible(self.fields_viewport_height);
+                if reselected || matches!(self.fields[index].value, FieldValue::Bool(_)) {
+                    self.activate_field();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn select_category(&mut self, index: usize) {
+        if index < self.categories.len() && index != self.selected_category {
+            self.selected_category = index;
+            self.rebuild_fields();
+        }
+    }
+
+    /// Field shown at a row of the fields panel, counted from its top
+    fn field_at(&self, row: u16) -> Option<usize> {
+        if row >= self.fields_viewport_height {
+            return None;
+        }
+        let y = row + self.fields_scroll_offset;
+        let mut top = 0u16;
+        for (i, field) in self.fields.iter().enumerate() {
+            let bottom = top + self.field_height(field, i);
+            if (top..bottom).contains(&y) {
+                return Some(i);
+            }
+            top = bottom + 1; // +1 spacing
+        }
+        None
+    }
+
+    /// Edit the selected field: toggle or cycle it in place, or open its
+    /// editor
+    fn activate_field(&mut self) {
+        let field = &self.fields[self.selected_field];
+        match &field.value {
+            FieldValue::Select { selected, .. } if field.key == FieldKey::Theme => {
+                self.theme_picker = Some(*selected);
+            }
+            FieldValue::Bool(value) => {
+                // Toggle boolean on Enter too
+                let new_value = !value;
+                self.fields[self.selected_field].value = FieldValue::Bool(new_value);
+                self.apply_field_to_config(self.selected_field);
+            }
+            FieldValue::Text(value) => {
+                self.editing_input = Some(Input::new(value.clone()));
+            }
+            FieldValue::OptionalText(value) => {
+                self.editing_input = Some(Input::new(value.clone().unwrap_or_default()));
+            }
+            FieldValue::Number(value) => {
+                self.editing_input = Some(Input::new(value.to_string()));
+            }
+            FieldValue::Select { selected, options } => {
+                // Cycle through options
+                let new_selected = (*selected + 1) % options.len();
+                self.fields[self.selected_field].value = FieldValue::Select {
+                    selected: new_selected,
+                    options: options.clone(),
+                };
+                self.apply_field_to_config(self.selected_field);
+            }
+            FieldValue::List(_) => {
+                // Expand list for editing
+                self.list_edit_state = Some(ListEditState::default());
+            }
+        }
+    }
+
+    fn handle_theme_picker_key(&mut self, key: KeyEvent) -> SettingsAction {
+        let Some(FieldValue::Select { selected, options }) = self
+            .fields
+            .get(self.selected_field)
+            .map(|field| field.value.clone())
+        else {
+            self.theme_picker = None;
+            return SettingsAction::Continue;
+        };
+        let highlighted = self.theme_picker.unwrap_or(selected);
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                let index = highlighted.checked_sub(1).unwrap_or(options.len() - 1);
+                self.theme_picker = Some(index);
+                self.preview_theme_named(&options[index]);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let index = (highlighted + 1) % options.len();
+                self.theme_picker = Some(index);
+                self.preview_theme_named(&options[index]);
+            }
+            KeyCode::Enter => {
+                self.theme_picker = None;
+                if highlighted != selected {
+                    self.fields[self.selected_field].value = FieldValue::Select {
+                        selected: highlighted,
+                        options: options.clone(),
+                    };
+                    self.apply_field_to_config(self.selected_field);
+                }
+                self.preview_theme_named(&options[highlighted]);
+            }
+            KeyCode::Esc => {
+                self.theme_picker = None;
+                self.preview_theme_named(&options[selected]);
+            }
+            _ => {}
         }
+        SettingsAction::Continue
     }
 
     fn handle_text_edit_key(&mut self, key: KeyEvent) -> SettingsAction {
@@ -393,6 +541,11 @@ impl SettingsView {
                     w.delete_branch_on_cleanup = None;
                 }
             }
+            FieldKey::PullRequestRef => {
+                if let Some(ref mut w) = self.profile_config.worktree {
+                    w.pull_request_ref = None;
+                }
+            }
             // Sandbox
             FieldKey::DefaultImage => {
                 if let Some(ref mut s) = self.profile_config.sandbox {
@@ -431,6 +584,12 @@ impl SettingsView {
                     s.default_tool = None;
                 }
             }
+            // Theme
+            FieldKey::Theme => {
+                if let Some(ref mut t) = self.profile_config.theme {
+                    t.name = None;
+                }
+            }
             // New sandbox settings
             FieldKey::SandboxEnabledByDefault => {
                 if let Some(ref mut s) = self.profile_config.sandbox {
@@ -452,6 +611,36 @@ impl SettingsView {
                     s.volume_ignores = None;
                 }
             }
+            FieldKey::Runtime => {
+                if let Some(ref mut s) = self.profile_config.sandbox {
+                    s.runtime = None;
+                }
+            }
+            FieldKey::NetworkMode => {
+                if let Some(n) = self
+                    .profile_config
+                    .sandbox
+                    .as_mut()
+                    .and_then(|s| s.network.as_mut())
+                {
+                    n.mode = None;
+                }
+            }
+            FieldKey::NetworkAllowlist => {
+                if let Some(n) = self
+                    .profile_config
+                    .sandbox
+                    .as_mut()
+                    .and_then(|s| s.network.as_mut())
+                {
+                    n.allowlist = None;
+                }
+            }
+            FieldKey::Ports => {
+                if let Some(ref mut s) = self.profile_config.sandbox {
+                    s.ports = None;
+                }
+            }
         }
 
         self.has_changes = true;
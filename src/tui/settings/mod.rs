@@ -4,11 +4,14 @@ mod fields;
 mod input;
 mod render;
 
+use ratatui::layout::Rect;
 use tui_input::Input;
 
 use crate::session::{
     load_profile_config, save_config, save_profile_config, Config, ProfileConfig,
 };
+use crate::tui::keybindings::{Keymap, SettingsCommand};
+use crate::tui::styles::Theme;
 
 pub use fields::{FieldKey, FieldValue, SettingField, SettingsCategory};
 pub use input::SettingsAction;
@@ -78,6 +81,10 @@ pub struct SettingsView {
     /// Last known viewport height for the fields panel (set during render)
     pub(super) fields_viewport_height: u16,
 
+    /// Inside of the category list and the fields panel (set during render)
+    pub(super) categories_area: Rect,
+    pub(super) fields_area: Rect,
+
     /// Whether there are unsaved changes
     pub(super) has_changes: bool,
 
@@ -86,10 +93,19 @@ pub struct SettingsView {
 
     /// Success message to display
     pub(super) success_message: Option<String>,
+
+    /// Highlighted option while the theme picker is open
+    pub(super) theme_picker: Option<usize>,
+
+    /// Theme to render with while settings are open, once one is picked
+    pub(super) preview_theme: Option<Theme>,
+
+    /// Active key bindings
+    pub(super) keymap: Keymap<SettingsCommand>,
 }
 
 impl SettingsView {
-    pub fn new(profile: &str) -> anyhow::Result<Self> {
+    pub fn new(profile: &str, keymap: Keymap<SettingsCommand>) -> anyhow::Result<Self> {
         let global_config = Config::load()?;
         let profile_config = load_profile_config(profile)?;
 
@@ -99,6 +115,7 @@ impl SettingsView {
             SettingsCategory::Worktree,
             SettingsCategory::Updates,
             SettingsCategory::Tmux,
+            SettingsCategory::Theme,
         ];
 
         let mut view = Self {
@@ -115,9 +132,14 @@ impl SettingsView {
             list_edit_state: None,
             fields_scroll_offset: 0,
             fields_viewport_height: 0,
+            categories_area: Rect::default(),
+            fields_area: Rect::default(),
             has_changes: false,
             error_message: None,
             success_message: None,
+            theme_picker: None,
+            preview_theme: None,
+            keymap,
         };
 
         view.rebuild_fields();
@@ -215,6 +237,24 @@ impl SettingsView {
 
     /// Check if currently in an editing state (text field, list, etc.)
     pub fn is_editing(&self) -> bool {
-        self.editing_input.is_some() || self.list_edit_state.is_some()
+        self.editing_input.is_some()
+            || self.list_edit_state.is_some()
+            || self.theme_picker.is_some()
+    }
+
+    pub fn preview_theme(&self) -> Option<&Theme> {
+        self.preview_theme.as_ref()
+    }
+
+    /// Preview the theme named `name`, keeping the current one if it fails
+    /// to load.
+    pub(super) fn preview_theme_named(&mut self, name: &str) {
+        match Theme::load(name) {
+            Ok(theme) => {
+                self.preview_theme = Some(theme);
+                self.error_message = None;
+            }
+            Err(e) => self.error_message = Some(format!("{:#}", e)),
+        }
     }
 }
@@ -12,7 +12,9 @@ use ratatui::{
 };
 use tui_input::Input;
 
-use super::{FieldValue, SettingsFocus, SettingsScope, SettingsView};
+use super::{FieldKey, FieldValue, SettingsFocus, SettingsScope, SettingsView};
+use crate::session::secrets;
+use crate::tui::keybindings::SettingsCommand;
 use crate::tui::styles::Theme;
 
 impl SettingsView {
@@ -92,7 +94,7 @@ impl SettingsView {
         self.render_fields(frame, layout[1], theme);
     }
 
-    fn render_categories(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn render_categories(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let is_focused = self.focus == SettingsFocus::Categories;
 
         let border_style = if is_focused {
@@ -107,6 +109,7 @@ impl SettingsView {
             .padding(Padding::horizontal(1));
 
         let inner = block.inner(area);
+        self.categories_area = inner;
         frame.render_widget(block, area);
 
         let items: Vec<ListItem> = self
@@ -155,6 +158,7 @@ impl SettingsView {
             .padding(Padding::new(1, 1, 0, 0));
 
         let inner = block.inner(area);
+        self.fields_area = inner;
         frame.render_widget(block, area);
 
         if self.fields.is_empty() {
@@ -170,14 +174,7 @@ impl SettingsView {
         let fields_viewport_height = inner.height.saturating_sub(message_height);
         self.fields_viewport_height = fields_viewport_height;
 
-        // Calculate total content height
-        let mut total_content_height = 0u16;
-        for (i, field) in self.fields.iter().enumerate() {
-            if i > 0 {
-                total_content_height += 1; // spacing between fields
-            }
-            total_content_height += self.field_height(field, i);
-        }
+        let total_content_height = self.fields_content_height();
 
         let scroll_offset = self.fields_scroll_offset;
 
@@ -257,6 +254,18 @@ impl SettingsView {
         }
     }
 
+    /// Height of all fields with the spacing between them
+    pub(super) fn fields_content_height(&self) -> u16 {
+        let mut total = 0u16;
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                total += 1; // spacing between fields
+            }
+            total += self.field_height(field, i);
+        }
+        total
+    }
+
     pub(super) fn field_height(&self, field: &super::SettingField, index: usize) -> u16 {
         match &field.value {
             FieldValue::List(items) => {
@@ -267,6 +276,12 @@ impl SettingsView {
                     1 + 1 + 1 // Label + description + summary
                 }
             }
+            FieldValue::Select { options, .. }
+                if self.theme_picker.is_some() && index == self.selected_field =>
+            {
+                // label + description + options
+                1 + 1 + options.len() as u16
+            }
             _ => 1 + 1 + 1, // Label + description + value
         }
     }
@@ -332,9 +347,18 @@ impl SettingsView {
             FieldValue::Number(value) => {
                 self.render_number_field(frame, value_area, *value, index, is_selected, theme);
             }
+            FieldValue::Select { options, .. }
+                if self.theme_picker.is_some() && index == self.selected_field =>
+            {
+                self.render_theme_picker(frame, value_area, options, theme);
+            }
             FieldValue::Select { selected, options } => {
                 self.render_select_field(frame, value_area, *selected, options, is_selected, theme);
             }
+            FieldValue::List(items) if field.key == FieldKey::EnvironmentValues => {
+                let masked: Vec<String> = items.iter().map(|i| secrets::mask_entry(i)).collect();
+                self.render_list_field(frame, value_area, &masked, index, is_selected, theme);
+            }
             FieldValue::List(items) => {
                 self.render_list_field(frame, value_area, items, index, is_selected, theme);
             }
@@ -528,6 +552,42 @@ impl SettingsView {
         );
     }
 
+    fn render_theme_picker(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        options: &[String],
+        theme: &Theme,
+    ) {
+        let highlighted = self.theme_picker.unwrap_or_default();
+        for (i, option) in options.iter().enumerate() {
+            let item_y = area.y + 1 + i as u16;
+            if item_y >= area.y + area.height {
+                break;
+            }
+            let item_area = Rect {
+                y: item_y,
+                height: 1,
+                ..area
+            };
+
+            let (prefix, style) = if i == highlighted {
+                (
+                    "> ",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default().fg(theme.dimmed))
+            };
+            frame.render_widget(
+                Paragraph::new(format!("{}{}", prefix, option)).style(style),
+                item_area,
+            );
+        }
+    }
+
     fn render_list_field(
         &self,
         frame: &mut Frame,
@@ -653,8 +713,20 @@ impl SettingsView {
             "Enter: confirm | Esc: cancel"
         } else if self.list_edit_state.is_some() {
             "a: add | d: delete | Enter: edit | Esc: close list"
+        } else if self.theme_picker.is_some() {
+            "j/k: preview | Enter: select | Esc: cancel"
         } else {
-            "Tab: switch scope | Arrow keys: navigate | Enter: edit | Space: toggle | Ctrl+s: save | Esc: close"
+            let keys = &self.keymap;
+            &format!(
+                "{}: switch scope | {}/{}: navigate | {}: edit | {}: toggle | {}: save | {}: close",
+                keys.short_label(SettingsCommand::SwitchScope),
+                keys.short_label(SettingsCommand::Up),
+                keys.short_label(SettingsCommand::Down),
+                keys.short_label(SettingsCommand::Edit),
+                keys.short_label(SettingsCommand::Toggle),
+                keys.short_label(SettingsCommand::Save),
+                keys.short_label(SettingsCommand::Close),
+            )
         };
 
         let help = Paragraph::new(help_text)
@@ -0,0 +1,72 @@
+//! Background resource usage polling for sandbox containers
+//!
+//! `stats --no-stream` samples for about a second before printing, so it
+//! runs on its own thread like the git status poller.
+
+use std::sync::mpsc;
+use std::thread;
+
+use crate::docker::{stats, ContainerRuntime, ContainerStats};
+
+/// Background thread that collects container stats without blocking the UI
+pub struct StatsPoller {
+    request_tx: mpsc::Sender<Vec<ContainerRuntime>>,
+    result_rx: mpsc::Receiver<Vec<ContainerStats>>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl StatsPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<Vec<ContainerRuntime>>();
+        let (result_tx, result_rx) = mpsc::channel::<Vec<ContainerStats>>();
+
+        let handle = thread::spawn(move || {
+            Self::polling_loop(request_rx, result_tx);
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn polling_loop(
+        request_rx: mpsc::Receiver<Vec<ContainerRuntime>>,
+        result_tx: mpsc::Sender<Vec<ContainerStats>>,
+    ) {
+        while let Ok(runtimes) = request_rx.recv() {
+            let mut all_stats = Vec::new();
+            for runtime in runtimes {
+                match stats::collect_sandbox_stats(runtime) {
+                    Ok(stats) => all_stats.extend(stats),
+                    Err(e) => {
+                        tracing::debug!("Failed to collect {} stats: {}", runtime.display_name(), e)
+                    }
+                }
+            }
+
+            if result_tx.send(all_stats).is_err() {
+                // Receiver dropped, exit the loop
+                break;
+            }
+        }
+    }
+
+    /// Request stats for the containers of the given runtimes (non-blocking).
+    pub fn request_refresh(&self, runtimes: Vec<ContainerRuntime>) {
+        let _ = self.request_tx.send(runtimes);
+    }
+
+    /// Try to receive collected stats without blocking.
+    /// Returns None if no results are available yet.
+    pub fn try_recv_updates(&self) -> Option<Vec<ContainerStats>> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for StatsPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
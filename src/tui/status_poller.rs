@@ -7,6 +7,7 @@ use std::sync::mpsc;
 use std::thread;
 
 use crate::session::{Instance, Status};
+use crate::tmux::WaitingReason;
 
 /// Result of a status check for a single session
 #[derive(Debug)]
@@ -14,6 +15,7 @@ pub struct StatusUpdate {
     pub id: String,
     pub status: Status,
     pub last_error: Option<String>,
+    pub waiting_reason: Option<WaitingReason>,
 }
 
 /// Background thread that polls session status without blocking the UI
@@ -55,6 +57,7 @@ impl StatusPoller {
                         id: inst.id,
                         status: inst.status,
                         last_error: inst.last_error,
+                        waiting_reason: inst.waiting_reason,
                     }
                 })
                 .collect();
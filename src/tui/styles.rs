@@ -1,7 +1,19 @@
 //! TUI theme and styling
+//!
+//! Themes are selected with `[theme] name` in the config. The name is either
+//! one of [`BUILTIN_THEMES`] or a file in the app's `themes/` directory:
+//! `<name>.toml` assigning every `Theme` field a color, written as `#rrggbb`,
+//! an ANSI color name (`red`, `lightblue`, `darkgray`, ...) or a palette index.
 
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
 use ratatui::style::Color;
 
+/// Built-in theme names, in the order the theme picker lists them
+pub const BUILTIN_THEMES: &[&str] = &["phosphor", "tokyo-night", "dracula", "light"];
+
 #[derive(Debug, Clone)]
 pub struct Theme {
     // Background and borders
@@ -62,7 +74,6 @@ impl Theme {
         }
     }
 
-    #[allow(dead_code)]
     pub fn tokyo_night() -> Self {
         Self {
             background: Color::Rgb(26, 27, 38),
@@ -87,4 +98,212 @@ impl Theme {
             accent: Color::Rgb(122, 162, 247),
         }
     }
+
+    pub fn dracula() -> Self {
+        Self {
+            background: Color::Rgb(40, 42, 54),
+            border: Color::Rgb(68, 71, 90),
+            terminal_border: Color::Rgb(98, 114, 164),
+            selection: Color::Rgb(52, 55, 70),
+            session_selection: Color::Rgb(68, 71, 90),
+
+            title: Color::Rgb(189, 147, 249),
+            text: Color::Rgb(248, 248, 242),
+            dimmed: Color::Rgb(98, 114, 164),
+            hint: Color::Rgb(139, 153, 204),
+
+            running: Color::Rgb(80, 250, 123),
+            waiting: Color::Rgb(241, 250, 140),
+            idle: Color::Rgb(98, 114, 164),
+            error: Color::Rgb(255, 85, 85),
+            terminal_active: Color::Rgb(139, 233, 253),
+
+            group: Color::Rgb(255, 121, 198),
+            search: Color::Rgb(139, 233, 253),
+            accent: Color::Rgb(189, 147, 249),
+        }
+    }
+
+    /// For terminals with a light background
+    pub fn light() -> Self {
+        Self {
+            background: Color::Rgb(250, 250, 248),
+            border: Color::Rgb(190, 195, 200),
+            terminal_border: Color::Rgb(70, 130, 180),
+            selection: Color::Rgb(228, 232, 236),
+            session_selection: Color::Rgb(212, 220, 228),
+
+            title: Color::Rgb(20, 110, 60),
+            text: Color::Rgb(40, 44, 52),
+            dimmed: Color::Rgb(120, 126, 136),
+            hint: Color::Rgb(90, 100, 112),
+
+            running: Color::Rgb(0, 135, 90),
+            waiting: Color::Rgb(176, 104, 0),
+            idle: Color::Rgb(130, 136, 142),
+            error: Color::Rgb(200, 40, 40),
+            terminal_active: Color::Rgb(40, 90, 200),
+
+            group: Color::Rgb(110, 60, 170),
+            search: Color::Rgb(0, 110, 150),
+            accent: Color::Rgb(20, 110, 60),
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "phosphor" => Some(Self::phosphor()),
+            "tokyo-night" => Some(Self::tokyo_night()),
+            "dracula" => Some(Self::dracula()),
+            "light" => Some(Self::light()),
+            _ => None,
+        }
+    }
+
+    /// Load a built-in or user theme. An empty name is the default theme.
+    pub fn load(name: &str) -> Result<Self> {
+        if name.is_empty() {
+            return Ok(Self::default());
+        }
+        if let Some(theme) = Self::builtin(name) {
+            return Ok(theme);
+        }
+        let path = themes_dir()?.join(format!("{}.toml", name));
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Unknown theme '{}' ({} not found)", name, path.display()))?;
+        Self::from_toml(&contents).with_context(|| format!("Invalid theme {}", path.display()))
+    }
+
+    /// Like [`Theme::load`], falling back to the default theme with a warning.
+    pub fn load_or_default(name: &str) -> Self {
+        Self::load(name).unwrap_or_else(|e| {
+            tracing::warn!("{:#}", e);
+            Self::default()
+        })
+    }
+
+    /// Parse a theme file, which must set every color and nothing else.
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(contents)?;
+        let mut theme = Self::default();
+        for (name, color) in theme.colors_mut() {
+            let value = table
+                .remove(name)
+                .with_context(|| format!("Missing color '{}'", name))?;
+            let Some(value) = value.as_str() else {
+                bail!("Color '{}' must be a string", name);
+            };
+            *color = Color::from_str(value).map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid color '{}' for '{}': use #rrggbb or an ANSI color name",
+                    value,
+                    name
+                )
+            })?;
+        }
+        if let Some(unknown) = table.keys().next() {
+            bail!("Unknown color '{}'", unknown);
+        }
+        Ok(theme)
+    }
+
+    fn colors_mut(&mut self) -> [(&'static str, &mut Color); 17] {
+        [
+            ("background", &mut self.background),
+            ("border", &mut self.border),
+            ("terminal_border", &mut self.terminal_border),
+            ("selection", &mut self.selection),
+            ("session_selection", &mut self.session_selection),
+            ("title", &mut self.title),
+            ("text", &mut self.text),
+            ("dimmed", &mut self.dimmed),
+            ("hint", &mut self.hint),
+            ("running", &mut self.running),
+            ("waiting", &mut self.waiting),
+            ("idle", &mut self.idle),
+            ("error", &mut self.error),
+            ("terminal_active", &mut self.terminal_active),
+            ("group", &mut self.group),
+            ("search", &mut self.search),
+            ("accent", &mut self.accent),
+        ]
+    }
+}
+
+/// Directory holding user theme files
+pub fn themes_dir() -> Result<PathBuf> {
+    Ok(crate::session::get_app_dir()?.join("themes"))
+}
+
+/// Built-in themes followed by user themes, sorted by name.
+pub fn available_themes() -> Vec<String> {
+    let mut user: Vec<String> = themes_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                return None;
+            }
+            path.file_stem().and_then(|s| s.to_str()).map(String::from)
+        })
+        .filter(|name| !BUILTIN_THEMES.contains(&name.as_str()))
+        .collect();
+    user.sort();
+
+    BUILTIN_THEMES
+        .iter()
+        .map(|name| name.to_string())
+        .chain(user)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme_file(color: &str) -> String {
+        Theme::default()
+            .colors_mut()
+            .iter()
+            .map(|(name, _)| format!("{} = \"{}\"\n", name, color))
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_themes_load() {
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).is_some(), "missing builtin {}", name);
+            assert!(Theme::load(name).is_ok());
+        }
+        assert_eq!(Theme::load("").unwrap().title, Theme::phosphor().title);
+    }
+
+    #[test]
+    fn test_from_toml_accepts_hex_and_ansi() {
+        let theme = Theme::from_toml(&theme_file("#0a0b0c")).unwrap();
+        assert_eq!(theme.accent, Color::Rgb(10, 11, 12));
+
+        let theme = Theme::from_toml(&theme_file("lightblue")).unwrap();
+        assert_eq!(theme.text, Color::LightBlue);
+    }
+
+    #[test]
+    fn test_from_toml_requires_every_color() {
+        let partial = theme_file("red").replace("accent = \"red\"\n", "");
+        let err = Theme::from_toml(&partial).unwrap_err().to_string();
+        assert!(err.contains("accent"), "{}", err);
+
+        let extra = format!("{}bogus = \"red\"\n", theme_file("red"));
+        let err = Theme::from_toml(&extra).unwrap_err().to_string();
+        assert!(err.contains("bogus"), "{}", err);
+
+        let err = Theme::from_toml(&theme_file("notacolor"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("notacolor"), "{}", err);
+    }
 }
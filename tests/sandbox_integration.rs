@@ -5,11 +5,11 @@
 //! - Container cleanup when deleting a sandboxed session
 //! - Docker availability validation
 
-use agent_of_empires::docker::{is_daemon_running, is_docker_available, DockerContainer};
+use agent_of_empires::docker::{ContainerRuntime, DockerContainer};
 use agent_of_empires::session::{Instance, SandboxInfo, Storage};
 
 fn docker_available() -> bool {
-    is_docker_available() && is_daemon_running()
+    ContainerRuntime::Docker.is_available() && ContainerRuntime::Docker.is_daemon_running()
 }
 
 #[test]
@@ -23,6 +23,14 @@ fn test_sandbox_info_serialization() {
         yolo_mode: None,
         extra_env_keys: Some(vec!["MY_VAR".to_string()]),
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     };
 
     let json = serde_json::to_string(&sandbox_info).unwrap();
@@ -52,6 +60,14 @@ fn test_instance_is_sandboxed() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     });
     assert!(inst.is_sandboxed());
 
@@ -64,6 +80,14 @@ fn test_instance_is_sandboxed() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     });
     assert!(!inst.is_sandboxed());
 }
@@ -85,6 +109,14 @@ fn test_sandbox_info_persists_across_save_load() {
         yolo_mode: Some(true),
         extra_env_keys: Some(vec!["API_KEY".to_string(), "SECRET".to_string()]),
         extra_env_values: None,
+        network: None,
+        runtime: Default::default(),
+        build: None,
+        forwarded_ports: Vec::new(),
+        snapshot: None,
+        cpu_limit: None,
+        memory_limit: None,
+        isolated: false,
     });
 
     storage.save(&[inst.clone()]).unwrap();
@@ -130,7 +162,7 @@ fn test_container_lifecycle() {
             .as_millis()
     );
 
-    let container = DockerContainer::new(&session_id, "alpine:latest");
+    let container = DockerContainer::new(&session_id, "alpine:latest", ContainerRuntime::Docker);
 
     assert!(!container.exists().unwrap());
 
@@ -140,8 +172,10 @@ fn test_container_lifecycle() {
         named_volumes: vec![],
         anonymous_volumes: vec![],
         environment: vec![],
+        ports: vec![],
         cpu_limit: None,
         memory_limit: None,
+        network: agent_of_empires::docker::ContainerNetwork::Bridge,
     };
 
     let container_id = container.create(&config).unwrap();
@@ -173,7 +207,7 @@ fn test_container_force_remove() {
             .as_millis()
     );
 
-    let container = DockerContainer::new(&session_id, "alpine:latest");
+    let container = DockerContainer::new(&session_id, "alpine:latest", ContainerRuntime::Docker);
 
     let config = agent_of_empires::docker::ContainerConfig {
         working_dir: "/workspace".to_string(),
@@ -181,8 +215,10 @@ fn test_container_force_remove() {
         named_volumes: vec![],
         anonymous_volumes: vec![],
         environment: vec![],
+        ports: vec![],
         cpu_limit: None,
         memory_limit: None,
+        network: agent_of_empires::docker::ContainerNetwork::Bridge,
     };
 
     container.create(&config).unwrap();
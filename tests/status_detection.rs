@@ -15,7 +15,9 @@
 //! 4. Re-run tests
 
 use agent_of_empires::session::Status;
-use agent_of_empires::tmux::{detect_claude_status, detect_opencode_status};
+use agent_of_empires::tmux::{
+    detect_claude_status, detect_opencode_status, detect_status_from_content, QuickAnswer,
+};
 use std::fs;
 use std::path::PathBuf;
 
@@ -83,6 +85,31 @@ fn test_all_fixtures_in_dir<F>(
     }
 }
 
+/// Keys each quick answer sends for every fixture of a state
+fn assert_answer_keys(tool: &str, dir: &str, state: &str, expected: &[(QuickAnswer, &[&str])]) {
+    let dir = fixtures_path().join(dir).join(state);
+    for entry in fs::read_dir(&dir).unwrap().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| ext != "txt") {
+            continue;
+        }
+        let content = strip_fixture_header(&fs::read_to_string(&path).unwrap());
+        let detection = detect_status_from_content(&content, tool, None);
+        let reason = detection
+            .waiting_reason
+            .unwrap_or_else(|| panic!("Fixture {:?} has no waiting reason", path.file_name()));
+        for (answer, keys) in expected {
+            assert_eq!(
+                reason.answer_keys(*answer),
+                Some(keys.iter().map(|k| k.to_string()).collect()),
+                "Fixture {:?} answers {:?} with the wrong keys",
+                path.file_name().unwrap(),
+                answer
+            );
+        }
+    }
+}
+
 fn identity(s: String) -> String {
     s
 }
@@ -127,6 +154,20 @@ mod claude_code {
         );
     }
 
+    #[test]
+    fn test_waiting_permission_quick_answers() {
+        assert_answer_keys(
+            "claude",
+            "claude_code",
+            "waiting_permission",
+            &[
+                (QuickAnswer::ApproveOnce, &["1"]),
+                (QuickAnswer::ApproveAlways, &["2"]),
+                (QuickAnswer::Deny, &["Escape"]),
+            ],
+        );
+    }
+
     #[test]
     fn test_idle_state() {
         test_all_fixtures_in_dir(
@@ -164,6 +205,20 @@ mod opencode {
         );
     }
 
+    #[test]
+    fn test_waiting_permission_quick_answers() {
+        assert_answer_keys(
+            "opencode",
+            "opencode",
+            "waiting_permission",
+            &[
+                (QuickAnswer::ApproveOnce, &["Enter"]),
+                (QuickAnswer::ApproveAlways, &["Right", "Enter"]),
+                (QuickAnswer::Deny, &["Right", "Right", "Enter"]),
+            ],
+        );
+    }
+
     #[test]
     fn test_idle_state() {
         test_all_fixtures_in_dir(
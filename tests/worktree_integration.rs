@@ -49,6 +49,7 @@ fn test_add_session_with_worktree_flag() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        sync_conflicts: Vec::new(),
     });
 
     assert!(wt_path.exists());
@@ -71,6 +72,7 @@ fn test_session_has_worktree_info_after_creation() {
         managed_by_aoe: true,
         created_at: now,
         cleanup_on_delete: true,
+        sync_conflicts: Vec::new(),
     });
 
     let info = instance.worktree_info.as_ref().unwrap();
@@ -98,6 +100,7 @@ fn test_worktree_info_persists_across_save_load() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: false,
+        sync_conflicts: Vec::new(),
     });
 
     storage.save(&[instance.clone()]).unwrap();
@@ -166,6 +169,7 @@ fn test_worktree_cleanup_on_session_removal() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        sync_conflicts: Vec::new(),
     });
 
     git_wt.remove_worktree(&wt_path).unwrap();
@@ -193,6 +197,7 @@ fn test_worktree_preserved_when_keep_flag_used() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: false,
+        sync_conflicts: Vec::new(),
     });
 
     assert!(wt_path.exists());